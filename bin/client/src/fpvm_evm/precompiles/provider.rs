@@ -122,8 +122,12 @@ where
         // 2. If the precompile is not accelerated, use the default version.
         // 3. If the precompile is not found, return None.
         let output = if let Some(accelerated) = self.accelerated_precompiles.get(address) {
+            #[cfg(feature = "profiling")]
+            let _span = tracing::trace_span!("precompile", phase_key = %address).entered();
             (accelerated)(&input, gas_limit, &self.hint_writer, &self.oracle_reader)
         } else if let Some(precompile) = self.inner.precompiles.get(address) {
+            #[cfg(feature = "profiling")]
+            let _span = tracing::trace_span!("precompile", phase_key = %address).entered();
             (*precompile)(&input, gas_limit)
         } else {
             return Ok(None);
@@ -28,7 +28,7 @@ static HINT_WRITER: HintWriter<FileChannel> = HintWriter::new(HINT_WRITER_PIPE);
 
 #[client_entry]
 fn main() -> Result<(), String> {
-    #[cfg(feature = "client-tracing")]
+    #[cfg(all(feature = "client-tracing", not(feature = "profiling")))]
     {
         use kona_std_fpvm::tracing::FpvmTracingSubscriber;
 
@@ -37,5 +37,24 @@ fn main() -> Result<(), String> {
             .expect("Failed to set tracing subscriber");
     }
 
-    kona_proof::block_on(kona_client::single::run(ORACLE_READER, HINT_WRITER))
+    #[cfg(feature = "profiling")]
+    {
+        use kona_std_fpvm::profiler::FpvmProfiler;
+
+        let subscriber = FpvmProfiler::new(tracing::Level::INFO);
+        tracing::subscriber::set_global_default(subscriber)
+            .expect("Failed to set tracing subscriber");
+    }
+
+    kona_proof::block_on(async {
+        let result = kona_client::single::run(ORACLE_READER, HINT_WRITER).await;
+
+        // Send the profile report to the host regardless of the outcome of the program.
+        #[cfg(feature = "profiling")]
+        if let Err(e) = kona_client::profiling::send_report(&HINT_WRITER).await {
+            tracing::error!(target: "client", "Failed to send profile report: {e}");
+        }
+
+        result
+    })
 }
@@ -10,3 +10,6 @@ extern crate alloc;
 pub mod fpvm_evm;
 pub mod interop;
 pub mod single;
+
+#[cfg(feature = "profiling")]
+pub mod profiling;
@@ -0,0 +1,15 @@
+//! Reporting for profiling builds of the fault proof program.
+
+use kona_preimage::HintWriterClient;
+use kona_proof::{HintType, errors::OracleProviderError};
+
+/// Sends the [ProfileReport] of the phases recorded by the [FpvmProfiler] so far to the host, as a
+/// [HintType::ClientProfile] hint.
+///
+/// [ProfileReport]: kona_std_fpvm::profiler::ProfileReport
+/// [FpvmProfiler]: kona_std_fpvm::profiler::FpvmProfiler
+pub async fn send_report<H: HintWriterClient>(hint_client: &H) -> Result<(), OracleProviderError> {
+    let report = kona_std_fpvm::profiler::report();
+    let data = serde_json::to_vec(&report).map_err(OracleProviderError::Serde)?;
+    HintType::ClientProfile.with_data(&[&data]).send(hint_client).await
+}
@@ -95,6 +95,17 @@ where
     //                   DERIVATION & EXECUTION                   //
     ////////////////////////////////////////////////////////////////
 
+    // Hint the host to fetch the execution witnesses of all blocks between the safe head and the
+    // claimed block in bulk. The witness of every executed block is still hinted during execution,
+    // in case the host fails to fetch the range. Hosts that don't know the hint end the session
+    // when receiving it, so it is only sent when opted into.
+    #[cfg(feature = "witness-range")]
+    kona_mpt::TrieHinter::hint_execution_witness_range(
+        &l2_provider,
+        safe_head_hash,
+        boot.claimed_l2_block_number - safe_head.number,
+    )?;
+
     // Create a new derivation driver with the given boot information and oracle.
     let cursor = new_oracle_pipeline_cursor(
         rollup_config.as_ref(),
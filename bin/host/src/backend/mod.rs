@@ -6,4 +6,10 @@ pub use offline::OfflineHostBackend;
 mod online;
 pub use online::{HintHandler, OnlineHostBackend, OnlineHostBackendCfg};
 
+mod profile;
+pub use profile::ProfileReportBackend;
+
+mod trace;
+pub use trace::{PreimageTracer, TraceEntry, TraceEvent, TracingHostBackend};
+
 pub(crate) mod util;
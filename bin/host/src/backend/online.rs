@@ -3,18 +3,30 @@
 use crate::SharedKeyValueStore;
 use anyhow::Result;
 use async_trait::async_trait;
+use futures::future::{join_all, try_join_all};
 use kona_preimage::{
     HintRouter, PreimageFetcher, PreimageKey,
     errors::{PreimageOracleError, PreimageOracleResult},
 };
 use kona_proof::{Hint, errors::HintParsingError};
-use std::{collections::HashSet, hash::Hash, str::FromStr, sync::Arc};
-use tokio::sync::RwLock;
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::Hash,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    sync::{RwLock, watch},
+    task::JoinSet,
+};
 use tracing::{debug, error, trace};
 
+/// The maximum number of prefetched hints that are remembered to avoid fetching them twice.
+const MAX_SEEN_HINTS: usize = 1 << 16;
+
 /// The [OnlineHostBackendCfg] trait is used to define the type configuration for the
 /// [OnlineHostBackend].
-pub trait OnlineHostBackendCfg {
+pub trait OnlineHostBackendCfg: Send + Sync {
     /// The hint type describing the range of hints that can be received.
     type HintType: FromStr<Err = HintParsingError> + Hash + Eq + PartialEq + Clone + Send + Sync;
 
@@ -36,6 +48,24 @@ pub trait HintHandler {
         providers: &<Self::Cfg as OnlineHostBackendCfg>::Providers,
         kv: SharedKeyValueStore,
     ) -> Result<()>;
+
+    /// Fetches data in response to a batch of independent hints.
+    ///
+    /// By default, the hints are fetched concurrently through [HintHandler::fetch_hint].
+    /// Implementations may override this method to coalesce the remote requests of several hints
+    /// into batched requests.
+    async fn fetch_hints(
+        hints: Vec<Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>>,
+        cfg: &Self::Cfg,
+        providers: &<Self::Cfg as OnlineHostBackendCfg>::Providers,
+        kv: SharedKeyValueStore,
+    ) -> Result<()> {
+        try_join_all(
+            hints.into_iter().map(|hint| Self::fetch_hint(hint, cfg, providers, kv.clone())),
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 /// The [OnlineHostBackend] is a [HintRouter] and [PreimageFetcher] that is used to fetch data from
@@ -49,15 +79,17 @@ where
     H: HintHandler,
 {
     /// The configuration that is used to route hints.
-    cfg: C,
+    cfg: Arc<C>,
     /// The key-value store that is used to store preimages.
     kv: SharedKeyValueStore,
     /// The providers that are used to fetch data in response to hints.
-    providers: C::Providers,
+    providers: Arc<C::Providers>,
     /// Hints that should be immediately executed by the host.
     proactive_hints: HashSet<C::HintType>,
-    /// The last hint that was received.
+    /// The last hint that was received in the client session of the backend.
     last_hint: Arc<RwLock<Option<Hint<C::HintType>>>>,
+    /// The prefetcher for received hints, if prefetching is enabled.
+    prefetcher: Option<HintPrefetcher<C::HintType>>,
     /// Phantom marker for the [HintHandler].
     _hint_handler: std::marker::PhantomData<H>,
 }
@@ -71,11 +103,12 @@ where
     /// external configuration.
     pub fn new(cfg: C, kv: SharedKeyValueStore, providers: C::Providers, _: H) -> Self {
         Self {
-            cfg,
+            cfg: Arc::new(cfg),
             kv,
-            providers,
+            providers: Arc::new(providers),
             proactive_hints: HashSet::default(),
             last_hint: Arc::new(RwLock::new(None)),
+            prefetcher: None,
             _hint_handler: std::marker::PhantomData,
         }
     }
@@ -85,13 +118,106 @@ where
         self.proactive_hints.insert(hint_type);
         self
     }
+
+    /// Creates a backend for a new client session. The session shares the configuration,
+    /// key-value store, providers and prefetcher of this backend, but keeps track of its own last
+    /// hint, so that concurrent sessions never fetch each other's hints.
+    pub fn new_session(&self) -> Self {
+        Self {
+            cfg: self.cfg.clone(),
+            kv: self.kv.clone(),
+            providers: self.providers.clone(),
+            proactive_hints: self.proactive_hints.clone(),
+            last_hint: Arc::new(RwLock::new(None)),
+            prefetcher: self.prefetcher.clone(),
+            _hint_handler: std::marker::PhantomData,
+        }
+    }
+
+    /// Enables prefetching of received hints in the background.
+    ///
+    /// Hints are queued as they are received, and fetched by up to `concurrency` concurrent
+    /// workers. Each worker drains up to `max_batch_size` queued hints at once, and passes them to
+    /// [HintHandler::fetch_hints]. If a batch fails, its hints are fetched individually, and hints
+    /// that still fail are forgotten so that they are fetched again when they are received again.
+    /// Preimage requests that miss the key-value store wait for all queued hints to be fetched
+    /// before falling back to fetching the last hint, preserving the ordering guarantees of the
+    /// preimage oracle.
+    pub fn with_prefetching(mut self, concurrency: usize, max_batch_size: usize) -> Self {
+        self.prefetcher = Some(HintPrefetcher::new(concurrency, max_batch_size));
+        self
+    }
+}
+
+impl<C, H> OnlineHostBackend<C, H>
+where
+    C: OnlineHostBackendCfg + 'static,
+    H: HintHandler<Cfg = C> + 'static,
+{
+    /// Spawns a new prefetch worker, if the concurrency limit of the [HintPrefetcher] allows it.
+    async fn spawn_prefetch_worker(&self, prefetcher: &HintPrefetcher<C::HintType>) {
+        let mut workers = prefetcher.workers.lock().await;
+
+        // Reap any workers that have already exited.
+        while workers.try_join_next().is_some() {}
+        if workers.len() >= prefetcher.concurrency {
+            return;
+        }
+
+        let (cfg, providers, kv) = (self.cfg.clone(), self.providers.clone(), self.kv.clone());
+        let prefetcher = prefetcher.clone();
+        workers.spawn(async move {
+            while let Some(batch) = prefetcher.next_batch() {
+                Self::prefetch_batch(&cfg, &providers, &kv, &prefetcher, batch).await;
+            }
+        });
+    }
+
+    /// Waits for all queued hints to be fetched, helping the workers drain the queue.
+    async fn drain_prefetcher(&self, prefetcher: &HintPrefetcher<C::HintType>) {
+        while let Some(batch) = prefetcher.next_batch() {
+            Self::prefetch_batch(&self.cfg, &self.providers, &self.kv, prefetcher, batch).await;
+        }
+        prefetcher.wait_idle().await;
+    }
+
+    /// Fetches a batch of queued hints. If the batch fails, its hints are fetched individually.
+    async fn prefetch_batch(
+        cfg: &C,
+        providers: &C::Providers,
+        kv: &SharedKeyValueStore,
+        prefetcher: &HintPrefetcher<C::HintType>,
+        batch: Vec<Hint<C::HintType>>,
+    ) {
+        trace!(target: "host_backend", "Prefetching batch of {} hints", batch.len());
+        if H::fetch_hints(batch.clone(), cfg, providers, kv.clone()).await.is_ok() {
+            prefetcher.complete(batch.len(), Vec::new());
+            return;
+        }
+
+        let results = join_all(
+            batch.iter().map(|hint| H::fetch_hint(hint.clone(), cfg, providers, kv.clone())),
+        )
+        .await;
+        let count = batch.len();
+        let failed = batch
+            .into_iter()
+            .zip(results)
+            .filter_map(|(hint, result)| {
+                let e = result.err()?;
+                error!(target: "host_backend", "Failed to prefetch hint: {e}");
+                Some(hint)
+            })
+            .collect();
+        prefetcher.complete(count, failed);
+    }
 }
 
 #[async_trait]
 impl<C, H> HintRouter for OnlineHostBackend<C, H>
 where
-    C: OnlineHostBackendCfg + Send + Sync,
-    H: HintHandler<Cfg = C> + Send + Sync,
+    C: OnlineHostBackendCfg + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
 {
     /// Set the last hint to be received.
     async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
@@ -106,6 +232,12 @@ where
                 .await
                 .map_err(|e| PreimageOracleError::Other(e.to_string()))?;
         } else {
+            if let Some(ref prefetcher) = self.prefetcher {
+                if prefetcher.enqueue(parsed_hint.clone()) {
+                    self.spawn_prefetch_worker(prefetcher).await;
+                }
+            }
+
             let mut hint_lock = self.last_hint.write().await;
             hint_lock.replace(parsed_hint);
         }
@@ -117,8 +249,8 @@ where
 #[async_trait]
 impl<C, H> PreimageFetcher for OnlineHostBackend<C, H>
 where
-    C: OnlineHostBackendCfg + Send + Sync,
-    H: HintHandler<Cfg = C> + Send + Sync,
+    C: OnlineHostBackendCfg + 'static,
+    H: HintHandler<Cfg = C> + Send + Sync + 'static,
 {
     /// Get the preimage for the given key.
     async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
@@ -131,6 +263,14 @@ where
         // Drop the read lock before beginning the retry loop.
         drop(kv_lock);
 
+        // If the preimage is not yet available, wait for all prefetched hints to land.
+        if preimage.is_none() {
+            if let Some(ref prefetcher) = self.prefetcher {
+                self.drain_prefetcher(prefetcher).await;
+                preimage = self.kv.read().await.get(key.into());
+            }
+        }
+
         // Use a loop to keep retrying the prefetch as long as the key is not found
         while preimage.is_none() {
             if let Some(hint) = self.last_hint.read().await.as_ref() {
@@ -150,3 +290,285 @@ where
         preimage.ok_or(PreimageOracleError::KeyNotFound)
     }
 }
+
+/// The [HintPrefetcher] holds the queue of hints that are awaiting a background fetch, as well as
+/// the workers that drain it.
+#[derive(Debug)]
+struct HintPrefetcher<HT> {
+    /// The maximum number of concurrent prefetch workers.
+    concurrency: usize,
+    /// The maximum number of hints that a worker drains from the queue at once.
+    max_batch_size: usize,
+    /// The queue of hints awaiting a fetch.
+    queue: Arc<Mutex<VecDeque<Hint<HT>>>>,
+    /// The hints that have recently been queued, used to avoid fetching the same hint twice.
+    seen: Arc<Mutex<SeenHints<HT>>>,
+    /// The number of hints that are queued or being fetched.
+    pending: Arc<watch::Sender<usize>>,
+    /// The running prefetch workers.
+    workers: Arc<tokio::sync::Mutex<JoinSet<()>>>,
+}
+
+impl<HT> Clone for HintPrefetcher<HT> {
+    fn clone(&self) -> Self {
+        Self {
+            concurrency: self.concurrency,
+            max_batch_size: self.max_batch_size,
+            queue: self.queue.clone(),
+            seen: self.seen.clone(),
+            pending: self.pending.clone(),
+            workers: self.workers.clone(),
+        }
+    }
+}
+
+impl<HT> HintPrefetcher<HT>
+where
+    HT: Hash + Eq + Clone,
+{
+    /// Creates a new [HintPrefetcher] with the given concurrency and maximum batch size.
+    fn new(concurrency: usize, max_batch_size: usize) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            max_batch_size: max_batch_size.max(1),
+            queue: Default::default(),
+            seen: Arc::new(Mutex::new(SeenHints::new(MAX_SEEN_HINTS))),
+            pending: Arc::new(watch::channel(0).0),
+            workers: Default::default(),
+        }
+    }
+
+    /// Queues the given hint for a background fetch. Returns `false` if the hint was already
+    /// queued recently.
+    fn enqueue(&self, hint: Hint<HT>) -> bool {
+        let Ok(mut seen) = self.seen.lock() else { return false };
+        if !seen.insert(hint.clone()) {
+            return false;
+        }
+        drop(seen);
+
+        // Count the hint as pending before a worker can take it from the queue.
+        let Ok(mut queue) = self.queue.lock() else { return false };
+        self.pending.send_modify(|pending| *pending += 1);
+        queue.push_back(hint);
+        true
+    }
+
+    /// Marks `count` taken hints as done. The `failed` hints are forgotten, so that they are
+    /// queued again when they are received again.
+    fn complete(&self, count: usize, failed: Vec<Hint<HT>>) {
+        if let Ok(mut seen) = self.seen.lock() {
+            failed.iter().for_each(|hint| seen.remove(hint));
+        }
+        self.pending.send_modify(|pending| *pending = pending.saturating_sub(count));
+    }
+
+    /// Waits until all queued hints have been fetched.
+    async fn wait_idle(&self) {
+        let _ = self.pending.subscribe().wait_for(|pending| *pending == 0).await;
+    }
+
+    /// Takes the next batch of hints from the queue, in the order they were received.
+    fn next_batch(&self) -> Option<Vec<Hint<HT>>> {
+        let mut queue = self.queue.lock().ok()?;
+        let batch_size = queue.len().min(self.max_batch_size);
+        (batch_size > 0).then(|| queue.drain(..batch_size).collect())
+    }
+}
+
+/// A bounded set of hints that evicts the least recently inserted hint once full.
+#[derive(Debug)]
+struct SeenHints<HT> {
+    /// The maximum number of hints in the set.
+    capacity: usize,
+    /// The hints in the set.
+    set: HashSet<Hint<HT>>,
+    /// The hints in the set, in insertion order.
+    order: VecDeque<Hint<HT>>,
+}
+
+impl<HT> SeenHints<HT>
+where
+    HT: Hash + Eq + Clone,
+{
+    /// Creates a new, empty [SeenHints] set with the given capacity.
+    fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), set: HashSet::new(), order: VecDeque::new() }
+    }
+
+    /// Inserts the given hint, evicting the oldest hint if the set is full. Returns `false` if the
+    /// hint was already in the set.
+    fn insert(&mut self, hint: Hint<HT>) -> bool {
+        if !self.set.insert(hint.clone()) {
+            return false;
+        }
+        self.order.push_back(hint);
+
+        if self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.set.remove(&evicted);
+            }
+        }
+        true
+    }
+
+    /// Removes the given hint from the set.
+    fn remove(&mut self, hint: &Hint<HT>) {
+        if self.set.remove(hint) {
+            self.order.retain(|seen| seen != hint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{KeyValueStore, MemoryKeyValueStore};
+    use alloy_primitives::{B256, keccak256};
+    use kona_proof::HintType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A test configuration for the [OnlineHostBackend].
+    struct TestCfg;
+
+    impl OnlineHostBackendCfg for TestCfg {
+        type HintType = HintType;
+        type Providers = ();
+    }
+
+    /// A [HintHandler] that stores the hint data as a keccak256 preimage.
+    struct TestHintHandler;
+
+    #[async_trait]
+    impl HintHandler for TestHintHandler {
+        type Cfg = TestCfg;
+
+        async fn fetch_hint(
+            hint: Hint<HintType>,
+            _: &TestCfg,
+            _: &(),
+            kv: SharedKeyValueStore,
+        ) -> Result<()> {
+            let key = PreimageKey::new_keccak256(*keccak256(hint.data.as_ref()));
+            kv.write().await.set(key.into(), hint.data.to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefetched_hints_are_served() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend =
+            OnlineHostBackend::new(TestCfg, kv.clone(), (), TestHintHandler).with_prefetching(4, 2);
+
+        for i in 0..8u8 {
+            let hint = Hint::new(HintType::L2StateNode, B256::repeat_byte(i));
+            backend.route_hint(hint.encode()).await.unwrap();
+        }
+
+        // Every hint was queued for a background fetch, and all of them have landed once a preimage
+        // is requested.
+        let key = PreimageKey::new_keccak256(*keccak256(B256::repeat_byte(0)));
+        assert_eq!(backend.get_preimage(key).await.unwrap(), B256::repeat_byte(0).to_vec());
+        for i in 0..8u8 {
+            let key = PreimageKey::new_keccak256(*keccak256(B256::repeat_byte(i)));
+            assert!(kv.read().await.get(key.into()).is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sessions_keep_their_last_hint() {
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend = OnlineHostBackend::new(TestCfg, kv, (), TestHintHandler);
+        let (a, b) = (backend.new_session(), backend.new_session());
+
+        a.route_hint(Hint::new(HintType::L2StateNode, B256::repeat_byte(1)).encode())
+            .await
+            .unwrap();
+        b.route_hint(Hint::new(HintType::L2StateNode, B256::repeat_byte(2)).encode())
+            .await
+            .unwrap();
+
+        // Every session fetches its own last hint when a preimage is missing.
+        let key = PreimageKey::new_keccak256(*keccak256(B256::repeat_byte(1)));
+        assert_eq!(a.get_preimage(key).await.unwrap(), B256::repeat_byte(1).to_vec());
+        let key = PreimageKey::new_keccak256(*keccak256(B256::repeat_byte(2)));
+        assert_eq!(b.get_preimage(key).await.unwrap(), B256::repeat_byte(2).to_vec());
+        assert!(backend.last_hint.read().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prefetched_last_hint_is_retried() {
+        /// A [HintHandler] whose first fetch succeeds without storing the preimage, e.g. because
+        /// the provider lagged behind.
+        struct LaggingHintHandler;
+
+        static FETCHES: AtomicUsize = AtomicUsize::new(0);
+
+        #[async_trait]
+        impl HintHandler for LaggingHintHandler {
+            type Cfg = TestCfg;
+
+            async fn fetch_hint(
+                hint: Hint<HintType>,
+                cfg: &TestCfg,
+                providers: &(),
+                kv: SharedKeyValueStore,
+            ) -> Result<()> {
+                if FETCHES.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Ok(());
+                }
+                TestHintHandler::fetch_hint(hint, cfg, providers, kv).await
+            }
+        }
+
+        let kv: SharedKeyValueStore = Arc::new(RwLock::new(MemoryKeyValueStore::new()));
+        let backend =
+            OnlineHostBackend::new(TestCfg, kv, (), LaggingHintHandler).with_prefetching(1, 1);
+        backend
+            .route_hint(Hint::new(HintType::L2StateNode, B256::repeat_byte(1)).encode())
+            .await
+            .unwrap();
+
+        // The prefetch succeeded without producing the preimage, so the last hint is fetched again.
+        let key = PreimageKey::new_keccak256(*keccak256(B256::repeat_byte(1)));
+        assert_eq!(backend.get_preimage(key).await.unwrap(), B256::repeat_byte(1).to_vec());
+        assert_eq!(FETCHES.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_prefetcher_dedups_and_batches() {
+        let prefetcher = HintPrefetcher::<HintType>::new(1, 2);
+        let hint = Hint::new(HintType::L2StateNode, B256::ZERO);
+
+        assert!(prefetcher.enqueue(hint.clone()));
+        assert!(!prefetcher.enqueue(hint));
+        assert!(prefetcher.enqueue(Hint::new(HintType::L2Code, B256::ZERO)));
+        assert!(prefetcher.enqueue(Hint::new(HintType::L1BlockHeader, B256::ZERO)));
+
+        assert_eq!(prefetcher.next_batch().map(|b| b.len()), Some(2));
+        assert_eq!(prefetcher.next_batch().map(|b| b.len()), Some(1));
+        assert_eq!(prefetcher.next_batch(), None);
+        // Failed hints are queued again when they are received again.
+        prefetcher.complete(3, vec![Hint::new(HintType::L2Code, B256::ZERO)]);
+        assert!(!prefetcher.enqueue(Hint::new(HintType::L2StateNode, B256::ZERO)));
+        assert!(prefetcher.enqueue(Hint::new(HintType::L2Code, B256::ZERO)));
+        assert_eq!(*prefetcher.pending.borrow(), 1);
+    }
+
+    #[test]
+    fn test_seen_hints_bounded() {
+        let mut seen = SeenHints::<HintType>::new(2);
+        let hints = (0..3u8)
+            .map(|i| Hint::new(HintType::L2StateNode, B256::repeat_byte(i)))
+            .collect::<Vec<_>>();
+
+        assert!(seen.insert(hints[0].clone()));
+        assert!(seen.insert(hints[1].clone()));
+        assert!(!seen.insert(hints[1].clone()));
+        assert!(seen.insert(hints[2].clone()));
+
+        // The oldest hint was evicted.
+        assert!(!seen.set.contains(&hints[0]));
+        assert!(seen.set.contains(&hints[1]) && seen.set.contains(&hints[2]));
+    }
+}
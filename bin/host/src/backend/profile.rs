@@ -0,0 +1,97 @@
+//! Contains the [ProfileReportBackend], which receives the profile report of a profiling build of
+//! the client program.
+
+use alloy_primitives::hex;
+use async_trait::async_trait;
+use kona_preimage::{
+    HintRouter, PreimageFetcher, PreimageKey,
+    errors::{PreimageOracleError, PreimageOracleResult},
+};
+use kona_proof::HintType;
+use std::path::PathBuf;
+use tracing::info;
+
+/// A [HintRouter] and [PreimageFetcher] that intercepts [HintType::ClientProfile] hints and writes
+/// the profile report that they carry to a file. All other requests are forwarded to the wrapped
+/// backend.
+#[derive(Debug)]
+pub struct ProfileReportBackend<B> {
+    /// The wrapped backend.
+    inner: B,
+    /// The path to write the profile report to. If `None`, the report is logged instead.
+    path: Option<PathBuf>,
+}
+
+impl<B> ProfileReportBackend<B> {
+    /// Creates a new [ProfileReportBackend] that wraps the given backend.
+    pub const fn new(inner: B, path: Option<PathBuf>) -> Self {
+        Self { inner, path }
+    }
+}
+
+#[async_trait]
+impl<B> HintRouter for ProfileReportBackend<B>
+where
+    B: HintRouter + Send + Sync,
+{
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        let profile_hint: &str = HintType::ClientProfile.into();
+        let Some(data) = hint
+            .split_once(' ')
+            .and_then(|(hint_type, data)| (hint_type == profile_hint).then_some(data))
+        else {
+            return self.inner.route_hint(hint).await;
+        };
+
+        let report = hex::decode(data).map_err(|e| PreimageOracleError::Other(e.to_string()))?;
+        match self.path {
+            Some(ref path) => {
+                std::fs::write(path, &report)
+                    .map_err(|e| PreimageOracleError::Other(e.to_string()))?;
+                info!(target: "host_profile", "Wrote client profile report to {}", path.display());
+            }
+            None => {
+                info!(
+                    target: "host_profile",
+                    "Client profile report: {}",
+                    String::from_utf8_lossy(&report)
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<B> PreimageFetcher for ProfileReportBackend<B>
+where
+    B: PreimageFetcher + Send + Sync,
+{
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        self.inner.get_preimage(key).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{MemoryKeyValueStore, OfflineHostBackend};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_profile_report_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let report_path = dir.path().join("profile.json");
+
+        let backend = ProfileReportBackend::new(
+            OfflineHostBackend::new(Arc::new(RwLock::new(MemoryKeyValueStore::new()))),
+            Some(report_path.clone()),
+        );
+        let report = br#"{"phases":[]}"#;
+        backend.route_hint(HintType::ClientProfile.with_data(&[report]).encode()).await.unwrap();
+        backend.route_hint("l1-block-header 0xdead".to_string()).await.unwrap();
+
+        assert_eq!(std::fs::read(&report_path).unwrap(), report);
+    }
+}
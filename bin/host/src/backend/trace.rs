@@ -0,0 +1,207 @@
+//! Contains the [TracingHostBackend], which records every hint and preimage request served by
+//! the wrapped backend.
+
+use alloy_primitives::B256;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use kona_preimage::{HintRouter, PreimageFetcher, PreimageKey, errors::PreimageOracleResult};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufRead, BufReader, LineWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use tracing::warn;
+
+/// A single entry of a preimage access trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceEntry {
+    /// The sequence number of the entry, assigned in the order that entries are written to the
+    /// trace.
+    pub seq: u64,
+    /// The time taken to serve the request, in microseconds.
+    pub elapsed_micros: u64,
+    /// The traced request.
+    #[serde(flatten)]
+    pub event: TraceEvent,
+}
+
+/// A request that was served by a [TracingHostBackend].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TraceEvent {
+    /// A hint was routed.
+    Hint {
+        /// The raw hint, as sent by the client program.
+        hint: String,
+    },
+    /// A preimage was requested.
+    Preimage {
+        /// The requested preimage key.
+        key: B256,
+        /// The size of the preimage, or `None` if the preimage could not be served.
+        size: Option<usize>,
+    },
+}
+
+/// A shared, line-buffered writer of [TraceEntry]s, encoded as JSON lines.
+#[derive(Debug, Clone)]
+pub struct PreimageTracer {
+    writer: Arc<Mutex<TraceWriter>>,
+}
+
+/// The writer of a [PreimageTracer], along with the sequence number of the next entry.
+#[derive(Debug)]
+struct TraceWriter {
+    file: LineWriter<File>,
+    seq: u64,
+}
+
+impl PreimageTracer {
+    /// Creates a new [PreimageTracer] that writes to the file at the given path.
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .map_err(|e| anyhow!("Failed to create preimage trace at {path:?}: {e}"))?;
+        let writer = TraceWriter { file: LineWriter::new(file), seq: 0 };
+        Ok(Self { writer: Arc::new(Mutex::new(writer)) })
+    }
+
+    /// Reads all [TraceEntry]s from the trace file at the given path.
+    pub fn read(path: &Path) -> Result<Vec<TraceEntry>> {
+        let file = File::open(path)
+            .map_err(|e| anyhow!("Failed to open preimage trace at {path:?}: {e}"))?;
+        BufReader::new(file)
+            .lines()
+            .map(|line| -> Result<TraceEntry> { Ok(serde_json::from_str(&line?)?) })
+            .collect::<Result<Vec<_>>>()
+    }
+
+    /// Reads the set of preimage keys that were successfully served in the trace file at the
+    /// given path.
+    pub fn read_keys(path: &Path) -> Result<HashSet<B256>> {
+        Ok(Self::read(path)?
+            .into_iter()
+            .filter_map(|entry| match entry.event {
+                TraceEvent::Preimage { key, size: Some(_) } => Some(key),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Writes a [TraceEntry] to the trace file, assigning it the next sequence number.
+    fn write(&self, start: Instant, event: TraceEvent) {
+        let elapsed_micros = start.elapsed().as_micros() as u64;
+        let result = self.writer.lock().map_err(|_| anyhow!("Trace writer poisoned")).and_then(
+            |mut writer| {
+                let entry = TraceEntry { seq: writer.seq, elapsed_micros, event };
+                writeln!(writer.file, "{}", serde_json::to_string(&entry)?)?;
+                writer.seq += 1;
+                Ok(())
+            },
+        );
+
+        if let Err(e) = result {
+            warn!(target: "host_trace", "Failed to write preimage trace entry: {e}");
+        }
+    }
+}
+
+/// A [HintRouter] and [PreimageFetcher] that records every request served by the wrapped backend
+/// through an optional [PreimageTracer].
+#[derive(Debug)]
+pub struct TracingHostBackend<B> {
+    /// The wrapped backend.
+    inner: B,
+    /// The tracer, if tracing is enabled.
+    tracer: Option<PreimageTracer>,
+}
+
+impl<B> TracingHostBackend<B> {
+    /// Creates a new [TracingHostBackend] that wraps the given backend. If no [PreimageTracer] is
+    /// passed, requests are forwarded to the wrapped backend without being recorded.
+    pub const fn new(inner: B, tracer: Option<PreimageTracer>) -> Self {
+        Self { inner, tracer }
+    }
+}
+
+#[async_trait]
+impl<B> HintRouter for TracingHostBackend<B>
+where
+    B: HintRouter + Send + Sync,
+{
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        let Some(ref tracer) = self.tracer else {
+            return self.inner.route_hint(hint).await;
+        };
+
+        let start = Instant::now();
+        let event = TraceEvent::Hint { hint: hint.clone() };
+        let result = self.inner.route_hint(hint).await;
+        tracer.write(start, event);
+        result
+    }
+}
+
+#[async_trait]
+impl<B> PreimageFetcher for TracingHostBackend<B>
+where
+    B: PreimageFetcher + Send + Sync,
+{
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let Some(ref tracer) = self.tracer else {
+            return self.inner.get_preimage(key).await;
+        };
+
+        let start = Instant::now();
+        let result = self.inner.get_preimage(key).await;
+        let size = result.as_ref().ok().map(|preimage| preimage.len());
+        tracer.write(start, TraceEvent::Preimage { key: key.into(), size });
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{KeyValueStore, MemoryKeyValueStore, OfflineHostBackend};
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_trace_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let trace_path = dir.path().join("trace.jsonl");
+
+        let key = PreimageKey::new_keccak256([0xAA; 32]);
+        let mut kv = MemoryKeyValueStore::new();
+        kv.set(key.into(), vec![0xBE, 0xEF]).unwrap();
+
+        let backend = TracingHostBackend::new(
+            OfflineHostBackend::new(Arc::new(RwLock::new(kv))),
+            Some(PreimageTracer::create(&trace_path).unwrap()),
+        );
+        backend.route_hint("l1-block-header 0xdead".to_string()).await.unwrap();
+        backend.get_preimage(key).await.unwrap();
+        backend.get_preimage(PreimageKey::new_keccak256([0xBB; 32])).await.unwrap_err();
+
+        // Hints are forwarded unchanged, even if they are malformed.
+        backend.route_hint("l1-block-header 0xzz".to_string()).await.unwrap();
+
+        let entries = PreimageTracer::read(&trace_path).unwrap();
+        assert_eq!(entries.len(), 4);
+        assert!(entries.iter().enumerate().all(|(i, entry)| entry.seq == i as u64));
+        assert_eq!(
+            entries[0].event,
+            TraceEvent::Hint { hint: "l1-block-header 0xdead".to_string() }
+        );
+        assert_eq!(entries[3].event, TraceEvent::Hint { hint: "l1-block-header 0xzz".to_string() });
+        assert_eq!(entries[1].event, TraceEvent::Preimage { key: key.into(), size: Some(2) });
+        assert_eq!(
+            PreimageTracer::read_keys(&trace_path).unwrap(),
+            HashSet::from([B256::from(key)])
+        );
+    }
+}
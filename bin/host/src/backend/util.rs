@@ -1,12 +1,23 @@
 //! Utilities for the preimage server backend.
 
-use crate::KeyValueStore;
+use crate::{KeyValueStore, SharedKeyValueStore};
 use alloy_consensus::EMPTY_ROOT_HASH;
-use alloy_primitives::keccak256;
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, B256, Bytes, keccak256};
+use alloy_provider::{Provider, RootProvider};
 use alloy_rlp::EMPTY_STRING_CODE;
+use alloy_rpc_client::BatchRequest;
+use alloy_rpc_types::{EIP1186AccountProofResponse, debug::ExecutionWitness};
 use anyhow::Result;
+use futures::future::{join_all, try_join_all};
 use kona_preimage::{PreimageKey, PreimageKeyType};
+use op_alloy_network::Optimism;
+use std::collections::HashSet;
 use tokio::sync::RwLock;
+use tracing::warn;
+
+/// The maximum number of `debug_executionWitness` requests within a single batch.
+const WITNESS_RANGE_BATCH_SIZE: usize = 32;
 
 /// Constructs a merkle patricia trie from the ordered list passed and stores all encoded
 /// intermediate nodes of the trie in the [KeyValueStore].
@@ -39,3 +50,148 @@ pub(crate) async fn store_ordered_trie<KV: KeyValueStore + ?Sized, T: AsRef<[u8]
 
     Ok(())
 }
+
+/// Fetches the preimages of the given state trie node hashes from the L2 execution layer with a
+/// single batched `debug_dbGet` request, and stores them in the [KeyValueStore]. Preimages that
+/// fail within the batch are refetched individually.
+pub(crate) async fn fetch_state_nodes_batched(
+    provider: &RootProvider<Optimism>,
+    hashes: &[B256],
+    kv: &SharedKeyValueStore,
+) -> Result<()> {
+    if hashes.is_empty() {
+        return Ok(());
+    }
+
+    let mut batch = BatchRequest::new(provider.client());
+    let waiters = hashes
+        .iter()
+        .map(|hash| batch.add_call::<_, Bytes>("debug_dbGet", &[hash]))
+        .collect::<Result<Vec<_>, _>>()?;
+    batch.send().await?;
+    let results = join_all(waiters).await;
+
+    let mut preimages = Vec::with_capacity(hashes.len());
+    for (hash, result) in hashes.iter().zip(results) {
+        let preimage = match result {
+            Ok(preimage) => preimage,
+            Err(e) => {
+                warn!(target: "host_backend", "Batched preimage fetch failed for {hash}: {e}");
+                provider.client().request::<_, Bytes>("debug_dbGet", &[hash]).await?
+            }
+        };
+        preimages.push((*hash, preimage));
+    }
+
+    let mut kv_write_lock = kv.write().await;
+    for (hash, preimage) in preimages {
+        kv_write_lock.set(PreimageKey::new_keccak256(*hash).into(), preimage.into())?;
+    }
+
+    Ok(())
+}
+
+/// Fetches the account and storage proofs for the given `(block number, address, storage slots)`
+/// requests from the L2 execution layer with a single batched `eth_getProof` request, and stores
+/// all proof nodes in the [KeyValueStore]. Proofs that fail within the batch are refetched
+/// individually.
+pub(crate) async fn fetch_storage_proofs_batched(
+    provider: &RootProvider<Optimism>,
+    requests: &[(u64, Address, Vec<B256>)],
+    kv: &SharedKeyValueStore,
+) -> Result<()> {
+    if requests.is_empty() {
+        return Ok(());
+    }
+
+    let mut batch = BatchRequest::new(provider.client());
+    let waiters = requests
+        .iter()
+        .map(|(block_number, address, slots)| {
+            batch.add_call::<_, EIP1186AccountProofResponse>(
+                "eth_getProof",
+                &(address, slots, BlockId::from(*block_number)),
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    batch.send().await?;
+    let results = join_all(waiters).await;
+
+    let mut proofs = Vec::with_capacity(requests.len());
+    for ((block_number, address, slots), result) in requests.iter().zip(results) {
+        let proof = match result {
+            Ok(proof) => proof,
+            Err(e) => {
+                warn!(target: "host_backend", "Batched proof fetch failed for {address}: {e}");
+                provider.get_proof(*address, slots.clone()).block_id((*block_number).into()).await?
+            }
+        };
+        proofs.push(proof);
+    }
+
+    // Write the account and storage proof nodes to the key-value store.
+    let mut kv_write_lock = kv.write().await;
+    for proof in proofs {
+        let storage_nodes = proof.storage_proof.into_iter().flat_map(|p| p.proof);
+        for node in proof.account_proof.into_iter().chain(storage_nodes) {
+            let key = PreimageKey::new_keccak256(*keccak256(node.as_ref()));
+            kv_write_lock.set(key.into(), node.into())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetches the execution witnesses of the `block_count` blocks following the block with the given
+/// number from the L2 execution layer, with batched `debug_executionWitness` requests. Returns the
+/// deduplicated preimages of the merged witness.
+pub(crate) async fn fetch_execution_witness_range(
+    provider: &RootProvider<Optimism>,
+    parent_number: u64,
+    block_count: u64,
+) -> Result<HashSet<Bytes>> {
+    let mut preimages = HashSet::new();
+    let block_numbers = (parent_number + 1..=parent_number + block_count).collect::<Vec<_>>();
+
+    for chunk in block_numbers.chunks(WITNESS_RANGE_BATCH_SIZE) {
+        let mut batch = BatchRequest::new(provider.client());
+        let waiters = chunk
+            .iter()
+            .map(|number| {
+                batch.add_call::<_, ExecutionWitness>(
+                    "debug_executionWitness",
+                    &[BlockNumberOrTag::Number(*number)],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        batch.send().await?;
+
+        for witness in try_join_all(waiters).await? {
+            preimages.extend(witness.state.into_iter().chain(witness.codes).chain(witness.keys));
+        }
+    }
+
+    Ok(preimages)
+}
+
+/// Adds an `eth_getProof` request for the given account, and optionally storage slot, to the list
+/// of batched proof requests, merging it with an existing request for the same block and account.
+pub(crate) fn push_proof_request(
+    requests: &mut Vec<(u64, Address, Vec<B256>)>,
+    block_number: u64,
+    address: Address,
+    slot: Option<B256>,
+) {
+    let index = requests
+        .iter()
+        .position(|(n, a, _)| *n == block_number && *a == address)
+        .unwrap_or_else(|| {
+            requests.push((block_number, address, Vec::new()));
+            requests.len() - 1
+        });
+
+    let slots = &mut requests[index].2;
+    if let Some(slot) = slot.filter(|slot| !slots.contains(slot)) {
+        slots.push(slot);
+    }
+}
@@ -13,8 +13,9 @@ use tracing_subscriber::EnvFilter;
 
 const ABOUT: &str = "
 kona-host is a CLI application that runs the Kona pre-image server and client program. The host
-can run in two modes: server mode and native mode. In server mode, the host runs the pre-image
-server and waits for the client program in the parent process to request pre-images. In native
+can run in three modes: server mode, listener mode and native mode. In server mode, the host runs
+the pre-image server and waits for the client program in the parent process to request pre-images.
+In listener mode, the host accepts client sessions over a TCP or Unix domain socket. In native
 mode, the host runs the client program in a separate thread with the pre-image server in the
 primary thread.
 ";
@@ -41,6 +42,8 @@ pub enum HostMode {
     /// Run the host in super-chain (interop) mode.
     #[cfg(feature = "interop")]
     Super(kona_host::interop::InteropHost),
+    /// Inspect, prune, minimize or export a persistent preimage store.
+    Store(kona_host::store::StoreCommand),
 }
 
 #[tokio::main(flavor = "multi_thread")]
@@ -57,6 +60,9 @@ async fn main() -> Result<()> {
         HostMode::Super(cfg) => {
             cfg.start().await?;
         }
+        HostMode::Store(cfg) => {
+            cfg.run()?;
+        }
     }
 
     info!(target: "host", "Exiting host program.");
@@ -3,7 +3,10 @@
 use super::InteropHost;
 use crate::{
     HintHandler, OnlineHostBackend, OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore,
-    backend::util::store_ordered_trie,
+    backend::util::{
+        fetch_state_nodes_batched, fetch_storage_proofs_batched, push_proof_request,
+        store_ordered_trie,
+    },
 };
 use alloy_consensus::{Header, Sealed};
 use alloy_eips::{
@@ -18,6 +21,7 @@ use alloy_rpc_types::Block;
 use anyhow::{Result, anyhow, ensure};
 use ark_ff::{BigInteger, PrimeField};
 use async_trait::async_trait;
+use futures::future::try_join_all;
 use kona_derive::EthereumDataSource;
 use kona_driver::Driver;
 use kona_executor::TrieDBProvider;
@@ -35,7 +39,7 @@ use kona_proof::{
 use kona_proof_interop::{HintType, PreState};
 use kona_protocol::{BlockInfo, OutputRoot, Predeploys};
 use kona_registry::ROLLUP_CONFIGS;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::task;
 use tracing::{Instrument, debug, info, info_span, warn};
 
@@ -584,8 +588,73 @@ impl HintHandler for InteropHintHandler {
                     "L2PayloadWitness hint not implemented for interop hint handler, ignoring hint"
                 );
             }
+            HintType::L2PayloadWitnessRange => {
+                warn!(
+                    target: "interop_hint_handler",
+                    "L2PayloadWitnessRange hint not implemented for interop hint handler, ignoring hint"
+                );
+            }
         }
 
         Ok(())
     }
+
+    async fn fetch_hints(
+        hints: Vec<Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>>,
+        cfg: &Self::Cfg,
+        providers: &<Self::Cfg as OnlineHostBackendCfg>::Providers,
+        kv: SharedKeyValueStore,
+    ) -> Result<()> {
+        let mut batches = HashMap::<u64, (Vec<B256>, Vec<_>)>::default();
+        let mut remaining = Vec::new();
+
+        // Coalesce the state node and proof hints into batched requests per chain. All other
+        // hints are fetched concurrently.
+        for hint in hints {
+            let data = hint.data.as_ref();
+            match hint.ty {
+                HintType::L2StateNode if data.len() == 32 + 8 => {
+                    let chain_id = u64::from_be_bytes(data[32..].try_into()?);
+                    batches.entry(chain_id).or_default().0.push(B256::from_slice(&data[..32]));
+                }
+                HintType::L2AccountProof if data.len() == 8 + 20 + 8 => {
+                    let chain_id = u64::from_be_bytes(data[28..].try_into()?);
+                    push_proof_request(
+                        &mut batches.entry(chain_id).or_default().1,
+                        u64::from_be_bytes(data[..8].try_into()?),
+                        Address::from_slice(&data[8..28]),
+                        None,
+                    );
+                }
+                HintType::L2AccountStorageProof if data.len() == 8 + 20 + 32 + 8 => {
+                    let chain_id = u64::from_be_bytes(data[60..].try_into()?);
+                    push_proof_request(
+                        &mut batches.entry(chain_id).or_default().1,
+                        u64::from_be_bytes(data[..8].try_into()?),
+                        Address::from_slice(&data[8..28]),
+                        Some(B256::from_slice(&data[28..60])),
+                    );
+                }
+                _ => remaining.push(hint),
+            }
+        }
+
+        let batched = batches.iter().map(|(chain_id, (state_nodes, proof_requests))| {
+            let kv = kv.clone();
+            async move {
+                let provider = providers.l2(chain_id)?;
+                tokio::try_join!(
+                    fetch_state_nodes_batched(provider, state_nodes, &kv),
+                    fetch_storage_proofs_batched(provider, proof_requests, &kv),
+                )?;
+                Ok::<(), anyhow::Error>(())
+            }
+        });
+
+        let remaining =
+            remaining.into_iter().map(|hint| Self::fetch_hint(hint, cfg, providers, kv.clone()));
+        tokio::try_join!(try_join_all(batched), try_join_all(remaining))?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,201 @@
+//! Contains the [WitnessBundle], a portable, single-file encoding of the preimages of a run.
+
+use super::{KeyValueStore, MemoryKeyValueStore, StoreBootInfo};
+use alloy_primitives::{B256, keccak256};
+use anyhow::{Result, anyhow, bail, ensure};
+use miniz_oxide::{deflate::compress_to_vec_zlib, inflate::decompress_to_vec_zlib_with_limit};
+use std::path::Path;
+
+/// The magic bytes that prefix every witness bundle.
+const WITNESS_BUNDLE_MAGIC: [u8; 8] = *b"KONAWTNS";
+
+/// The current version of the witness bundle format.
+const WITNESS_BUNDLE_VERSION: u8 = 2;
+
+/// The zlib compression level of the witness bundle body.
+const WITNESS_BUNDLE_COMPRESSION_LEVEL: u8 = 9;
+
+/// The maximum size of the uncompressed witness bundle body.
+const MAX_BODY_SIZE: usize = 1 << 31;
+
+/// The size of the encoded [StoreBootInfo] within the witness bundle header.
+const BOOT_INFO_SIZE: usize = 32 * 3 + 8 + 1 + 8;
+
+/// The size of the witness bundle header.
+const HEADER_SIZE: usize = WITNESS_BUNDLE_MAGIC.len() + 1 + BOOT_INFO_SIZE + 8 + 32;
+
+/// A portable bundle of preimages, keyed by [PreimageKey], that allows a run to be reproduced
+/// without any RPC access.
+///
+/// **Layout**:
+/// | Field       | Size     | Description                                      |
+/// |-------------|----------|--------------------------------------------------|
+/// | magic       | 8        | `KONAWTNS`                                       |
+/// | version     | 1        | The version of the format.                       |
+/// | boot info   | 113      | The [StoreBootInfo] of the run.                  |
+/// | entry count | 8        | The number of preimages within the bundle.       |
+/// | checksum    | 32       | The `keccak256` digest of the uncompressed body. |
+/// | body        | variable | The zlib-compressed preimages.                   |
+///
+/// The boot info is encoded as `l1 head (32) ++ agreed pre-state (32) ++ claimed post-state (32)
+/// ++ claimed position (8) ++ chain id flag (1) ++ chain id (8)`, where the flag is `1` if the
+/// chain id is present and `0` otherwise.
+///
+/// Each preimage in the body is encoded as `key (32) ++ length (4) ++ value`, sorted by key.
+///
+/// [PreimageKey]: kona_preimage::PreimageKey
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WitnessBundle {
+    /// The boot info of the run that produced the bundle.
+    pub boot_info: StoreBootInfo,
+    /// The preimages within the bundle.
+    pub preimages: MemoryKeyValueStore,
+}
+
+impl WitnessBundle {
+    /// Creates a new [WitnessBundle] from the given boot info and preimages.
+    pub const fn new(boot_info: StoreBootInfo, preimages: MemoryKeyValueStore) -> Self {
+        Self { boot_info, preimages }
+    }
+
+    /// Encodes the [WitnessBundle] into its binary format.
+    ///
+    /// Fails if a preimage is too large to be encoded.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut entries = self.preimages.store.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+
+        let mut body = Vec::new();
+        for (key, value) in entries.iter() {
+            body.extend_from_slice(key.as_slice());
+            let len = u32::try_from(value.len())
+                .map_err(|_| anyhow!("Preimage {key} is too large: {} bytes", value.len()))?;
+            body.extend_from_slice(&len.to_be_bytes());
+            body.extend_from_slice(value);
+        }
+
+        let mut encoded = Vec::with_capacity(HEADER_SIZE + body.len() / 2);
+        encoded.extend_from_slice(&WITNESS_BUNDLE_MAGIC);
+        encoded.push(WITNESS_BUNDLE_VERSION);
+        encoded.extend_from_slice(self.boot_info.l1_head.as_slice());
+        encoded.extend_from_slice(self.boot_info.agreed_l2_pre_state.as_slice());
+        encoded.extend_from_slice(self.boot_info.claimed_l2_post_state.as_slice());
+        encoded.extend_from_slice(&self.boot_info.claimed_l2_position.to_be_bytes());
+        encoded.push(self.boot_info.l2_chain_id.is_some() as u8);
+        encoded.extend_from_slice(&self.boot_info.l2_chain_id.unwrap_or_default().to_be_bytes());
+        encoded.extend_from_slice(&(entries.len() as u64).to_be_bytes());
+        encoded.extend_from_slice(keccak256(&body).as_slice());
+        encoded.extend_from_slice(&compress_to_vec_zlib(&body, WITNESS_BUNDLE_COMPRESSION_LEVEL));
+        Ok(encoded)
+    }
+
+    /// Decodes a [WitnessBundle] from its binary format, verifying the header and checksum.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        ensure!(data.len() >= HEADER_SIZE, "Witness bundle is too short");
+        ensure!(data[..8] == WITNESS_BUNDLE_MAGIC, "Invalid witness bundle magic");
+        ensure!(
+            data[8] == WITNESS_BUNDLE_VERSION,
+            "Unsupported witness bundle version: {}",
+            data[8]
+        );
+
+        let l2_chain_id = match data[113] {
+            0 => None,
+            1 => Some(u64::from_be_bytes(data[114..122].try_into()?)),
+            flag => bail!("Invalid witness bundle chain id flag: {flag}"),
+        };
+        let boot_info = StoreBootInfo {
+            l1_head: B256::from_slice(&data[9..41]),
+            agreed_l2_pre_state: B256::from_slice(&data[41..73]),
+            claimed_l2_post_state: B256::from_slice(&data[73..105]),
+            claimed_l2_position: u64::from_be_bytes(data[105..113].try_into()?),
+            l2_chain_id,
+        };
+        let entry_count = u64::from_be_bytes(data[122..130].try_into()?);
+        let checksum = B256::from_slice(&data[130..HEADER_SIZE]);
+
+        let body = decompress_to_vec_zlib_with_limit(&data[HEADER_SIZE..], MAX_BODY_SIZE)
+            .map_err(|e| anyhow!("Failed to decompress witness bundle: {e:?}"))?;
+        ensure!(keccak256(&body) == checksum, "Witness bundle checksum mismatch");
+
+        let mut preimages = MemoryKeyValueStore::new();
+        let mut cursor = body.as_slice();
+        for _ in 0..entry_count {
+            ensure!(cursor.len() >= 36, "Truncated witness bundle entry");
+            let key = B256::from_slice(&cursor[..32]);
+            let len = u32::from_be_bytes(cursor[32..36].try_into()?) as usize;
+            ensure!(cursor.len() >= 36 + len, "Truncated witness bundle entry");
+
+            preimages.set(key, cursor[36..36 + len].to_vec())?;
+            cursor = &cursor[36 + len..];
+        }
+        ensure!(cursor.is_empty(), "Trailing data in witness bundle");
+
+        Ok(Self { boot_info, preimages })
+    }
+
+    /// Writes the [WitnessBundle] to the file at the given path.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.encode()?)
+            .map_err(|e| anyhow!("Failed to write witness bundle to {path:?}: {e}"))
+    }
+
+    /// Reads a [WitnessBundle] from the file at the given path.
+    pub fn read(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path)
+            .map_err(|e| anyhow!("Failed to read witness bundle from {path:?}: {e}"))?;
+        Self::decode(&data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::{
+        arbitrary::any,
+        collection::{hash_map, vec},
+        proptest,
+        test_runner::Config,
+    };
+
+    proptest! {
+        #![proptest_config(Config::with_cases(16))]
+
+        /// Test that encoding and decoding a [WitnessBundle] is lossless.
+        #[test]
+        fn roundtrip_witness_bundle(k_v in hash_map(any::<[u8; 32]>(), vec(any::<u8>(), 0..128), 0..128)) {
+            let mut preimages = MemoryKeyValueStore::new();
+            k_v.iter().for_each(|(k, v)| {
+                preimages.set(k.into(), v.to_vec()).unwrap();
+            });
+            let boot_info = StoreBootInfo {
+                claimed_l2_position: 10,
+                l2_chain_id: Some(10),
+                ..Default::default()
+            };
+
+            let bundle = WitnessBundle::new(boot_info, preimages);
+            assert_eq!(WitnessBundle::decode(&bundle.encode().unwrap()).unwrap(), bundle);
+        }
+    }
+
+    #[test]
+    fn test_decode_corrupt_bundle() {
+        let mut preimages = MemoryKeyValueStore::new();
+        preimages.set(B256::repeat_byte(0xFF), vec![0xBE, 0xEF]).unwrap();
+        let encoded = WitnessBundle::new(Default::default(), preimages).encode().unwrap();
+
+        // Corrupt the checksum.
+        let mut corrupt = encoded.clone();
+        corrupt[HEADER_SIZE - 1] ^= 0xFF;
+        assert!(WitnessBundle::decode(&corrupt).is_err());
+
+        // Bundles of another version are rejected, even with a valid checksum.
+        for version in [WITNESS_BUNDLE_VERSION - 1, WITNESS_BUNDLE_VERSION + 1] {
+            let mut corrupt = encoded.clone();
+            corrupt[8] = version;
+            let err = WitnessBundle::decode(&corrupt).unwrap_err();
+            assert!(err.to_string().contains("Unsupported witness bundle version"));
+        }
+    }
+}
@@ -3,24 +3,77 @@
 
 use super::{KeyValueStore, MemoryKeyValueStore};
 use alloy_primitives::B256;
-use anyhow::{Result, anyhow};
-use rocksdb::{DB, Options};
+use anyhow::{Result, anyhow, bail};
+use rocksdb::{DB, IteratorMode, Options};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// The name of the column family that holds the metadata of the store.
+const METADATA_CF: &str = "metadata";
+
+/// The key of the [StoreBootInfo] within the metadata column family.
+const BOOT_INFO_KEY: &[u8] = b"boot_info";
+
+/// The boot information that a persistent [DiskKeyValueStore] is tagged with.
+///
+/// A persistent store may only be reused by a later run if the boot information of that run
+/// matches the tag of the store.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreBootInfo {
+    /// The L1 head hash of the run.
+    pub l1_head: B256,
+    /// The agreed upon L2 pre-state commitment. In single-chain mode, this is the agreed L2 output
+    /// root. In interop mode, this is the hash of the agreed pre-state.
+    pub agreed_l2_pre_state: B256,
+    /// The claimed L2 post-state commitment. In single-chain mode, this is the claimed L2 output
+    /// root. In interop mode, this is the claimed super root.
+    pub claimed_l2_post_state: B256,
+    /// The position of the claim. In single-chain mode, this is the claimed L2 block number. In
+    /// interop mode, this is the claimed L2 timestamp.
+    pub claimed_l2_position: u64,
+    /// The L2 chain ID. Absent in interop mode, where the claim spans several chains.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2_chain_id: Option<u64>,
+}
+
 /// A simple, synchronous key-value store that stores data on disk.
+///
+/// An ephemeral store destroys its data directory when it is dropped. A persistent store, opened
+/// via [DiskKeyValueStore::try_new_persistent], keeps its data on disk so that it can be reused by
+/// later runs with the same [StoreBootInfo].
 #[derive(Debug)]
 pub struct DiskKeyValueStore {
     data_directory: PathBuf,
     db: DB,
+    destroy_on_drop: bool,
 }
 
 impl DiskKeyValueStore {
-    /// Create a new [DiskKeyValueStore] with the given data directory.
+    /// Create a new ephemeral [DiskKeyValueStore] with the given data directory. The data
+    /// directory is destroyed when the store is dropped.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the database can't be opened. Use [DiskKeyValueStore::open] to handle the error.
     pub fn new(data_directory: PathBuf) -> Self {
-        let db = DB::open(&Self::get_db_options(), data_directory.as_path())
-            .unwrap_or_else(|e| panic!("Failed to open database at {data_directory:?}: {e}"));
+        Self::open(data_directory, true).unwrap_or_else(|e| panic!("{e}"))
+    }
 
-        Self { data_directory, db }
+    /// Opens a persistent [DiskKeyValueStore] with the given data directory. The data directory is
+    /// kept on disk when the store is dropped.
+    pub fn try_new_persistent(data_directory: PathBuf) -> Result<Self> {
+        Self::open(data_directory, false)
+    }
+
+    /// Opens a [DiskKeyValueStore] with the given data directory, returning an error if the
+    /// database can't be opened, e.g. because it is still locked by another handle. If
+    /// `destroy_on_drop` is set, the data directory is destroyed when the store is dropped.
+    pub fn open(data_directory: PathBuf, destroy_on_drop: bool) -> Result<Self> {
+        let db = DB::open_cf(&Self::get_db_options(), data_directory.as_path(), [METADATA_CF])
+            .map_err(|e| anyhow!("Failed to open database at {data_directory:?}: {e}"))?;
+
+        Ok(Self { data_directory, db, destroy_on_drop })
     }
 
     /// Gets the [Options] for the underlying RocksDB instance.
@@ -28,8 +81,79 @@ impl DiskKeyValueStore {
         let mut options = Options::default();
         options.set_compression_type(rocksdb::DBCompressionType::Snappy);
         options.create_if_missing(true);
+        options.create_missing_column_families(true);
         options
     }
+
+    /// Returns the [StoreBootInfo] that the store is tagged with, if any.
+    pub fn boot_info(&self) -> Result<Option<StoreBootInfo>> {
+        let cf = self.metadata_cf()?;
+        let Some(raw) = self.db.get_cf(cf, BOOT_INFO_KEY)? else {
+            return Ok(None);
+        };
+
+        serde_json::from_slice(&raw)
+            .map(Some)
+            .map_err(|e| anyhow!("Failed to deserialize store boot info: {e}"))
+    }
+
+    /// Tags the store with the given [StoreBootInfo], overwriting any existing tag.
+    ///
+    /// A store should only be tagged once the run that populated it succeeded, so that a failed
+    /// run can't mark an incomplete store as reusable.
+    pub fn set_boot_info(&mut self, boot_info: &StoreBootInfo) -> Result<()> {
+        let cf = self.metadata_cf()?;
+        let raw = serde_json::to_vec(boot_info)?;
+        self.db.put_cf(cf, BOOT_INFO_KEY, raw).map_err(|e| anyhow!("Failed to set boot info: {e}"))
+    }
+
+    /// Checks that the store may be reused by a run with the given [StoreBootInfo].
+    ///
+    /// An untagged store may be reused by any run. A store that is tagged with different boot info
+    /// is rejected.
+    pub fn check_boot_info(&self, boot_info: &StoreBootInfo) -> Result<()> {
+        match self.boot_info()? {
+            Some(existing) if existing != *boot_info => bail!(
+                "Data directory {:?} is tagged with different boot info: {existing:?}",
+                self.data_directory
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns an iterator over all preimage keys within the store.
+    pub fn keys(&self) -> impl Iterator<Item = Result<B256>> + '_ {
+        self.db.full_iterator(IteratorMode::Start).map(|entry| {
+            let (key, _) = entry?;
+            B256::try_from(key.as_ref())
+                .map_err(|e| anyhow!("Failed to convert slice to B256: {e}"))
+        })
+    }
+
+    /// Returns an iterator over all key-value pairs within the store.
+    pub fn entries(&self) -> impl Iterator<Item = Result<(B256, Vec<u8>)>> + '_ {
+        self.db.full_iterator(IteratorMode::Start).map(|entry| {
+            let (key, value) = entry?;
+            let key = B256::try_from(key.as_ref())
+                .map_err(|e| anyhow!("Failed to convert slice to B256: {e}"))?;
+            Ok((key, value.to_vec()))
+        })
+    }
+
+    /// Removes the value associated with the given key.
+    pub fn remove(&mut self, key: B256) -> Result<()> {
+        self.db.delete(*key).map_err(|e| anyhow!("Failed to remove key: {e}"))
+    }
+
+    /// Compacts the underlying database, reclaiming the disk space of removed entries.
+    pub fn compact(&self) {
+        self.db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    }
+
+    /// Returns the handle of the metadata column family.
+    fn metadata_cf(&self) -> Result<&rocksdb::ColumnFamily> {
+        self.db.cf_handle(METADATA_CF).ok_or_else(|| anyhow!("Missing metadata column family"))
+    }
 }
 
 impl KeyValueStore for DiskKeyValueStore {
@@ -44,7 +168,9 @@ impl KeyValueStore for DiskKeyValueStore {
 
 impl Drop for DiskKeyValueStore {
     fn drop(&mut self) {
-        let _ = DB::destroy(&Self::get_db_options(), self.data_directory.as_path());
+        if self.destroy_on_drop {
+            let _ = DB::destroy(&Self::get_db_options(), self.data_directory.as_path());
+        }
     }
 }
 
@@ -53,14 +179,9 @@ impl TryFrom<DiskKeyValueStore> for MemoryKeyValueStore {
 
     fn try_from(disk_store: DiskKeyValueStore) -> Result<Self> {
         let mut memory_store = Self::new();
-        let mut db_iter = disk_store.db.full_iterator(rocksdb::IteratorMode::Start);
-
-        while let Some(Ok((key, value))) = db_iter.next() {
-            memory_store.set(
-                B256::try_from(key.as_ref())
-                    .map_err(|e| anyhow!("Failed to convert slice to B256: {e}"))?,
-                value.to_vec(),
-            )?;
+        for entry in disk_store.entries() {
+            let (key, value) = entry?;
+            memory_store.set(key, value)?;
         }
 
         Ok(memory_store)
@@ -69,15 +190,15 @@ impl TryFrom<DiskKeyValueStore> for MemoryKeyValueStore {
 
 #[cfg(test)]
 mod test {
-    use super::DiskKeyValueStore;
+    use super::{DiskKeyValueStore, StoreBootInfo};
     use crate::kv::{KeyValueStore, MemoryKeyValueStore};
+    use alloy_primitives::B256;
     use proptest::{
         arbitrary::any,
         collection::{hash_map, vec},
         proptest,
         test_runner::Config,
     };
-    use std::env::temp_dir;
 
     proptest! {
         #![proptest_config(Config::with_cases(16))]
@@ -85,8 +206,8 @@ mod test {
         /// Test that converting from a [DiskKeyValueStore] to a [MemoryKeyValueStore] is lossless.
         #[test]
         fn convert_disk_kv_to_mem_kv(k_v in hash_map(any::<[u8; 32]>(), vec(any::<u8>(), 0..128), 1..128)) {
-            let tempdir = temp_dir();
-            let mut disk_kv = DiskKeyValueStore::new(tempdir);
+            let tempdir = tempfile::tempdir().unwrap();
+            let mut disk_kv = DiskKeyValueStore::new(tempdir.path().join("db"));
             k_v.iter().for_each(|(k, v)| {
                 disk_kv.set(k.into(), v.to_vec()).unwrap();
             });
@@ -97,4 +218,45 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_persistent_store_reuse() {
+        let dir = tempfile::tempdir().unwrap();
+        let boot_info = StoreBootInfo { l1_head: B256::repeat_byte(0x01), ..Default::default() };
+
+        let mut disk_kv = DiskKeyValueStore::try_new_persistent(dir.path().to_path_buf()).unwrap();
+        disk_kv.check_boot_info(&boot_info).unwrap();
+        disk_kv.set(B256::repeat_byte(0xFF), vec![0xBE, 0xEF]).unwrap();
+        // The store is only tagged once the run succeeded.
+        assert_eq!(disk_kv.boot_info().unwrap(), None);
+        disk_kv.set_boot_info(&boot_info).unwrap();
+        drop(disk_kv);
+
+        // Reopening the store without destroying it on drop keeps its data.
+        drop(DiskKeyValueStore::open(dir.path().to_path_buf(), false).unwrap());
+
+        let mut disk_kv = DiskKeyValueStore::try_new_persistent(dir.path().to_path_buf()).unwrap();
+        assert_eq!(disk_kv.boot_info().unwrap(), Some(boot_info));
+        assert_eq!(disk_kv.get(B256::repeat_byte(0xFF)), Some(vec![0xBE, 0xEF]));
+
+        // A run with different boot info may not reuse the store.
+        let other = StoreBootInfo { l2_chain_id: Some(10), ..boot_info };
+        assert!(disk_kv.check_boot_info(&other).is_err());
+
+        disk_kv.remove(B256::repeat_byte(0xFF)).unwrap();
+        assert_eq!(disk_kv.keys().count(), 0);
+    }
+
+    #[test]
+    fn test_ephemeral_store_destroyed() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+
+        let mut disk_kv = DiskKeyValueStore::new(data_dir.clone());
+        disk_kv.set(B256::repeat_byte(0xFF), vec![0xBE, 0xEF]).unwrap();
+        drop(disk_kv);
+
+        let disk_kv = DiskKeyValueStore::new(data_dir);
+        assert_eq!(disk_kv.get(B256::repeat_byte(0xFF)), None);
+    }
 }
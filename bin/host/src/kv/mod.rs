@@ -9,11 +9,17 @@ mod mem;
 pub use mem::MemoryKeyValueStore;
 
 mod disk;
-pub use disk::DiskKeyValueStore;
+pub use disk::{DiskKeyValueStore, StoreBootInfo};
 
 mod split;
 pub use split::SplitKeyValueStore;
 
+mod bundle;
+pub use bundle::WitnessBundle;
+
+mod recorder;
+pub use recorder::{RecordingKeyValueStore, WitnessRecorder};
+
 /// A type alias for a shared key-value store.
 pub type SharedKeyValueStore = Arc<RwLock<dyn KeyValueStore + Send + Sync>>;
 
@@ -25,3 +31,16 @@ pub trait KeyValueStore {
     /// Set the value associated with the given key.
     fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()>;
 }
+
+impl<KV> KeyValueStore for Box<KV>
+where
+    KV: KeyValueStore + ?Sized,
+{
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        self.as_ref().get(key)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.as_mut().set(key, value)
+    }
+}
@@ -0,0 +1,97 @@
+//! Contains a [KeyValueStore] wrapper that records every preimage read from the wrapped
+//! [KeyValueStore].
+
+use super::{KeyValueStore, MemoryKeyValueStore, StoreBootInfo, WitnessBundle};
+use alloy_primitives::B256;
+use anyhow::{Result, anyhow};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+/// A shared handle to the preimages recorded by one or more [RecordingKeyValueStore]s.
+#[derive(Debug, Clone, Default)]
+pub struct WitnessRecorder {
+    recorded: Arc<Mutex<MemoryKeyValueStore>>,
+}
+
+impl WitnessRecorder {
+    /// Records the given preimage.
+    fn record(&self, key: B256, value: &[u8]) {
+        if let Ok(mut recorded) = self.recorded.lock() {
+            recorded.store.entry(key).or_insert_with(|| value.to_vec());
+        }
+    }
+
+    /// Returns the number of preimages that have been recorded.
+    pub fn len(&self) -> usize {
+        self.recorded.lock().map(|recorded| recorded.store.len()).unwrap_or_default()
+    }
+
+    /// Returns `true` if no preimages have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes all recorded preimages to a [WitnessBundle] at the given path.
+    pub fn export(&self, boot_info: StoreBootInfo, path: &Path) -> Result<()> {
+        let recorded =
+            self.recorded.lock().map_err(|_| anyhow!("Witness recorder lock poisoned"))?.clone();
+        WitnessBundle::new(boot_info, recorded).write(path)
+    }
+}
+
+/// A [KeyValueStore] that records every preimage read from the wrapped [KeyValueStore] into a
+/// [WitnessRecorder].
+#[derive(Debug)]
+pub struct RecordingKeyValueStore<KV>
+where
+    KV: KeyValueStore,
+{
+    inner: KV,
+    recorder: WitnessRecorder,
+}
+
+impl<KV> RecordingKeyValueStore<KV>
+where
+    KV: KeyValueStore,
+{
+    /// Create a new [RecordingKeyValueStore] that wraps the given [KeyValueStore].
+    pub const fn new(inner: KV, recorder: WitnessRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl<KV> KeyValueStore for RecordingKeyValueStore<KV>
+where
+    KV: KeyValueStore,
+{
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        let value = self.inner.get(key)?;
+        self.recorder.record(key, &value);
+        Some(value)
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        self.inner.set(key, value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_records_reads_only() {
+        let recorder = WitnessRecorder::default();
+        let mut kv = RecordingKeyValueStore::new(MemoryKeyValueStore::new(), recorder.clone());
+
+        kv.set(B256::repeat_byte(0x01), vec![0x01]).unwrap();
+        kv.set(B256::repeat_byte(0x02), vec![0x02]).unwrap();
+        assert!(recorder.is_empty());
+
+        assert_eq!(kv.get(B256::repeat_byte(0x01)), Some(vec![0x01]));
+        assert_eq!(kv.get(B256::repeat_byte(0x03)), None);
+        assert_eq!(recorder.len(), 1);
+    }
+}
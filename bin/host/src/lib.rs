@@ -2,18 +2,24 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod server;
-pub use server::{PreimageServer, PreimageServerError};
+pub use server::{PreimageServer, PreimageServerError, accept_sessions, serve_sessions};
 
 mod kv;
 pub use kv::{
-    DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, SharedKeyValueStore, SplitKeyValueStore,
+    DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, RecordingKeyValueStore,
+    SharedKeyValueStore, SplitKeyValueStore, StoreBootInfo, WitnessBundle, WitnessRecorder,
 };
 
 mod backend;
-pub use backend::{HintHandler, OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg};
+pub use backend::{
+    HintHandler, OfflineHostBackend, OnlineHostBackend, OnlineHostBackendCfg, PreimageTracer,
+    ProfileReportBackend, TraceEntry, TraceEvent, TracingHostBackend,
+};
 
 pub mod eth;
 
+pub mod store;
+
 #[cfg(feature = "single")]
 pub mod single;
 
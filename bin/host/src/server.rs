@@ -1,11 +1,13 @@
 //! This module contains the [PreimageServer] struct and its implementation.
 
 use kona_preimage::{
-    HintReaderServer, PreimageOracleServer, PreimageServerBackend, errors::PreimageOracleError,
+    Channel, HintReader, HintReaderServer, OracleServer, PreimageOracleServer,
+    PreimageServerBackend, SocketChannel, SocketEndpoint, SocketListener,
+    errors::PreimageOracleError,
 };
-use std::sync::Arc;
-use tokio::spawn;
-use tracing::{error, info};
+use std::{io, sync::Arc};
+use tokio::{spawn, sync::mpsc, task::JoinSet};
+use tracing::{error, info, warn};
 
 /// The [PreimageServer] is responsible for waiting for incoming preimage requests and
 /// serving them to the client.
@@ -97,3 +99,91 @@ where
         }
     }
 }
+
+/// Serves a [PreimageServer] for every client session received over `sessions`, each session
+/// consisting of a hint and a preimage channel. The backend of every session is created with
+/// `new_backend`, so that sessions don't share per-session state such as their last hint.
+///
+/// Returns once `sessions` is closed and all sessions have finished, with the first error
+/// encountered by any of the sessions. Errors of individual sessions are logged as they occur, and
+/// do not affect other sessions.
+pub async fn serve_sessions<C, B, F>(
+    mut sessions: mpsc::Receiver<(C, C)>,
+    mut new_backend: F,
+) -> Result<(), PreimageServerError>
+where
+    C: Channel + Send + Sync + 'static,
+    B: PreimageServerBackend + Send + Sync + 'static,
+    F: FnMut() -> B,
+{
+    let mut tasks = JoinSet::new();
+    let mut result = Ok(());
+    let mut record = |session_result: Result<(), PreimageServerError>| {
+        if let Err(e) = session_result {
+            error!(target: "host_server", "Preimage server session failed: {e}");
+            if result.is_ok() {
+                result = Err(e);
+            }
+        }
+    };
+
+    loop {
+        tokio::select! {
+            session = sessions.recv() => {
+                let Some((hint, preimage)) = session else {
+                    break;
+                };
+                let oracle_server = OracleServer::new(preimage);
+                let hint_reader = HintReader::new(hint);
+                let backend = Arc::new(new_backend());
+                tasks.spawn(PreimageServer::new(oracle_server, hint_reader, backend).start());
+            }
+            Some(joined) = tasks.join_next() => {
+                record(joined.map_err(PreimageServerError::from).and_then(|r| r));
+            }
+        }
+    }
+
+    while let Some(joined) = tasks.join_next().await {
+        record(joined.map_err(PreimageServerError::from).and_then(|r| r));
+    }
+    result
+}
+
+/// Binds a [SocketListener] to the given [SocketEndpoint], and forwards the hint and preimage
+/// channels of every accepted client session over the returned receiver, ready to be passed to
+/// [serve_sessions].
+///
+/// Sessions are accepted until the host is interrupted. The receiver is then closed, which lets
+/// the sessions in flight finish before the server exits.
+pub async fn accept_sessions(
+    endpoint: &SocketEndpoint,
+) -> io::Result<mpsc::Receiver<(SocketChannel, SocketChannel)>> {
+    let mut listener = SocketListener::bind(endpoint).await?;
+    info!(target: "host", "Listening for client sessions on {endpoint}");
+
+    let (session_tx, session_rx) = mpsc::channel(1);
+    spawn(async move {
+        let shutdown = tokio::signal::ctrl_c();
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((session, channels)) => {
+                        info!(target: "host", "Accepted client session {session}");
+                        if session_tx.send((channels.hint, channels.preimage)).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => warn!(target: "host", "Failed to accept client session: {e}"),
+                },
+                _ = &mut shutdown => {
+                    info!(target: "host", "Interrupted, no longer accepting client sessions");
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(session_rx)
+}
@@ -2,28 +2,30 @@
 
 use super::{SingleChainHintHandler, SingleChainLocalInputs};
 use crate::{
-    DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::http_provider, server::PreimageServerError,
+    DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
+    OnlineHostBackendCfg, PreimageTracer, ProfileReportBackend, RecordingKeyValueStore,
+    SharedKeyValueStore, SplitKeyValueStore, StoreBootInfo, TracingHostBackend, WitnessBundle,
+    WitnessRecorder,
+    eth::http_provider,
+    server::{PreimageServerError, accept_sessions, serve_sessions},
 };
 use alloy_primitives::B256;
 use alloy_provider::RootProvider;
 use clap::Parser;
 use kona_cli::cli_styles;
 use kona_genesis::RollupConfig;
-use kona_preimage::{
-    BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer,
-};
+use kona_preimage::{BidirectionalChannel, Channel, HintWriter, OracleReader, SocketEndpoint};
 use kona_proof::HintType;
 use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
 use serde::Serialize;
-use std::{path::PathBuf, sync::Arc};
+use std::{num::NonZeroUsize, path::PathBuf, sync::Arc};
 use tokio::{
-    sync::RwLock,
+    sync::{RwLock, mpsc},
     task::{self, JoinHandle},
 };
+use tracing::info;
 
 /// The host binary CLI application arguments.
 #[derive(Default, Parser, Serialize, Clone, Debug)]
@@ -77,16 +79,62 @@ pub struct SingleChainHost {
         long,
         visible_alias = "db",
         required_unless_present_all = ["l2_node_address", "l1_node_address", "l1_beacon_address"],
+        required_unless_present = "import_witness",
         env
     )]
     pub data_dir: Option<PathBuf>,
+    /// Keep the data directory on disk after the run, tagged with the boot info of the run. A
+    /// later run with matching boot info reuses the preimages within the data directory.
+    #[arg(long, requires = "data_dir", env)]
+    pub persist_data_dir: bool,
+    /// Write every preimage read by the client program to a portable witness bundle at the given
+    /// path once the run completes.
+    #[arg(long, env)]
+    pub export_witness: Option<PathBuf>,
+    /// Serve preimages from the witness bundle at the given path, without any RPC access.
+    #[arg(
+        long,
+        conflicts_with_all = ["data_dir", "l2_node_address", "l1_node_address", "l1_beacon_address"],
+        env
+    )]
+    pub import_witness: Option<PathBuf>,
+    /// Record every hint and preimage request served by the host, along with its response time, to
+    /// a JSON lines trace at the given path.
+    #[arg(long, env)]
+    pub preimage_trace: Option<PathBuf>,
+    /// Write the profile report sent by a profiling build of the client program to the given
+    /// path. If not provided, the report is logged instead.
+    #[arg(long, env)]
+    pub profile_report: Option<PathBuf>,
+    /// Prefetch received hints in the background with up to the given number of concurrent
+    /// workers. If not provided, hints are only fetched once the client requests a preimage that
+    /// is missing from the key-value store.
+    #[arg(long, env)]
+    pub hint_concurrency: Option<NonZeroUsize>,
+    /// The maximum number of prefetched hints that are coalesced into a single batch of JSON-RPC
+    /// requests.
+    #[arg(long, default_value_t = 16, requires = "hint_concurrency", env)]
+    pub hint_batch_size: usize,
     /// Run the client program natively.
-    #[arg(long, conflicts_with = "server", required_unless_present = "server")]
+    #[arg(
+        long,
+        conflicts_with_all = ["server", "listen"],
+        required_unless_present_any = ["server", "listen"]
+    )]
     pub native: bool,
     /// Run in pre-image server mode without executing any client program. If not provided, the
     /// host will run the client program in the host process.
-    #[arg(long, conflicts_with = "native", required_unless_present = "native")]
+    #[arg(
+        long,
+        conflicts_with_all = ["native", "listen"],
+        required_unless_present_any = ["native", "listen"]
+    )]
     pub server: bool,
+    /// Run in pre-image server mode, accepting client sessions on the given socket endpoint
+    /// (`tcp://<ip>:<port>` or `unix://<path>`) rather than serving a single client over the
+    /// pre-image pipes.
+    #[arg(long, conflicts_with_all = ["native", "server"], env)]
+    pub listen: Option<SocketEndpoint>,
     /// The L2 chain ID of a supported chain. If provided, the host will look for the corresponding
     /// rollup config in the superchain registry.
     #[arg(
@@ -127,6 +175,9 @@ pub enum SingleChainHostError {
     /// Task failed to execute to completion.
     #[error("Join error: {0}")]
     ExecutionError(#[from] tokio::task::JoinError),
+    /// An error when opening the key-value store.
+    #[error("Key-value store error: {0}")]
+    KeyValueStoreError(anyhow::Error),
     /// Any other error.
     #[error("Error: {0}")]
     Other(&'static str),
@@ -135,12 +186,16 @@ pub enum SingleChainHostError {
 impl SingleChainHost {
     /// Starts the [SingleChainHost] application.
     pub async fn start(self) -> Result<(), SingleChainHostError> {
-        if self.server {
+        if let Some(ref endpoint) = self.listen {
+            self.start_listener(endpoint).await?.await??;
+            self.tag_data_dir()
+        } else if self.server {
             let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
 
-            self.start_server(hint, preimage).await?.await?
+            self.start_server(hint, preimage).await?.await??;
+            self.tag_data_dir()
         } else {
             self.start_native().await
         }
@@ -155,41 +210,92 @@ impl SingleChainHost {
     where
         C: Channel + Send + Sync + 'static,
     {
-        let kv_store = self.create_key_value_store()?;
+        let (session_tx, session_rx) = mpsc::channel(1);
+        session_tx
+            .send((hint, preimage))
+            .await
+            .map_err(|_| SingleChainHostError::Other("Failed to start session"))?;
+        self.start_sessions(session_rx).await
+    }
+
+    /// Starts the preimage server, accepting client sessions on the given [SocketEndpoint] until
+    /// the host is interrupted. The server exits once the sessions in flight have finished.
+    pub async fn start_listener(
+        &self,
+        endpoint: &SocketEndpoint,
+    ) -> Result<JoinHandle<Result<(), SingleChainHostError>>, SingleChainHostError> {
+        let sessions = accept_sessions(endpoint).await?;
+        self.start_sessions(sessions).await
+    }
+
+    /// Starts the preimage server, serving every client session received over `sessions` from a
+    /// shared backend until `sessions` is closed.
+    async fn start_sessions<C>(
+        &self,
+        sessions: mpsc::Receiver<(C, C)>,
+    ) -> Result<JoinHandle<Result<(), SingleChainHostError>>, SingleChainHostError>
+    where
+        C: Channel + Send + Sync + 'static,
+    {
+        let recorder = self.export_witness.is_some().then(WitnessRecorder::default);
+        let kv_store = self.create_key_value_store(recorder.as_ref())?;
+        let tracer = self
+            .preimage_trace
+            .as_deref()
+            .map(PreimageTracer::create)
+            .transpose()
+            .map_err(SingleChainHostError::KeyValueStoreError)?;
+
+        let profile_report = self.profile_report.clone();
 
         let task_handle = if self.is_offline() {
-            task::spawn(async {
-                PreimageServer::new(
-                    OracleServer::new(preimage),
-                    HintReader::new(hint),
-                    Arc::new(OfflineHostBackend::new(kv_store)),
+            let new_backend = move || {
+                ProfileReportBackend::new(
+                    TracingHostBackend::new(
+                        OfflineHostBackend::new(kv_store.clone()),
+                        tracer.clone(),
+                    ),
+                    profile_report.clone(),
                 )
-                .start()
-                .await
-                .map_err(SingleChainHostError::from)
+            };
+            task::spawn(async {
+                serve_sessions(sessions, new_backend).await.map_err(SingleChainHostError::from)
             })
         } else {
             let providers = self.create_providers().await?;
-            let backend = OnlineHostBackend::new(
+            let mut backend = OnlineHostBackend::new(
                 self.clone(),
                 kv_store.clone(),
                 providers,
                 SingleChainHintHandler,
             )
-            .with_proactive_hint(HintType::L2PayloadWitness);
+            .with_proactive_hint(HintType::L2PayloadWitness)
+            .with_proactive_hint(HintType::L2PayloadWitnessRange);
+            if let Some(concurrency) = self.hint_concurrency {
+                backend = backend.with_prefetching(concurrency.get(), self.hint_batch_size);
+            }
 
-            task::spawn(async {
-                PreimageServer::new(
-                    OracleServer::new(preimage),
-                    HintReader::new(hint),
-                    Arc::new(backend),
+            let new_backend = move || {
+                ProfileReportBackend::new(
+                    TracingHostBackend::new(backend.new_session(), tracer.clone()),
+                    profile_report.clone(),
                 )
-                .start()
-                .await
-                .map_err(SingleChainHostError::from)
+            };
+            task::spawn(async {
+                serve_sessions(sessions, new_backend).await.map_err(SingleChainHostError::from)
             })
         };
 
+        // If a witness bundle was requested, export the recorded preimages once the server exits.
+        if let (Some(path), Some(recorder)) = (self.export_witness.clone(), recorder) {
+            let boot_info = self.boot_info();
+            return Ok(task::spawn(async move {
+                task_handle.await??;
+                info!(target: "host", "Exporting {} preimages to {path:?}", recorder.len());
+                recorder.export(boot_info, &path).map_err(SingleChainHostError::KeyValueStoreError)
+            }));
+        }
+
         Ok(task_handle)
     }
 
@@ -205,7 +311,10 @@ impl SingleChainHost {
             HintWriter::new(hint.client),
         ));
 
-        let (_, client_result) = tokio::try_join!(server_task, client_task)?;
+        let (server_result, client_result) = tokio::try_join!(server_task, client_task)?;
+        if server_result.is_ok() && client_result.is_ok() {
+            self.tag_data_dir()?;
+        }
 
         // Bubble up the exit status of the client program if execution completes.
         std::process::exit(client_result.is_err() as i32)
@@ -216,7 +325,7 @@ impl SingleChainHost {
         self.l1_node_address.is_none() &&
             self.l2_node_address.is_none() &&
             self.l1_beacon_address.is_none() &&
-            self.data_dir.is_some()
+            (self.data_dir.is_some() || self.import_witness.is_some())
     }
 
     /// Reads the [RollupConfig] from the file system and returns it as a string.
@@ -234,18 +343,69 @@ impl SingleChainHost {
         serde_json::from_str(&ser_config).map_err(SingleChainHostError::ParseError)
     }
 
-    /// Creates the key-value store for the host backend.
-    pub fn create_key_value_store(&self) -> Result<SharedKeyValueStore, SingleChainHostError> {
+    /// Returns the [StoreBootInfo] that a persistent data directory is tagged with.
+    pub fn boot_info(&self) -> StoreBootInfo {
+        StoreBootInfo {
+            l1_head: self.l1_head,
+            agreed_l2_pre_state: self.agreed_l2_output_root,
+            claimed_l2_post_state: self.claimed_l2_output_root,
+            claimed_l2_position: self.claimed_l2_block_number,
+            l2_chain_id: self.l2_chain_id,
+        }
+    }
+
+    /// Tags the persistent data directory with the [StoreBootInfo] of the run. Must only be called
+    /// once the run succeeded, after the key-value store of the run was closed.
+    fn tag_data_dir(&self) -> Result<(), SingleChainHostError> {
+        if let (Some(data_dir), true) = (self.data_dir.as_ref(), self.persist_data_dir) {
+            DiskKeyValueStore::try_new_persistent(data_dir.clone())
+                .and_then(|mut store| store.set_boot_info(&self.boot_info()))
+                .map_err(SingleChainHostError::KeyValueStoreError)?;
+        }
+        Ok(())
+    }
+
+    /// Creates the key-value store for the host backend. If a [WitnessRecorder] is passed, every
+    /// preimage read from the remote store is recorded.
+    pub fn create_key_value_store(
+        &self,
+        recorder: Option<&WitnessRecorder>,
+    ) -> Result<SharedKeyValueStore, SingleChainHostError> {
         let local_kv_store = SingleChainLocalInputs::new(self.clone());
 
-        let kv_store: SharedKeyValueStore = if let Some(ref data_dir) = self.data_dir {
-            let disk_kv_store = DiskKeyValueStore::new(data_dir.clone());
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, disk_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
+        let remote_kv_store: Box<dyn KeyValueStore + Send + Sync> =
+            if let Some(ref path) = self.import_witness {
+                let bundle =
+                    WitnessBundle::read(path).map_err(SingleChainHostError::KeyValueStoreError)?;
+                if bundle.boot_info != self.boot_info() {
+                    return Err(SingleChainHostError::Other(
+                        "Witness bundle boot info does not match the boot info of the run.",
+                    ));
+                }
+                Box::new(bundle.preimages)
+            } else if let Some(ref data_dir) = self.data_dir {
+                if self.persist_data_dir {
+                    let disk_kv_store = DiskKeyValueStore::try_new_persistent(data_dir.clone())
+                        .map_err(SingleChainHostError::KeyValueStoreError)?;
+                    disk_kv_store
+                        .check_boot_info(&self.boot_info())
+                        .map_err(SingleChainHostError::KeyValueStoreError)?;
+                    Box::new(disk_kv_store)
+                } else {
+                    Box::new(
+                        DiskKeyValueStore::open(data_dir.clone(), true)
+                            .map_err(SingleChainHostError::KeyValueStoreError)?,
+                    )
+                }
+            } else {
+                Box::new(MemoryKeyValueStore::new())
+            };
+
+        let kv_store: SharedKeyValueStore = if let Some(recorder) = recorder {
+            let recording_kv_store = RecordingKeyValueStore::new(remote_kv_store, recorder.clone());
+            Arc::new(RwLock::new(SplitKeyValueStore::new(local_kv_store, recording_kv_store)))
         } else {
-            let mem_kv_store = MemoryKeyValueStore::new();
-            let split_kv_store = SplitKeyValueStore::new(local_kv_store, mem_kv_store);
-            Arc::new(RwLock::new(split_kv_store))
+            Arc::new(RwLock::new(SplitKeyValueStore::new(local_kv_store, remote_kv_store)))
         };
 
         Ok(kv_store)
@@ -346,16 +506,79 @@ mod test {
                 .as_slice(),
                 true,
             ),
+            (
+                ["--server", "--l2-chain-id", "0", "--data-dir", "dummy", "--persist-data-dir"]
+                    .as_slice(),
+                true,
+            ),
+            (["--native", "--l2-chain-id", "0", "--import-witness", "dummy"].as_slice(), true),
+            (
+                ["--listen", "tcp://127.0.0.1:7000", "--l2-chain-id", "0", "--data-dir", "dummy"]
+                    .as_slice(),
+                true,
+            ),
+            (
+                ["--listen", "unix:///tmp/kona.sock", "--l2-chain-id", "0", "--data-dir", "dummy"]
+                    .as_slice(),
+                true,
+            ),
             // invalid
             (["--server", "--native", "--l2-chain-id", "0"].as_slice(), false),
             (["--l2-chain-id", "0", "--rollup-config-path", "dummy", "--server"].as_slice(), false),
             (["--server"].as_slice(), false),
+            (
+                [
+                    "--listen",
+                    "tcp://127.0.0.1:7000",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                ["--listen", "127.0.0.1:7000", "--l2-chain-id", "0", "--data-dir", "dummy"]
+                    .as_slice(),
+                false,
+            ),
             (["--native"].as_slice(), false),
             (["--rollup-config-path", "dummy"].as_slice(), false),
             (["--l2-chain-id", "0"].as_slice(), false),
             (["--l1-node-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
             (["--l2-node-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
             (["--l1-beacon-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--persist-data-dir",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--import-witness",
+                    "dummy",
+                ]
+                .as_slice(),
+                false,
+            ),
             ([].as_slice(), false),
         ];
 
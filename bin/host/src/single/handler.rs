@@ -1,7 +1,12 @@
 //! [HintHandler] for the [SingleChainHost].
 
 use crate::{
-    HintHandler, OnlineHostBackendCfg, backend::util::store_ordered_trie, kv::SharedKeyValueStore,
+    HintHandler, OnlineHostBackendCfg,
+    backend::util::{
+        fetch_execution_witness_range, fetch_state_nodes_batched, fetch_storage_proofs_batched,
+        push_proof_request, store_ordered_trie,
+    },
+    kv::SharedKeyValueStore,
     single::cfg::SingleChainHost,
 };
 use alloy_consensus::Header;
@@ -16,6 +21,7 @@ use alloy_rpc_types::{Block, debug::ExecutionWitness};
 use anyhow::{Result, anyhow, ensure};
 use ark_ff::{BigInteger, PrimeField};
 use async_trait::async_trait;
+use futures::future::try_join_all;
 use kona_preimage::{PreimageKey, PreimageKeyType};
 use kona_proof::{Hint, HintType, l1::ROOTS_OF_UNITY};
 use kona_protocol::{BlockInfo, OutputRoot, Predeploys};
@@ -372,8 +378,108 @@ impl HintHandler for SingleChainHintHandler {
                     kv_lock.set(key.into(), preimage.into())?;
                 }
             }
+            HintType::L2PayloadWitnessRange => {
+                if !cfg.enable_experimental_witness_endpoint {
+                    warn!(
+                        target: "single_hint_handler",
+                        "L2PayloadWitnessRange hint was sent, but payload witness is disabled. Skipping hint."
+                    );
+                    return Ok(());
+                }
+
+                ensure!(hint.data.len() >= 32 + 8, "Invalid hint data length");
+
+                let parent_block_hash = B256::from_slice(&hint.data.as_ref()[..32]);
+                let block_count = u64::from_be_bytes(hint.data.as_ref()[32..40].try_into()?);
+
+                let parent_number = providers
+                    .l2
+                    .get_block_by_hash(parent_block_hash)
+                    .await?
+                    .ok_or(anyhow!("Block not found"))?
+                    .header
+                    .number;
+
+                let Ok(preimages) =
+                    fetch_execution_witness_range(&providers.l2, parent_number, block_count).await
+                else {
+                    // Allow this hint to fail silently, as not all execution clients support
+                    // the `debug_executionWitness` method. Preimages that the range doesn't
+                    // cover are fetched on demand during execution.
+                    return Ok(());
+                };
+
+                let mut kv_lock = kv.write().await;
+                for preimage in preimages {
+                    let computed_hash = keccak256(preimage.as_ref());
+
+                    let key = PreimageKey::new_keccak256(*computed_hash);
+                    kv_lock.set(key.into(), preimage.into())?;
+                }
+            }
+            HintType::ClientProfile => {
+                // Profile reports are intercepted by the `ProfileReportBackend`, and carry no
+                // preimages to fetch.
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_hints(
+        hints: Vec<Hint<<Self::Cfg as OnlineHostBackendCfg>::HintType>>,
+        cfg: &Self::Cfg,
+        providers: &<Self::Cfg as OnlineHostBackendCfg>::Providers,
+        kv: SharedKeyValueStore,
+    ) -> Result<()> {
+        let mut state_nodes = Vec::new();
+        let mut proof_requests = Vec::new();
+        let mut remaining = Vec::new();
+
+        // Coalesce the state node and proof hints into batched requests. All other hints are
+        // fetched concurrently.
+        for hint in hints {
+            let data = hint.data.as_ref();
+            match hint.ty {
+                HintType::L2StateNode if data.len() == 32 => {
+                    state_nodes.push(B256::from_slice(data));
+                }
+                HintType::L2AccountProof if data.len() == 8 + 20 => push_proof_request(
+                    &mut proof_requests,
+                    u64::from_be_bytes(data[..8].try_into()?),
+                    Address::from_slice(&data[8..28]),
+                    None,
+                ),
+                HintType::L2AccountStorageProof if data.len() == 8 + 20 + 32 => push_proof_request(
+                    &mut proof_requests,
+                    u64::from_be_bytes(data[..8].try_into()?),
+                    Address::from_slice(&data[8..28]),
+                    Some(B256::from_slice(&data[28..])),
+                ),
+                _ => remaining.push(hint),
+            }
         }
 
+        if !state_nodes.is_empty() {
+            warn!(
+                target: "single_hint_handler",
+                "L2StateNode hints were sent for {} node hashes",
+                state_nodes.len()
+            );
+            warn!(
+                target: "single_hint_handler",
+                "`debug_executePayload` failed to return a complete witness."
+            );
+        }
+
+        let remaining =
+            remaining.into_iter().map(|hint| Self::fetch_hint(hint, cfg, providers, kv.clone()));
+        tokio::try_join!(
+            fetch_state_nodes_batched(&providers.l2, &state_nodes, &kv),
+            fetch_storage_proofs_batched(&providers.l2, &proof_requests, &kv),
+            try_join_all(remaining),
+        )?;
+
         Ok(())
     }
 }
@@ -0,0 +1,277 @@
+//! This module contains the `store` mode of the host, used to inspect, prune, minimize and export
+//! persistent preimage stores.
+
+use crate::{DiskKeyValueStore, PreimageTracer, WitnessBundle};
+use alloy_primitives::B256;
+use anyhow::{Result, ensure};
+use clap::{Parser, Subcommand, ValueEnum};
+use kona_cli::cli_styles;
+use kona_preimage::PreimageKeyType;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// The `store` CLI application, used to manage persistent preimage stores.
+#[derive(Parser, Serialize, Clone, Debug)]
+#[command(styles = cli_styles())]
+pub struct StoreCommand {
+    /// The action to perform on the store.
+    #[command(subcommand)]
+    pub action: StoreAction,
+}
+
+/// The actions that can be performed on a persistent preimage store.
+#[derive(Subcommand, Serialize, Clone, Debug)]
+pub enum StoreAction {
+    /// Print the boot info and the number of preimages held by the store.
+    Info(StoreInfoArgs),
+    /// Remove preimages from the store.
+    Prune(StorePruneArgs),
+    /// Export all preimages within the store to a portable witness bundle.
+    Export(StoreExportArgs),
+    /// Remove all preimages from the store that were not read during a traced run.
+    Minimize(StoreMinimizeArgs),
+}
+
+/// The arguments for the `store info` action.
+#[derive(Parser, Serialize, Clone, Debug)]
+pub struct StoreInfoArgs {
+    /// The data directory of the persistent preimage store.
+    #[arg(long, visible_alias = "db", env)]
+    pub data_dir: PathBuf,
+}
+
+/// The arguments for the `store prune` action.
+#[derive(Parser, Serialize, Clone, Debug)]
+pub struct StorePruneArgs {
+    /// The data directory of the persistent preimage store.
+    #[arg(long, visible_alias = "db", env)]
+    pub data_dir: PathBuf,
+    /// The preimage key types to remove from the store.
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub key_types: Vec<StoreKeyType>,
+}
+
+/// The arguments for the `store export` action.
+#[derive(Parser, Serialize, Clone, Debug)]
+pub struct StoreExportArgs {
+    /// The data directory of the persistent preimage store.
+    #[arg(long, visible_alias = "db", env)]
+    pub data_dir: PathBuf,
+    /// The path to write the witness bundle to.
+    #[arg(long, short)]
+    pub output: PathBuf,
+}
+
+/// The arguments for the `store minimize` action.
+#[derive(Parser, Serialize, Clone, Debug)]
+pub struct StoreMinimizeArgs {
+    /// The data directory of the persistent preimage store.
+    #[arg(long, visible_alias = "db", env)]
+    pub data_dir: PathBuf,
+    /// The preimage trace of the run, as written by `--preimage-trace`.
+    #[arg(long)]
+    pub trace: PathBuf,
+}
+
+/// The preimage key types that may be held by a persistent preimage store.
+#[derive(ValueEnum, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StoreKeyType {
+    /// Keccak256 preimages.
+    Keccak256,
+    /// Sha256 preimages.
+    Sha256,
+    /// Blob field elements.
+    Blob,
+    /// Precompile results.
+    Precompile,
+}
+
+impl From<StoreKeyType> for PreimageKeyType {
+    fn from(key_type: StoreKeyType) -> Self {
+        match key_type {
+            StoreKeyType::Keccak256 => Self::Keccak256,
+            StoreKeyType::Sha256 => Self::Sha256,
+            StoreKeyType::Blob => Self::Blob,
+            StoreKeyType::Precompile => Self::Precompile,
+        }
+    }
+}
+
+/// A summary of the contents of a persistent preimage store.
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreSummary {
+    /// The boot info that the store is tagged with, if any.
+    pub boot_info: Option<crate::StoreBootInfo>,
+    /// The number of preimages held by the store, keyed by preimage key type.
+    pub entries: BTreeMap<String, u64>,
+    /// The total size of all preimages held by the store, in bytes.
+    pub total_size: u64,
+}
+
+impl StoreCommand {
+    /// Runs the [StoreCommand].
+    pub fn run(self) -> Result<()> {
+        match self.action {
+            StoreAction::Info(args) => {
+                let store = open_existing(&args.data_dir)?;
+                let summary = summarize(&store)?;
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            }
+            StoreAction::Prune(args) => {
+                let mut store = open_existing(&args.data_dir)?;
+                let key_types =
+                    args.key_types.into_iter().map(PreimageKeyType::from).collect::<Vec<_>>();
+
+                let removed = prune(&mut store, |key| {
+                    PreimageKeyType::try_from(key[0])
+                        .is_ok_and(|key_type| key_types.contains(&key_type))
+                })?;
+                info!(target: "host_store", "Pruned {removed} preimages from {:?}", args.data_dir);
+            }
+            StoreAction::Minimize(args) => {
+                let mut store = open_existing(&args.data_dir)?;
+                let read_keys = PreimageTracer::read_keys(&args.trace)?;
+
+                let removed = prune(&mut store, |key| !read_keys.contains(key))?;
+                info!(
+                    target: "host_store",
+                    "Removed {removed} unread preimages from {:?}, {} preimages remain",
+                    args.data_dir,
+                    read_keys.len()
+                );
+            }
+            StoreAction::Export(args) => {
+                let store = open_existing(&args.data_dir)?;
+                let boot_info = store.boot_info()?.unwrap_or_default();
+                let bundle = WitnessBundle::new(boot_info, store.try_into()?);
+                bundle.write(&args.output)?;
+                info!(
+                    target: "host_store",
+                    "Exported {} preimages to {:?}",
+                    bundle.preimages.store.len(),
+                    args.output
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Opens the persistent preimage store at the given data directory, which must already exist.
+fn open_existing(data_dir: &Path) -> Result<DiskKeyValueStore> {
+    ensure!(data_dir.is_dir(), "No preimage store found at {data_dir:?}");
+    DiskKeyValueStore::try_new_persistent(data_dir.to_path_buf())
+}
+
+/// Summarizes the contents of the given [DiskKeyValueStore].
+pub fn summarize(store: &DiskKeyValueStore) -> Result<StoreSummary> {
+    let mut summary = StoreSummary { boot_info: store.boot_info()?, ..Default::default() };
+    for entry in store.entries() {
+        let (key, value) = entry?;
+        let key_type = PreimageKeyType::try_from(key[0])
+            .map_or_else(|_| "unknown".to_string(), |ty| format!("{ty:?}").to_lowercase());
+
+        *summary.entries.entry(key_type).or_default() += 1;
+        summary.total_size += value.len() as u64;
+    }
+
+    Ok(summary)
+}
+
+/// Removes all preimages with a key matching the given predicate from the [DiskKeyValueStore],
+/// and compacts the store afterwards. Returns the number of removed preimages.
+pub fn prune<F>(store: &mut DiskKeyValueStore, should_remove: F) -> Result<u64>
+where
+    F: Fn(&B256) -> bool,
+{
+    let keys = store
+        .keys()
+        .filter(|key| key.as_ref().ok().is_none_or(&should_remove))
+        .collect::<Result<Vec<_>>>()?;
+
+    for key in keys.iter() {
+        store.remove(*key)?;
+    }
+    store.compact();
+
+    Ok(keys.len() as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{KeyValueStore, TraceEntry, TraceEvent};
+    use kona_preimage::PreimageKey;
+
+    #[test]
+    fn test_summarize_and_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = DiskKeyValueStore::try_new_persistent(dir.path().to_path_buf()).unwrap();
+
+        let keccak_key = PreimageKey::new_keccak256([0xAA; 32]);
+        let blob_key = PreimageKey::new([0xBB; 32], PreimageKeyType::Blob);
+        store.set(keccak_key.into(), vec![0u8; 4]).unwrap();
+        store.set(blob_key.into(), vec![0u8; 32]).unwrap();
+
+        let summary = summarize(&store).unwrap();
+        assert_eq!(summary.entries.get("keccak256"), Some(&1));
+        assert_eq!(summary.entries.get("blob"), Some(&1));
+        assert_eq!(summary.total_size, 36);
+
+        let removed = prune(&mut store, |key| key[0] == PreimageKeyType::Blob as u8).unwrap();
+        assert_eq!(removed, 1);
+        assert!(store.get(blob_key.into()).is_none());
+        assert!(store.get(keccak_key.into()).is_some());
+    }
+
+    #[test]
+    fn test_minimize_from_trace() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = dir.path().join("db");
+        let trace_path = dir.path().join("trace.jsonl");
+
+        let read_key: B256 = PreimageKey::new_keccak256([0xAA; 32]).into();
+        let unread_key: B256 = PreimageKey::new_keccak256([0xBB; 32]).into();
+        let missing_key: B256 = PreimageKey::new_keccak256([0xCC; 32]).into();
+        let mut store = DiskKeyValueStore::try_new_persistent(data_dir.clone()).unwrap();
+        store.set(read_key, vec![0x01]).unwrap();
+        store.set(unread_key, vec![0x02]).unwrap();
+        store.set(missing_key, vec![0x03]).unwrap();
+        drop(store);
+
+        // Only preimages that were served during the run are kept.
+        let trace = [
+            TraceEvent::Hint { hint: "l1-block-header 0xaa".to_string() },
+            TraceEvent::Preimage { key: read_key, size: Some(1) },
+            TraceEvent::Preimage { key: missing_key, size: None },
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(seq, event)| {
+            serde_json::to_string(&TraceEntry { seq: seq as u64, elapsed_micros: 1, event })
+                .unwrap()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+        std::fs::write(&trace_path, trace).unwrap();
+
+        let command = StoreCommand {
+            action: StoreAction::Minimize(StoreMinimizeArgs {
+                data_dir: data_dir.clone(),
+                trace: trace_path,
+            }),
+        };
+        command.run().unwrap();
+
+        let store = DiskKeyValueStore::try_new_persistent(data_dir).unwrap();
+        assert!(store.get(read_key).is_some());
+        assert!(store.get(unread_key).is_none());
+        assert!(store.get(missing_key).is_none());
+    }
+}
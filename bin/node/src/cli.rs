@@ -1,7 +1,10 @@
 //! Contains the node CLI.
 
 use crate::{
-    commands::{BootstoreCommand, InfoCommand, NetCommand, NodeCommand, RegistryCommand},
+    commands::{
+        BootstoreCommand, ChannelsCommand, DeriveAuditCommand, InfoCommand, NetCommand,
+        NodeCommand, RegistryCommand,
+    },
     flags::{GlobalArgs, init_unified_metrics},
     version,
 };
@@ -27,6 +30,12 @@ pub enum Commands {
     Bootstore(BootstoreCommand),
     /// Get info about op chain.
     Info(InfoCommand),
+    /// Decodes the channels and batches posted by the batcher.
+    #[command(alias = "c", alias = "decode")]
+    Channels(ChannelsCommand),
+    /// Audits derivation against the L2 chain of a reference node.
+    #[command(alias = "audit")]
+    DeriveAudit(DeriveAuditCommand),
 }
 
 /// The node CLI.
@@ -61,6 +70,8 @@ impl Cli {
             Commands::Registry(ref registry) => registry.init_logs(&self.global)?,
             Commands::Bootstore(ref bootstore) => bootstore.init_logs(&self.global)?,
             Commands::Info(ref info) => info.init_logs(&self.global)?,
+            Commands::Channels(ref channels) => channels.init_logs(&self.global)?,
+            Commands::DeriveAudit(ref audit) => audit.init_logs(&self.global)?,
         }
 
         // Allow subcommands to initialize cli metrics.
@@ -78,6 +89,8 @@ impl Cli {
             Commands::Registry(registry) => registry.run(&self.global),
             Commands::Bootstore(bootstore) => bootstore.run(&self.global),
             Commands::Info(info) => info.run(&self.global),
+            Commands::Channels(channels) => Self::run_until_ctrl_c(channels.run(&self.global)),
+            Commands::DeriveAudit(audit) => Self::run_until_ctrl_c(audit.run(&self.global)),
         }
     }
 
@@ -114,6 +127,10 @@ mod tests {
     #[case::bootstore_subcommand_long(Commands::Bootstore(Default::default()), "boot")]
     #[case::bootstore_subcommand_long2(Commands::Bootstore(Default::default()), "store")]
     #[case::info_subcommand(Commands::Info(Default::default()), "info")]
+    #[case::channels_subcommand_short(Commands::Channels(Default::default()), "c")]
+    #[case::channels_subcommand_long(Commands::Channels(Default::default()), "decode")]
+    #[case::derive_audit_subcommand_short(Commands::DeriveAudit(Default::default()), "audit")]
+    #[case::derive_audit_subcommand_long(Commands::DeriveAudit(Default::default()), "derive-audit")]
     fn test_parse_cli(#[case] subcommand: Commands, #[case] subcommand_alias: &str) {
         let args = vec!["kona-node", subcommand_alias, "--help"];
         let cli = Cli::parse_from(args);
@@ -0,0 +1,268 @@
+//! Compares derived payload attributes against the block produced by the reference node.
+
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, B256, keccak256};
+use kona_protocol::OpAttributesWithParent;
+use op_alloy_consensus::{DEPOSIT_TX_TYPE_ID, OpBlock};
+use serde_json::{Value, json};
+
+/// A difference between the derived payload attributes of an L2 block and the block produced by
+/// the reference node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Mismatch {
+    /// The block timestamps differ.
+    Timestamp {
+        /// The derived timestamp.
+        derived: u64,
+        /// The timestamp of the reference block.
+        reference: u64,
+    },
+    /// The block gas limits differ.
+    GasLimit {
+        /// The derived gas limit, if the attributes set one.
+        derived: Option<u64>,
+        /// The gas limit of the reference block.
+        reference: u64,
+    },
+    /// The fee recipients differ.
+    FeeRecipient {
+        /// The derived fee recipient.
+        derived: Address,
+        /// The beneficiary of the reference block.
+        reference: Address,
+    },
+    /// The number of transactions differs.
+    TransactionCount {
+        /// The number of derived transactions.
+        derived: usize,
+        /// The number of transactions in the reference block.
+        reference: usize,
+    },
+    /// The first transaction that differs.
+    Transaction {
+        /// The index of the transaction in the block.
+        index: usize,
+        /// The hash of the derived transaction.
+        derived: B256,
+        /// The hash of the transaction in the reference block.
+        reference: B256,
+    },
+    /// The deposit transactions, or their order, differ.
+    Deposits {
+        /// The hashes of the derived deposit transactions, in order.
+        derived: Vec<B256>,
+        /// The hashes of the deposit transactions in the reference block, in order.
+        reference: Vec<B256>,
+    },
+}
+
+impl Mismatch {
+    /// Returns the name of the mismatched field.
+    pub(super) const fn field(&self) -> &'static str {
+        match self {
+            Self::Timestamp { .. } => "timestamp",
+            Self::GasLimit { .. } => "gas_limit",
+            Self::FeeRecipient { .. } => "fee_recipient",
+            Self::TransactionCount { .. } => "transaction_count",
+            Self::Transaction { .. } => "transaction",
+            Self::Deposits { .. } => "deposits",
+        }
+    }
+
+    /// Renders the mismatch as JSON.
+    pub(super) fn to_json(&self) -> Value {
+        let (derived, reference) = match self {
+            Self::Timestamp { derived, reference } => (json!(derived), json!(reference)),
+            Self::GasLimit { derived, reference } => (json!(derived), json!(reference)),
+            Self::FeeRecipient { derived, reference } => (json!(derived), json!(reference)),
+            Self::TransactionCount { derived, reference } => (json!(derived), json!(reference)),
+            Self::Transaction { derived, reference, .. } => (json!(derived), json!(reference)),
+            Self::Deposits { derived, reference } => (json!(derived), json!(reference)),
+        };
+        let mut value = json!({
+            "field": self.field(),
+            "derived": derived,
+            "reference": reference,
+        });
+        if let Self::Transaction { index, .. } = self {
+            value["index"] = json!(index);
+        }
+        value
+    }
+}
+
+/// Compares the derived payload attributes against the reference block, returning every
+/// [`Mismatch`] found.
+pub(super) fn compare(attributes: &OpAttributesWithParent, block: &OpBlock) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    let header = &block.header;
+    let inner = &attributes.inner;
+
+    let timestamp = inner.payload_attributes.timestamp;
+    if timestamp != header.timestamp {
+        mismatches.push(Mismatch::Timestamp { derived: timestamp, reference: header.timestamp });
+    }
+    if inner.gas_limit != Some(header.gas_limit) {
+        mismatches
+            .push(Mismatch::GasLimit { derived: inner.gas_limit, reference: header.gas_limit });
+    }
+    let fee_recipient = inner.payload_attributes.suggested_fee_recipient;
+    if fee_recipient != header.beneficiary {
+        mismatches
+            .push(Mismatch::FeeRecipient { derived: fee_recipient, reference: header.beneficiary });
+    }
+
+    // Transaction hashes are the keccak256 hash of their EIP-2718 encoding.
+    let derived: Vec<(bool, B256)> = inner
+        .transactions
+        .iter()
+        .flatten()
+        .map(|tx| (tx.first() == Some(&DEPOSIT_TX_TYPE_ID), keccak256(tx)))
+        .collect();
+    let reference: Vec<(bool, B256)> = block
+        .body
+        .transactions
+        .iter()
+        .map(|tx| (tx.is_deposit(), keccak256(tx.encoded_2718())))
+        .collect();
+
+    let derived_deposits = deposit_hashes(&derived);
+    let reference_deposits = deposit_hashes(&reference);
+    if derived_deposits != reference_deposits {
+        mismatches
+            .push(Mismatch::Deposits { derived: derived_deposits, reference: reference_deposits });
+    }
+
+    if derived.len() != reference.len() {
+        mismatches.push(Mismatch::TransactionCount {
+            derived: derived.len(),
+            reference: reference.len(),
+        });
+    }
+    if let Some((index, (derived, reference))) =
+        derived.iter().zip(reference.iter()).enumerate().find(|(_, (d, r))| d.1 != r.1)
+    {
+        mismatches.push(Mismatch::Transaction {
+            index,
+            derived: derived.1,
+            reference: reference.1,
+        });
+    }
+
+    mismatches
+}
+
+/// Returns the hashes of the deposit transactions, in order.
+fn deposit_hashes(transactions: &[(bool, B256)]) -> Vec<B256> {
+    transactions.iter().filter(|(is_deposit, _)| *is_deposit).map(|(_, hash)| *hash).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Block, BlockBody, Header, Sealed};
+    use alloy_primitives::{Bytes, address};
+    use alloy_rpc_types_engine::PayloadAttributes;
+    use kona_protocol::L2BlockInfo;
+    use op_alloy_consensus::{OpTxEnvelope, TxDeposit};
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    const FEE_RECIPIENT: Address = address!("4200000000000000000000000000000000000011");
+
+    fn deposit(source_hash: u8) -> OpTxEnvelope {
+        OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+            source_hash: B256::repeat_byte(source_hash),
+            ..Default::default()
+        }))
+    }
+
+    fn block(transactions: Vec<OpTxEnvelope>) -> OpBlock {
+        Block {
+            header: Header {
+                timestamp: 2,
+                gas_limit: 30_000_000,
+                beneficiary: FEE_RECIPIENT,
+                ..Default::default()
+            },
+            body: BlockBody { transactions, ommers: Vec::new(), withdrawals: None },
+        }
+    }
+
+    fn attributes(transactions: &[OpTxEnvelope]) -> OpAttributesWithParent {
+        let transactions: Vec<Bytes> =
+            transactions.iter().map(|tx| tx.encoded_2718().into()).collect();
+        OpAttributesWithParent::new(
+            OpPayloadAttributes {
+                payload_attributes: PayloadAttributes {
+                    timestamp: 2,
+                    suggested_fee_recipient: FEE_RECIPIENT,
+                    ..Default::default()
+                },
+                transactions: Some(transactions),
+                gas_limit: Some(30_000_000),
+                ..Default::default()
+            },
+            L2BlockInfo::default(),
+            None,
+            false,
+        )
+    }
+
+    #[test]
+    fn test_compare_matching_block() {
+        let transactions = vec![deposit(1), deposit(2)];
+        assert!(compare(&attributes(&transactions), &block(transactions)).is_empty());
+    }
+
+    #[test]
+    fn test_compare_header_fields() {
+        let mut attributes = attributes(&[]);
+        attributes.inner.payload_attributes.timestamp = 4;
+        attributes.inner.payload_attributes.suggested_fee_recipient = Address::ZERO;
+        attributes.inner.gas_limit = None;
+
+        let mismatches = compare(&attributes, &block(Vec::new()));
+        assert_eq!(
+            mismatches,
+            vec![
+                Mismatch::Timestamp { derived: 4, reference: 2 },
+                Mismatch::GasLimit { derived: None, reference: 30_000_000 },
+                Mismatch::FeeRecipient { derived: Address::ZERO, reference: FEE_RECIPIENT },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_deposit_order() {
+        let (first, second) = (deposit(1), deposit(2));
+        let attributes = attributes(&[second.clone(), first.clone()]);
+        let block = block(vec![first.clone(), second.clone()]);
+
+        let mismatches = compare(&attributes, &block);
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(
+            mismatches[0],
+            Mismatch::Deposits {
+                derived: vec![second.tx_hash(), first.tx_hash()],
+                reference: vec![first.tx_hash(), second.tx_hash()],
+            }
+        );
+        assert_eq!(
+            mismatches[1],
+            Mismatch::Transaction {
+                index: 0,
+                derived: second.tx_hash(),
+                reference: first.tx_hash()
+            }
+        );
+        assert_eq!(mismatches[1].to_json()["index"], json!(0));
+    }
+
+    #[test]
+    fn test_compare_missing_transaction() {
+        let transactions = vec![deposit(1), deposit(2)];
+        let mismatches = compare(&attributes(&transactions[..1]), &block(transactions));
+        assert_eq!(mismatches[0].field(), "deposits");
+        assert_eq!(mismatches[1], Mismatch::TransactionCount { derived: 1, reference: 2 });
+    }
+}
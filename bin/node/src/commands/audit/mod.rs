@@ -0,0 +1,413 @@
+//! Derive Audit Subcommand
+
+use crate::flags::GlobalArgs;
+use alloy_provider::RootProvider;
+use anyhow::{Result, anyhow, bail};
+use backon::{BackoffBuilder, ExponentialBuilder};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_derive::{
+    ActivationSignal, ChainProvider, OriginProvider, Pipeline, PipelineError, PipelineErrorKind,
+    ResetError, SignalReceiver, StepResult,
+};
+use kona_genesis::RollupConfig;
+use kona_protocol::{BatchValidationProvider, L2BlockInfo};
+use kona_providers_alloy::{
+    AlloyChainProvider, AlloyL2ChainProvider, OnlineAltDAClient, OnlineBeaconClient,
+    OnlineBlobProvider, OnlinePipeline, OnlinePipelineOptions,
+};
+use op_alloy_network::Optimism;
+use serde_json::{Value, json};
+use std::{fs::File, path::PathBuf, sync::Arc, time::Duration};
+use tracing::{debug, info, warn};
+use url::Url;
+
+mod compare;
+use compare::{Mismatch, compare};
+
+/// The maximum number of consecutive retries of the pipeline after a temporary error.
+const MAX_TEMPORARY_RETRIES: usize = 8;
+
+/// The delay before the first retry of the pipeline after a temporary error.
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// The maximum delay between retries of the pipeline after a temporary error.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(16);
+
+/// The `derive-audit` Subcommand
+///
+/// The `derive-audit` subcommand runs the derivation pipeline over a historical L1 range, and
+/// compares the payload attributes derived for every L2 block against the block produced by a
+/// reference node. No execution engine is driven: the pipeline is stepped on the reference
+/// node's chain. Every difference in the transactions, gas limit, timestamp, fee recipient and
+/// deposit ordering is written to a JSON report.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node derive-audit --l1-eth-rpc <URL> --l1-beacon <URL> --l2-eth-rpc <URL> \
+///     --start <L1 BLOCK> --end <L1 BLOCK>
+/// ```
+#[derive(Parser, PartialEq, Debug, Clone)]
+#[command(about = "Audits derivation against the L2 chain of a reference node")]
+pub struct DeriveAuditCommand {
+    /// URL of the L1 execution client RPC API.
+    #[arg(long, visible_alias = "l1", env = "KONA_NODE_L1_ETH_RPC")]
+    pub l1_eth_rpc: Url,
+    /// URL of the L1 beacon API.
+    #[arg(long, visible_alias = "l1.beacon", env = "KONA_NODE_L1_BEACON")]
+    pub l1_beacon: Url,
+    /// URL of the L2 execution client RPC API of the reference node.
+    #[arg(long, visible_alias = "l2", env = "KONA_NODE_AUDIT_L2_ETH_RPC")]
+    pub l2_eth_rpc: Url,
+    /// The first L1 block to derive from.
+    ///
+    /// Only the L2 blocks derived from the L1 blocks between `--start` and `--end` are audited.
+    /// Derivation starts from an earlier L2 block, so that the channels read from this block are
+    /// complete.
+    #[arg(long)]
+    pub start: u64,
+    /// The last L1 block to derive from.
+    #[arg(long)]
+    pub end: u64,
+    /// Path to a custom L2 rollup configuration file
+    /// (overrides the default rollup configuration from the registry)
+    #[arg(long, visible_alias = "rollup-cfg", env = "KONA_NODE_ROLLUP_CONFIG")]
+    pub l2_config_file: Option<PathBuf>,
+    /// URL of the Alt-DA server that input commitments are resolved against.
+    /// Required if the rollup config enables Alt-DA.
+    #[arg(long = "altda.da-server", env = "KONA_NODE_ALTDA_DA_SERVER")]
+    pub altda_da_server: Option<Url>,
+    /// Writes the JSON report to the given file instead of stdout.
+    #[arg(long, short = 'o')]
+    pub output: Option<PathBuf>,
+    /// Exits with an error if any mismatch is found, after writing the report.
+    #[arg(long)]
+    pub fail_on_mismatch: bool,
+}
+
+impl Default for DeriveAuditCommand {
+    fn default() -> Self {
+        Self {
+            l1_eth_rpc: Url::parse("http://localhost:8545").unwrap(),
+            l1_beacon: Url::parse("http://localhost:5052").unwrap(),
+            l2_eth_rpc: Url::parse("http://localhost:9545").unwrap(),
+            start: 0,
+            end: 0,
+            l2_config_file: None,
+            altda_da_server: None,
+            output: None,
+            fail_on_mismatch: false,
+        }
+    }
+}
+
+/// The mismatches found in a single L2 block.
+#[derive(Debug)]
+struct BlockMismatches {
+    /// The L2 block that was audited.
+    l2_block: L2BlockInfo,
+    /// The number of the L1 block that the attributes were derived from, if known.
+    derived_from: Option<u64>,
+    /// The mismatches found.
+    mismatches: Vec<Mismatch>,
+}
+
+impl DeriveAuditCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub async fn run(self, args: &GlobalArgs) -> Result<()> {
+        let cfg =
+            Arc::new(args.apply_overrides(args.get_l2_config(self.l2_config_file.as_deref())?));
+        let upgrades = args.upgrade_registry()?;
+        if self.start > self.end {
+            bail!("The start block {} is after the end block {}", self.start, self.end);
+        }
+
+        let mut l1_provider = AlloyChainProvider::new_http(self.l1_eth_rpc.clone(), 1024);
+        let mut l2_provider = AlloyL2ChainProvider::new(
+            RootProvider::<Optimism>::new_http(self.l2_eth_rpc.clone()),
+            cfg.clone(),
+            1024,
+        );
+        let blob_provider =
+            OnlineBlobProvider::init(OnlineBeaconClient::new_http(self.l1_beacon.to_string()))
+                .await;
+
+        // Start from an L2 block whose batch is included before the start block, walking the L1
+        // origin back by a channel timeout as the engine does on reset.
+        let window_start = self.start.saturating_sub(cfg.seq_window_size);
+        let window_start_block = l1_provider
+            .block_info_by_number(window_start)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch L1 block {window_start}: {e}"))?;
+        let safe_head_number = last_l2_block_before(&cfg, window_start_block.timestamp);
+        let safe_head = l2_provider
+            .l2_block_info_by_number(safe_head_number)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch L2 block {safe_head_number}: {e}"))?;
+        let origin_number = safe_head
+            .l1_origin
+            .number
+            .saturating_sub(cfg.channel_timeout(safe_head.block_info.timestamp));
+        let l1_origin = l1_provider
+            .block_info_by_number(origin_number)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch L1 block {origin_number}: {e}"))?;
+
+        info!(
+            target: "audit",
+            l2_safe_head = safe_head.block_info.number,
+            l1_origin = l1_origin.number,
+            start = self.start,
+            end = self.end,
+            "Starting derivation audit"
+        );
+        let mut pipeline = OnlinePipeline::new(
+            cfg.clone(),
+            safe_head,
+            l1_origin,
+            blob_provider,
+            l1_provider,
+            l2_provider.clone(),
+            OnlinePipelineOptions::default()
+                .with_altda_client(
+                    self.altda_da_server
+                        .as_ref()
+                        .map(|url| OnlineAltDAClient::new_http(url.to_string())),
+                )
+                .with_upgrades(upgrades),
+        )
+        .await
+        .map_err(|e| anyhow!("Failed to initialize the derivation pipeline: {e}"))?;
+
+        let mut cursor = safe_head;
+        let mut audited = 0u64;
+        let mut blocks = Vec::new();
+        let mut backoff = Self::backoff();
+        while pipeline.origin().is_some_and(|origin| origin.number <= self.end) {
+            match pipeline.step(cursor).await {
+                StepResult::PreparedAttributes => {
+                    backoff = Self::backoff();
+                    let Some(attributes) = pipeline.next() else { continue };
+                    let number = attributes.parent.block_info.number + 1;
+
+                    // Blocks derived before the start block are stepped over, not audited.
+                    if attributes.derived_from.is_some_and(|origin| origin.number < self.start) {
+                        cursor = l2_provider
+                            .l2_block_info_by_number(number)
+                            .await
+                            .map_err(|e| anyhow!("Failed to fetch L2 block {number}: {e}"))?;
+                        continue;
+                    }
+
+                    let block = l2_provider
+                        .block_by_number(number)
+                        .await
+                        .map_err(|e| anyhow!("Failed to fetch L2 block {number}: {e}"))?;
+                    let mismatches = compare(&attributes, &block);
+
+                    // Continue on the reference chain, whether or not the block matched.
+                    cursor = l2_provider
+                        .l2_block_info_by_number(number)
+                        .await
+                        .map_err(|e| anyhow!("Failed to fetch L2 block {number}: {e}"))?;
+                    audited += 1;
+
+                    if mismatches.is_empty() {
+                        debug!(target: "audit", l2_block = number, "Derived block matches");
+                    } else {
+                        warn!(
+                            target: "audit",
+                            l2_block = number,
+                            fields = ?mismatches.iter().map(Mismatch::field).collect::<Vec<_>>(),
+                            "Derived block does not match the reference block"
+                        );
+                        blocks.push(BlockMismatches {
+                            l2_block: cursor,
+                            derived_from: attributes.derived_from.map(|origin| origin.number),
+                            mismatches,
+                        });
+                    }
+                }
+                StepResult::AdvancedOrigin => {
+                    backoff = Self::backoff();
+                    debug!(target: "audit", l1_block = ?pipeline.origin(), "Advanced L1 origin");
+                }
+                StepResult::OriginAdvanceErr(e) | StepResult::StepFailed(e) => match e {
+                    PipelineErrorKind::Temporary(PipelineError::Eof) => {
+                        warn!(target: "audit", "Reached the L1 head before the end block");
+                        break;
+                    }
+                    PipelineErrorKind::Temporary(PipelineError::NotEnoughData) => continue,
+                    PipelineErrorKind::Temporary(e) => {
+                        let Some(delay) = backoff.next() else {
+                            bail!(
+                                "Derivation failed at L2 block {} after {MAX_TEMPORARY_RETRIES} \
+                                 retries: {e}",
+                                cursor.block_info.number
+                            );
+                        };
+                        warn!(target: "audit", ?delay, "Temporary derivation error, retrying: {e}");
+                        tokio::time::sleep(delay).await;
+                    }
+                    PipelineErrorKind::Reset(ResetError::HoloceneActivation) => {
+                        let l1_origin =
+                            pipeline.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+                        let system_config =
+                            pipeline.system_config_by_number(cursor.block_info.number).await?;
+                        pipeline
+                            .signal(
+                                ActivationSignal {
+                                    l2_safe_head: cursor,
+                                    l1_origin,
+                                    system_config: Some(system_config),
+                                }
+                                .signal(),
+                            )
+                            .await?;
+                    }
+                    PipelineErrorKind::Reset(e) => {
+                        bail!("Derivation reset at L2 block {}: {e}", cursor.block_info.number)
+                    }
+                    PipelineErrorKind::Critical(e) => {
+                        bail!(
+                            "Critical derivation error at L2 block {}: {e}",
+                            cursor.block_info.number
+                        )
+                    }
+                },
+            }
+        }
+
+        let mismatched = blocks.len();
+        info!(
+            target: "audit",
+            audited,
+            mismatched,
+            l2_head = cursor.block_info.number,
+            "Finished derivation audit"
+        );
+
+        let report = self.report_json(&cfg, audited, &cursor, &blocks);
+        match &self.output {
+            Some(path) => serde_json::to_writer_pretty(File::create(path)?, &report)?,
+            None => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+
+        if self.fail_on_mismatch && mismatched > 0 {
+            bail!("Found mismatches in {mismatched} of {audited} audited L2 blocks");
+        }
+        Ok(())
+    }
+
+    /// Returns the backoff between retries of the pipeline after a temporary error.
+    fn backoff() -> impl Iterator<Item = Duration> {
+        ExponentialBuilder::default()
+            .with_min_delay(MIN_RETRY_DELAY)
+            .with_max_delay(MAX_RETRY_DELAY)
+            .with_max_times(MAX_TEMPORARY_RETRIES)
+            .build()
+    }
+
+    /// Renders the audit report as JSON.
+    fn report_json(
+        &self,
+        cfg: &RollupConfig,
+        audited: u64,
+        l2_head: &L2BlockInfo,
+        blocks: &[BlockMismatches],
+    ) -> Value {
+        let mismatches: Vec<Value> = blocks
+            .iter()
+            .map(|block| {
+                let mismatches: Vec<Value> =
+                    block.mismatches.iter().map(Mismatch::to_json).collect();
+                json!({
+                    "l2_block": block.l2_block.block_info.number,
+                    "l2_hash": block.l2_block.block_info.hash,
+                    "derived_from": block.derived_from,
+                    "mismatches": mismatches,
+                })
+            })
+            .collect();
+
+        json!({
+            "l2_chain_id": cfg.l2_chain_id.id(),
+            "start": self.start,
+            "end": self.end,
+            "audited": audited,
+            "l2_head": l2_head.block_info.number,
+            "mismatched": blocks.len(),
+            "blocks": mismatches,
+        })
+    }
+}
+
+/// Returns the number of the last L2 block with a timestamp before the given timestamp, or the
+/// L2 genesis block if there is none.
+///
+/// The L1 origin of such a block is older than the given timestamp, so its batch is included
+/// before the sequencing window of the L1 block with that timestamp ends.
+fn last_l2_block_before(cfg: &RollupConfig, timestamp: u64) -> u64 {
+    let Some(elapsed) = timestamp.checked_sub(cfg.genesis.l2_time + 1) else {
+        return cfg.genesis.l2.number;
+    };
+    cfg.genesis.l2.number + elapsed / cfg.block_time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn default_flags() -> &'static [&'static str] {
+        &[
+            "derive-audit",
+            "--l1-eth-rpc",
+            "http://localhost:8545",
+            "--l1-beacon",
+            "http://localhost:5052",
+            "--l2-eth-rpc",
+            "http://localhost:9545",
+        ]
+    }
+
+    #[test]
+    fn test_derive_audit_cli_requires_range() {
+        let err = DeriveAuditCommand::try_parse_from(default_flags()).unwrap_err();
+        assert!(err.to_string().contains("--start"));
+    }
+
+    #[test]
+    fn test_last_l2_block_before() {
+        let mut cfg = RollupConfig { block_time: 2, ..Default::default() };
+        cfg.genesis.l2.number = 100;
+        cfg.genesis.l2_time = 1000;
+
+        assert_eq!(last_l2_block_before(&cfg, 900), 100);
+        assert_eq!(last_l2_block_before(&cfg, 1000), 100);
+        assert_eq!(last_l2_block_before(&cfg, 1001), 100);
+        assert_eq!(last_l2_block_before(&cfg, 1002), 100);
+        assert_eq!(last_l2_block_before(&cfg, 1003), 101);
+        assert_eq!(last_l2_block_before(&cfg, 1010), 104);
+    }
+
+    #[test]
+    fn test_derive_audit_cli() {
+        let args = DeriveAuditCommand::parse_from(
+            default_flags()
+                .iter()
+                .chain(["--start", "100", "--end", "200", "--fail-on-mismatch"].iter())
+                .copied(),
+        );
+        assert_eq!(args.start, 100);
+        assert_eq!(args.end, 200);
+        assert!(args.fail_on_mismatch);
+        assert!(args.output.is_none());
+    }
+}
@@ -0,0 +1,357 @@
+//! Reassembles channels from frames, recording why frames and channels are dropped.
+
+use alloy_primitives::{Bytes, hex, map::HashMap};
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use kona_derive::{
+    ChannelDropReason, ChannelProvider, ChannelReaderProvider, DerivationEvent,
+    DerivationEventSink, EventEmitter, FrameDropReason, FrameQueue, FrameQueueProvider,
+    OriginAdvancer, OriginProvider, PipelineError, PipelineErrorKind, PipelineResult, Signal,
+    SignalReceiver,
+};
+use kona_genesis::RollupConfig;
+use kona_protocol::{BlockInfo, ChannelId, Frame};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// A frame of a channel, as seen on L1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FrameInfo {
+    /// The frame number.
+    pub(super) number: u16,
+    /// The number of the L1 block that included the frame.
+    pub(super) l1_block: u64,
+    /// The size of the frame data.
+    pub(super) size: usize,
+    /// Whether the frame is the last frame of the channel.
+    pub(super) is_last: bool,
+}
+
+/// A frame that was not added to any channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct DroppedFrame {
+    /// The channel of the frame.
+    pub(super) channel_id: ChannelId,
+    /// The frame.
+    pub(super) frame: FrameInfo,
+    /// Why the frame was dropped.
+    pub(super) reason: String,
+}
+
+/// What happened to a channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ChannelStatus {
+    /// The channel was read at the given L1 origin.
+    Ready {
+        /// The L1 origin at which the channel was read.
+        origin: BlockInfo,
+        /// The compressed channel data.
+        data: Bytes,
+    },
+    /// The channel timed out at the given L1 block.
+    TimedOut {
+        /// The number of the L1 block at which the channel timed out.
+        l1_block: u64,
+    },
+    /// The channel was dropped before it was complete.
+    Dropped {
+        /// The number of the L1 block at which the channel was dropped.
+        l1_block: u64,
+        /// Why the channel was dropped.
+        reason: String,
+    },
+    /// The channel was still incomplete at the end of the input.
+    Incomplete,
+}
+
+/// A channel, and the frames that were added to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ChannelReport {
+    /// The channel id.
+    pub(super) id: ChannelId,
+    /// The L1 block in which the channel was opened.
+    pub(super) open_block: BlockInfo,
+    /// The frames that were added to the channel, in the order they were seen.
+    pub(super) frames: Vec<FrameInfo>,
+    /// What happened to the channel.
+    pub(super) status: ChannelStatus,
+}
+
+/// A channel that is being assembled.
+#[derive(Debug)]
+struct PendingChannel {
+    /// The L1 block in which the channel was opened.
+    open_block: BlockInfo,
+    /// The frames that were added to the channel.
+    frames: Vec<FrameInfo>,
+}
+
+/// Feeds the batcher data of a sequence of L1 blocks to the [FrameQueue], one L1 block at a time.
+#[derive(Debug)]
+struct BatcherDataSource {
+    /// The current L1 origin.
+    origin: Option<BlockInfo>,
+    /// The batcher data of the current L1 origin that has not been read yet.
+    data: VecDeque<Bytes>,
+    /// The L1 blocks after the current origin, and their batcher data.
+    blocks: VecDeque<(BlockInfo, Vec<Bytes>)>,
+}
+
+impl BatcherDataSource {
+    /// Creates a new [BatcherDataSource] whose origin is the first of the given L1 blocks.
+    fn new(blocks: Vec<(BlockInfo, Vec<Bytes>)>) -> Self {
+        let mut source = Self { origin: None, data: VecDeque::new(), blocks: blocks.into() };
+        source.next_block();
+        source
+    }
+
+    /// Moves to the next L1 block. Returns `false` if there is none.
+    fn next_block(&mut self) -> bool {
+        let Some((origin, data)) = self.blocks.pop_front() else {
+            return false;
+        };
+        self.origin = Some(origin);
+        self.data = data.into();
+        true
+    }
+}
+
+#[async_trait]
+impl FrameQueueProvider for BatcherDataSource {
+    type Item = Bytes;
+
+    async fn next_data(&mut self) -> PipelineResult<Bytes> {
+        self.data.pop_front().ok_or(PipelineError::Eof.temp())
+    }
+}
+
+#[async_trait]
+impl OriginAdvancer for BatcherDataSource {
+    async fn advance_origin(&mut self) -> PipelineResult<()> {
+        if self.next_block() { Ok(()) } else { Err(PipelineError::Eof.temp()) }
+    }
+}
+
+impl OriginProvider for BatcherDataSource {
+    fn origin(&self) -> Option<BlockInfo> {
+        self.origin
+    }
+}
+
+#[async_trait]
+impl SignalReceiver for BatcherDataSource {
+    async fn signal(&mut self, _: Signal) -> PipelineResult<()> {
+        Ok(())
+    }
+}
+
+/// A [DerivationEventSink] that buffers the events emitted by the stages.
+#[derive(Debug, Default)]
+struct EventBuffer(Mutex<Vec<DerivationEvent>>);
+
+impl EventBuffer {
+    /// Takes the buffered events.
+    fn take(&self) -> Vec<DerivationEvent> {
+        std::mem::take(&mut *self.0.lock().expect("event buffer lock poisoned"))
+    }
+}
+
+impl DerivationEventSink for EventBuffer {
+    fn emit(&self, event: DerivationEvent) {
+        self.0.lock().expect("event buffer lock poisoned").push(event);
+    }
+}
+
+/// Reassembles channels from the batcher data of a sequence of L1 blocks.
+///
+/// The data is fed through the `FrameQueue` and the `ChannelBank` or `ChannelAssembler` stages of
+/// the derivation pipeline, and the fate of every frame and channel is reconstructed from the
+/// events that the stages emit.
+#[derive(Debug)]
+pub(super) struct ChannelInspector {
+    /// The `FrameQueue` and the `ChannelBank` or `ChannelAssembler` stages.
+    stages: ChannelProvider<FrameQueue<BatcherDataSource>>,
+    /// The events emitted by the stages.
+    events: Arc<EventBuffer>,
+    /// The frames of the batcher data that have not been accepted or dropped yet, by channel and
+    /// frame number.
+    frames: HashMap<(ChannelId, u16), VecDeque<FrameInfo>>,
+    /// The channels that are being assembled.
+    channels: HashMap<ChannelId, PendingChannel>,
+    /// The channels that are being assembled, in the order they were opened.
+    queue: Vec<ChannelId>,
+    /// The channels that have been read, timed out or dropped.
+    reports: Vec<ChannelReport>,
+    /// The frames that were not added to any channel.
+    dropped: Vec<DroppedFrame>,
+}
+
+impl ChannelInspector {
+    /// Creates a new [ChannelInspector] over the batcher data of the given L1 blocks.
+    pub(super) fn new(cfg: &RollupConfig, blocks: Vec<(BlockInfo, Vec<Bytes>)>) -> Self {
+        let mut frames = HashMap::<_, VecDeque<_>>::default();
+        for (origin, data) in &blocks {
+            for frame in data.iter().flat_map(|data| Frame::parse_frames(data).unwrap_or_default())
+            {
+                let info = FrameInfo::new(&frame, *origin);
+                frames.entry((frame.id, frame.number)).or_default().push_back(info);
+            }
+        }
+
+        let cfg = Arc::new(cfg.clone());
+        let events = Arc::new(EventBuffer::default());
+        let emitter = EventEmitter::new(events.clone());
+        let frame_queue = FrameQueue::new(BatcherDataSource::new(blocks), cfg.clone())
+            .with_events(emitter.clone());
+        Self {
+            stages: ChannelProvider::new(cfg, frame_queue).with_events(emitter),
+            events,
+            frames,
+            channels: HashMap::default(),
+            queue: Vec::new(),
+            reports: Vec::new(),
+            dropped: Vec::new(),
+        }
+    }
+
+    /// Reads all channels, and returns the channel reports in the order in which the channels
+    /// were completed, followed by the incomplete channels, and the dropped frames.
+    pub(super) async fn run(mut self) -> Result<(Vec<ChannelReport>, Vec<DroppedFrame>)> {
+        if self.stages.origin().is_some() {
+            loop {
+                self.read_origin().await?;
+                match self.stages.advance_origin().await {
+                    Ok(()) => {}
+                    Err(PipelineErrorKind::Temporary(PipelineError::Eof)) => break,
+                    Err(e) => bail!("Failed to advance the L1 origin: {e}"),
+                }
+            }
+        }
+
+        for id in std::mem::take(&mut self.queue) {
+            if let Some(PendingChannel { open_block, frames }) = self.channels.remove(&id) {
+                let status = ChannelStatus::Incomplete;
+                self.reports.push(ChannelReport { id, open_block, frames, status });
+            }
+        }
+        Ok((self.reports, self.dropped))
+    }
+
+    /// Steps the stages until all batcher data of the current L1 origin has been read.
+    async fn read_origin(&mut self) -> Result<()> {
+        loop {
+            let result = self.stages.next_data().await;
+            let origin = self.stages.origin().unwrap_or_default();
+            self.handle_events(origin, result.as_ref().ok().cloned().flatten());
+            match result {
+                Ok(_) | Err(PipelineErrorKind::Temporary(PipelineError::NotEnoughData)) => {}
+                Err(PipelineErrorKind::Temporary(PipelineError::Eof)) => return Ok(()),
+                Err(e) => bail!("Failed to read channels at L1 block {}: {e}", origin.number),
+            }
+        }
+    }
+
+    /// Records the fate of the frames and channels described by the buffered events. The data of
+    /// a channel that was read is given.
+    fn handle_events(&mut self, origin: BlockInfo, mut data: Option<Bytes>) {
+        let events = self.events.take();
+        for (index, event) in events.iter().enumerate() {
+            match *event {
+                DerivationEvent::FrameAccepted { channel_id, frame_number, .. } => {
+                    let frame = self.take_frame(channel_id, frame_number, origin);
+                    self.open_channel(channel_id, origin).frames.push(frame);
+                }
+                DerivationEvent::FrameDropped { channel_id, frame_number, reason, .. } => {
+                    let frame = self.take_frame(channel_id, frame_number, origin);
+                    let reason = frame_drop_reason(reason).to_string();
+                    self.dropped.push(DroppedFrame { channel_id, frame, reason });
+                }
+                DerivationEvent::ChannelReady { channel_id, .. } => {
+                    let data = data.take().unwrap_or_default();
+                    self.report(channel_id, ChannelStatus::Ready { origin, data });
+                }
+                DerivationEvent::ChannelTimedOut { channel_id, .. } => {
+                    self.report(channel_id, ChannelStatus::TimedOut { l1_block: origin.number });
+                }
+                DerivationEvent::ChannelDropped { channel_id, reason, .. } => {
+                    // A channel is superseded by the channel of the frame that is processed next.
+                    let next = events[index + 1..].iter().find_map(|event| match event {
+                        DerivationEvent::FrameAccepted { channel_id: next, .. } |
+                        DerivationEvent::FrameDropped { channel_id: next, .. } => Some(*next),
+                        _ => None,
+                    });
+                    let reason = match (reason, next) {
+                        (ChannelDropReason::Superseded, Some(next)) => {
+                            format!("superseded by channel {}", hex::encode(next))
+                        }
+                        (reason, _) => channel_drop_reason(reason).to_string(),
+                    };
+                    let status = ChannelStatus::Dropped { l1_block: origin.number, reason };
+                    self.report(channel_id, status);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Takes the description of the given frame, which was processed at the given L1 origin.
+    fn take_frame(&mut self, channel_id: ChannelId, number: u16, origin: BlockInfo) -> FrameInfo {
+        self.frames
+            .get_mut(&(channel_id, number))
+            .and_then(VecDeque::pop_front)
+            .unwrap_or(FrameInfo { number, l1_block: origin.number, size: 0, is_last: false })
+    }
+
+    /// Returns the given channel, opening it in the given L1 block if it is not tracked yet.
+    fn open_channel(&mut self, id: ChannelId, origin: BlockInfo) -> &mut PendingChannel {
+        self.channels.entry(id).or_insert_with(|| {
+            self.queue.push(id);
+            PendingChannel { open_block: origin, frames: Vec::new() }
+        })
+    }
+
+    /// Stops tracking the given channel, recording its status.
+    fn report(&mut self, id: ChannelId, status: ChannelStatus) {
+        let Some(PendingChannel { open_block, frames }) = self.channels.remove(&id) else {
+            return;
+        };
+        self.queue.retain(|queued| *queued != id);
+        self.reports.push(ChannelReport { id, open_block, frames, status });
+    }
+}
+
+/// Describes why the derivation pipeline dropped a frame.
+const fn frame_drop_reason(reason: FrameDropReason) -> &'static str {
+    match reason {
+        FrameDropReason::NonSequential => "frame is not sequential to the previous frame",
+        FrameDropReason::AfterLastFrame => "frame follows the last frame of its channel",
+        FrameDropReason::NotFirstFrame => "frame opens a channel, but is not its first frame",
+        FrameDropReason::IncompleteChannel => "channel was superseded before it was complete",
+        FrameDropReason::ChannelTimedOut => "channel timed out",
+        FrameDropReason::Rejected => "frame was rejected by its channel",
+        FrameDropReason::NoChannel => "no channel is open",
+    }
+}
+
+/// Describes why the derivation pipeline dropped a channel.
+const fn channel_drop_reason(reason: ChannelDropReason) -> &'static str {
+    match reason {
+        ChannelDropReason::BankFull => "channel bank is full",
+        ChannelDropReason::TooLarge => "channel exceeds the max RLP bytes per channel",
+        ChannelDropReason::Superseded => "superseded by a new channel",
+    }
+}
+
+impl FrameInfo {
+    /// Describes the given frame, included in the given L1 block.
+    fn new(frame: &Frame, origin: BlockInfo) -> Self {
+        Self {
+            number: frame.number,
+            l1_block: origin.number,
+            size: frame.data.len(),
+            is_last: frame.is_last,
+        }
+    }
+}
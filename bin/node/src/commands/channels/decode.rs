@@ -0,0 +1,189 @@
+//! Decodes the batches of reassembled channels, and renders the inspection report as JSON.
+
+use super::assembler::{ChannelReport, ChannelStatus, DroppedFrame, FrameInfo};
+use alloy_primitives::{Bytes, hex};
+use alloy_rlp::Decodable;
+use kona_genesis::RollupConfig;
+use kona_protocol::{Batch, BatchReader, BlockInfo, SingleBatch, SpanBatch};
+use serde_json::{Value, json};
+
+/// A batcher transaction whose data could not be parsed into frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct InvalidData {
+    /// The number of the L1 block that included the data.
+    pub(super) l1_block: u64,
+    /// The index of the data within the L1 block.
+    pub(super) index: usize,
+    /// Why the data could not be parsed.
+    pub(super) reason: String,
+}
+
+/// The batches decoded from a channel.
+#[derive(Debug, Default)]
+pub(super) struct DecodedChannel {
+    /// The batches that were decoded, in order.
+    pub(super) batches: Vec<Batch>,
+    /// Why decoding stopped before the end of the channel, if it did. The derivation pipeline
+    /// drops the remainder of the channel in this case.
+    pub(super) error: Option<String>,
+}
+
+/// Decodes the batches of a channel read at the given L1 origin, as the `ChannelReader` does.
+pub(super) fn decode_channel(
+    cfg: &RollupConfig,
+    origin: &BlockInfo,
+    data: &Bytes,
+) -> DecodedChannel {
+    let max_rlp_bytes = cfg.max_rlp_bytes_per_channel(origin.timestamp) as usize;
+    let mut reader = BatchReader::new(data.to_vec(), max_rlp_bytes);
+    if let Err(e) = reader.decompress() {
+        return DecodedChannel { batches: Vec::new(), error: Some(e.to_string()) };
+    }
+
+    let mut decoded = DecodedChannel::default();
+    let mut remaining = reader.decompressed.as_slice();
+    while !remaining.is_empty() {
+        let bytes = match Bytes::decode(&mut remaining) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                decoded.error = Some(format!("invalid batch RLP: {e}"));
+                break;
+            }
+        };
+        match Batch::decode(&mut bytes.as_ref(), cfg) {
+            Ok(batch) if reader.brotli_used && !cfg.is_fjord_active(batch.timestamp()) => {
+                decoded.error = Some("brotli compressed batch before Fjord".to_string());
+                break;
+            }
+            Ok(batch)
+                if (reader.zstd_used || reader.raw_used) &&
+                    !cfg.is_experimental_compression_active(batch.timestamp()) =>
+            {
+                decoded.error = Some("experimental channel format is not enabled".to_string());
+                break;
+            }
+            Ok(batch) => decoded.batches.push(batch),
+            Err(e) => {
+                decoded.error = Some(e.to_string());
+                break;
+            }
+        }
+    }
+    decoded
+}
+
+/// Renders the inspection report as JSON.
+pub(super) fn report_json(
+    cfg: &RollupConfig,
+    channels: &[ChannelReport],
+    dropped: &[DroppedFrame],
+    invalid: &[InvalidData],
+) -> Value {
+    json!({
+        "channels": channels.iter().map(|c| channel_json(cfg, c)).collect::<Vec<_>>(),
+        "dropped_frames": dropped
+            .iter()
+            .map(|d| json!({
+                "channel_id": hex::encode(d.channel_id),
+                "frame": frame_json(&d.frame),
+                "reason": d.reason,
+            }))
+            .collect::<Vec<_>>(),
+        "invalid_data": invalid
+            .iter()
+            .map(|i| json!({ "l1_block": i.l1_block, "index": i.index, "reason": i.reason }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Renders a channel, decoding its batches if it was read.
+fn channel_json(cfg: &RollupConfig, channel: &ChannelReport) -> Value {
+    let timeout = channel.open_block.number + cfg.channel_timeout(channel.open_block.timestamp);
+    let mut value = json!({
+        "id": hex::encode(channel.id),
+        "open_block": channel.open_block.number,
+        "timeout_block": timeout,
+        "frames": channel.frames.iter().map(frame_json).collect::<Vec<_>>(),
+    });
+
+    match &channel.status {
+        ChannelStatus::Ready { origin, data } => {
+            let decoded = decode_channel(cfg, origin, data);
+            value["status"] = "ready".into();
+            value["read_block"] = origin.number.into();
+            value["compressed_size"] = data.len().into();
+            value["batches"] = decoded.batches.iter().map(batch_json).collect::<Vec<_>>().into();
+            if let Some(error) = decoded.error {
+                value["invalid_batch"] = json!({
+                    "index": decoded.batches.len(),
+                    "reason": error,
+                });
+            }
+        }
+        ChannelStatus::TimedOut { l1_block } => {
+            value["status"] = "timed_out".into();
+            value["closed_block"] = (*l1_block).into();
+        }
+        ChannelStatus::Dropped { l1_block, reason } => {
+            value["status"] = "dropped".into();
+            value["closed_block"] = (*l1_block).into();
+            value["reason"] = reason.as_str().into();
+        }
+        ChannelStatus::Incomplete => value["status"] = "incomplete".into(),
+    }
+    value
+}
+
+/// Renders a frame.
+fn frame_json(frame: &FrameInfo) -> Value {
+    json!({
+        "number": frame.number,
+        "l1_block": frame.l1_block,
+        "size": frame.size,
+        "is_last": frame.is_last,
+    })
+}
+
+/// Renders a batch.
+fn batch_json(batch: &Batch) -> Value {
+    match batch {
+        Batch::Single(batch) => single_batch_json(batch),
+        Batch::Span(batch) => span_batch_json(batch),
+    }
+}
+
+/// Renders a [SingleBatch].
+fn single_batch_json(batch: &SingleBatch) -> Value {
+    json!({
+        "type": "single",
+        "parent_hash": batch.parent_hash.to_string(),
+        "epoch_num": batch.epoch_num,
+        "epoch_hash": batch.epoch_hash.to_string(),
+        "timestamp": batch.timestamp,
+        "transactions": batch.transactions.iter().map(ToString::to_string).collect::<Vec<_>>(),
+    })
+}
+
+/// Renders a [SpanBatch], with one entry per L2 block.
+fn span_batch_json(batch: &SpanBatch) -> Value {
+    json!({
+        "type": "span",
+        "parent_check": batch.parent_check.to_string(),
+        "l1_origin_check": batch.l1_origin_check.to_string(),
+        "start_timestamp": batch.starting_timestamp(),
+        "final_timestamp": batch.final_timestamp(),
+        "blocks": batch
+            .batches
+            .iter()
+            .map(|element| json!({
+                "epoch_num": element.epoch_num,
+                "timestamp": element.timestamp,
+                "transactions": element
+                    .transactions
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
@@ -0,0 +1,333 @@
+//! Channels Subcommand
+
+use crate::flags::GlobalArgs;
+use alloy_eips::eip4844::{BYTES_PER_BLOB, Blob};
+use alloy_primitives::{Address, Bytes, hex};
+use anyhow::{Result, anyhow, bail};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_derive::{
+    BlobData, ChainProvider, DataAvailabilityProvider, EthereumDataSource, PipelineError,
+};
+use kona_genesis::RollupConfig;
+use kona_protocol::{BlockInfo, Frame};
+use kona_providers_alloy::{AlloyChainProvider, OnlineBeaconClient, OnlineBlobProvider};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, info};
+use url::Url;
+
+mod assembler;
+use assembler::ChannelInspector;
+
+mod decode;
+use decode::{InvalidData, report_json};
+
+/// The `channels` Subcommand
+///
+/// The `channels` subcommand decodes the data posted by the batcher. Frames are read from an L1
+/// block range or from files, reassembled into channels following the rules of the derivation
+/// pipeline, and the batches of every channel are printed as JSON, along with the channels that
+/// timed out or were dropped, and the frames that were not added to any channel.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node channels --l1-eth-rpc <URL> --l1-beacon <URL> --start <BLOCK> [--end <BLOCK>]
+/// kona-node channels --file <PATH>...
+/// ```
+#[derive(Parser, Default, PartialEq, Debug, Clone)]
+#[command(about = "Decodes the channels and batches posted by the batcher")]
+pub struct ChannelsCommand {
+    /// URL of the L1 execution client RPC API.
+    #[arg(
+        long,
+        visible_alias = "l1",
+        requires_all = ["l1_beacon", "start"],
+        env = "KONA_NODE_L1_ETH_RPC"
+    )]
+    pub l1_eth_rpc: Option<Url>,
+    /// URL of the L1 beacon API, used to fetch the blobs of batcher transactions.
+    #[arg(long, visible_alias = "l1.beacon", env = "KONA_NODE_L1_BEACON")]
+    pub l1_beacon: Option<Url>,
+    /// The first L1 block to read batcher transactions from.
+    #[arg(long, requires = "l1_eth_rpc")]
+    pub start: Option<u64>,
+    /// The last L1 block to read batcher transactions from. Defaults to the start block.
+    #[arg(long, requires = "start")]
+    pub end: Option<u64>,
+    /// The batcher address. Defaults to the batcher address of the genesis system config.
+    #[arg(long)]
+    pub batcher: Option<Address>,
+    /// Files containing the raw calldata or blob of batcher transactions, as binary or hex.
+    ///
+    /// Each file is treated as included in its own L1 block, numbered from zero in the order
+    /// given, using the hardforks active at the current time.
+    #[arg(long = "file", conflicts_with = "l1_eth_rpc", required_unless_present = "l1_eth_rpc")]
+    pub files: Vec<PathBuf>,
+    /// Path to a custom L2 rollup configuration file
+    /// (overrides the default rollup configuration from the registry)
+    #[arg(long, visible_alias = "rollup-cfg", env = "KONA_NODE_ROLLUP_CONFIG")]
+    pub l2_config_file: Option<PathBuf>,
+    /// Writes the JSON report to the given file instead of stdout.
+    #[arg(long, short = 'o')]
+    pub output: Option<PathBuf>,
+}
+
+impl ChannelsCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub async fn run(self, args: &GlobalArgs) -> Result<()> {
+        let cfg = args.apply_overrides(args.get_l2_config(self.l2_config_file.as_deref())?);
+        let inputs = if self.l1_eth_rpc.is_some() {
+            self.fetch_l1_data(&cfg).await?
+        } else {
+            self.read_files()?
+        };
+
+        let mut invalid = Vec::new();
+        for (origin, data) in &inputs {
+            for (index, data) in data.iter().enumerate() {
+                if let Err(e) = Frame::parse_frames(data) {
+                    invalid.push(InvalidData {
+                        l1_block: origin.number,
+                        index,
+                        reason: e.to_string(),
+                    });
+                }
+            }
+        }
+        let (channels, dropped) = ChannelInspector::new(&cfg, inputs).run().await?;
+        info!(
+            target: "channels",
+            "Decoded {} channels, with {} dropped frames and {} invalid transactions",
+            channels.len(),
+            dropped.len(),
+            invalid.len()
+        );
+
+        let report = report_json(&cfg, &channels, &dropped, &invalid);
+        match &self.output {
+            Some(path) => serde_json::to_writer_pretty(File::create(path)?, &report)?,
+            None => println!("{}", serde_json::to_string_pretty(&report)?),
+        }
+        Ok(())
+    }
+
+    /// Fetches the batcher data of the configured L1 block range, filtered by the batcher address
+    /// and batch inbox as in the derivation pipeline.
+    async fn fetch_l1_data(&self, cfg: &RollupConfig) -> Result<Vec<(BlockInfo, Vec<Bytes>)>> {
+        let (Some(l1_eth_rpc), Some(l1_beacon), Some(start)) =
+            (self.l1_eth_rpc.clone(), self.l1_beacon.as_ref(), self.start)
+        else {
+            bail!("--l1-eth-rpc, --l1-beacon and --start are required to read from L1");
+        };
+        let end = self.end.unwrap_or(start);
+        if end < start {
+            bail!("The end block {end} is before the start block {start}");
+        }
+        let batcher = self
+            .batcher
+            .or_else(|| cfg.genesis.system_config.as_ref().map(|sc| sc.batcher_address))
+            .ok_or_else(|| anyhow!("No batcher address configured, set one with --batcher"))?;
+
+        let mut chain_provider = AlloyChainProvider::new_http(l1_eth_rpc, 64);
+        let blob_provider =
+            OnlineBlobProvider::init(OnlineBeaconClient::new_http(l1_beacon.to_string())).await;
+        let mut source =
+            EthereumDataSource::new_from_parts(chain_provider.clone(), blob_provider, cfg);
+
+        let mut inputs = Vec::with_capacity((end - start + 1) as usize);
+        for number in start..=end {
+            let block = chain_provider
+                .block_info_by_number(number)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch L1 block {number}: {e}"))?;
+
+            let mut data = Vec::new();
+            loop {
+                match source.next(&block, batcher).await {
+                    Ok(item) => data.push(item),
+                    Err(e) if e == PipelineError::Eof.temp() => break,
+                    Err(e) => bail!("Failed to fetch the batcher data of L1 block {number}: {e}"),
+                }
+            }
+            source.clear();
+
+            debug!(
+                target: "channels",
+                "Read {} batcher transactions from L1 block {number}",
+                data.len()
+            );
+            inputs.push((block, data));
+        }
+        Ok(inputs)
+    }
+
+    /// Reads the batcher data from the configured files.
+    fn read_files(&self) -> Result<Vec<(BlockInfo, Vec<Bytes>)>> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(number, path)| {
+                let origin = BlockInfo { number: number as u64, timestamp, ..Default::default() };
+                Ok((origin, vec![Self::read_file(path)?]))
+            })
+            .collect()
+    }
+
+    /// Reads the batcher data from a file containing transaction calldata or a blob, as binary
+    /// or hex.
+    fn read_file(path: &Path) -> Result<Bytes> {
+        let raw =
+            std::fs::read(path).map_err(|e| anyhow!("Failed to read {}: {e}", path.display()))?;
+        let data = std::str::from_utf8(&raw)
+            .ok()
+            .and_then(|text| hex::decode(text.trim()).ok())
+            .unwrap_or(raw);
+
+        if data.len() == BYTES_PER_BLOB {
+            let blob = Blob::try_from(data.as_slice())?;
+            return BlobData::decode_blob(&blob)
+                .map_err(|e| anyhow!("Failed to decode the blob in {}: {e}", path.display()));
+        }
+        Ok(data.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_genesis::HardForkConfig;
+    use kona_protocol::{Batch, DERIVATION_VERSION_0, SingleBatch};
+
+    #[test]
+    fn test_channels_cli_requires_input() {
+        let err = ChannelsCommand::try_parse_from(["channels"]).unwrap_err();
+        assert!(err.to_string().contains("--file"));
+    }
+
+    #[test]
+    fn test_channels_cli_l1_requires_beacon_and_start() {
+        let err = ChannelsCommand::try_parse_from(["channels", "--l1-eth-rpc", "http://localhost"])
+            .unwrap_err();
+        assert!(err.to_string().contains("--l1-beacon"));
+    }
+
+    #[test]
+    fn test_channels_cli_files() {
+        let args = ChannelsCommand::parse_from(["channels", "--file", "a.hex", "--file", "b.hex"]);
+        assert_eq!(args.files, vec![PathBuf::from("a.hex"), PathBuf::from("b.hex")]);
+    }
+
+    /// Returns the compressed data of a channel holding a single default [SingleBatch].
+    fn channel_data() -> Vec<u8> {
+        let mut encoded = Vec::new();
+        Batch::Single(SingleBatch::default()).encode(&mut encoded).unwrap();
+        let rlp = alloy_rlp::encode(encoded.as_slice());
+        miniz_oxide::deflate::compress_to_vec_zlib(&rlp, 9)
+    }
+
+    /// Returns the data of a batcher transaction carrying the given frames.
+    fn tx_data(frames: &[Frame]) -> Bytes {
+        let mut data = vec![DERIVATION_VERSION_0];
+        frames.iter().for_each(|frame| data.extend(frame.encode()));
+        data.into()
+    }
+
+    /// Returns the L1 block with the given number.
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo { number, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_inspect_split_channel() {
+        let cfg = RollupConfig { channel_timeout: 10, ..Default::default() };
+        let data = channel_data();
+        let (first, second) = data.split_at(data.len() / 2);
+        let inputs = vec![
+            (block(0), vec![tx_data(&[Frame::new([1; 16], 0, first.to_vec(), false)])]),
+            (block(1), vec![tx_data(&[Frame::new([1; 16], 1, second.to_vec(), true)])]),
+        ];
+
+        let (channels, dropped) = ChannelInspector::new(&cfg, inputs).run().await.unwrap();
+        assert!(dropped.is_empty());
+
+        let report = report_json(&cfg, &channels, &dropped, &[]);
+        let channel = &report["channels"][0];
+        assert_eq!(channel["status"], "ready");
+        assert_eq!(channel["read_block"], 1);
+        assert_eq!(channel["frames"].as_array().unwrap().len(), 2);
+        assert_eq!(channel["batches"][0]["type"], "single");
+        assert!(channel.get("invalid_batch").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_inspect_timed_out_channel() {
+        let cfg = RollupConfig { channel_timeout: 2, ..Default::default() };
+        let inputs = vec![
+            (block(0), vec![tx_data(&[Frame::new([1; 16], 0, vec![0xff], false)])]),
+            (block(3), vec![tx_data(&[Frame::new([1; 16], 1, vec![0xff], true)])]),
+        ];
+
+        let (channels, dropped) = ChannelInspector::new(&cfg, inputs).run().await.unwrap();
+        let report = report_json(&cfg, &channels, &dropped, &[]);
+        assert_eq!(report["channels"][0]["status"], "timed_out");
+        assert_eq!(report["channels"][0]["timeout_block"], 2);
+        assert_eq!(report["channels"][1]["status"], "incomplete");
+        assert!(dropped.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_inspect_holocene_drops_out_of_order_frames() {
+        let cfg = RollupConfig {
+            channel_timeout: 10,
+            hardforks: HardForkConfig { holocene_time: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+        let inputs = vec![(
+            block(0),
+            vec![
+                tx_data(&[
+                    Frame::new([1; 16], 1, vec![0xff], false),
+                    Frame::new([2; 16], 0, vec![0xff], false),
+                    Frame::new([2; 16], 2, vec![0xff], true),
+                ]),
+                tx_data(&[Frame::new([3; 16], 0, vec![0xff], false)]),
+            ],
+        )];
+
+        let (channels, dropped) = ChannelInspector::new(&cfg, inputs).run().await.unwrap();
+        assert_eq!(dropped.len(), 2);
+        assert_eq!(dropped[0].reason, "channel was superseded before it was complete");
+        assert_eq!(dropped[1].reason, "frame is not sequential to the previous frame");
+
+        let report = report_json(&cfg, &channels, &dropped, &[]);
+        assert_eq!(report["channels"][0]["status"], "dropped");
+        assert_eq!(
+            report["channels"][0]["reason"],
+            format!("superseded by channel {}", hex::encode([3; 16]))
+        );
+        assert_eq!(report["channels"][1]["status"], "incomplete");
+    }
+
+    #[tokio::test]
+    async fn test_inspect_invalid_channel_data() {
+        let cfg = RollupConfig::default();
+        let inputs = vec![(block(0), vec![tx_data(&[Frame::new([1; 16], 0, vec![0xff], true)])])];
+
+        let (channels, dropped) = ChannelInspector::new(&cfg, inputs).run().await.unwrap();
+        let report = report_json(&cfg, &channels, &dropped, &[]);
+        assert_eq!(report["channels"][0]["status"], "ready");
+        assert_eq!(report["channels"][0]["invalid_batch"]["index"], 0);
+    }
+}
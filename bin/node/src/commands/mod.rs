@@ -14,3 +14,9 @@ pub use net::NetCommand;
 
 mod registry;
 pub use registry::RegistryCommand;
+
+mod channels;
+pub use channels::ChannelsCommand;
+
+mod audit;
+pub use audit::DeriveAuditCommand;
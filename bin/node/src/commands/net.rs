@@ -72,7 +72,13 @@ impl NetCommand {
             NetworkActor::new(NetworkBuilder::from(p2p_config));
 
         let (blocks, mut blocks_rx) = tokio::sync::mpsc::channel(1024);
-        network.start(NetworkContext { blocks, cancellation: CancellationToken::new() }).await?;
+        network
+            .start(NetworkContext {
+                blocks,
+                engine_query: None,
+                cancellation: CancellationToken::new(),
+            })
+            .await?;
 
         info!(target: "net", "Network started, receiving blocks.");
 
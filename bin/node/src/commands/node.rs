@@ -9,11 +9,13 @@ use anyhow::{Result, bail};
 use backon::{ExponentialBuilder, Retryable};
 use clap::Parser;
 use kona_cli::{LogConfig, metrics_args::MetricsArgs};
+use kona_engine::{EngineEndpoint, SafeHeadDb};
 use kona_genesis::RollupConfig;
-use kona_node_service::{NodeMode, RollupNode, RollupNodeService};
-use kona_registry::scr_rollup_config_by_alloy_ident;
+use kona_node_service::{
+    CheckpointConfig, DEFAULT_CHECKPOINT_INTERVAL, NodeMode, RollupNode, RollupNodeService,
+    SyncMode,
+};
 use op_alloy_provider::ext::engine::OpEngineApi;
-use serde_json::from_reader;
 use std::{fs::File, path::PathBuf, sync::Arc};
 use strum::IntoEnumIterator;
 use tracing::{debug, error, info};
@@ -61,6 +63,12 @@ pub(super) enum JwtValidationError {
 ///           --l1-beacon http://localhost:5052 \
 ///           --l2-engine-rpc http://localhost:8551 \
 ///           --l2-jwt-secret /path/to/jwt.hex
+///
+/// # Sync the execution layer by deriving every block from the rollup genesis
+/// kona node --syncmode consensus-layer \
+///           --l1-eth-rpc http://localhost:8545 \
+///           --l1-beacon http://localhost:5052 \
+///           --l2-engine-rpc http://localhost:8551
 /// ```
 #[derive(Parser, PartialEq, Debug, Clone)]
 #[command(about = "Runs the consensus node")]
@@ -79,6 +87,23 @@ pub struct NodeCommand {
         )
     )]
     pub node_mode: NodeMode,
+    /// The mode to sync the execution layer in.
+    #[arg(
+        long = "syncmode",
+        default_value_t = SyncMode::ExecutionLayer,
+        env = "KONA_NODE_SYNC_MODE",
+        help = format!(
+            "The mode to sync the execution layer in. In \"{}\" mode, derivation starts from the \
+            EL's current head (the rollup genesis on a fresh EL, or the derivation checkpoint if \
+            set) without waiting for EL sync. Supported modes are: {}",
+            SyncMode::ConsensusLayer,
+            SyncMode::iter()
+                .map(|mode| format!("\"{mode}\""))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    )]
+    pub sync_mode: SyncMode,
     /// URL of the L1 execution client RPC API.
     #[arg(long, visible_alias = "l1", env = "KONA_NODE_L1_ETH_RPC")]
     pub l1_eth_rpc: Url,
@@ -88,6 +113,19 @@ pub struct NodeCommand {
     /// URL of the engine API endpoint of an L2 execution client.
     #[arg(long, visible_alias = "l2", env = "KONA_NODE_L2_ENGINE_RPC")]
     pub l2_engine_rpc: Url,
+    /// URLs of the engine API endpoints of secondary L2 execution clients. When the primary
+    /// execution client fails, a synced secondary is promoted to primary in its place.
+    #[arg(long, value_delimiter = ',', env = "KONA_NODE_L2_ENGINE_FAILOVER_RPC")]
+    pub l2_engine_failover_rpc: Vec<Url>,
+    /// JWT secrets for the engine API endpoints of the secondary L2 execution clients, in the same
+    /// order as `--l2-engine-failover-rpc`. These MUST be valid paths to files containing the
+    /// hex-encoded JWT secrets. If unset, the secondaries share the JWT secret of the primary.
+    #[arg(long, value_delimiter = ',', env = "KONA_NODE_L2_ENGINE_FAILOVER_AUTH")]
+    pub l2_engine_failover_jwt_secret: Vec<PathBuf>,
+    /// Mirror new payloads to all L2 execution clients, so that secondaries keep up with the chain
+    /// and can be promoted without syncing.
+    #[arg(long, default_value_t = false, env = "KONA_NODE_L2_ENGINE_MIRROR_NEW_PAYLOAD")]
+    pub l2_engine_mirror_new_payload: bool,
     /// JWT secret for the auth-rpc endpoint of the execution client.
     /// This MUST be a valid path to a file containing the hex-encoded JWT secret.
     #[arg(long, visible_alias = "l2.jwt-secret", env = "KONA_NODE_L2_ENGINE_AUTH")]
@@ -96,6 +134,26 @@ pub struct NodeCommand {
     /// (overrides the default rollup configuration from the registry)
     #[arg(long, visible_alias = "rollup-cfg", env = "KONA_NODE_ROLLUP_CONFIG")]
     pub l2_config_file: Option<PathBuf>,
+    /// Path to the file that checkpoints of the derivation pipeline are persisted to.
+    /// If set, the derivation pipeline is restored from the checkpoint on boot, as long as the
+    /// checkpoint still matches the canonical chain.
+    #[arg(long = "derivation.checkpoint-path", env = "KONA_NODE_DERIVATION_CHECKPOINT_PATH")]
+    pub derivation_checkpoint_path: Option<PathBuf>,
+    /// The minimum number of L1 blocks between persisted derivation pipeline checkpoints.
+    #[arg(
+        long = "derivation.checkpoint-interval",
+        default_value_t = DEFAULT_CHECKPOINT_INTERVAL,
+        env = "KONA_NODE_DERIVATION_CHECKPOINT_INTERVAL"
+    )]
+    pub derivation_checkpoint_interval: u64,
+    /// Path to the database file that the L2 safe head derived from each L1 block is recorded in.
+    /// If set, the `optimism_safeHeadAtL1Block` RPC method is enabled.
+    #[arg(long = "safedb.path", env = "KONA_NODE_SAFEDB_PATH")]
+    pub safe_db_path: Option<PathBuf>,
+    /// URL of the Alt-DA server that input commitments are resolved against.
+    /// Required if the rollup config enables Alt-DA.
+    #[arg(long = "altda.da-server", env = "KONA_NODE_ALTDA_DA_SERVER")]
+    pub altda_da_server: Option<Url>,
     /// P2P CLI arguments.
     #[command(flatten)]
     pub p2p_flags: P2PArgs,
@@ -113,9 +171,17 @@ impl Default for NodeCommand {
             l1_eth_rpc: Url::parse("http://localhost:8545").unwrap(),
             l1_beacon: Url::parse("http://localhost:5052").unwrap(),
             l2_engine_rpc: Url::parse("http://localhost:8551").unwrap(),
+            l2_engine_failover_rpc: Vec::new(),
+            l2_engine_failover_jwt_secret: Vec::new(),
+            l2_engine_mirror_new_payload: false,
             l2_engine_jwt_secret: None,
             l2_config_file: None,
+            derivation_checkpoint_path: None,
+            derivation_checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            safe_db_path: None,
+            altda_da_server: None,
             node_mode: NodeMode::Validator,
+            sync_mode: SyncMode::ExecutionLayer,
             p2p_flags: P2PArgs::default(),
             rpc_flags: RpcArgs::default(),
             sequencer_flags: SequencerArgs::default(),
@@ -246,10 +312,14 @@ impl NodeCommand {
         args.metrics.enabled.then(|| init_rollup_config_metrics(&cfg));
 
         let jwt_secret = self.validate_jwt(&cfg).await?;
+        let failover_endpoints = self.failover_endpoints(jwt_secret)?;
 
         self.p2p_flags.check_ports()?;
         let p2p_config = self.p2p_flags.config(&cfg, args, Some(self.l1_eth_rpc.clone())).await?;
+        let checkpoint_config = self.checkpoint_config();
+        let safe_head_db = self.safe_head_db()?;
         let rpc_config = self.rpc_flags.into();
+        let upgrades = args.upgrade_registry()?;
 
         info!(
             target: "rollup_node",
@@ -262,13 +332,20 @@ impl NodeCommand {
 
         RollupNode::builder(cfg)
             .with_mode(self.node_mode)
+            .with_sync_mode(self.sync_mode)
             .with_jwt_secret(jwt_secret)
             .with_l1_provider_rpc_url(self.l1_eth_rpc)
             .with_l1_beacon_api_url(self.l1_beacon)
             .with_l2_engine_rpc_url(self.l2_engine_rpc)
+            .with_l2_engine_failover_endpoints(failover_endpoints)
+            .with_mirror_new_payload(self.l2_engine_mirror_new_payload)
             .with_p2p_config(p2p_config)
             .with_rpc_config(rpc_config)
             .with_sequencer_config(self.sequencer_flags.config())
+            .with_checkpoint_config(checkpoint_config)
+            .with_safe_head_db(safe_head_db)
+            .with_altda_server_url(self.altda_da_server)
+            .with_upgrade_registry(upgrades)
             .build()
             .start()
             .await
@@ -280,23 +357,27 @@ impl NodeCommand {
         Ok(())
     }
 
+    /// Returns the [`CheckpointConfig`] for the derivation pipeline, if a checkpoint path is set.
+    pub fn checkpoint_config(&self) -> Option<CheckpointConfig> {
+        self.derivation_checkpoint_path
+            .clone()
+            .map(|path| CheckpointConfig { path, interval: self.derivation_checkpoint_interval })
+    }
+
+    /// Opens the [`SafeHeadDb`], if a safe head database path is set.
+    pub fn safe_head_db(&self) -> Result<Option<Arc<SafeHeadDb>>> {
+        let Some(path) = self.safe_db_path.as_ref() else {
+            return Ok(None);
+        };
+        let db = SafeHeadDb::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open safe head database: {}", e))?;
+        info!(target: "rollup_node", ?path, "Opened safe head database");
+        Ok(Some(Arc::new(db)))
+    }
+
     /// Get the L2 rollup config, either from a file or the superchain registry.
     pub fn get_l2_config(&self, args: &GlobalArgs) -> Result<RollupConfig> {
-        match &self.l2_config_file {
-            Some(path) => {
-                debug!("Loading l2 config from file: {:?}", path);
-                let file = File::open(path)
-                    .map_err(|e| anyhow::anyhow!("Failed to open l2 config file: {}", e))?;
-                from_reader(file).map_err(|e| anyhow::anyhow!("Failed to parse l2 config: {}", e))
-            }
-            None => {
-                debug!("Loading l2 config from superchain registry");
-                let Some(cfg) = scr_rollup_config_by_alloy_ident(&args.l2_chain_id) else {
-                    bail!("Failed to find l2 config for chain ID {}", args.l2_chain_id);
-                };
-                Ok(cfg.clone())
-            }
-        }
+        args.get_l2_config(self.l2_config_file.as_deref())
     }
 
     /// Returns the JWT secret for the engine API
@@ -311,6 +392,43 @@ impl NodeCommand {
         Self::default_jwt_secret()
     }
 
+    /// Returns the [`EngineEndpoint`]s of the secondary L2 execution clients.
+    ///
+    /// Each secondary is authenticated with the JWT secret at the same position in
+    /// `--l2-engine-failover-jwt-secret`, or with the given JWT secret of the primary if none are
+    /// set.
+    pub fn failover_endpoints(&self, jwt_secret: JwtSecret) -> Result<Vec<EngineEndpoint>> {
+        if self.l2_engine_failover_jwt_secret.is_empty() {
+            return Ok(self
+                .l2_engine_failover_rpc
+                .iter()
+                .map(|url| EngineEndpoint::new(url.clone(), jwt_secret))
+                .collect());
+        }
+
+        if self.l2_engine_failover_jwt_secret.len() != self.l2_engine_failover_rpc.len() {
+            bail!(
+                "Expected {} failover JWT secrets, one per failover engine, but got {}",
+                self.l2_engine_failover_rpc.len(),
+                self.l2_engine_failover_jwt_secret.len()
+            );
+        }
+
+        self.l2_engine_failover_rpc
+            .iter()
+            .zip(&self.l2_engine_failover_jwt_secret)
+            .map(|(url, path)| {
+                let secret = std::fs::read_to_string(path).map_err(|e| {
+                    anyhow::anyhow!("Failed to read failover JWT secret {:?}: {}", path, e)
+                })?;
+                let jwt = JwtSecret::from_hex(secret.trim()).map_err(|e| {
+                    anyhow::anyhow!("Invalid failover JWT secret {:?}: {}", path, e)
+                })?;
+                Ok(EngineEndpoint::new(url.clone(), jwt))
+            })
+            .collect()
+    }
+
     /// Uses the current directory to attempt to read
     /// the JWT secret from a file named `jwt.hex`.
     /// If the file is not found, it will return `None`.
@@ -367,6 +485,139 @@ mod tests {
     fn test_node_cli_defaults() {
         let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
         assert_eq!(args.node_mode, NodeMode::Validator);
+        assert_eq!(args.sync_mode, SyncMode::ExecutionLayer);
+    }
+
+    #[test]
+    fn test_node_cli_sync_mode() {
+        let args = NodeCommand::parse_from(
+            ["node", "--syncmode", "consensus-layer"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.sync_mode, SyncMode::ConsensusLayer);
+
+        let args = NodeCommand::parse_from(
+            ["node", "--syncmode", "execution-layer"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.sync_mode, SyncMode::ExecutionLayer);
+
+        let err = NodeCommand::try_parse_from(
+            ["node", "--syncmode", "snap"].iter().chain(default_flags().iter()).copied(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn test_node_cli_derivation_checkpoint() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert!(args.checkpoint_config().is_none());
+
+        let args = NodeCommand::parse_from(
+            ["node", "--derivation.checkpoint-path", "/tmp/checkpoint.json"]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert_eq!(
+            args.checkpoint_config(),
+            Some(CheckpointConfig::new(PathBuf::from("/tmp/checkpoint.json")))
+        );
+    }
+
+    #[test]
+    fn test_node_cli_safe_db() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert!(args.safe_head_db().unwrap().is_none());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("safe_db");
+        let args = NodeCommand::parse_from(
+            ["node", "--safedb.path", path.to_str().unwrap()]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert_eq!(args.safe_db_path, Some(path.clone()));
+        let db = args.safe_head_db().unwrap().unwrap();
+        assert_eq!(db.path(), path);
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_node_cli_engine_failover() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert!(args.l2_engine_failover_rpc.is_empty());
+        assert!(args.l2_engine_failover_jwt_secret.is_empty());
+        assert!(!args.l2_engine_mirror_new_payload);
+        assert!(args.failover_endpoints(JwtSecret::random()).unwrap().is_empty());
+
+        let dir = tempfile::tempdir().unwrap();
+        let secondary_jwt = JwtSecret::random();
+        let secondary_path = dir.path().join("secondary.hex");
+        std::fs::write(&secondary_path, alloy_primitives::hex::encode(secondary_jwt.as_bytes()))
+            .unwrap();
+        let tertiary_jwt = JwtSecret::random();
+        let tertiary_path = dir.path().join("tertiary.hex");
+        std::fs::write(&tertiary_path, alloy_primitives::hex::encode(tertiary_jwt.as_bytes()))
+            .unwrap();
+        let jwt_secrets = format!("{},{}", secondary_path.display(), tertiary_path.display());
+
+        let args = NodeCommand::parse_from(
+            [
+                "node",
+                "--l2-engine-failover-rpc",
+                "http://localhost:8552,http://localhost:8553",
+                "--l2-engine-failover-jwt-secret",
+                jwt_secrets.as_str(),
+                "--l2-engine-mirror-new-payload",
+            ]
+            .iter()
+            .chain(default_flags().iter())
+            .copied(),
+        );
+        assert_eq!(
+            args.l2_engine_failover_rpc,
+            vec![
+                Url::parse("http://localhost:8552").unwrap(),
+                Url::parse("http://localhost:8553").unwrap()
+            ]
+        );
+        assert!(args.l2_engine_mirror_new_payload);
+        assert_eq!(
+            args.failover_endpoints(JwtSecret::random()).unwrap(),
+            vec![
+                EngineEndpoint::new(Url::parse("http://localhost:8552").unwrap(), secondary_jwt),
+                EngineEndpoint::new(Url::parse("http://localhost:8553").unwrap(), tertiary_jwt),
+            ]
+        );
+
+        // Without failover JWT secrets, the secondaries share the JWT secret of the primary.
+        let primary_jwt = JwtSecret::random();
+        let args = NodeCommand::parse_from(
+            ["node", "--l2-engine-failover-rpc", "http://localhost:8552"]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert_eq!(
+            args.failover_endpoints(primary_jwt).unwrap(),
+            vec![EngineEndpoint::new(Url::parse("http://localhost:8552").unwrap(), primary_jwt)]
+        );
+
+        // The number of failover JWT secrets must match the number of failover engines.
+        let args = NodeCommand::parse_from(
+            [
+                "node",
+                "--l2-engine-failover-rpc",
+                "http://localhost:8552,http://localhost:8553",
+                "--l2-engine-failover-jwt-secret",
+                secondary_path.to_str().unwrap(),
+            ]
+            .iter()
+            .chain(default_flags().iter())
+            .copied(),
+        );
+        assert!(args.failover_endpoints(primary_jwt).is_err());
     }
 
     #[test]
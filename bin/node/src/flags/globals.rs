@@ -4,7 +4,10 @@ use alloy_primitives::Address;
 use clap::Parser;
 use kona_cli::{log::LogArgs, metrics_args::MetricsArgs};
 use kona_genesis::RollupConfig;
-use kona_registry::OPCHAINS;
+use kona_hardforks::UpgradeRegistry;
+use kona_registry::{OPCHAINS, scr_rollup_config_by_alloy_ident};
+use std::{fs::File, path::Path};
+use tracing::debug;
 
 /// Global arguments for the CLI.
 #[derive(Parser, Default, Clone, Debug)]
@@ -26,6 +29,9 @@ pub struct GlobalArgs {
     /// Embed the override flags globally to provide override values adjacent to the configs.
     #[command(flatten)]
     pub override_args: super::OverrideArgs,
+    /// The network upgrade flags.
+    #[command(flatten)]
+    pub upgrade_args: super::UpgradeArgs,
     /// Prometheus CLI arguments.
     #[command(flatten)]
     pub metrics: MetricsArgs,
@@ -39,6 +45,31 @@ impl GlobalArgs {
         self.override_args.apply(config)
     }
 
+    /// Get the L2 rollup config, either from the given file or the superchain registry.
+    pub fn get_l2_config(&self, l2_config_file: Option<&Path>) -> anyhow::Result<RollupConfig> {
+        match l2_config_file {
+            Some(path) => {
+                debug!("Loading l2 config from file: {:?}", path);
+                let file = File::open(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to open l2 config file: {}", e))?;
+                serde_json::from_reader(file)
+                    .map_err(|e| anyhow::anyhow!("Failed to parse l2 config: {}", e))
+            }
+            None => {
+                debug!("Loading l2 config from superchain registry");
+                let Some(cfg) = scr_rollup_config_by_alloy_ident(&self.l2_chain_id) else {
+                    anyhow::bail!("Failed to find l2 config for chain ID {}", self.l2_chain_id);
+                };
+                Ok(cfg.clone())
+            }
+        }
+    }
+
+    /// Loads the [`UpgradeRegistry`] that provides the network upgrade transactions, if set.
+    pub fn upgrade_registry(&self) -> anyhow::Result<Option<UpgradeRegistry>> {
+        self.upgrade_args.registry()
+    }
+
     /// Returns the signer [`Address`] from the rollup config for the given l2 chain id.
     pub fn genesis_signer(&self) -> anyhow::Result<Address> {
         let id = self.l2_chain_id;
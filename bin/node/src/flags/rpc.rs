@@ -59,6 +59,7 @@ impl From<RpcArgs> for Option<RpcBuilder> {
             admin_persistence: args.admin_persistence,
             ws_enabled: args.ws_enabled,
             dev_enabled: args.dev_enabled,
+            safe_head_db: None,
         })
     }
 }
@@ -0,0 +1,111 @@
+//! Flags to configure the network upgrades.
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use kona_hardforks::UpgradeRegistry;
+use std::{fs, path::PathBuf};
+use tracing::debug;
+
+/// Network upgrade flags.
+#[derive(Parser, Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpgradeArgs {
+    /// Path to a JSON or TOML file describing network upgrades, merged into the built-in
+    /// hardforks to build the network upgrade transactions. Upgrades replace the built-in
+    /// upgrade with the same name.
+    #[arg(long = "upgrades.registry", env = "KONA_NODE_UPGRADE_REGISTRY")]
+    pub upgrade_registry: Option<PathBuf>,
+}
+
+impl UpgradeArgs {
+    /// Loads the [`UpgradeRegistry`], if a registry file is set.
+    ///
+    /// Files with a `.toml` extension are parsed as TOML, all others as JSON. The upgrades of the
+    /// file are merged into [`UpgradeRegistry::builtin`], so built-in hardforks missing from the
+    /// file keep their transactions. The upgrades of built-in hardforks must reproduce their
+    /// transactions exactly.
+    pub fn registry(&self) -> Result<Option<UpgradeRegistry>> {
+        let Some(path) = self.upgrade_registry.as_ref() else {
+            return Ok(None);
+        };
+        debug!("Loading upgrade registry from file: {:?}", path);
+        let raw = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read upgrade registry file: {}", e))?;
+        let file = Self::parse(&raw, path.extension().is_some_and(|ext| ext == "toml"))?;
+        file.verify_builtin().map_err(|e| anyhow!("Invalid upgrade registry: {}", e))?;
+        Ok(Some(
+            file.upgrades
+                .into_iter()
+                .fold(UpgradeRegistry::builtin(), UpgradeRegistry::with_upgrade),
+        ))
+    }
+
+    fn parse(raw: &str, toml: bool) -> Result<UpgradeRegistry> {
+        if toml {
+            toml::from_str(raw).map_err(|e| anyhow!("Failed to parse upgrade registry: {}", e))
+        } else {
+            serde_json::from_str(raw)
+                .map_err(|e| anyhow!("Failed to parse upgrade registry: {}", e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+    use kona_genesis::{HardForkConfig, RollupConfig};
+    use kona_hardforks::{Hardfork, Hardforks};
+    use std::io::Write;
+
+    #[test]
+    fn test_upgrade_args_default() {
+        let args = UpgradeArgs::try_parse_from(["test"]).unwrap();
+        assert_eq!(args, UpgradeArgs::default());
+        assert!(args.registry().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_upgrade_args_load_json() {
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        let registry = UpgradeRegistry::builtin();
+        file.write_all(serde_json::to_string(&registry).unwrap().as_bytes()).unwrap();
+
+        let path = file.path().to_str().unwrap();
+        let args = UpgradeArgs::try_parse_from(["test", "--upgrades.registry", path]).unwrap();
+        assert_eq!(args.registry().unwrap(), Some(registry));
+    }
+
+    #[test]
+    fn test_upgrade_args_load_toml() {
+        let mut file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        file.write_all(b"upgrades = []").unwrap();
+
+        let args = UpgradeArgs { upgrade_registry: Some(file.path().to_path_buf()) };
+        assert_eq!(args.registry().unwrap(), Some(UpgradeRegistry::builtin()));
+    }
+
+    #[test]
+    fn test_upgrade_args_empty_file_keeps_builtin_deposits() {
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        file.write_all(br#"{"upgrades":[]}"#).unwrap();
+
+        let args = UpgradeArgs { upgrade_registry: Some(file.path().to_path_buf()) };
+        let registry = args.registry().unwrap().unwrap();
+        let cfg = RollupConfig {
+            hardforks: HardForkConfig { ecotone_time: Some(10), ..Default::default() },
+            ..Default::default()
+        };
+        assert_eq!(registry.txs(&cfg, 8, 10), Hardforks::ECOTONE.txs().collect::<Vec<Bytes>>());
+    }
+
+    #[test]
+    fn test_upgrade_args_rejects_invalid_builtin() {
+        let mut registry = UpgradeRegistry::builtin();
+        registry.upgrades[0].transactions.pop();
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        file.write_all(serde_json::to_string(&registry).unwrap().as_bytes()).unwrap();
+
+        let args = UpgradeArgs { upgrade_registry: Some(file.path().to_path_buf()) };
+        assert!(args.registry().is_err());
+    }
+}
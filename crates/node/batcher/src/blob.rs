@@ -0,0 +1,144 @@
+//! Contains the encoding of batcher transaction data into EIP-4844 blobs.
+
+use crate::BatcherError;
+use alloy_eips::eip4844::{BYTES_PER_BLOB, Blob};
+
+/// The version of the blob encoding.
+pub const BLOB_ENCODING_VERSION: u8 = 0;
+
+/// The maximum number of bytes that can be encoded into a blob.
+pub const BLOB_MAX_DATA_SIZE: usize = (4 * 31 + 3) * 1024 - 4;
+
+/// The number of encoding rounds, each of which fills 4 field elements.
+const BLOB_ENCODING_ROUNDS: usize = 1024;
+
+/// Encodes the given data into a [Blob].
+///
+/// Each round packs 127 bytes of data into 4 field elements: 31 bytes fill the low bytes of each
+/// field element, and the remaining 3 bytes are split into 6 bit chunks that fill their high
+/// bytes. The first field element carries the encoding version and the 3 byte data length.
+pub fn encode_blob(data: &[u8]) -> Result<Box<Blob>, BatcherError> {
+    if data.len() > BLOB_MAX_DATA_SIZE {
+        return Err(BatcherError::BlobDataTooLarge(data.len()));
+    }
+
+    let mut blob = Box::new(Blob::ZERO);
+    let mut reader = BlobDataReader { data, offset: 0 };
+    let mut write_offset = 0;
+    let mut chunk = [0u8; 31];
+
+    for round in 0..BLOB_ENCODING_ROUNDS {
+        if reader.offset >= data.len() {
+            break;
+        }
+
+        if round == 0 {
+            // The first field element carries the version and length before the data.
+            chunk[0] = BLOB_ENCODING_VERSION;
+            chunk[1..4].copy_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+            reader.read_into(&mut chunk[4..]);
+        } else {
+            reader.read_into(&mut chunk);
+        }
+        let x = reader.read_byte();
+        write_field_element(&mut blob, &mut write_offset, x & 0b0011_1111, &chunk);
+
+        reader.read_into(&mut chunk);
+        let y = reader.read_byte();
+        let b = (y & 0b0000_1111) | ((x & 0b1100_0000) >> 2);
+        write_field_element(&mut blob, &mut write_offset, b, &chunk);
+
+        reader.read_into(&mut chunk);
+        let z = reader.read_byte();
+        write_field_element(&mut blob, &mut write_offset, z & 0b0011_1111, &chunk);
+
+        reader.read_into(&mut chunk);
+        let d = ((z & 0b1100_0000) >> 2) | ((y & 0b1111_0000) >> 4);
+        write_field_element(&mut blob, &mut write_offset, d, &chunk);
+    }
+
+    debug_assert!(write_offset <= BYTES_PER_BLOB);
+    Ok(blob)
+}
+
+/// Writes a field element made of the given high byte and low 31 bytes.
+fn write_field_element(blob: &mut Blob, offset: &mut usize, high: u8, low: &[u8; 31]) {
+    blob[*offset] = high;
+    blob[*offset + 1..*offset + 32].copy_from_slice(low);
+    *offset += 32;
+}
+
+/// Reads the data to encode, padding it with zeros past its end.
+struct BlobDataReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl BlobDataReader<'_> {
+    /// Reads the next byte.
+    fn read_byte(&mut self) -> u8 {
+        let byte = self.data.get(self.offset).copied().unwrap_or_default();
+        self.offset += 1;
+        byte
+    }
+
+    /// Fills the given buffer with the next bytes.
+    fn read_into(&mut self, buf: &mut [u8]) {
+        let start = self.offset.min(self.data.len());
+        let len = (self.data.len() - start).min(buf.len());
+        buf[..len].copy_from_slice(&self.data[start..start + len]);
+        buf[len..].fill(0);
+        self.offset += buf.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes a blob, as done by the derivation pipeline.
+    fn decode_blob(blob: &Blob) -> Vec<u8> {
+        assert_eq!(blob[1], BLOB_ENCODING_VERSION);
+        let len = u32::from_be_bytes([0, blob[2], blob[3], blob[4]]) as usize;
+
+        let mut out = Vec::with_capacity(BLOB_MAX_DATA_SIZE);
+        for round in 0..BLOB_ENCODING_ROUNDS {
+            let elements = &blob[round * 128..(round + 1) * 128];
+            let high: [u8; 4] = core::array::from_fn(|i| elements[i * 32]);
+            assert!(high.iter().all(|h| h & 0b1100_0000 == 0));
+
+            let low = |i: usize| &elements[i * 32 + 1..(i + 1) * 32];
+            let x = (high[0] & 0b0011_1111) | ((high[1] & 0b0011_0000) << 2);
+            let y = (high[1] & 0b0000_1111) | ((high[3] & 0b0000_1111) << 4);
+            let z = (high[2] & 0b0011_1111) | ((high[3] & 0b0011_0000) << 2);
+            out.extend_from_slice(if round == 0 { &low(0)[4..] } else { low(0) });
+            out.push(x);
+            out.extend_from_slice(low(1));
+            out.push(y);
+            out.extend_from_slice(low(2));
+            out.push(z);
+            out.extend_from_slice(low(3));
+        }
+        assert!(out[len..].iter().all(|b| *b == 0));
+        out.truncate(len);
+        out
+    }
+
+    #[test]
+    fn test_encode_blob_roundtrip() {
+        for len in [0, 1, 27, 28, 127, 128, 4096, BLOB_MAX_DATA_SIZE] {
+            let data = (0..len).map(|i| (i * 7 + 3) as u8).collect::<Vec<_>>();
+            let blob = encode_blob(&data).unwrap();
+            assert_eq!(decode_blob(&blob), data, "roundtrip failed for length {len}");
+        }
+    }
+
+    #[test]
+    fn test_encode_blob_too_large() {
+        let data = vec![0u8; BLOB_MAX_DATA_SIZE + 1];
+        assert!(matches!(
+            encode_blob(&data),
+            Err(BatcherError::BlobDataTooLarge(len)) if len == BLOB_MAX_DATA_SIZE + 1
+        ));
+    }
+}
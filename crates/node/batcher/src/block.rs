@@ -0,0 +1,38 @@
+//! Contains the conversion of L2 blocks into batches.
+
+use crate::BatcherError;
+use alloy_eips::eip2718::Encodable2718;
+use kona_protocol::{L1BlockInfoTx, SingleBatch};
+use op_alloy_consensus::OpBlock;
+
+/// Converts an L2 block into a [SingleBatch], returning it along with the [L1BlockInfoTx] of the
+/// block's L1 info deposit.
+///
+/// Deposit transactions are derived from L1, and are thus left out of the batch.
+pub fn block_to_batch(block: &OpBlock) -> Result<(SingleBatch, L1BlockInfoTx), BatcherError> {
+    let Some(first) = block.body.transactions.first() else {
+        return Err(BatcherError::EmptyBlock(block.header.hash_slow()));
+    };
+    let Some(deposit) = first.as_deposit() else {
+        return Err(BatcherError::FirstTxNotDeposit(block.header.hash_slow()));
+    };
+    let l1_info = L1BlockInfoTx::decode_calldata(deposit.input.as_ref())?;
+
+    let transactions = block
+        .body
+        .transactions
+        .iter()
+        .filter(|tx| !tx.is_deposit())
+        .map(|tx| tx.encoded_2718().into())
+        .collect();
+
+    let epoch = l1_info.id();
+    let batch = SingleBatch {
+        parent_hash: block.header.parent_hash,
+        epoch_num: epoch.number,
+        epoch_hash: epoch.hash,
+        timestamp: block.header.timestamp,
+        transactions,
+    };
+    Ok((batch, l1_info))
+}
@@ -0,0 +1,468 @@
+//! Contains the [Channel], which builds and tracks the frames of a single channel.
+
+use crate::{
+    BatcherCompressor, BatcherConfig, BatcherError, DataAvailabilityType, TxData, TxId,
+    block_to_batch,
+};
+use kona_comp::{ChannelOut, ChannelOutError, CompressorError, CompressorWriter};
+use kona_genesis::RollupConfig;
+use kona_protocol::{Batch, BatchType, BlockInfo, ChannelId, Frame, SingleBatch, SpanBatch};
+use op_alloy_consensus::OpBlock;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+};
+
+/// The size of the fixed fields of a version 0 frame.
+pub const FRAME_V0_OVERHEAD_SIZE: usize = 23;
+
+/// A channel of the batch submitter.
+///
+/// Blocks are added to the channel until it is full, after which it is closed and its frames are
+/// handed out as [TxData]. The channel keeps its blocks until it is pruned, so that it can be
+/// rebuilt if its transactions time out or are reorged out of L1.
+pub struct Channel<'a> {
+    /// The channel output.
+    out: ChannelOut<'a, BatcherCompressor>,
+    /// The type of batches in the channel.
+    batch_type: BatchType,
+    /// The span batch of the channel's blocks, for span batch channels. It is only encoded into
+    /// the channel output when the channel is closed.
+    span: SpanBatch,
+    /// An estimate of the encoded size of the span batch.
+    span_size: u64,
+    /// The maximum size of an encoded frame.
+    max_frame_size: usize,
+    /// The number of frames per transaction.
+    frames_per_tx: usize,
+    /// How frames are posted to L1.
+    da_type: DataAvailabilityType,
+    /// The maximum number of L1 blocks that the channel may stay open for.
+    max_channel_duration: u64,
+    /// The margin kept to the end of the sequencing window of the first block.
+    sub_safety_margin: u64,
+    /// The L1 block number at which the channel was opened.
+    opened_at: u64,
+    /// The blocks in the channel.
+    blocks: Vec<OpBlock>,
+    /// The L1 origin number of the first block.
+    first_epoch: Option<u64>,
+    /// Whether a block was rejected because the channel is full.
+    full: bool,
+    /// The frames that have not been handed out yet.
+    frames: VecDeque<Frame>,
+    /// The frames of the transactions that have been handed out, but not confirmed yet.
+    pending: HashMap<TxId, Vec<Frame>>,
+    /// The L1 blocks that include the confirmed transactions.
+    confirmed: HashMap<TxId, BlockInfo>,
+}
+
+impl fmt::Debug for Channel<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Channel")
+            .field("id", &self.out.id)
+            .field("batch_type", &self.batch_type)
+            .field("opened_at", &self.opened_at)
+            .field("blocks", &self.blocks.len())
+            .field("closed", &self.out.closed)
+            .field("frames", &self.frames.len())
+            .field("pending", &self.pending.len())
+            .field("confirmed", &self.confirmed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Channel<'a> {
+    /// Creates a new, empty [Channel].
+    pub fn new(
+        id: ChannelId,
+        rollup_config: &'a RollupConfig,
+        config: &BatcherConfig,
+        compressor: BatcherCompressor,
+        opened_at: u64,
+    ) -> Self {
+        Self {
+            out: ChannelOut::new(id, rollup_config, compressor),
+            batch_type: config.batch_type.clone(),
+            span: SpanBatch {
+                genesis_timestamp: rollup_config.genesis.l2_time,
+                chain_id: rollup_config.l2_chain_id.id(),
+                ..Default::default()
+            },
+            span_size: 0,
+            max_frame_size: config.max_frame_size(),
+            frames_per_tx: config.frames_per_tx(),
+            da_type: config.da_type,
+            max_channel_duration: config.max_channel_duration,
+            sub_safety_margin: config.sub_safety_margin,
+            opened_at,
+            blocks: Vec::new(),
+            first_epoch: None,
+            full: false,
+            frames: VecDeque::new(),
+            pending: HashMap::new(),
+            confirmed: HashMap::new(),
+        }
+    }
+
+    /// Returns the [ChannelId].
+    pub const fn id(&self) -> ChannelId {
+        self.out.id
+    }
+
+    /// Returns the blocks in the channel.
+    pub fn blocks(&self) -> &[OpBlock] {
+        &self.blocks
+    }
+
+    /// Consumes the channel, returning its blocks.
+    pub fn into_blocks(self) -> Vec<OpBlock> {
+        self.blocks
+    }
+
+    /// Returns `true` if the channel is closed.
+    pub const fn is_closed(&self) -> bool {
+        self.out.closed
+    }
+
+    /// Adds the given block and its batch to the channel. Returns `false` if the channel is full,
+    /// in which case the block was not added.
+    pub fn add_block(
+        &mut self,
+        block: OpBlock,
+        batch: SingleBatch,
+        seq_num: u64,
+    ) -> Result<bool, BatcherError> {
+        let epoch = batch.epoch_num;
+        let result = match self.batch_type {
+            BatchType::Single => self.out.add_batch(Batch::Single(batch)),
+            BatchType::Span => {
+                // Span batches are not appendable once encoded, so the span batch is only encoded
+                // into the channel output when the channel is closed. The encoding of a span
+                // batch is more compact than the encodings of its single batches, whose size is
+                // thus used to estimate its size.
+                let added = alloy_rlp::Encodable::length(&batch);
+                let timestamp = batch.timestamp;
+                self.span.append_singular_batch(batch, seq_num)?;
+
+                let result = self.check_span_size(added, timestamp);
+                if result.is_err() {
+                    self.rebuild_span()?;
+                }
+                result
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                self.first_epoch.get_or_insert(epoch);
+                self.blocks.push(block);
+                Ok(true)
+            }
+            Err(
+                ChannelOutError::Compression(CompressorError::Full) |
+                ChannelOutError::ExceedsMaxRlpBytesPerChannel,
+            ) if !self.blocks.is_empty() => {
+                self.full = true;
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Checks whether the span batch still fits the channel after growing by `added` bytes.
+    ///
+    /// The span batch is only encoded and compressed to measure its size once the size estimate
+    /// exceeds the limits of the channel.
+    fn check_span_size(&mut self, added: usize, timestamp: u64) -> Result<(), ChannelOutError> {
+        let max_rlp_bytes = self.out.config.max_rlp_bytes_per_channel(timestamp);
+        let size = self.span_size + added as u64;
+        if size <= max_rlp_bytes && self.out.compressor.fits_estimate(added) {
+            self.span_size = size;
+            return Ok(());
+        }
+
+        let mut encoded = Vec::new();
+        Batch::Span(self.span.clone())
+            .encode(&mut encoded)
+            .map_err(|_| ChannelOutError::BatchEncoding)?;
+        let encoded = alloy_rlp::encode(encoded.as_slice());
+        if encoded.len() as u64 > max_rlp_bytes {
+            return Err(ChannelOutError::ExceedsMaxRlpBytesPerChannel);
+        }
+        // Like the compressor, the channel accepts its first block even if it exceeds the target
+        // output size.
+        if !self.out.compressor.fits(&encoded)? && !self.blocks.is_empty() {
+            return Err(ChannelOutError::Compression(CompressorError::Full));
+        }
+        self.span_size = encoded.len() as u64;
+        Ok(())
+    }
+
+    /// Rebuilds the span batch from the blocks of the channel, dropping a rejected batch.
+    fn rebuild_span(&mut self) -> Result<(), BatcherError> {
+        let mut span = SpanBatch {
+            genesis_timestamp: self.span.genesis_timestamp,
+            chain_id: self.span.chain_id,
+            ..Default::default()
+        };
+        for block in &self.blocks {
+            let (batch, l1_info) = block_to_batch(block)?;
+            span.append_singular_batch(batch, l1_info.sequence_number())?;
+        }
+        self.span = span;
+        Ok(())
+    }
+
+    /// Returns `true` if the channel should be closed at the given L1 head: because it is full,
+    /// because it has been open for the maximum duration, or because the sequencing window of its
+    /// first block is about to end.
+    pub fn should_close(&self, rollup_config: &RollupConfig, l1_head: u64) -> bool {
+        let duration_exceeded =
+            self.max_channel_duration > 0 && l1_head >= self.opened_at + self.max_channel_duration;
+        let window_ending = self.first_epoch.is_some_and(|epoch| {
+            l1_head + self.sub_safety_margin >= epoch + rollup_config.seq_window_size
+        });
+        self.full || duration_exceeded || window_ending
+    }
+
+    /// Closes the channel, and outputs all of its frames.
+    pub fn close(&mut self) -> Result<(), BatcherError> {
+        if self.is_closed() {
+            return Ok(());
+        }
+
+        if self.batch_type == BatchType::Span && !self.blocks.is_empty() {
+            self.out.add_batch(Batch::Span(std::mem::take(&mut self.span)))?;
+        }
+        self.out.close();
+        self.out.compressor.close().map_err(ChannelOutError::from)?;
+        loop {
+            let frame = self.out.output_frame(self.max_frame_size)?;
+            let is_last = frame.is_last;
+            self.frames.push_back(frame);
+            if is_last {
+                break;
+            }
+        }
+
+        debug!(
+            target: "batcher",
+            "Closed channel {} with {} blocks into {} frames",
+            alloy_primitives::hex::encode(self.id()),
+            self.blocks.len(),
+            self.frames.len()
+        );
+        Ok(())
+    }
+
+    /// Hands out the next transaction of the channel, if any of its frames are ready.
+    pub fn next_tx(&mut self) -> Option<TxData> {
+        if self.frames.is_empty() {
+            return None;
+        }
+
+        let count = self.frames_per_tx.min(self.frames.len());
+        let frames = self.frames.drain(..count).collect::<Vec<_>>();
+        let tx = TxData { frames, da_type: self.da_type };
+        self.pending.insert(tx.id().ok()?, tx.frames.clone());
+        Some(tx)
+    }
+
+    /// Marks the given transaction as failed, queueing its frames for re-submission.
+    pub fn tx_failed(&mut self, id: &TxId) {
+        if let Some(frames) = self.pending.remove(id) {
+            for frame in frames.into_iter().rev() {
+                self.frames.push_front(frame);
+            }
+        }
+    }
+
+    /// Marks the given transaction as confirmed in the given L1 block.
+    pub fn tx_confirmed(&mut self, id: &TxId, inclusion: BlockInfo) {
+        if self.pending.remove(id).is_some() {
+            self.confirmed.insert(*id, inclusion);
+        }
+    }
+
+    /// Returns the L1 blocks that include the confirmed transactions of the channel.
+    pub fn inclusion_blocks(&self) -> impl Iterator<Item = &BlockInfo> {
+        self.confirmed.values()
+    }
+
+    /// Returns `true` if any transaction of the channel was included at or after the given L1
+    /// block number.
+    pub fn included_since(&self, number: u64) -> bool {
+        self.inclusion_blocks().any(|block| block.number >= number)
+    }
+
+    /// Returns `true` if all frames of the channel have been confirmed.
+    pub fn is_fully_confirmed(&self) -> bool {
+        self.is_closed() && self.frames.is_empty() && self.pending.is_empty()
+    }
+
+    /// Returns `true` if the transactions of the channel were included further apart than the
+    /// channel timeout, in which case the channel is dropped by the derivation pipeline. A channel
+    /// whose transactions are included exactly the channel timeout apart is still read.
+    pub fn is_timed_out(&self, rollup_config: &RollupConfig) -> bool {
+        let Some(first) = self.blocks.first() else {
+            return false;
+        };
+        let numbers = self.inclusion_blocks().map(|block| block.number);
+        let (Some(min), Some(max)) = (numbers.clone().min(), numbers.max()) else {
+            return false;
+        };
+        max - min > rollup_config.channel_timeout(first.header.timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{block_to_batch, test_utils::test_block};
+    use kona_comp::CompressionAlgo;
+    use kona_protocol::BatchReader;
+
+    fn test_channel<'a>(rollup_config: &'a RollupConfig, config: &BatcherConfig) -> Channel<'a> {
+        let compressor = BatcherCompressor::new(CompressionAlgo::Zlib.into(), usize::MAX);
+        Channel::new([0x01; 16], rollup_config, config, compressor, 0)
+    }
+
+    fn add_block(channel: &mut Channel<'_>, block: OpBlock) -> bool {
+        let (batch, l1_info) = block_to_batch(&block).unwrap();
+        channel.add_block(block, batch, l1_info.sequence_number()).unwrap()
+    }
+
+    #[test]
+    fn test_channel_frames_decode() {
+        let rollup_config = RollupConfig { block_time: 1, ..Default::default() };
+        for batch_type in [BatchType::Single, BatchType::Span] {
+            let config = BatcherConfig { batch_type, target_tx_size: 64, ..Default::default() };
+            let mut channel = test_channel(&rollup_config, &config);
+
+            let first = test_block(1, Default::default(), 0);
+            let second = test_block(2, first.header.hash_slow(), 1);
+            assert!(add_block(&mut channel, first));
+            assert!(add_block(&mut channel, second));
+            channel.close().unwrap();
+
+            let mut data = Vec::new();
+            let mut frame_number = 0;
+            while let Some(tx) = channel.next_tx() {
+                let frame = &tx.frames[0];
+                assert_eq!(frame.number, frame_number);
+                assert!(tx.calldata().len() <= config.target_tx_size);
+                data.extend_from_slice(&frame.data);
+                frame_number += 1;
+                channel.tx_confirmed(&tx.id().unwrap(), BlockInfo::default());
+            }
+            assert!(channel.is_fully_confirmed());
+
+            let mut reader = BatchReader::new(data, usize::MAX);
+            let batch = reader.next_batch(&rollup_config).unwrap();
+            match batch {
+                Batch::Single(batch) => {
+                    assert_eq!(batch.timestamp, 1);
+                    assert!(matches!(reader.next_batch(&rollup_config), Some(Batch::Single(_))));
+                }
+                Batch::Span(batch) => assert_eq!(batch.batches.len(), 2),
+            }
+        }
+    }
+
+    #[test]
+    fn test_channel_full() {
+        let rollup_config = RollupConfig::default();
+        let config = BatcherConfig { batch_type: BatchType::Single, ..Default::default() };
+        let compressor = BatcherCompressor::new(CompressionAlgo::Zlib.into(), 1);
+        let mut channel = Channel::new([0x01; 16], &rollup_config, &config, compressor, 0);
+
+        let first = test_block(1, Default::default(), 0);
+        let second = test_block(2, first.header.hash_slow(), 1);
+        assert!(add_block(&mut channel, first));
+        assert!(!add_block(&mut channel, second));
+        assert_eq!(channel.blocks().len(), 1);
+        assert!(channel.should_close(&rollup_config, 0));
+    }
+
+    #[test]
+    fn test_span_channel_full() {
+        let rollup_config = RollupConfig { block_time: 1, ..Default::default() };
+        let config = BatcherConfig { batch_type: BatchType::Span, ..Default::default() };
+        let compressor = BatcherCompressor::new(CompressionAlgo::Zlib.into(), 1);
+        let mut channel = Channel::new([0x01; 16], &rollup_config, &config, compressor, 0);
+
+        let first = test_block(1, Default::default(), 0);
+        let second = test_block(2, first.header.hash_slow(), 1);
+        assert!(add_block(&mut channel, first));
+        assert!(!add_block(&mut channel, second));
+        assert_eq!(channel.blocks().len(), 1);
+        assert!(channel.should_close(&rollup_config, 0));
+
+        // The rejected block is not part of the span batch.
+        channel.close().unwrap();
+        let mut data = Vec::new();
+        while let Some(tx) = channel.next_tx() {
+            data.extend(tx.frames.iter().flat_map(|frame| frame.data.iter().copied()));
+        }
+        let mut reader = BatchReader::new(data, usize::MAX);
+        match reader.next_batch(&rollup_config).unwrap() {
+            Batch::Span(batch) => assert_eq!(batch.batches.len(), 1),
+            batch => panic!("Unexpected batch: {batch:?}"),
+        }
+    }
+
+    #[test]
+    fn test_channel_should_close() {
+        let rollup_config = RollupConfig { seq_window_size: 100, ..Default::default() };
+        let config =
+            BatcherConfig { max_channel_duration: 10, sub_safety_margin: 5, ..Default::default() };
+        let channel = test_channel(&rollup_config, &config);
+        assert!(!channel.should_close(&rollup_config, 9));
+        assert!(channel.should_close(&rollup_config, 10));
+
+        let config = BatcherConfig { sub_safety_margin: 5, ..Default::default() };
+        let mut channel = test_channel(&rollup_config, &config);
+        assert!(add_block(&mut channel, test_block(1, Default::default(), 0)));
+        assert!(!channel.should_close(&rollup_config, 94));
+        assert!(channel.should_close(&rollup_config, 95));
+    }
+
+    #[test]
+    fn test_channel_tx_failed_requeues_frames() {
+        let rollup_config = RollupConfig::default();
+        let config = BatcherConfig { target_tx_size: 48, ..Default::default() };
+        let mut channel = test_channel(&rollup_config, &config);
+        assert!(add_block(&mut channel, test_block(1, Default::default(), 0)));
+        channel.close().unwrap();
+
+        let tx = channel.next_tx().unwrap();
+        channel.tx_failed(&tx.id().unwrap());
+        assert_eq!(channel.next_tx().unwrap(), tx);
+    }
+
+    #[test]
+    fn test_channel_timed_out() {
+        let rollup_config = RollupConfig { channel_timeout: 10, ..Default::default() };
+        let config = BatcherConfig { target_tx_size: 48, ..Default::default() };
+
+        // A channel whose transactions are included exactly the channel timeout apart is still
+        // read by the derivation pipeline.
+        for (last_inclusion, timed_out) in [(11, false), (12, true)] {
+            let mut channel = test_channel(&rollup_config, &config);
+            assert!(add_block(&mut channel, test_block(1, Default::default(), 0)));
+            channel.close().unwrap();
+
+            let first = channel.next_tx().unwrap();
+            let second = channel.next_tx().unwrap();
+            let first_inclusion = BlockInfo { number: 1, ..Default::default() };
+            channel.tx_confirmed(&first.id().unwrap(), first_inclusion);
+            assert!(!channel.is_timed_out(&rollup_config));
+            let second_inclusion = BlockInfo { number: last_inclusion, ..Default::default() };
+            channel.tx_confirmed(&second.id().unwrap(), second_inclusion);
+            assert_eq!(channel.is_timed_out(&rollup_config), timed_out);
+            assert!(channel.included_since(last_inclusion));
+            assert!(!channel.included_since(last_inclusion + 1));
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Contains the [BatcherCompressor].
+
+use kona_comp::{
+    ChannelCompressor, CompressorError, CompressorResult, CompressorWriter, VariantCompressor,
+};
+
+/// The margin added to the compressed size estimate of the [BatcherCompressor], which accounts
+/// for the headers, checksums and block overhead of the compression formats. See the
+/// `ShadowCompressor` of `kona-comp`.
+const SAFE_COMPRESSION_OVERHEAD: usize = 51;
+
+/// The [ChannelCompressor] used by the batch submitter.
+///
+/// Wraps a [VariantCompressor], and rejects writes with [CompressorError::Full] rather than
+/// growing the compressed data past the target output size, unless the compressor is empty. A
+/// rejected write leaves the compressor untouched, so the batch can be added to the next channel.
+///
+/// Written data is buffered, and only compressed into the output on [flush] or [close]. To
+/// decide whether a write fits, the compressor keeps an upper bound of the compressed size,
+/// which grows by the size of every write. The buffered data is only compressed to measure its
+/// size once the bound exceeds the target output size.
+///
+/// The compressed data is prefixed with the [channel version](VariantCompressor::channel_version)
+/// of the wrapped compressor, if it has one.
+///
+/// [flush]: CompressorWriter::flush
+/// [close]: CompressorWriter::close
+#[derive(Debug, Clone)]
+pub struct BatcherCompressor {
+    /// The wrapped compressor, which holds the compressed output.
+    inner: VariantCompressor,
+    /// The compressor used to measure the compressed size of the input.
+    probe: VariantCompressor,
+    /// The target size of the compressed data.
+    target_output_size: usize,
+    /// The channel version that prefixes the compressed data, if any.
+    version: Option<u8>,
+    /// Whether the channel version prefix has been read.
+    prefix_read: bool,
+    /// The data written to the compressor.
+    input: Vec<u8>,
+    /// The number of input bytes that have been flushed into the wrapped compressor.
+    flushed: usize,
+    /// An upper bound of the compressed size of the input.
+    bound: usize,
+}
+
+impl BatcherCompressor {
+    /// Creates a new [BatcherCompressor].
+    pub fn new(inner: VariantCompressor, target_output_size: usize) -> Self {
+        let version = inner.channel_version();
+        Self {
+            probe: inner.clone(),
+            inner,
+            target_output_size,
+            version,
+            prefix_read: false,
+            input: Vec::new(),
+            flushed: 0,
+            bound: SAFE_COMPRESSION_OVERHEAD,
+        }
+    }
+
+    /// Returns `true` if the compressed size of the input still fits the target output size
+    /// after growing by `added` bytes, going by the size estimate alone. If so, the estimate is
+    /// updated.
+    ///
+    /// A `false` result is not conclusive, and must be followed up by [Self::fits].
+    pub const fn fits_estimate(&mut self, added: usize) -> bool {
+        let bound = self.bound + added;
+        if bound + self.prefix_len() > self.target_output_size {
+            return false;
+        }
+        self.bound = bound;
+        true
+    }
+
+    /// Compresses the given input to check whether it fits the target output size. If so, the
+    /// size estimate is reset to the measured size.
+    pub fn fits(&mut self, input: &[u8]) -> CompressorResult<bool> {
+        self.probe.reset();
+        self.probe.write(input)?;
+        let len = self.probe.len();
+        self.probe.reset();
+
+        if len + self.prefix_len() > self.target_output_size {
+            return Ok(false);
+        }
+        self.bound = len + SAFE_COMPRESSION_OVERHEAD;
+        Ok(true)
+    }
+
+    /// Returns the length of the channel version prefix.
+    const fn prefix_len(&self) -> usize {
+        self.version.is_some() as usize
+    }
+
+    /// Returns the number of prefix bytes that have not been read yet.
+    const fn pending_prefix_len(&self) -> usize {
+        (self.version.is_some() && !self.prefix_read) as usize
+    }
+}
+
+impl CompressorWriter for BatcherCompressor {
+    fn write(&mut self, data: &[u8]) -> CompressorResult<usize> {
+        if self.input.is_empty() {
+            self.bound += data.len();
+        } else if !self.fits_estimate(data.len()) {
+            let input = [self.input.as_slice(), data].concat();
+            if !self.fits(&input)? {
+                return Err(CompressorError::Full);
+            }
+        }
+        self.input.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> CompressorResult<()> {
+        if self.flushed < self.input.len() {
+            self.inner.write(&self.input[self.flushed..])?;
+            self.flushed = self.input.len();
+        }
+        self.inner.flush()
+    }
+
+    fn close(&mut self) -> CompressorResult<()> {
+        self.flush()?;
+        self.inner.close()
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.prefix_read = false;
+        self.input.clear();
+        self.flushed = 0;
+        self.bound = SAFE_COMPRESSION_OVERHEAD;
+    }
+
+    fn len(&self) -> usize {
+        match self.inner.len() {
+            0 => 0,
+            len => len + self.pending_prefix_len(),
+        }
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> CompressorResult<usize> {
+        if buf.is_empty() || self.len() == 0 {
+            return Ok(0);
+        }
+
+        let mut read = 0;
+        if let Some(version) = self.version.filter(|_| !self.prefix_read) {
+            buf[0] = version;
+            self.prefix_read = true;
+            read += 1;
+        }
+        Ok(read + self.inner.read(&mut buf[read..])?)
+    }
+}
+
+impl ChannelCompressor for BatcherCompressor {
+    fn get_compressed(&self) -> Vec<u8> {
+        let mut compressed = Vec::with_capacity(self.version.is_some() as usize + self.inner.len());
+        compressed.extend(self.version);
+        compressed.extend(self.inner.get_compressed());
+        compressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_comp::CompressionAlgo;
+    use kona_protocol::BatchReader;
+
+    #[test]
+    fn test_compressor_full() {
+        let mut compressor = BatcherCompressor::new(CompressionAlgo::Zlib.into(), 32);
+
+        // The first write is always accepted.
+        compressor.write(&[0xab; 16]).unwrap();
+        let len = compressor.len();
+
+        let incompressible = (0..64u8).map(|i| i.wrapping_mul(97)).collect::<Vec<_>>();
+        assert_eq!(compressor.write(&incompressible), Err(CompressorError::Full));
+        assert_eq!(compressor.len(), len);
+
+        // Only the accepted write is compressed into the output.
+        compressor.close().unwrap();
+        let compressed = compressor.get_compressed();
+        assert_eq!(kona_comp::decompress_zlib(&compressed).unwrap(), [0xab; 16]);
+    }
+
+    #[test]
+    fn test_compressor_compresses_on_flush() {
+        let mut compressor = BatcherCompressor::new(CompressionAlgo::Zlib.into(), 1024);
+
+        // Writes within the size estimate are buffered without being compressed.
+        for _ in 0..8 {
+            compressor.write(&[0xab; 64]).unwrap();
+        }
+        assert_eq!(compressor.len(), 0);
+        assert_eq!(compressor.bound, SAFE_COMPRESSION_OVERHEAD + 512);
+
+        // Once the estimate exceeds the target, the input is compressed to measure its size.
+        compressor.write(&[0xab; 1024]).unwrap();
+        assert!(compressor.bound < 1024);
+
+        compressor.flush().unwrap();
+        let compressed = compressor.get_compressed();
+        assert_eq!(compressor.len(), compressed.len());
+        assert_eq!(kona_comp::decompress_zlib(&compressed).unwrap(), [0xab; 1536]);
+    }
+
+    #[test]
+    fn test_compressor_brotli_prefix() {
+        let mut compressor = BatcherCompressor::new(CompressionAlgo::Brotli10.into(), usize::MAX);
+        compressor.write(&[0xab; 64]).unwrap();
+        compressor.flush().unwrap();
+        let compressed = compressor.get_compressed();
+        assert_eq!(compressed[0], BatchReader::CHANNEL_VERSION_BROTLI);
+        assert_eq!(compressor.len(), compressed.len());
+
+        let mut first = [0u8; 2];
+        assert_eq!(compressor.read(&mut first).unwrap(), 2);
+        let mut rest = vec![0u8; compressed.len()];
+        let read = compressor.read(&mut rest).unwrap();
+        assert_eq!([&first[..], &rest[..read]].concat(), compressed);
+        assert_eq!(compressor.len(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_compressor_zstd_prefix() {
+        let mut compressor = BatcherCompressor::new(
+            CompressionAlgo::Zstd(kona_comp::DEFAULT_ZSTD_LEVEL).into(),
+            usize::MAX,
+        );
+        compressor.write(&[0xab; 64]).unwrap();
+        compressor.flush().unwrap();
+        let compressed = compressor.get_compressed();
+        assert_eq!(compressed[0], BatchReader::CHANNEL_VERSION_ZSTD);
+        assert_eq!(compressor.len(), compressed.len());
+    }
+}
@@ -0,0 +1,82 @@
+//! Contains the [BatcherConfig].
+
+use crate::BLOB_MAX_DATA_SIZE;
+use kona_comp::CompressionAlgo;
+use kona_protocol::BatchType;
+
+/// The default target size of a calldata batcher transaction, in bytes.
+pub const DEFAULT_TARGET_TX_SIZE: usize = 120_000;
+
+/// The way batcher transactions post their frames to L1.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DataAvailabilityType {
+    /// Frames are posted as the calldata of the transaction.
+    #[default]
+    Calldata,
+    /// Frames are posted in the EIP-4844 blobs of the transaction, one frame per blob.
+    Blobs,
+}
+
+/// The configuration of the batch submitter.
+#[derive(Debug, Clone)]
+pub struct BatcherConfig {
+    /// The type of batches to build.
+    pub batch_type: BatchType,
+    /// How frames are posted to L1.
+    pub da_type: DataAvailabilityType,
+    /// The compression algorithm of the channels. If unset, channels are compressed with brotli
+    /// after Fjord, and with zlib before.
+    pub compression_algo: Option<CompressionAlgo>,
+    /// The maximum number of L1 blocks that a channel may stay open for. Zero disables the limit.
+    pub max_channel_duration: u64,
+    /// The maximum number of frames per channel. Channels are closed once their compressed data
+    /// fills this many frames.
+    pub max_frames_per_channel: usize,
+    /// The target size of the data of a calldata batcher transaction, in bytes.
+    pub target_tx_size: usize,
+    /// The number of blobs per blob batcher transaction.
+    pub blobs_per_tx: usize,
+    /// The number of L1 blocks of margin that channels keep to the end of the sequencing window
+    /// of their first block.
+    pub sub_safety_margin: u64,
+}
+
+impl Default for BatcherConfig {
+    fn default() -> Self {
+        Self {
+            batch_type: BatchType::Span,
+            da_type: DataAvailabilityType::Calldata,
+            compression_algo: None,
+            max_channel_duration: 0,
+            max_frames_per_channel: 1,
+            target_tx_size: DEFAULT_TARGET_TX_SIZE,
+            blobs_per_tx: 1,
+            sub_safety_margin: 10,
+        }
+    }
+}
+
+impl BatcherConfig {
+    /// Returns the maximum size of an encoded frame, leaving room for the derivation version byte
+    /// that prefixes the frames of a transaction.
+    pub const fn max_frame_size(&self) -> usize {
+        match self.da_type {
+            DataAvailabilityType::Calldata => self.target_tx_size.saturating_sub(1),
+            DataAvailabilityType::Blobs => BLOB_MAX_DATA_SIZE - 1,
+        }
+    }
+
+    /// Returns the number of frames carried by a single batcher transaction.
+    pub const fn frames_per_tx(&self) -> usize {
+        match self.da_type {
+            DataAvailabilityType::Calldata => 1,
+            DataAvailabilityType::Blobs => {
+                if self.blobs_per_tx == 0 {
+                    1
+                } else {
+                    self.blobs_per_tx
+                }
+            }
+        }
+    }
+}
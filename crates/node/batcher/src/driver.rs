@@ -0,0 +1,218 @@
+//! Contains the [BatchSubmitter], which drives the [ChannelManager].
+
+use crate::{BatcherError, BatcherL2Provider, ChannelManager, TxSender};
+use kona_derive::ChainProvider;
+use kona_genesis::RollupConfig;
+use std::time::Duration;
+
+/// The [BatchSubmitter] loads unsafe L2 blocks from the rollup node, and submits them to L1 as
+/// batcher transactions.
+///
+/// Each [step](Self::step) loads the new unsafe blocks, requeues the channels whose transactions
+/// were reorged out of L1, drops the channels whose blocks are safe, and sends the pending
+/// batcher transactions.
+#[derive(Debug)]
+pub struct BatchSubmitter<'a, L1, L2, S>
+where
+    L1: ChainProvider,
+    L2: BatcherL2Provider,
+    S: TxSender,
+{
+    /// The rollup config.
+    pub rollup_config: &'a RollupConfig,
+    /// The channel manager.
+    pub manager: ChannelManager<'a>,
+    /// The L1 chain provider.
+    pub l1_provider: L1,
+    /// The L2 provider.
+    pub l2_provider: L2,
+    /// The transaction sender.
+    pub sender: S,
+    /// The number of the last L2 block loaded into the channel manager.
+    last_loaded: Option<u64>,
+}
+
+impl<'a, L1, L2, S> BatchSubmitter<'a, L1, L2, S>
+where
+    L1: ChainProvider + Send,
+    L2: BatcherL2Provider + Send,
+    S: TxSender + Send,
+{
+    /// Creates a new [BatchSubmitter].
+    pub const fn new(
+        rollup_config: &'a RollupConfig,
+        manager: ChannelManager<'a>,
+        l1_provider: L1,
+        l2_provider: L2,
+        sender: S,
+    ) -> Self {
+        Self { rollup_config, manager, l1_provider, l2_provider, sender, last_loaded: None }
+    }
+
+    /// Runs the batch submitter, stepping it at the given interval.
+    pub async fn run(mut self, poll_interval: Duration) {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.step().await {
+                error!(target: "batcher", "Batch submitter step failed: {e}");
+            }
+        }
+    }
+
+    /// Performs a single step of the batch submitter.
+    pub async fn step(&mut self) -> Result<(), BatcherError> {
+        let status = self
+            .l2_provider
+            .sync_status()
+            .await
+            .map_err(|e| BatcherError::L2Provider(e.to_string()))?;
+        let safe_l2 = status.safe_l2.block_info.number;
+        let unsafe_l2 = status.unsafe_l2.block_info.number;
+
+        self.load_blocks(safe_l2, unsafe_l2).await?;
+        self.check_l1_reorgs().await?;
+        self.manager.prune(safe_l2);
+        self.submit(status.head_l1.number).await
+    }
+
+    /// Closes the open channel and submits all pending batcher transactions. Used when shutting
+    /// down.
+    pub async fn flush(&mut self, l1_head: u64) -> Result<(), BatcherError> {
+        self.manager.close()?;
+        self.submit(l1_head).await
+    }
+
+    /// Loads the unsafe L2 blocks that are not in the channel manager yet.
+    async fn load_blocks(&mut self, safe_l2: u64, unsafe_l2: u64) -> Result<(), BatcherError> {
+        // Blocks at or below the safe head have been submitted already.
+        let start = self.last_loaded.map_or(safe_l2, |last| last.max(safe_l2)) + 1;
+        for number in start..=unsafe_l2 {
+            let block = self
+                .l2_provider
+                .block_by_number(number)
+                .await
+                .map_err(|e| BatcherError::L2Provider(e.to_string()))?;
+
+            match self.manager.add_l2_block(block) {
+                Ok(()) => self.last_loaded = Some(number),
+                Err(e @ BatcherError::L2Reorg { .. }) => {
+                    warn!(target: "batcher", "{e}, clearing the channel manager");
+                    self.manager.clear();
+                    self.last_loaded = None;
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Requeues the channels whose transactions were included in L1 blocks that have since been
+    /// reorged out.
+    async fn check_l1_reorgs(&mut self) -> Result<(), BatcherError> {
+        let mut inclusions = self.manager.inclusion_blocks().copied().collect::<Vec<_>>();
+        inclusions.sort_by_key(|block| block.number);
+        inclusions.dedup();
+
+        for inclusion in inclusions {
+            let canonical = self
+                .l1_provider
+                .block_info_by_number(inclusion.number)
+                .await
+                .map_err(|e| BatcherError::L1Provider(e.to_string()))?;
+            if canonical.hash != inclusion.hash {
+                self.manager.handle_l1_reorg(inclusion.number);
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the pending batcher transactions, stopping at the first failure.
+    async fn submit(&mut self, l1_head: u64) -> Result<(), BatcherError> {
+        while let Some(tx) = self.manager.tx_data(l1_head)? {
+            let id = tx.id()?;
+            let candidate = tx.to_candidate(self.rollup_config.batch_inbox_address)?;
+            match self.sender.send(candidate).await {
+                Ok(inclusion) => {
+                    debug!(
+                        target: "batcher",
+                        "Batcher transaction {id:?} included in L1 block {}",
+                        inclusion.number
+                    );
+                    self.manager.tx_confirmed(&id, inclusion);
+                }
+                Err(e) => {
+                    warn!(target: "batcher", "Failed to send batcher transaction {id:?}: {e}");
+                    self.manager.tx_failed(&id);
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BatcherConfig,
+        test_utils::{TestL2Provider, TestTxSender, test_block},
+    };
+    use alloy_primitives::B256;
+    use kona_derive::test_utils::TestChainProvider;
+    use kona_protocol::BlockInfo;
+
+    fn test_submitter(
+        rollup_config: &RollupConfig,
+    ) -> BatchSubmitter<'_, TestChainProvider, TestL2Provider, TestTxSender> {
+        let manager = ChannelManager::new(rollup_config, BatcherConfig::default());
+        let first = test_block(1, B256::ZERO, 0);
+        let second = test_block(2, first.header.hash_slow(), 1);
+        let l2_provider = TestL2Provider { head_l1: 90, safe_l2: 0, blocks: vec![first, second] };
+        let inclusion =
+            BlockInfo { number: 90, hash: B256::with_last_byte(1), ..Default::default() };
+        let sender = TestTxSender { inclusion, ..Default::default() };
+        BatchSubmitter::new(rollup_config, manager, Default::default(), l2_provider, sender)
+    }
+
+    #[tokio::test]
+    async fn test_step_submits_blocks() {
+        let rollup_config = RollupConfig {
+            seq_window_size: 100,
+            channel_timeout: 100,
+            batch_inbox_address: alloy_primitives::Address::with_last_byte(0xff),
+            ..Default::default()
+        };
+        let mut submitter = test_submitter(&rollup_config);
+        submitter.l1_provider.insert_block(90, submitter.sender.inclusion);
+
+        submitter.step().await.unwrap();
+        assert_eq!(submitter.sender.sent.len(), 1);
+        assert_eq!(submitter.sender.sent[0].to, rollup_config.batch_inbox_address);
+        assert_eq!(submitter.manager.pending_blocks(), 0);
+
+        // Once the blocks are safe, the channel is pruned.
+        submitter.l2_provider.safe_l2 = 2;
+        submitter.step().await.unwrap();
+        assert_eq!(submitter.manager.channels(), 0);
+        assert_eq!(submitter.sender.sent.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_step_resubmits_after_l1_reorg() {
+        let rollup_config =
+            RollupConfig { seq_window_size: 100, channel_timeout: 100, ..Default::default() };
+        let mut submitter = test_submitter(&rollup_config);
+        submitter.step().await.unwrap();
+        assert_eq!(submitter.sender.sent.len(), 1);
+
+        // The inclusion block is replaced by a block with a different hash.
+        submitter.l1_provider.insert_block(90, BlockInfo { number: 90, ..Default::default() });
+        submitter.step().await.unwrap();
+        assert_eq!(submitter.sender.sent.len(), 2);
+        assert_eq!(submitter.manager.channels(), 1);
+    }
+}
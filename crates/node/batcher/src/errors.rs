@@ -0,0 +1,46 @@
+//! Error types for the batch submitter.
+
+use alloy_primitives::B256;
+use kona_comp::ChannelOutError;
+use kona_protocol::{DecodeError, SpanBatchError};
+use thiserror::Error;
+
+/// An error returned by the batch submitter.
+#[derive(Error, Debug)]
+pub enum BatcherError {
+    /// The L2 block has no transactions, and thus no L1 info deposit.
+    #[error("L2 block {0} has no transactions")]
+    EmptyBlock(B256),
+    /// The first transaction of the L2 block is not the L1 info deposit.
+    #[error("The first transaction of L2 block {0} is not a deposit")]
+    FirstTxNotDeposit(B256),
+    /// The L1 info deposit of the L2 block could not be decoded.
+    #[error("Failed to decode the L1 info deposit: {0}")]
+    L1Info(#[from] DecodeError),
+    /// The L2 block does not build on the last block added to the batcher.
+    #[error("L2 reorg detected: block with parent {parent} does not build on {tip}")]
+    L2Reorg {
+        /// The hash of the last block added to the batcher.
+        tip: B256,
+        /// The parent hash of the new block.
+        parent: B256,
+    },
+    /// The block could not be added to the span batch.
+    #[error("Span batch error: {0}")]
+    SpanBatch(#[from] SpanBatchError),
+    /// An error from the channel.
+    #[error("Channel error: {0}")]
+    ChannelOut(#[from] ChannelOutError),
+    /// The batcher transaction carries no frames.
+    #[error("Batcher transaction carries no frames")]
+    EmptyTx,
+    /// The data does not fit into a blob.
+    #[error("{0} bytes of data do not fit into a blob")]
+    BlobDataTooLarge(usize),
+    /// An error from the L1 provider.
+    #[error("L1 provider error: {0}")]
+    L1Provider(String),
+    /// An error from the L2 provider.
+    #[error("L2 provider error: {0}")]
+    L2Provider(String),
+}
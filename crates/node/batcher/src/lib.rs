@@ -0,0 +1,44 @@
+#![doc = include_str!("../README.md")]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/op-rs/kona/main/assets/square.png",
+    html_favicon_url = "https://raw.githubusercontent.com/op-rs/kona/main/assets/favicon.ico",
+    issue_tracker_base_url = "https://github.com/op-rs/kona/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+#[macro_use]
+extern crate tracing;
+
+mod config;
+pub use config::{BatcherConfig, DEFAULT_TARGET_TX_SIZE, DataAvailabilityType};
+
+mod blob;
+pub use blob::{BLOB_ENCODING_VERSION, BLOB_MAX_DATA_SIZE, encode_blob};
+
+mod errors;
+pub use errors::BatcherError;
+
+mod compressor;
+pub use compressor::BatcherCompressor;
+
+mod block;
+pub use block::block_to_batch;
+
+mod tx;
+pub use tx::{TxCandidate, TxData, TxId};
+
+mod traits;
+pub use traits::{BatcherL2Provider, TxSender};
+
+mod channel;
+pub use channel::{Channel, FRAME_V0_OVERHEAD_SIZE};
+
+mod manager;
+pub use manager::ChannelManager;
+
+mod driver;
+pub use driver::BatchSubmitter;
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,394 @@
+//! Contains the [ChannelManager], which turns L2 blocks into batcher transactions.
+
+use crate::{
+    BatcherCompressor, BatcherConfig, BatcherError, Channel, FRAME_V0_OVERHEAD_SIZE, TxData, TxId,
+    block_to_batch,
+};
+use alloy_primitives::{B256, keccak256};
+use kona_comp::VariantCompressor;
+use kona_genesis::RollupConfig;
+use kona_protocol::{BlockInfo, ChannelId};
+use op_alloy_consensus::OpBlock;
+use std::collections::VecDeque;
+
+/// The [ChannelManager] queues L2 blocks, packs them into channels, and hands out the frames of
+/// the channels as batcher transactions.
+///
+/// Channels are kept until all of their blocks are safe, so that they can be rebuilt if one of
+/// their transactions fails to be included in time, or is reorged out of L1.
+#[derive(Debug)]
+pub struct ChannelManager<'a> {
+    /// The rollup config.
+    rollup_config: &'a RollupConfig,
+    /// The batcher config.
+    config: BatcherConfig,
+    /// The blocks that have not been added to a channel yet.
+    blocks: VecDeque<OpBlock>,
+    /// The channels, from oldest to newest. Only the last channel may be open.
+    channels: VecDeque<Channel<'a>>,
+    /// The hash of the last block added to the manager.
+    tip: Option<B256>,
+    /// The number of channels opened so far, used to derive unique channel ids.
+    channel_nonce: u64,
+}
+
+impl<'a> ChannelManager<'a> {
+    /// Creates a new, empty [ChannelManager].
+    pub const fn new(rollup_config: &'a RollupConfig, config: BatcherConfig) -> Self {
+        Self {
+            rollup_config,
+            config,
+            blocks: VecDeque::new(),
+            channels: VecDeque::new(),
+            tip: None,
+            channel_nonce: 0,
+        }
+    }
+
+    /// Returns the number of blocks that have not been added to a channel yet.
+    pub fn pending_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the number of channels held by the manager.
+    pub fn channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Queues the given L2 block.
+    ///
+    /// Returns [BatcherError::L2Reorg] if the block does not build on the last queued block, in
+    /// which case the manager should be [cleared](Self::clear).
+    pub fn add_l2_block(&mut self, block: OpBlock) -> Result<(), BatcherError> {
+        if let Some(tip) = self.tip {
+            if block.header.parent_hash != tip {
+                return Err(BatcherError::L2Reorg { tip, parent: block.header.parent_hash });
+            }
+        }
+
+        self.tip = Some(block.header.hash_slow());
+        self.blocks.push_back(block);
+        Ok(())
+    }
+
+    /// Returns the next batcher transaction to send at the given L1 head, if any.
+    ///
+    /// Frames of older channels are handed out first. Otherwise, queued blocks are added to the
+    /// open channel, which is closed once it is full or should otherwise be submitted.
+    pub fn tx_data(&mut self, l1_head: u64) -> Result<Option<TxData>, BatcherError> {
+        if let Some(tx) = self.channels.iter_mut().find_map(Channel::next_tx) {
+            return Ok(Some(tx));
+        }
+
+        self.process_blocks(l1_head)?;
+        if let Some(channel) = self.channels.back_mut() {
+            if !channel.is_closed() && channel.should_close(self.rollup_config, l1_head) {
+                channel.close()?;
+            }
+        }
+        Ok(self.channels.iter_mut().find_map(Channel::next_tx))
+    }
+
+    /// Adds the queued blocks to the open channel, opening new channels as needed.
+    fn process_blocks(&mut self, l1_head: u64) -> Result<(), BatcherError> {
+        while let Some(block) = self.blocks.front().cloned() {
+            if self.channels.back().is_none_or(Channel::is_closed) {
+                let channel = self.open_channel(&block, l1_head);
+                self.channels.push_back(channel);
+            }
+            let channel = self.channels.back_mut().expect("A channel was just opened");
+
+            let (batch, l1_info) = block_to_batch(&block)?;
+            if !channel.add_block(block, batch, l1_info.sequence_number())? {
+                // The channel is full. Close it, and retry the block in a new channel.
+                channel.close()?;
+                continue;
+            }
+            self.blocks.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Opens a new channel, whose first block is the given block.
+    fn open_channel(&mut self, first: &OpBlock, l1_head: u64) -> Channel<'a> {
+        let mut preimage = first.header.hash_slow().to_vec();
+        preimage.extend_from_slice(&self.channel_nonce.to_be_bytes());
+        self.channel_nonce += 1;
+        let mut id = ChannelId::default();
+        id.copy_from_slice(&keccak256(preimage)[..16]);
+
+        let inner = self.config.compression_algo.map_or_else(
+            || VariantCompressor::from_timestamp(self.rollup_config, first.header.timestamp),
+            VariantCompressor::from,
+        );
+        let target_output_size = self.config.max_frames_per_channel *
+            self.config.max_frame_size().saturating_sub(FRAME_V0_OVERHEAD_SIZE);
+        let compressor = BatcherCompressor::new(inner, target_output_size);
+
+        debug!(
+            target: "batcher",
+            "Opening channel {} at L1 block {l1_head}",
+            alloy_primitives::hex::encode(id)
+        );
+        Channel::new(id, self.rollup_config, &self.config, compressor, l1_head)
+    }
+
+    /// Marks the given transaction as included in the given L1 block.
+    ///
+    /// If the transactions of the channel were included too far apart for the channel to be read,
+    /// the channel and all newer channels are rebuilt from their blocks.
+    pub fn tx_confirmed(&mut self, id: &TxId, inclusion: BlockInfo) {
+        let Some(index) = self.channel_index(id) else {
+            warn!(target: "batcher", "Confirmed transaction of unknown channel {:?}", id);
+            return;
+        };
+
+        let channel = &mut self.channels[index];
+        channel.tx_confirmed(id, inclusion);
+        if channel.is_timed_out(self.rollup_config) {
+            warn!(
+                target: "batcher",
+                "Channel {} timed out, requeueing its blocks",
+                alloy_primitives::hex::encode(id.channel_id)
+            );
+            self.requeue_from(index);
+        }
+    }
+
+    /// Marks the given transaction as failed, queueing its frames for re-submission.
+    pub fn tx_failed(&mut self, id: &TxId) {
+        if let Some(index) = self.channel_index(id) {
+            self.channels[index].tx_failed(id);
+        }
+    }
+
+    /// Handles an L1 reorg of the given block number, rebuilding the channels that had
+    /// transactions included at or after it.
+    pub fn handle_l1_reorg(&mut self, number: u64) {
+        if let Some(index) = self.channels.iter().position(|c| c.included_since(number)) {
+            warn!(
+                target: "batcher",
+                "L1 reorg of block {number}, requeueing {} channels",
+                self.channels.len() - index
+            );
+            self.requeue_from(index);
+        }
+    }
+
+    /// Returns the L1 blocks that include the confirmed batcher transactions.
+    pub fn inclusion_blocks(&self) -> impl Iterator<Item = &BlockInfo> {
+        self.channels.iter().flat_map(Channel::inclusion_blocks)
+    }
+
+    /// Drops the fully confirmed channels whose blocks are all at or below the given safe L2
+    /// block number.
+    pub fn prune(&mut self, safe_l2_number: u64) {
+        while self.channels.front().is_some_and(|channel| {
+            channel.is_fully_confirmed() &&
+                channel.blocks().last().is_some_and(|b| b.header.number <= safe_l2_number)
+        }) {
+            self.channels.pop_front();
+        }
+    }
+
+    /// Closes the open channel, so that its frames can be submitted. Used when shutting down.
+    pub fn close(&mut self) -> Result<(), BatcherError> {
+        if let Some(channel) = self.channels.back_mut() {
+            channel.close()?;
+        }
+        Ok(())
+    }
+
+    /// Drops all blocks and channels.
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+        self.channels.clear();
+        self.tip = None;
+    }
+
+    /// Returns the index of the channel of the given transaction.
+    fn channel_index(&self, id: &TxId) -> Option<usize> {
+        self.channels.iter().position(|channel| channel.id() == id.channel_id)
+    }
+
+    /// Drops the channel at the given index and all newer channels, putting their blocks back at
+    /// the front of the queue.
+    fn requeue_from(&mut self, index: usize) {
+        let blocks =
+            self.channels.drain(index..).flat_map(Channel::into_blocks).collect::<Vec<_>>();
+        for block in blocks.into_iter().rev() {
+            self.blocks.push_front(block);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_block;
+    use kona_comp::CompressionAlgo;
+    use kona_protocol::BatchType;
+
+    fn rollup_config() -> RollupConfig {
+        RollupConfig { seq_window_size: 100, channel_timeout: 100, ..Default::default() }
+    }
+
+    fn chain(len: u64) -> Vec<OpBlock> {
+        let mut parent = B256::ZERO;
+        (1..=len)
+            .map(|number| {
+                let block = test_block(number, parent, number - 1);
+                parent = block.header.hash_slow();
+                block
+            })
+            .collect()
+    }
+
+    /// Returns a manager holding a single block, whose channel is closed at L1 block 1.
+    fn manager_with_block(rollup_config: &RollupConfig) -> ChannelManager<'_> {
+        let config = BatcherConfig { max_channel_duration: 1, ..Default::default() };
+        let mut manager = ChannelManager::new(rollup_config, config);
+        manager.add_l2_block(chain(1).remove(0)).unwrap();
+        assert_eq!(manager.tx_data(0).unwrap(), None);
+        manager
+    }
+
+    #[test]
+    fn test_add_l2_block_reorg() {
+        let rollup_config = rollup_config();
+        let mut manager = ChannelManager::new(&rollup_config, BatcherConfig::default());
+        let blocks = chain(2);
+        manager.add_l2_block(blocks[0].clone()).unwrap();
+
+        let err = manager.add_l2_block(test_block(2, B256::ZERO, 1)).unwrap_err();
+        assert!(matches!(err, BatcherError::L2Reorg { .. }));
+        manager.add_l2_block(blocks[1].clone()).unwrap();
+        assert_eq!(manager.pending_blocks(), 2);
+    }
+
+    #[test]
+    fn test_tx_data_waits_for_channel_to_close() {
+        let rollup_config = rollup_config();
+        let config = BatcherConfig { max_channel_duration: 5, ..Default::default() };
+        let mut manager = ChannelManager::new(&rollup_config, config);
+        for block in chain(3) {
+            manager.add_l2_block(block).unwrap();
+        }
+
+        assert_eq!(manager.tx_data(0).unwrap(), None);
+        assert_eq!(manager.pending_blocks(), 0);
+        assert_eq!(manager.channels(), 1);
+
+        let tx = manager.tx_data(5).unwrap().unwrap();
+        assert!(tx.frames.last().unwrap().is_last);
+        assert_eq!(manager.tx_data(5).unwrap(), None);
+    }
+
+    #[test]
+    fn test_tx_data_closes_channel_at_end_of_sequencing_window() {
+        let rollup_config = rollup_config();
+        let mut manager = ChannelManager::new(&rollup_config, BatcherConfig::default());
+        manager.add_l2_block(chain(1).remove(0)).unwrap();
+
+        assert_eq!(manager.tx_data(89).unwrap(), None);
+        assert!(manager.tx_data(90).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_full_channel_is_closed() {
+        let rollup_config = rollup_config();
+        let config = BatcherConfig {
+            batch_type: BatchType::Single,
+            max_channel_duration: 1,
+            target_tx_size: FRAME_V0_OVERHEAD_SIZE + 2,
+            ..Default::default()
+        };
+        let mut manager = ChannelManager::new(&rollup_config, config);
+        for block in chain(2) {
+            manager.add_l2_block(block).unwrap();
+        }
+
+        // The first block fills its channel, which is closed when the second block is added.
+        let tx = manager.tx_data(0).unwrap().unwrap();
+        assert_eq!(tx.frames[0].number, 0);
+        assert_eq!(manager.channels(), 2);
+        assert_eq!(manager.pending_blocks(), 0);
+    }
+
+    #[test]
+    fn test_compression_algo_override() {
+        let rollup_config = rollup_config();
+        let config = BatcherConfig {
+            max_channel_duration: 1,
+            compression_algo: Some(CompressionAlgo::Raw),
+            ..Default::default()
+        };
+        let mut manager = ChannelManager::new(&rollup_config, config);
+        manager.add_l2_block(chain(1).remove(0)).unwrap();
+
+        let tx = manager.tx_data(1).unwrap().unwrap();
+        assert_eq!(tx.frames[0].data[0], kona_protocol::BatchReader::CHANNEL_VERSION_RAW);
+    }
+
+    #[test]
+    fn test_failed_tx_is_resent() {
+        let rollup_config = rollup_config();
+        let mut manager = manager_with_block(&rollup_config);
+
+        let tx = manager.tx_data(1).unwrap().unwrap();
+        manager.tx_failed(&tx.id().unwrap());
+        assert_eq!(manager.tx_data(1).unwrap(), Some(tx));
+    }
+
+    #[test]
+    fn test_timed_out_channel_is_requeued() {
+        let rollup_config = RollupConfig { channel_timeout: 10, ..rollup_config() };
+        let config = BatcherConfig {
+            max_channel_duration: 1,
+            target_tx_size: FRAME_V0_OVERHEAD_SIZE + 9,
+            ..Default::default()
+        };
+        let mut manager = ChannelManager::new(&rollup_config, config);
+        manager.add_l2_block(chain(1).remove(0)).unwrap();
+        assert_eq!(manager.tx_data(0).unwrap(), None);
+
+        let first = manager.tx_data(1).unwrap().unwrap();
+        let second = manager.tx_data(1).unwrap().unwrap();
+        manager.tx_confirmed(&first.id().unwrap(), BlockInfo { number: 1, ..Default::default() });
+        manager.tx_confirmed(&second.id().unwrap(), BlockInfo { number: 12, ..Default::default() });
+        assert_eq!(manager.channels(), 0);
+        assert_eq!(manager.pending_blocks(), 1);
+    }
+
+    #[test]
+    fn test_l1_reorg_requeues_blocks() {
+        let rollup_config = rollup_config();
+        let mut manager = manager_with_block(&rollup_config);
+
+        let tx = manager.tx_data(1).unwrap().unwrap();
+        manager.tx_confirmed(&tx.id().unwrap(), BlockInfo { number: 10, ..Default::default() });
+        assert_eq!(manager.inclusion_blocks().count(), 1);
+
+        manager.handle_l1_reorg(11);
+        assert_eq!(manager.channels(), 1);
+        manager.handle_l1_reorg(10);
+        assert_eq!(manager.channels(), 0);
+        assert_eq!(manager.pending_blocks(), 1);
+    }
+
+    #[test]
+    fn test_prune() {
+        let rollup_config = rollup_config();
+        let mut manager = manager_with_block(&rollup_config);
+
+        let tx = manager.tx_data(1).unwrap().unwrap();
+        manager.prune(1);
+        assert_eq!(manager.channels(), 1);
+
+        manager.tx_confirmed(&tx.id().unwrap(), BlockInfo::default());
+        manager.prune(0);
+        assert_eq!(manager.channels(), 1);
+        manager.prune(1);
+        assert_eq!(manager.channels(), 0);
+    }
+}
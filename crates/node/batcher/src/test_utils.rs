@@ -0,0 +1,84 @@
+//! Test utilities for the batch submitter.
+
+use crate::{BatcherL2Provider, TxCandidate, TxSender};
+use alloy_consensus::{BlockBody, Header};
+use alloy_primitives::{B256, Sealed};
+use async_trait::async_trait;
+use kona_protocol::{BlockInfo, L1BlockInfoEcotone, L1BlockInfoTx, L2BlockInfo, SyncStatus};
+use op_alloy_consensus::{OpBlock, OpTxEnvelope, TxDeposit};
+
+/// Returns an L2 block with the given number and parent hash, whose timestamp is its number and
+/// whose only transaction is the L1 info deposit of L1 origin zero.
+pub(crate) fn test_block(number: u64, parent_hash: B256, sequence_number: u64) -> OpBlock {
+    let l1_info =
+        L1BlockInfoTx::Ecotone(L1BlockInfoEcotone { sequence_number, ..Default::default() });
+    OpBlock {
+        header: Header { number, parent_hash, timestamp: number, ..Default::default() },
+        body: BlockBody {
+            transactions: vec![OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+                input: l1_info.encode_calldata(),
+                ..Default::default()
+            }))],
+            ..Default::default()
+        },
+    }
+}
+
+/// A [BatcherL2Provider] serving a fixed chain, whose last block is the unsafe head.
+#[derive(Debug, Default)]
+pub(crate) struct TestL2Provider {
+    /// The number of the L1 head.
+    pub(crate) head_l1: u64,
+    /// The number of the safe L2 head.
+    pub(crate) safe_l2: u64,
+    /// The blocks, starting at block number one.
+    pub(crate) blocks: Vec<OpBlock>,
+}
+
+#[async_trait]
+impl BatcherL2Provider for TestL2Provider {
+    type Error = String;
+
+    async fn sync_status(&mut self) -> Result<SyncStatus, Self::Error> {
+        let l2_block = |number| L2BlockInfo {
+            block_info: BlockInfo { number, ..Default::default() },
+            ..Default::default()
+        };
+        Ok(SyncStatus {
+            current_l1: Default::default(),
+            current_l1_finalized: Default::default(),
+            head_l1: BlockInfo { number: self.head_l1, ..Default::default() },
+            safe_l1: Default::default(),
+            finalized_l1: Default::default(),
+            unsafe_l2: l2_block(self.blocks.len() as u64),
+            safe_l2: l2_block(self.safe_l2),
+            finalized_l2: Default::default(),
+            cross_unsafe_l2: Default::default(),
+            local_safe_l2: Default::default(),
+        })
+    }
+
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+        let index = number.checked_sub(1).ok_or_else(|| "block not found".to_string())?;
+        self.blocks.get(index as usize).cloned().ok_or_else(|| "block not found".to_string())
+    }
+}
+
+/// A [TxSender] that records the sent candidates, and includes them in a fixed L1 block.
+#[derive(Debug, Default)]
+pub(crate) struct TestTxSender {
+    /// The candidates sent so far.
+    pub(crate) sent: Vec<TxCandidate>,
+    /// The L1 block that includes the candidates.
+    pub(crate) inclusion: BlockInfo,
+}
+
+#[async_trait]
+impl TxSender for TestTxSender {
+    type Error = String;
+
+    async fn send(&mut self, candidate: TxCandidate) -> Result<BlockInfo, Self::Error> {
+        self.sent.push(candidate);
+        Ok(self.inclusion)
+    }
+}
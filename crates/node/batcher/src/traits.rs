@@ -0,0 +1,33 @@
+//! Contains the traits that the batch submitter is generic over.
+
+use crate::TxCandidate;
+use async_trait::async_trait;
+use core::fmt::Display;
+use kona_protocol::{BlockInfo, SyncStatus};
+use op_alloy_consensus::OpBlock;
+
+/// Sends batcher transactions to L1.
+///
+/// Implementations are responsible for signing, fee management and retries, and only return once
+/// the transaction has been included, or has definitively failed.
+#[async_trait]
+pub trait TxSender {
+    /// The error type for the [TxSender].
+    type Error: Display;
+
+    /// Sends the given [TxCandidate], returning the L1 block in which it was included.
+    async fn send(&mut self, candidate: TxCandidate) -> Result<BlockInfo, Self::Error>;
+}
+
+/// Provides the L2 chain that the batch submitter follows.
+#[async_trait]
+pub trait BatcherL2Provider {
+    /// The error type for the [BatcherL2Provider].
+    type Error: Display;
+
+    /// Returns the [SyncStatus] of the rollup node.
+    async fn sync_status(&mut self) -> Result<SyncStatus, Self::Error>;
+
+    /// Returns the L2 block with the given number.
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error>;
+}
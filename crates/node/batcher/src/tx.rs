@@ -0,0 +1,113 @@
+//! Contains the types describing batcher transactions.
+
+use crate::{BatcherError, DataAvailabilityType, encode_blob};
+use alloy_eips::eip4844::Blob;
+use alloy_primitives::{Address, Bytes};
+use kona_protocol::{ChannelId, DERIVATION_VERSION_0, Frame};
+
+/// Identifies a batcher transaction by the channel and the first frame that it carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TxId {
+    /// The channel of the frames.
+    pub channel_id: ChannelId,
+    /// The number of the first frame.
+    pub first_frame: u16,
+}
+
+/// The frames carried by a batcher transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxData {
+    /// The frames, in order.
+    pub frames: Vec<Frame>,
+    /// How the frames are posted to L1.
+    pub da_type: DataAvailabilityType,
+}
+
+impl TxData {
+    /// Returns the [TxId] of the transaction, or [BatcherError::EmptyTx] if the transaction
+    /// carries no frames.
+    pub fn id(&self) -> Result<TxId, BatcherError> {
+        let first = self.frames.first().ok_or(BatcherError::EmptyTx)?;
+        Ok(TxId { channel_id: first.id, first_frame: first.number })
+    }
+
+    /// Returns the calldata of a calldata batcher transaction: the derivation version byte
+    /// followed by the encoded frames.
+    pub fn calldata(&self) -> Bytes {
+        let mut data = vec![DERIVATION_VERSION_0];
+        for frame in &self.frames {
+            data.extend(frame.encode());
+        }
+        data.into()
+    }
+
+    /// Returns the blobs of a blob batcher transaction, each of which carries the derivation
+    /// version byte followed by a single encoded frame.
+    pub fn blobs(&self) -> Result<Vec<Box<Blob>>, BatcherError> {
+        self.frames
+            .iter()
+            .map(|frame| {
+                encode_blob(&[&[DERIVATION_VERSION_0], frame.encode().as_slice()].concat())
+            })
+            .collect()
+    }
+
+    /// Builds the [TxCandidate] that sends the transaction to the given batch inbox.
+    pub fn to_candidate(&self, batch_inbox_address: Address) -> Result<TxCandidate, BatcherError> {
+        Ok(match self.da_type {
+            DataAvailabilityType::Calldata => {
+                TxCandidate { to: batch_inbox_address, data: self.calldata(), blobs: Vec::new() }
+            }
+            DataAvailabilityType::Blobs => {
+                TxCandidate { to: batch_inbox_address, data: Bytes::new(), blobs: self.blobs()? }
+            }
+        })
+    }
+}
+
+/// A batcher transaction that is ready to be signed and sent by a [TxSender].
+///
+/// [TxSender]: crate::TxSender
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxCandidate {
+    /// The batch inbox address.
+    pub to: Address,
+    /// The calldata of the transaction.
+    pub data: Bytes,
+    /// The blobs of the transaction. Empty for calldata transactions.
+    pub blobs: Vec<Box<Blob>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calldata_parses_into_frames() {
+        let frames = vec![
+            Frame::new([0x01; 16], 0, vec![0xaa; 8], false),
+            Frame::new([0x01; 16], 1, vec![0xbb; 4], true),
+        ];
+        let tx = TxData { frames: frames.clone(), da_type: DataAvailabilityType::Calldata };
+        assert_eq!(tx.id().unwrap(), TxId { channel_id: [0x01; 16], first_frame: 0 });
+
+        let calldata = tx.calldata();
+        assert_eq!(calldata[0], DERIVATION_VERSION_0);
+        assert_eq!(Frame::parse_frames(&calldata).unwrap(), frames);
+    }
+
+    #[test]
+    fn test_empty_tx_id() {
+        let tx = TxData { frames: Vec::new(), da_type: DataAvailabilityType::Calldata };
+        assert!(matches!(tx.id(), Err(BatcherError::EmptyTx)));
+    }
+
+    #[test]
+    fn test_blob_candidate() {
+        let frames = vec![Frame::new([0x02; 16], 3, vec![0xcc; 16], true)];
+        let tx = TxData { frames, da_type: DataAvailabilityType::Blobs };
+        let candidate = tx.to_candidate(Address::ZERO).unwrap();
+        assert!(candidate.data.is_empty());
+        assert_eq!(candidate.blobs.len(), 1);
+    }
+}
@@ -1,6 +1,6 @@
 //! An Engine API Client.
 
-use crate::Metrics;
+use crate::{EngineBackends, EngineEndpoint, Metrics};
 use alloy_eips::eip1898::BlockNumberOrTag;
 use alloy_network::Network;
 use alloy_primitives::{B256, BlockHash, Bytes};
@@ -10,7 +10,7 @@ use alloy_rpc_types_engine::{
     ClientVersionV1, ExecutionPayloadBodiesV1, ExecutionPayloadEnvelopeV2, ExecutionPayloadInputV2,
     ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated, JwtSecret, PayloadId, PayloadStatus,
 };
-use alloy_rpc_types_eth::Block;
+use alloy_rpc_types_eth::{Block, SyncStatus};
 use alloy_transport::{RpcError, TransportErrorKind, TransportResult};
 use alloy_transport_http::{
     AuthLayer, AuthService, Http, HyperClient,
@@ -19,7 +19,6 @@ use alloy_transport_http::{
         rt::TokioExecutor,
     },
 };
-use derive_more::Deref;
 use http_body_util::Full;
 use kona_genesis::RollupConfig;
 use kona_protocol::{FromBlockError, L2BlockInfo};
@@ -30,8 +29,13 @@ use op_alloy_rpc_types_engine::{
     OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4, OpExecutionPayloadV4,
     OpPayloadAttributes, ProtocolVersion,
 };
-use std::{sync::Arc, time::Instant};
+use std::{
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use thiserror::Error;
+use tokio::task::JoinSet;
 use tower::ServiceBuilder;
 use url::Url;
 
@@ -55,6 +59,17 @@ type HyperAuthClient<B = Full<Bytes>> = HyperClient<B, AuthService<Client<HttpCo
 /// execution layers. It automatically selects the appropriate Engine API version based on the
 /// rollup configuration and block timestamps.
 ///
+/// ## Failover
+///
+/// The [`EngineClient`] may be backed by several execution clients, created with
+/// [`EngineClient::new_http_failover`]. Engine API calls are sent to the primary execution client.
+/// When a call to the primary fails, the execution clients are health checked with
+/// `engine_exchangeCapabilities` and `eth_syncing`, and the next synced execution client that has
+/// caught up with the unsafe head is promoted to primary. Forkchoice updates and new payloads are
+/// then retried on the new primary. Optionally, new payloads are mirrored to all healthy
+/// secondaries in the background, so that they keep up with the chain and can be promoted without
+/// syncing.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -74,18 +89,37 @@ type HyperAuthClient<B = Full<Bytes>> = HyperClient<B, AuthService<Client<HttpCo
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Deref, Clone)]
+#[derive(Debug, Clone)]
 pub struct EngineClient {
-    /// The L2 engine provider for Engine API calls.
-    #[deref]
-    engine: RootProvider<Optimism>,
+    /// The L2 execution clients for Engine API calls.
+    backends: Arc<EngineBackends>,
+    /// Whether new payloads are mirrored to all execution clients.
+    mirror_new_payload: bool,
     /// The L1 chain provider for reading L1 data.
     l1_provider: RootProvider,
     /// The [`RollupConfig`] for determining Engine API versions based on hardfork activations.
     cfg: Arc<RollupConfig>,
 }
 
+impl Deref for EngineClient {
+    type Target = RootProvider<Optimism>;
+
+    fn deref(&self) -> &Self::Target {
+        self.l2_engine()
+    }
+}
+
 impl EngineClient {
+    /// The interval at which execution clients are health checked, when there are several.
+    pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// The maximum time to wait for an execution client to answer a health check.
+    pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// The maximum time the mirror queue of a secondary execution client waits for it to answer a
+    /// mirrored payload.
+    pub const MIRROR_TIMEOUT: Duration = Duration::from_secs(10);
+
     /// Creates a new RPC client for the given address and JWT secret.
     fn rpc_client<T: Network>(addr: Url, jwt: JwtSecret) -> RootProvider<T> {
         let hyper_client = Client::builder(TokioExecutor::new()).build_http::<Full<Bytes>>();
@@ -110,15 +144,65 @@ impl EngineClient {
     /// * `cfg` - Rollup configuration for version selection
     /// * `jwt` - JWT secret for authentication
     pub fn new_http(engine: Url, l1_rpc: Url, cfg: Arc<RollupConfig>, jwt: JwtSecret) -> Self {
-        let engine = Self::rpc_client::<Optimism>(engine, jwt);
+        Self::new_http_failover(vec![EngineEndpoint::new(engine, jwt)], l1_rpc, cfg)
+    }
+
+    /// Creates a new [`EngineClient`] backed by several execution clients.
+    ///
+    /// The first [`EngineEndpoint`] is the initial primary, and the others are secondaries that
+    /// are promoted in order when the primary fails.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if no [`EngineEndpoint`]s are given.
+    pub fn new_http_failover(
+        engines: Vec<EngineEndpoint>,
+        l1_rpc: Url,
+        cfg: Arc<RollupConfig>,
+    ) -> Self {
+        let backends = engines
+            .into_iter()
+            .map(|endpoint| {
+                let engine = Self::rpc_client::<Optimism>(endpoint.url.clone(), endpoint.jwt);
+                (endpoint.url, engine)
+            })
+            .collect();
         let l1_provider = RootProvider::new_http(l1_rpc);
 
-        Self { engine, l1_provider, cfg }
+        Self {
+            backends: Arc::new(EngineBackends::new(backends)),
+            mirror_new_payload: false,
+            l1_provider,
+            cfg,
+        }
+    }
+
+    /// Sets whether new payloads are mirrored to all execution clients, rather than only sent to
+    /// the primary.
+    pub const fn with_new_payload_mirroring(mut self, mirror_new_payload: bool) -> Self {
+        self.mirror_new_payload = mirror_new_payload;
+        self
     }
 
-    /// Returns a reference to the inner L2 [`RootProvider`].
-    pub const fn l2_engine(&self) -> &RootProvider<Optimism> {
-        &self.engine
+    /// Records the block number of the unsafe head of the engine. Secondaries that are behind the
+    /// unsafe head are not promoted to primary.
+    pub fn set_unsafe_head(&self, unsafe_head: u64) {
+        self.backends.set_unsafe_head(unsafe_head);
+    }
+
+    /// Returns a reference to the L2 [`RootProvider`] of the primary execution client.
+    pub fn l2_engine(&self) -> &RootProvider<Optimism> {
+        &self.backends.primary().engine
+    }
+
+    /// Returns the Engine API URL of the primary execution client.
+    pub fn primary_url(&self) -> &Url {
+        &self.backends.primary().url
+    }
+
+    /// Returns the number of execution clients backing the [`EngineClient`].
+    pub fn backend_count(&self) -> usize {
+        self.backends.len()
     }
 
     /// Returns a reference to the inner L1 [`RootProvider`].
@@ -136,7 +220,7 @@ impl EngineClient {
         &self,
         numtag: BlockNumberOrTag,
     ) -> Result<Option<Block<Transaction>>, EngineClientError> {
-        Ok(<RootProvider<Optimism>>::get_block_by_number(&self.engine, numtag).full().await?)
+        Ok(<RootProvider<Optimism>>::get_block_by_number(self.l2_engine(), numtag).full().await?)
     }
 
     /// Fetches the [L2BlockInfo] by [BlockNumberOrTag].
@@ -145,12 +229,171 @@ impl EngineClient {
         numtag: BlockNumberOrTag,
     ) -> Result<Option<L2BlockInfo>, EngineClientError> {
         let block =
-            <RootProvider<Optimism>>::get_block_by_number(&self.engine, numtag).full().await?;
+            <RootProvider<Optimism>>::get_block_by_number(self.l2_engine(), numtag).full().await?;
         let Some(block) = block else {
             return Ok(None);
         };
         Ok(Some(L2BlockInfo::from_block_and_genesis(&block.into_consensus(), &self.cfg.genesis)?))
     }
+
+    /// Health checks all execution clients, promoting a healthy secondary if the primary is
+    /// unhealthy.
+    ///
+    /// An execution client is healthy if it answers `engine_exchangeCapabilities` and reports
+    /// that it is not syncing. A secondary is only promoted if its latest block, or the latest
+    /// mirrored payload it accepted, has caught up with the unsafe head.
+    pub async fn check_health(&self) {
+        self.refresh_health().await;
+
+        let primary = self.backends.primary_index();
+        if !self.backends.is_healthy(primary) {
+            self.promote(primary);
+        }
+    }
+
+    /// Health checks all execution clients concurrently, recording the outcomes.
+    async fn refresh_health(&self) {
+        let mut checks = JoinSet::new();
+        for (index, backend) in self.backends.iter().enumerate() {
+            let engine = backend.engine.clone();
+            checks.spawn(async move { (index, Self::check_backend(engine).await) });
+        }
+
+        while let Some(check) = checks.join_next().await {
+            let Ok((index, latest)) = check else {
+                continue;
+            };
+            let healthy = latest.is_some();
+            if let Some(latest) = latest {
+                self.backends.set_latest(index, latest);
+            }
+            let backend = self.backends.get(index);
+            if healthy != self.backends.is_healthy(index) {
+                info!(target: "engine", url = %backend.url, healthy, "Execution client health");
+            }
+            self.backends.set_healthy(index, healthy);
+            kona_macros::set!(
+                gauge,
+                Metrics::ENGINE_BACKEND_HEALTH,
+                "url",
+                backend.url.to_string(),
+                if healthy { 1.0 } else { 0.0 }
+            );
+        }
+    }
+
+    /// Returns the latest block number of the execution client if it answers
+    /// `engine_exchangeCapabilities` and is synced, or `None` if it is unhealthy.
+    async fn check_backend(engine: RootProvider<Optimism>) -> Option<u64> {
+        let check = async {
+            <RootProvider<Optimism> as OpEngineApi<
+                Optimism,
+                Http<HyperAuthClient>,
+            >>::exchange_capabilities(&engine, vec![])
+            .await?;
+            if !matches!(engine.syncing().await?, SyncStatus::None) {
+                return Ok(None);
+            }
+            Ok::<_, RpcError<TransportErrorKind>>(Some(engine.get_block_number().await?))
+        };
+
+        tokio::time::timeout(Self::HEALTH_CHECK_TIMEOUT, check).await.ok()?.ok()?
+    }
+
+    /// Promotes the next healthy execution client that has caught up with the unsafe head in place
+    /// of the failed primary at index `failed`. Returns the index of the new primary, if any.
+    fn promote(&self, failed: usize) -> Option<usize> {
+        let failed_url = &self.backends.get(failed).url;
+        let Some(primary) = self.backends.promote(failed) else {
+            error!(target: "engine", url = %failed_url, "No synced execution client to promote");
+            return None;
+        };
+
+        if primary != failed {
+            warn!(
+                target: "engine",
+                failed = %failed_url,
+                primary = %self.backends.get(primary).url,
+                "Promoted execution client to primary"
+            );
+            kona_macros::inc!(counter, Metrics::ENGINE_FAILOVER_COUNT);
+        }
+        Some(primary)
+    }
+
+    /// Sends an Engine API call to the primary execution client.
+    ///
+    /// If the primary fails to answer, a healthy secondary is promoted to primary. If `retry` is
+    /// set, the call is then retried on the new primary.
+    async fn call_with_failover<T, F, Fut>(
+        &self,
+        method: &'static str,
+        retry: bool,
+        call: F,
+    ) -> TransportResult<T>
+    where
+        F: Fn(RootProvider<Optimism>) -> Fut + Send + Sync,
+        Fut: Future<Output = TransportResult<T>> + Send,
+    {
+        let primary = self.backends.primary_index();
+        let result =
+            record_call_time(call(self.backends.get(primary).engine.clone()), method).await;
+
+        // Only failures to reach the execution client trigger a failover. Errors returned by the
+        // execution client are forwarded to the caller.
+        let failed = matches!(result, Err(RpcError::Transport(_)));
+        if !failed || self.backends.len() == 1 {
+            return result;
+        }
+
+        if let Err(err) = &result {
+            warn!(
+                target: "engine",
+                url = %self.backends.get(primary).url,
+                method,
+                ?err,
+                "Primary execution client failed to answer"
+            );
+        }
+        self.refresh_health().await;
+        self.backends.set_healthy(primary, false);
+
+        match self.promote(primary) {
+            Some(new_primary) if retry && new_primary != primary => {
+                record_call_time(call(self.backends.get(new_primary).engine.clone()), method).await
+            }
+            _ => result,
+        }
+    }
+
+    /// Sends the new payload of the block with the given number to the primary execution client
+    /// with [`Self::call_with_failover`], mirroring it to the healthy secondaries if enabled.
+    ///
+    /// Mirrored payloads are queued per secondary and sent in order in the background, so that
+    /// slow secondaries never delay the primary.
+    async fn new_payload_with_failover<F, Fut>(
+        &self,
+        number: u64,
+        call: F,
+    ) -> TransportResult<PayloadStatus>
+    where
+        F: Fn(RootProvider<Optimism>) -> Fut + Send + Sync,
+        Fut: Future<Output = TransportResult<PayloadStatus>> + Send + 'static,
+    {
+        if self.mirror_new_payload {
+            let primary = self.backends.primary_index();
+            let secondaries = self
+                .backends
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != primary && self.backends.is_healthy(*i));
+            for (index, backend) in secondaries {
+                self.backends.mirror(index, number, Box::pin(call(backend.engine.clone())));
+            }
+        }
+
+        self.call_with_failover(Metrics::NEW_PAYLOAD_METHOD, true, call).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -159,12 +402,18 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         &self,
         payload: ExecutionPayloadInputV2,
     ) -> TransportResult<PayloadStatus> {
-        let call = <RootProvider<Optimism> as OpEngineApi<
-            Optimism,
-            Http<HyperAuthClient>,
-        >>::new_payload_v2(&self.engine, payload);
-
-        record_call_time(call, Metrics::NEW_PAYLOAD_METHOD).await
+        let number = payload.execution_payload.block_number;
+        self.new_payload_with_failover(number, move |engine| {
+            let payload = payload.clone();
+            async move {
+                <RootProvider<Optimism> as OpEngineApi<
+                    Optimism,
+                    Http<HyperAuthClient>,
+                >>::new_payload_v2(&engine, payload)
+                .await
+            }
+        })
+        .await
     }
 
     async fn new_payload_v3(
@@ -172,12 +421,18 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         payload: ExecutionPayloadV3,
         parent_beacon_block_root: B256,
     ) -> TransportResult<PayloadStatus> {
-        let call = <RootProvider<Optimism> as OpEngineApi<
-            Optimism,
-            Http<HyperAuthClient>,
-        >>::new_payload_v3(&self.engine, payload, parent_beacon_block_root);
-
-        record_call_time(call, Metrics::NEW_PAYLOAD_METHOD).await
+        let number = payload.payload_inner.payload_inner.block_number;
+        self.new_payload_with_failover(number, move |engine| {
+            let payload = payload.clone();
+            async move {
+                <RootProvider<Optimism> as OpEngineApi<
+                    Optimism,
+                    Http<HyperAuthClient>,
+                >>::new_payload_v3(&engine, payload, parent_beacon_block_root)
+                .await
+            }
+        })
+        .await
     }
 
     async fn new_payload_v4(
@@ -185,12 +440,18 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         payload: OpExecutionPayloadV4,
         parent_beacon_block_root: B256,
     ) -> TransportResult<PayloadStatus> {
-        let call = <RootProvider<Optimism> as OpEngineApi<
-            Optimism,
-            Http<HyperAuthClient>,
-        >>::new_payload_v4(&self.engine, payload, parent_beacon_block_root);
-
-        record_call_time(call, Metrics::NEW_PAYLOAD_METHOD).await
+        let number = payload.payload_inner.payload_inner.payload_inner.block_number;
+        self.new_payload_with_failover(number, move |engine| {
+            let payload = payload.clone();
+            async move {
+                <RootProvider<Optimism> as OpEngineApi<
+                    Optimism,
+                    Http<HyperAuthClient>,
+                >>::new_payload_v4(&engine, payload, parent_beacon_block_root)
+                .await
+            }
+        })
+        .await
     }
 
     async fn fork_choice_updated_v2(
@@ -198,12 +459,17 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         fork_choice_state: ForkchoiceState,
         payload_attributes: Option<OpPayloadAttributes>,
     ) -> TransportResult<ForkchoiceUpdated> {
-        let call = <RootProvider<Optimism> as OpEngineApi<
-            Optimism,
-            Http<HyperAuthClient>,
-        >>::fork_choice_updated_v2(&self.engine, fork_choice_state, payload_attributes);
-
-        record_call_time(call, Metrics::FORKCHOICE_UPDATE_METHOD).await
+        self.call_with_failover(Metrics::FORKCHOICE_UPDATE_METHOD, true, move |engine| {
+            let payload_attributes = payload_attributes.clone();
+            async move {
+                <RootProvider<Optimism> as OpEngineApi<
+                    Optimism,
+                    Http<HyperAuthClient>,
+                >>::fork_choice_updated_v2(&engine, fork_choice_state, payload_attributes)
+                .await
+            }
+        })
+        .await
     }
 
     async fn fork_choice_updated_v3(
@@ -211,48 +477,59 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         fork_choice_state: ForkchoiceState,
         payload_attributes: Option<OpPayloadAttributes>,
     ) -> TransportResult<ForkchoiceUpdated> {
-        let call = <RootProvider<Optimism> as OpEngineApi<
-            Optimism,
-            Http<HyperAuthClient>,
-        >>::fork_choice_updated_v3(&self.engine, fork_choice_state, payload_attributes);
-
-        record_call_time(call, Metrics::FORKCHOICE_UPDATE_METHOD).await
+        self.call_with_failover(Metrics::FORKCHOICE_UPDATE_METHOD, true, move |engine| {
+            let payload_attributes = payload_attributes.clone();
+            async move {
+                <RootProvider<Optimism> as OpEngineApi<
+                    Optimism,
+                    Http<HyperAuthClient>,
+                >>::fork_choice_updated_v3(&engine, fork_choice_state, payload_attributes)
+                .await
+            }
+        })
+        .await
     }
 
     async fn get_payload_v2(
         &self,
         payload_id: PayloadId,
     ) -> TransportResult<ExecutionPayloadEnvelopeV2> {
-        let call = <RootProvider<Optimism> as OpEngineApi<
-            Optimism,
-            Http<HyperAuthClient>,
-        >>::get_payload_v2(&self.engine, payload_id);
-
-        record_call_time(call, Metrics::GET_PAYLOAD_METHOD).await
+        self.call_with_failover(Metrics::GET_PAYLOAD_METHOD, false, move |engine| async move {
+            <RootProvider<Optimism> as OpEngineApi<
+                    Optimism,
+                    Http<HyperAuthClient>,
+                >>::get_payload_v2(&engine, payload_id)
+                .await
+        })
+        .await
     }
 
     async fn get_payload_v3(
         &self,
         payload_id: PayloadId,
     ) -> TransportResult<OpExecutionPayloadEnvelopeV3> {
-        let call = <RootProvider<Optimism> as OpEngineApi<
-            Optimism,
-            Http<HyperAuthClient>,
-        >>::get_payload_v3(&self.engine, payload_id);
-
-        record_call_time(call, Metrics::GET_PAYLOAD_METHOD).await
+        self.call_with_failover(Metrics::GET_PAYLOAD_METHOD, false, move |engine| async move {
+            <RootProvider<Optimism> as OpEngineApi<
+                    Optimism,
+                    Http<HyperAuthClient>,
+                >>::get_payload_v3(&engine, payload_id)
+                .await
+        })
+        .await
     }
 
     async fn get_payload_v4(
         &self,
         payload_id: PayloadId,
     ) -> TransportResult<OpExecutionPayloadEnvelopeV4> {
-        let call = <RootProvider<Optimism> as OpEngineApi<
-            Optimism,
-            Http<HyperAuthClient>,
-        >>::get_payload_v4(&self.engine, payload_id);
-
-        record_call_time(call, Metrics::GET_PAYLOAD_METHOD).await
+        self.call_with_failover(Metrics::GET_PAYLOAD_METHOD, false, move |engine| async move {
+            <RootProvider<Optimism> as OpEngineApi<
+                    Optimism,
+                    Http<HyperAuthClient>,
+                >>::get_payload_v4(&engine, payload_id)
+                .await
+        })
+        .await
     }
 
     async fn get_payload_bodies_by_hash_v1(
@@ -262,7 +539,7 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         <RootProvider<Optimism> as OpEngineApi<
             Optimism,
             Http<HyperAuthClient>,
-        >>::get_payload_bodies_by_hash_v1(&self.engine, block_hashes).await
+        >>::get_payload_bodies_by_hash_v1(self.l2_engine(), block_hashes).await
     }
 
     async fn get_payload_bodies_by_range_v1(
@@ -273,7 +550,7 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         <RootProvider<Optimism> as OpEngineApi<
             Optimism,
             Http<HyperAuthClient>,
-        >>::get_payload_bodies_by_range_v1(&self.engine, start, count).await
+        >>::get_payload_bodies_by_range_v1(self.l2_engine(), start, count).await
     }
 
     async fn get_client_version_v1(
@@ -283,7 +560,7 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         <RootProvider<Optimism> as OpEngineApi<
             Optimism,
             Http<HyperAuthClient>,
-        >>::get_client_version_v1(&self.engine, client_version).await
+        >>::get_client_version_v1(self.l2_engine(), client_version).await
     }
 
     async fn signal_superchain_v1(
@@ -294,7 +571,7 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         <RootProvider<Optimism> as OpEngineApi<
             Optimism,
             Http<HyperAuthClient>,
-        >>::signal_superchain_v1(&self.engine, recommended, required).await
+        >>::signal_superchain_v1(self.l2_engine(), recommended, required).await
     }
 
     async fn exchange_capabilities(
@@ -304,7 +581,7 @@ impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
         <RootProvider<Optimism> as OpEngineApi<
             Optimism,
             Http<HyperAuthClient>,
-        >>::exchange_capabilities(&self.engine, capabilities).await
+        >>::exchange_capabilities(self.l2_engine(), capabilities).await
     }
 }
 
@@ -0,0 +1,348 @@
+//! Failover between the execution clients backing the [`EngineClient`].
+//!
+//! [`EngineClient`]: crate::EngineClient
+
+use alloy_provider::RootProvider;
+use alloy_rpc_types_engine::{JwtSecret, PayloadStatus, PayloadStatusEnum};
+use alloy_transport::TransportResult;
+use op_alloy_network::Optimism;
+use std::{
+    pin::Pin,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+};
+use tokio::sync::mpsc;
+use url::Url;
+
+/// A pending `engine_newPayload` call mirrored to a secondary execution client.
+pub(crate) type MirroredPayload =
+    Pin<Box<dyn Future<Output = TransportResult<PayloadStatus>> + Send + 'static>>;
+
+/// An authenticated Engine API endpoint of an execution client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineEndpoint {
+    /// The Engine API URL.
+    pub url: Url,
+    /// The JWT secret used to authenticate with the endpoint.
+    pub jwt: JwtSecret,
+}
+
+impl EngineEndpoint {
+    /// Creates a new [`EngineEndpoint`].
+    pub const fn new(url: Url, jwt: JwtSecret) -> Self {
+        Self { url, jwt }
+    }
+}
+
+/// An execution client backing the [`EngineClient`].
+///
+/// [`EngineClient`]: crate::EngineClient
+#[derive(Debug)]
+pub(crate) struct EngineBackend {
+    /// The Engine API URL of the execution client.
+    pub(crate) url: Url,
+    /// The authenticated provider for the execution client.
+    pub(crate) engine: RootProvider<Optimism>,
+    /// Whether the execution client passed its last health check.
+    healthy: AtomicBool,
+    /// The latest block number reported by the execution client at its last health check.
+    latest: AtomicU64,
+    /// The highest block number of a mirrored payload that the execution client found valid.
+    accepted: Arc<AtomicU64>,
+    /// The queue of mirrored payloads, sent to the execution client in order by a background
+    /// task. Created on the first mirrored payload.
+    mirror: OnceLock<mpsc::Sender<(u64, MirroredPayload)>>,
+}
+
+/// The set of execution clients backing the [`EngineClient`], one of which is the primary.
+///
+/// Engine API calls are sent to the primary. When the primary fails, a healthy secondary that has
+/// caught up with the unsafe head, either by syncing or by accepting mirrored payloads, is
+/// promoted in its place.
+///
+/// [`EngineClient`]: crate::EngineClient
+#[derive(Debug)]
+pub(crate) struct EngineBackends {
+    /// The execution clients. The first one is the initial primary.
+    backends: Vec<EngineBackend>,
+    /// The index of the primary execution client.
+    primary: AtomicUsize,
+    /// The block number of the unsafe head of the engine.
+    unsafe_head: AtomicU64,
+}
+
+impl EngineBackends {
+    /// The maximum number of mirrored payloads queued for a single execution client.
+    const MIRROR_QUEUE_SIZE: usize = 64;
+
+    /// Creates a new [`EngineBackends`] from the given providers. The first provider is the
+    /// initial primary. All backends are assumed healthy until checked.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if no providers are given.
+    pub(crate) fn new(providers: Vec<(Url, RootProvider<Optimism>)>) -> Self {
+        assert!(!providers.is_empty(), "at least one engine backend is required");
+        let backends = providers
+            .into_iter()
+            .map(|(url, engine)| EngineBackend {
+                url,
+                engine,
+                healthy: AtomicBool::new(true),
+                latest: AtomicU64::new(0),
+                accepted: Default::default(),
+                mirror: OnceLock::new(),
+            })
+            .collect();
+        Self { backends, primary: AtomicUsize::new(0), unsafe_head: AtomicU64::new(0) }
+    }
+
+    /// Returns the number of execution clients.
+    pub(crate) fn len(&self) -> usize {
+        self.backends.len()
+    }
+
+    /// Returns the execution clients.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &EngineBackend> {
+        self.backends.iter()
+    }
+
+    /// Returns the index of the primary execution client.
+    pub(crate) fn primary_index(&self) -> usize {
+        self.primary.load(Ordering::Acquire)
+    }
+
+    /// Returns the primary execution client.
+    pub(crate) fn primary(&self) -> &EngineBackend {
+        &self.backends[self.primary_index()]
+    }
+
+    /// Returns the execution client at the given index.
+    pub(crate) fn get(&self, index: usize) -> &EngineBackend {
+        &self.backends[index]
+    }
+
+    /// Returns whether the execution client at the given index passed its last health check.
+    pub(crate) fn is_healthy(&self, index: usize) -> bool {
+        self.backends[index].healthy.load(Ordering::Acquire)
+    }
+
+    /// Records the outcome of a health check of the execution client at the given index.
+    pub(crate) fn set_healthy(&self, index: usize, healthy: bool) {
+        self.backends[index].healthy.store(healthy, Ordering::Release);
+    }
+
+    /// Records the latest block number reported by the execution client at the given index.
+    pub(crate) fn set_latest(&self, index: usize, latest: u64) {
+        self.backends[index].latest.store(latest, Ordering::Release);
+    }
+
+    /// Records the block number of the unsafe head of the engine.
+    pub(crate) fn set_unsafe_head(&self, unsafe_head: u64) {
+        self.unsafe_head.store(unsafe_head, Ordering::Release);
+    }
+
+    /// Returns whether the execution client at the given index can be promoted to primary, i.e.
+    /// whether it is healthy and has caught up with the unsafe head of the engine.
+    ///
+    /// Mirrored payloads don't advance the canonical head of a secondary, so a secondary has also
+    /// caught up if it accepted the mirrored payload of the unsafe head.
+    pub(crate) fn is_promotable(&self, index: usize) -> bool {
+        let backend = &self.backends[index];
+        let head =
+            backend.latest.load(Ordering::Acquire).max(backend.accepted.load(Ordering::Acquire));
+        self.is_healthy(index) && head >= self.unsafe_head.load(Ordering::Acquire)
+    }
+
+    /// Queues the payload with the given block number to be mirrored to the execution client at
+    /// the given index.
+    ///
+    /// Mirrored payloads are sent one at a time, in the order they were queued, by a background
+    /// task per execution client, so that slow secondaries never delay the primary. If the queue
+    /// of the execution client is full, the payload is dropped.
+    pub(crate) fn mirror(&self, index: usize, number: u64, payload: MirroredPayload) {
+        let backend = &self.backends[index];
+        let queue = backend.mirror.get_or_init(|| {
+            let (tx, rx) = mpsc::channel(Self::MIRROR_QUEUE_SIZE);
+            tokio::spawn(Self::drain_mirror(backend.url.clone(), backend.accepted.clone(), rx));
+            tx
+        });
+        if queue.try_send((number, payload)).is_err() {
+            debug!(
+                target: "engine",
+                url = %backend.url,
+                number,
+                "Mirror queue full, dropping payload"
+            );
+        }
+    }
+
+    /// Sends the mirrored payloads received over `queue` in order, recording the highest block
+    /// number of the payloads that the execution client found valid.
+    async fn drain_mirror(
+        url: Url,
+        accepted: Arc<AtomicU64>,
+        mut queue: mpsc::Receiver<(u64, MirroredPayload)>,
+    ) {
+        while let Some((number, payload)) = queue.recv().await {
+            match tokio::time::timeout(crate::EngineClient::MIRROR_TIMEOUT, payload).await {
+                Ok(Ok(status)) if matches!(status.status, PayloadStatusEnum::Valid) => {
+                    trace!(target: "engine", %url, number, "Mirrored new payload");
+                    accepted.fetch_max(number, Ordering::AcqRel);
+                }
+                Ok(Ok(status)) => {
+                    debug!(target: "engine", %url, number, ?status, "Mirrored payload not accepted");
+                }
+                Ok(Err(err)) => {
+                    debug!(target: "engine", %url, number, ?err, "Failed to mirror new payload");
+                }
+                Err(_) => {
+                    debug!(target: "engine", %url, number, "Timed out mirroring new payload");
+                }
+            }
+        }
+    }
+
+    /// Replaces the failed primary at index `failed` with the next promotable execution client.
+    ///
+    /// Returns the index of the new primary, or `None` if no other execution client is promotable.
+    /// If the primary was already replaced concurrently, the current primary is returned.
+    pub(crate) fn promote(&self, failed: usize) -> Option<usize> {
+        let len = self.backends.len();
+        let next =
+            (1..len).map(|offset| (failed + offset) % len).find(|&i| self.is_promotable(i))?;
+        match self.primary.compare_exchange(failed, next, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => Some(next),
+            Err(current) => Some(current),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_backends(count: usize) -> EngineBackends {
+        EngineBackends::new(
+            (0..count)
+                .map(|i| {
+                    let url = Url::parse(&format!("http://localhost:{}", 8551 + i)).unwrap();
+                    (url.clone(), RootProvider::new_http(url))
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_promote_next_healthy() {
+        let backends = new_backends(3);
+        assert_eq!(backends.primary_index(), 0);
+
+        backends.set_healthy(0, false);
+        backends.set_healthy(1, false);
+        assert_eq!(backends.promote(0), Some(2));
+        assert_eq!(backends.primary_index(), 2);
+        assert_eq!(backends.primary().url.port(), Some(8553));
+
+        // Promotion wraps around to the first execution client.
+        backends.set_healthy(0, true);
+        assert_eq!(backends.promote(2), Some(0));
+        assert_eq!(backends.primary_index(), 0);
+    }
+
+    #[test]
+    fn test_promote_no_healthy_secondary() {
+        let backends = new_backends(2);
+        backends.set_healthy(1, false);
+        assert_eq!(backends.promote(0), None);
+        assert_eq!(backends.primary_index(), 0);
+
+        let backends = new_backends(1);
+        assert_eq!(backends.promote(0), None);
+    }
+
+    #[test]
+    fn test_promote_skips_stale_secondary() {
+        let backends = new_backends(3);
+        backends.set_unsafe_head(100);
+        backends.set_latest(1, 99);
+        backends.set_latest(2, 100);
+        assert_eq!(backends.promote(0), Some(2));
+
+        // No secondary has caught up with the unsafe head.
+        backends.set_unsafe_head(101);
+        backends.set_latest(0, 100);
+        assert_eq!(backends.promote(2), None);
+        assert_eq!(backends.primary_index(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_promote_mirrored_secondary() {
+        let backends = new_backends(2);
+        backends.set_unsafe_head(3);
+        assert_eq!(backends.promote(0), None);
+
+        // Payloads are mirrored in order, even if an earlier one takes longer to answer.
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        for number in 1..=3 {
+            let order = order.clone();
+            backends.mirror(
+                1,
+                number,
+                Box::pin(async move {
+                    tokio::time::sleep(std::time::Duration::from_millis(10 * (4 - number))).await;
+                    order.lock().unwrap().push(number);
+                    Ok(PayloadStatus { status: PayloadStatusEnum::Valid, latest_valid_hash: None })
+                }),
+            );
+        }
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while !backends.is_promotable(1) {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(*order.lock().unwrap(), vec![1, 2, 3]);
+        assert_eq!(backends.promote(0), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_mirrored_payload_not_accepted() {
+        let backends = new_backends(2);
+        backends.set_unsafe_head(1);
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+        backends.mirror(
+            1,
+            1,
+            Box::pin(async {
+                Ok(PayloadStatus { status: PayloadStatusEnum::Syncing, latest_valid_hash: None })
+            }),
+        );
+        backends.mirror(
+            1,
+            2,
+            Box::pin(async move {
+                let _ = done_tx.send(());
+                Err(alloy_transport::TransportErrorKind::backend_gone())
+            }),
+        );
+
+        done_rx.await.unwrap();
+        tokio::task::yield_now().await;
+        assert!(!backends.is_promotable(1));
+        assert_eq!(backends.promote(0), None);
+    }
+
+    #[test]
+    fn test_promote_already_replaced() {
+        let backends = new_backends(3);
+        assert_eq!(backends.promote(0), Some(1));
+        // A concurrent failure of the same primary keeps the newly promoted primary.
+        assert_eq!(backends.promote(0), Some(1));
+        assert_eq!(backends.primary_index(), 1);
+    }
+}
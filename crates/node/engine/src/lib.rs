@@ -27,12 +27,14 @@
 //!
 //! - **Task Queue** - Core engine task queue and execution logic via [`Engine`]
 //! - **Client** - HTTP client for Engine API communication via [`EngineClient`]
+//! - **Failover** - Failover across several execution clients via [`EngineEndpoint`]
 //! - **State** - Engine state management and synchronization via [`EngineState`]
 //! - **Versions** - Engine API version selection via [`EngineForkchoiceVersion`],
 //!   [`EngineNewPayloadVersion`], [`EngineGetPayloadVersion`]
 //! - **Attributes** - Payload attribute validation via [`AttributesMatch`]
 //! - **Kinds** - Engine client type identification via [`EngineKind`]
 //! - **Query** - Engine query interface via [`EngineQueries`]
+//! - **Safe DB** - On-disk index of L1 blocks to derived L2 safe heads via [`SafeHeadDb`]
 //! - **Metrics** - Optional Prometheus metrics collection via [`Metrics`]
 
 #[macro_use]
@@ -52,6 +54,10 @@ pub use attributes::{AttributesMatch, AttributesMismatch};
 mod client;
 pub use client::{EngineClient, EngineClientError};
 
+mod failover;
+pub(crate) use failover::EngineBackends;
+pub use failover::EngineEndpoint;
+
 mod versions;
 pub use versions::{EngineForkchoiceVersion, EngineGetPayloadVersion, EngineNewPayloadVersion};
 
@@ -64,5 +70,8 @@ pub use kinds::EngineKind;
 mod query;
 pub use query::{EngineQueries, EngineQueriesError, EngineQuerySender};
 
+mod safe_db;
+pub use safe_db::{SafeHeadDb, SafeHeadDbError, SafeHeadEntry};
+
 mod metrics;
 pub use metrics::Metrics;
@@ -72,6 +72,14 @@ impl Metrics {
     /// Identifier for the counter that tracks the number of times the engine has been reset.
     pub const ENGINE_RESET_COUNT: &str = "kona_node_engine_reset_count";
 
+    /// Identifier for the gauge that tracks the health of each execution client backing the
+    /// engine client.
+    pub const ENGINE_BACKEND_HEALTH: &str = "kona_node_engine_backend_health";
+
+    /// Identifier for the counter that tracks the number of times a secondary execution client
+    /// was promoted to primary.
+    pub const ENGINE_FAILOVER_COUNT: &str = "kona_node_engine_failover_count";
+
     /// Initializes metrics for the engine.
     ///
     /// This does two things:
@@ -106,6 +114,19 @@ impl Metrics {
             metrics::Unit::Count,
             "Engine reset count"
         );
+
+        // Engine backend health
+        metrics::describe_gauge!(
+            Self::ENGINE_BACKEND_HEALTH,
+            "Health of the execution clients backing the engine client"
+        );
+
+        // Engine failover counter
+        metrics::describe_counter!(
+            Self::ENGINE_FAILOVER_COUNT,
+            metrics::Unit::Count,
+            "Number of promotions of a secondary execution client to primary"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -125,5 +146,8 @@ impl Metrics {
 
         // Engine reset count
         kona_macros::set!(counter, Self::ENGINE_RESET_COUNT, 0);
+
+        // Engine failover count
+        kona_macros::set!(counter, Self::ENGINE_FAILOVER_COUNT, 0);
     }
 }
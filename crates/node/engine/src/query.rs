@@ -8,9 +8,15 @@ use std::sync::Arc;
 
 use alloy_eips::BlockNumberOrTag;
 use alloy_provider::Provider;
+use alloy_rpc_types_engine::{ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3};
+use alloy_rpc_types_eth::Block;
 use alloy_transport::{RpcError, TransportErrorKind};
 use kona_genesis::RollupConfig;
 use kona_protocol::{L2BlockInfo, OutputRoot, Predeploys};
+use op_alloy_rpc_types::Transaction;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayload, OpExecutionPayloadEnvelope, OpExecutionPayloadV4,
+};
 use tokio::sync::oneshot::Sender;
 
 use crate::{EngineClient, EngineClientError, EngineState};
@@ -38,12 +44,28 @@ pub enum EngineQueries {
         /// Response channel for (block_info, output_root, engine_state).
         sender: Sender<(L2BlockInfo, OutputRoot, EngineState)>,
     },
+    /// Request the execution payload of the canonical L2 block at the given number.
+    ///
+    /// Used to answer `payload_by_number` requests from peers. Responds with `None` if the
+    /// execution layer does not have the block.
+    PayloadByNumber {
+        /// The number of the L2 block to retrieve the payload for.
+        number: u64,
+        /// Response channel for the execution payload.
+        sender: Sender<Option<OpExecutionPayloadEnvelope>>,
+    },
     /// Subscribe to engine state updates via a watch channel receiver.
     StateReceiver(Sender<tokio::sync::watch::Receiver<EngineState>>),
     /// Development API: Subscribe to task queue length updates.
     QueueLengthReceiver(Sender<tokio::sync::watch::Receiver<usize>>),
     /// Development API: Get the current number of pending tasks in the queue.
     TaskQueueLength(Sender<usize>),
+    /// Development API: Subscribe to updates of the number of unsafe payloads buffered ahead of
+    /// the unsafe head.
+    UnsafePayloadQueueLengthReceiver(Sender<tokio::sync::watch::Receiver<usize>>),
+    /// Development API: Get the current number of unsafe payloads buffered ahead of the unsafe
+    /// head.
+    UnsafePayloadQueueLength(Sender<usize>),
 }
 
 /// An error that can occur when querying the engine.
@@ -72,6 +94,7 @@ impl EngineQueries {
         self,
         state_recv: &tokio::sync::watch::Receiver<EngineState>,
         queue_length_recv: &tokio::sync::watch::Receiver<usize>,
+        unsafe_payloads_recv: &tokio::sync::watch::Receiver<usize>,
         client: &Arc<EngineClient>,
         rollup_config: &Arc<RollupConfig>,
     ) -> Result<(), EngineQueriesError> {
@@ -126,6 +149,13 @@ impl EngineQueries {
                     .send((output_block_info, output_response_v0, state))
                     .map_err(|_| EngineQueriesError::OutputChannelClosed)
             }
+            Self::PayloadByNumber { number, sender } => {
+                let block = client.l2_block_by_label(BlockNumberOrTag::Number(number)).await?;
+                let payload = block
+                    .map(|block| Self::payload_from_block(block, rollup_config))
+                    .transpose()?;
+                sender.send(payload).map_err(|_| EngineQueriesError::OutputChannelClosed)
+            }
             Self::StateReceiver(subscription) => subscription
                 .send(state_recv.clone())
                 .map_err(|_| EngineQueriesError::OutputChannelClosed),
@@ -139,6 +169,47 @@ impl EngineQueries {
                 }
                 Ok(())
             }
+            Self::UnsafePayloadQueueLengthReceiver(subscription) => subscription
+                .send(unsafe_payloads_recv.clone())
+                .map_err(|_| EngineQueriesError::OutputChannelClosed),
+            Self::UnsafePayloadQueueLength(sender) => {
+                let queue_length = *unsafe_payloads_recv.borrow();
+                if sender.send(queue_length).is_err() {
+                    warn!(target: "engine", "Failed to send unsafe payload queue length response");
+                }
+                Ok(())
+            }
         }
     }
+
+    /// Builds the [`OpExecutionPayloadEnvelope`] for an L2 block fetched from the execution layer,
+    /// using the payload version that is active at the block's timestamp.
+    fn payload_from_block(
+        block: Block<Transaction>,
+        rollup_config: &RollupConfig,
+    ) -> Result<OpExecutionPayloadEnvelope, EngineQueriesError> {
+        let hash = block.header.hash;
+        let timestamp = block.header.timestamp;
+        let block = block.into_consensus().map_transactions(|tx| tx.inner.inner.into_inner());
+
+        let execution_payload = if rollup_config.is_isthmus_active(timestamp) {
+            let withdrawals_root =
+                block.header.withdrawals_root.ok_or(EngineQueriesError::NoWithdrawalsRoot)?;
+            OpExecutionPayload::V4(OpExecutionPayloadV4::from_v3_with_withdrawals_root(
+                ExecutionPayloadV3::from_block_unchecked(hash, &block),
+                withdrawals_root,
+            ))
+        } else if rollup_config.is_ecotone_active(timestamp) {
+            OpExecutionPayload::V3(ExecutionPayloadV3::from_block_unchecked(hash, &block))
+        } else if rollup_config.is_canyon_active(timestamp) {
+            OpExecutionPayload::V2(ExecutionPayloadV2::from_block_unchecked(hash, &block))
+        } else {
+            OpExecutionPayload::V1(ExecutionPayloadV1::from_block_unchecked(hash, &block))
+        };
+
+        Ok(OpExecutionPayloadEnvelope {
+            execution_payload,
+            parent_beacon_block_root: block.header.parent_beacon_block_root,
+        })
+    }
 }
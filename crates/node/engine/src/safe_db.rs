@@ -0,0 +1,353 @@
+//! An on-disk index of the L2 safe head derived from each L1 block.
+//!
+//! Mirrors the op-node's safe head database, which backs the `optimism_safeHeadAtL1Block` RPC
+//! method. Entries are stored as fixed-size records in an append-only file, ordered by L1 block
+//! number, so that lookups are a binary search and truncation on reorgs is a single `set_len`.
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use thiserror::Error;
+
+/// The size of a single encoded [`SafeHeadEntry`], in bytes.
+const ENTRY_SIZE: u64 = 2 * (8 + 32);
+
+/// An entry of the [`SafeHeadDb`], mapping an L1 block to the L2 safe head derived from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafeHeadEntry {
+    /// The L1 block.
+    pub l1_block: BlockNumHash,
+    /// The L2 safe head after all data of the L1 block was processed.
+    pub safe_head: BlockNumHash,
+}
+
+impl SafeHeadEntry {
+    /// Encodes the entry into its fixed-size on-disk representation.
+    fn encode(&self) -> [u8; ENTRY_SIZE as usize] {
+        let mut buf = [0u8; ENTRY_SIZE as usize];
+        buf[..8].copy_from_slice(&self.l1_block.number.to_be_bytes());
+        buf[8..40].copy_from_slice(self.l1_block.hash.as_slice());
+        buf[40..48].copy_from_slice(&self.safe_head.number.to_be_bytes());
+        buf[48..].copy_from_slice(self.safe_head.hash.as_slice());
+        buf
+    }
+
+    /// Decodes an entry from its fixed-size on-disk representation.
+    fn decode(buf: &[u8; ENTRY_SIZE as usize]) -> Self {
+        let number = |bytes: &[u8]| u64::from_be_bytes(bytes.try_into().expect("8 bytes"));
+        Self {
+            l1_block: BlockNumHash {
+                number: number(&buf[..8]),
+                hash: B256::from_slice(&buf[8..40]),
+            },
+            safe_head: BlockNumHash {
+                number: number(&buf[40..48]),
+                hash: B256::from_slice(&buf[48..]),
+            },
+        }
+    }
+}
+
+/// An error from the [`SafeHeadDb`].
+#[derive(Debug, Error)]
+pub enum SafeHeadDbError {
+    /// Failed to read or write the database file.
+    #[error("Safe head database file error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The database lock was poisoned by a panicking writer.
+    #[error("Safe head database lock poisoned")]
+    Poisoned,
+}
+
+/// An on-disk index of the L2 safe head derived from each L1 block.
+///
+/// The index is written by the engine as derived safe heads are promoted, via
+/// [`SafeHeadDb::safe_head_updated`], and truncated when the safe head is reset, via
+/// [`SafeHeadDb::safe_head_reset`], or when an L1 block is reorged, via [`SafeHeadDb::l1_reorg`].
+///
+/// All methods perform blocking file I/O, and should not be called from an async task.
+#[derive(Debug)]
+pub struct SafeHeadDb {
+    /// The path of the database file.
+    path: PathBuf,
+    /// The database file.
+    file: Mutex<File>,
+}
+
+impl SafeHeadDb {
+    /// Opens the [`SafeHeadDb`] at the given path, creating it if it does not exist.
+    ///
+    /// A trailing partial entry, left behind by a crash during a write, is discarded.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SafeHeadDbError> {
+        let path = path.as_ref().to_path_buf();
+        let file =
+            OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&path)?;
+
+        let len = file.metadata()?.len();
+        if len % ENTRY_SIZE != 0 {
+            warn!(target: "safe_db", ?path, len, "Discarding partial safe head database entry");
+            file.set_len(len - len % ENTRY_SIZE)?;
+        }
+
+        Ok(Self { path, file: Mutex::new(file) })
+    }
+
+    /// Returns the path of the database file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the number of entries in the database.
+    pub fn len(&self) -> Result<u64, SafeHeadDbError> {
+        let file = self.file.lock().map_err(|_| SafeHeadDbError::Poisoned)?;
+        Ok(file.metadata()?.len() / ENTRY_SIZE)
+    }
+
+    /// Returns `true` if the database has no entries.
+    pub fn is_empty(&self) -> Result<bool, SafeHeadDbError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Records that the given L2 safe head was derived from the given L1 block.
+    ///
+    /// Any entries for L1 blocks at or after the given L1 block are replaced, so that the latest
+    /// safe head derived from an L1 block is kept, and entries of reorged L1 blocks are dropped.
+    pub fn safe_head_updated(
+        &self,
+        l1_block: BlockNumHash,
+        safe_head: BlockNumHash,
+    ) -> Result<(), SafeHeadDbError> {
+        let mut file = self.file.lock().map_err(|_| SafeHeadDbError::Poisoned)?;
+
+        let len = file.metadata()?.len() / ENTRY_SIZE;
+        let keep =
+            Self::partition_point(&mut file, len, |entry| entry.l1_block.number < l1_block.number)?;
+        // Write the record before truncating the replaced entries, so that an interrupted update
+        // never leaves the database without it.
+        file.seek(SeekFrom::Start(keep * ENTRY_SIZE))?;
+        file.write_all(&SafeHeadEntry { l1_block, safe_head }.encode())?;
+        file.set_len((keep + 1) * ENTRY_SIZE)?;
+        file.sync_data()?;
+
+        trace!(
+            target: "safe_db",
+            l1_block = l1_block.number,
+            safe_head = safe_head.number,
+            "Recorded safe head"
+        );
+        Ok(())
+    }
+
+    /// Truncates the database following a reset of the L2 safe head to the given block.
+    ///
+    /// All entries with a safe head past the given safe head are removed, as is an entry at the
+    /// same height with a different hash.
+    pub fn safe_head_reset(&self, safe_head: BlockNumHash) -> Result<(), SafeHeadDbError> {
+        let mut file = self.file.lock().map_err(|_| SafeHeadDbError::Poisoned)?;
+
+        let len = file.metadata()?.len() / ENTRY_SIZE;
+        let keep = Self::partition_point(&mut file, len, |entry| {
+            entry.safe_head.number < safe_head.number ||
+                (entry.safe_head.number == safe_head.number &&
+                    entry.safe_head.hash == safe_head.hash)
+        })?;
+        if keep < len {
+            file.set_len(keep * ENTRY_SIZE)?;
+            file.sync_data()?;
+            debug!(
+                target: "safe_db",
+                safe_head = safe_head.number,
+                removed = len - keep,
+                "Truncated safe head database"
+            );
+        }
+        Ok(())
+    }
+
+    /// Truncates the database following a reorg of the L1 block with the given number.
+    ///
+    /// All entries for L1 blocks at or after the given L1 block number are removed.
+    pub fn l1_reorg(&self, l1_number: u64) -> Result<(), SafeHeadDbError> {
+        let mut file = self.file.lock().map_err(|_| SafeHeadDbError::Poisoned)?;
+
+        let len = file.metadata()?.len() / ENTRY_SIZE;
+        let keep =
+            Self::partition_point(&mut file, len, |entry| entry.l1_block.number < l1_number)?;
+        if keep < len {
+            file.set_len(keep * ENTRY_SIZE)?;
+            file.sync_data()?;
+            debug!(
+                target: "safe_db",
+                l1_block = l1_number,
+                removed = len - keep,
+                "Truncated safe head database after L1 reorg"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the latest entry for an L1 block at or before the given L1 block number.
+    ///
+    /// Returns [`None`] if no entry is recorded at or before the given L1 block number.
+    pub fn safe_head_at_l1(
+        &self,
+        l1_number: u64,
+    ) -> Result<Option<SafeHeadEntry>, SafeHeadDbError> {
+        let mut file = self.file.lock().map_err(|_| SafeHeadDbError::Poisoned)?;
+
+        let len = file.metadata()?.len() / ENTRY_SIZE;
+        let index =
+            Self::partition_point(&mut file, len, |entry| entry.l1_block.number <= l1_number)?;
+        if index == 0 {
+            return Ok(None);
+        }
+        Self::read_entry(&mut file, index - 1).map(Some)
+    }
+
+    /// Reads the entry at the given index.
+    fn read_entry(file: &mut File, index: u64) -> Result<SafeHeadEntry, SafeHeadDbError> {
+        let mut buf = [0u8; ENTRY_SIZE as usize];
+        file.seek(SeekFrom::Start(index * ENTRY_SIZE))?;
+        file.read_exact(&mut buf)?;
+        Ok(SafeHeadEntry::decode(&buf))
+    }
+
+    /// Returns the index of the first of the `len` entries for which the predicate is `false`.
+    ///
+    /// The entries must be partitioned by the predicate, which holds since both L1 block numbers
+    /// and safe head numbers are increasing.
+    fn partition_point(
+        file: &mut File,
+        len: u64,
+        pred: impl Fn(&SafeHeadEntry) -> bool,
+    ) -> Result<u64, SafeHeadDbError> {
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(&Self::read_entry(file, mid)?) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn num_hash(number: u64, tag: u8) -> BlockNumHash {
+        BlockNumHash { number, hash: B256::repeat_byte(tag) }
+    }
+
+    fn open_db(dir: &tempfile::TempDir) -> SafeHeadDb {
+        SafeHeadDb::open(dir.path().join("safe_db")).unwrap()
+    }
+
+    #[test]
+    fn test_entry_roundtrip() {
+        let entry = SafeHeadEntry { l1_block: num_hash(10, 1), safe_head: num_hash(20, 2) };
+        assert_eq!(SafeHeadEntry::decode(&entry.encode()), entry);
+    }
+
+    #[test]
+    fn test_safe_head_at_l1() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_db(&dir);
+        assert_eq!(db.safe_head_at_l1(100).unwrap(), None);
+
+        db.safe_head_updated(num_hash(10, 1), num_hash(100, 1)).unwrap();
+        db.safe_head_updated(num_hash(12, 1), num_hash(105, 1)).unwrap();
+        db.safe_head_updated(num_hash(15, 1), num_hash(110, 1)).unwrap();
+        assert_eq!(db.len().unwrap(), 3);
+
+        assert_eq!(db.safe_head_at_l1(9).unwrap(), None);
+        let at = |n| db.safe_head_at_l1(n).unwrap().map(|e| e.safe_head.number);
+        assert_eq!(at(10), Some(100));
+        assert_eq!(at(11), Some(100));
+        assert_eq!(at(12), Some(105));
+        assert_eq!(at(14), Some(105));
+        assert_eq!(at(15), Some(110));
+        assert_eq!(at(1_000), Some(110));
+    }
+
+    #[test]
+    fn test_safe_head_updated_replaces_later_l1_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_db(&dir);
+
+        // Multiple safe heads derived from the same L1 block keep the latest.
+        db.safe_head_updated(num_hash(10, 1), num_hash(100, 1)).unwrap();
+        db.safe_head_updated(num_hash(10, 1), num_hash(101, 1)).unwrap();
+        assert_eq!(db.len().unwrap(), 1);
+        assert_eq!(db.safe_head_at_l1(10).unwrap().unwrap().safe_head, num_hash(101, 1));
+
+        // An update from an earlier L1 block drops the entries of later L1 blocks.
+        db.safe_head_updated(num_hash(12, 1), num_hash(105, 1)).unwrap();
+        db.safe_head_updated(num_hash(11, 2), num_hash(103, 2)).unwrap();
+        assert_eq!(db.len().unwrap(), 2);
+        let entry = db.safe_head_at_l1(12).unwrap().unwrap();
+        assert_eq!(entry.l1_block, num_hash(11, 2));
+        assert_eq!(entry.safe_head, num_hash(103, 2));
+    }
+
+    #[test]
+    fn test_safe_head_reset() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_db(&dir);
+        db.safe_head_updated(num_hash(10, 1), num_hash(100, 1)).unwrap();
+        db.safe_head_updated(num_hash(12, 1), num_hash(105, 1)).unwrap();
+        db.safe_head_updated(num_hash(15, 1), num_hash(110, 1)).unwrap();
+
+        // A reset to a recorded safe head keeps its entry.
+        db.safe_head_reset(num_hash(105, 1)).unwrap();
+        assert_eq!(db.len().unwrap(), 2);
+
+        // A reset to a different block at a recorded height drops its entry.
+        db.safe_head_reset(num_hash(105, 2)).unwrap();
+        assert_eq!(db.len().unwrap(), 1);
+
+        db.safe_head_reset(num_hash(50, 2)).unwrap();
+        assert!(db.is_empty().unwrap());
+    }
+
+    #[test]
+    fn test_l1_reorg() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_db(&dir);
+        db.safe_head_updated(num_hash(10, 1), num_hash(100, 1)).unwrap();
+        db.safe_head_updated(num_hash(12, 1), num_hash(100, 1)).unwrap();
+        db.safe_head_updated(num_hash(15, 1), num_hash(100, 1)).unwrap();
+
+        // Entries of reorged L1 blocks are dropped, even if their safe head is unchanged.
+        db.l1_reorg(16).unwrap();
+        assert_eq!(db.len().unwrap(), 3);
+        db.l1_reorg(12).unwrap();
+        assert_eq!(db.len().unwrap(), 1);
+        assert_eq!(db.safe_head_at_l1(15).unwrap().unwrap().l1_block, num_hash(10, 1));
+    }
+
+    #[test]
+    fn test_reopen_discards_partial_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = open_db(&dir);
+        db.safe_head_updated(num_hash(10, 1), num_hash(100, 1)).unwrap();
+        db.safe_head_updated(num_hash(12, 1), num_hash(105, 1)).unwrap();
+        drop(db);
+
+        let mut file = OpenOptions::new().append(true).open(dir.path().join("safe_db")).unwrap();
+        file.write_all(&[0xFF; 17]).unwrap();
+        drop(file);
+
+        let db = open_db(&dir);
+        assert_eq!(db.len().unwrap(), 2);
+        assert_eq!(db.safe_head_at_l1(20).unwrap().unwrap().safe_head, num_hash(105, 1));
+    }
+}
@@ -145,6 +145,7 @@ impl EngineTaskExt for SynchronizeTask {
 
         // Apply the new sync state to the engine state.
         state.sync_state = new_sync_state;
+        self.client.set_unsafe_head(new_sync_state.unsafe_head().block_info.number);
 
         let fcu_duration = fcu_time_start.elapsed();
         debug!(
@@ -4,6 +4,7 @@ use alloy_consensus::Block;
 use alloy_eips::eip7685::EMPTY_REQUESTS_HASH;
 use alloy_primitives::{Address, B256};
 use alloy_rpc_types_engine::{ExecutionPayloadV3, PayloadError};
+use kona_genesis::RollupConfig;
 use libp2p::gossipsub::MessageAcceptance;
 use op_alloy_consensus::OpTxEnvelope;
 use op_alloy_rpc_types_engine::{
@@ -128,17 +129,11 @@ impl BlockHandler {
         }
 
         // CHECK: Ensure the block hash is valid.
-        let expected = envelope.payload.block_hash();
-        let mut block: Block<OpTxEnvelope> = envelope.payload.clone().try_into_block()?;
-        block.header.parent_beacon_block_root = envelope.parent_beacon_block_root;
-        // If isthmus is active, set the requests hash to the empty hash.
-        if self.rollup_config.is_isthmus_active(envelope.payload.timestamp()) {
-            block.header.requests_hash = Some(EMPTY_REQUESTS_HASH);
-        }
-        let received = block.header.hash_slow();
-        if received != expected {
-            return Err(BlockInvalidError::BlockHash { expected, received });
-        }
+        Self::validate_block_hash(
+            &self.rollup_config,
+            &envelope.payload,
+            envelope.parent_beacon_block_root,
+        )?;
 
         // CHECK: The payload is valid for the specific version of this block.
         Self::validate_version_specific_payload(envelope)?;
@@ -189,6 +184,26 @@ impl BlockHandler {
         Ok(())
     }
 
+    /// Checks that the block hash of the payload matches the hash of the block it commits to.
+    pub fn validate_block_hash(
+        rollup_config: &RollupConfig,
+        payload: &OpExecutionPayload,
+        parent_beacon_block_root: Option<B256>,
+    ) -> Result<(), BlockInvalidError> {
+        let expected = payload.block_hash();
+        let mut block: Block<OpTxEnvelope> = payload.clone().try_into_block()?;
+        block.header.parent_beacon_block_root = parent_beacon_block_root;
+        // If isthmus is active, set the requests hash to the empty hash.
+        if rollup_config.is_isthmus_active(payload.timestamp()) {
+            block.header.requests_hash = Some(EMPTY_REQUESTS_HASH);
+        }
+        let received = block.header.hash_slow();
+        if received != expected {
+            return Err(BlockInvalidError::BlockHash { expected, received });
+        }
+        Ok(())
+    }
+
     /// Validate version specific contents of the payload.
     pub const fn validate_version_specific_payload(
         envelope: &OpNetworkPayloadEnvelope,
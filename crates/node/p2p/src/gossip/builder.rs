@@ -4,14 +4,15 @@ use alloy_primitives::Address;
 use kona_genesis::RollupConfig;
 use kona_peers::{PeerMonitoring, PeerScoreLevel};
 use libp2p::{
-    Multiaddr, StreamProtocol, SwarmBuilder, gossipsub::Config, identity::Keypair,
-    noise::Config as NoiseConfig, tcp::Config as TcpConfig, yamux::Config as YamuxConfig,
+    Multiaddr, SwarmBuilder, gossipsub::Config, identity::Keypair, noise::Config as NoiseConfig,
+    tcp::Config as TcpConfig, yamux::Config as YamuxConfig,
 };
 use std::time::Duration;
 use tokio::sync::watch::{self};
 
 use crate::{
     Behaviour, BlockHandler, GossipDriver, GossipDriverBuilderError, gossip::gater::GaterConfig,
+    payload_by_number_protocol,
 };
 
 /// A builder for the [`GossipDriver`].
@@ -187,11 +188,8 @@ impl GossipDriverBuilder {
         // Let's setup the sync request/response protocol stream.
         let mut sync_handler = behaviour.sync_req_resp.new_control();
 
-        let protocol = format!("/opstack/req/payload_by_number/{}/0/", l2_chain_id);
-        let sync_protocol_name = StreamProtocol::try_from_owned(protocol)
-            .map_err(|_| GossipDriverBuilderError::SetupSyncReqRespError)?;
         let sync_protocol = sync_handler
-            .accept(sync_protocol_name)
+            .accept(payload_by_number_protocol(l2_chain_id.id()))
             .map_err(|_| GossipDriverBuilderError::SyncReqRespAlreadyAccepted)?;
 
         // Build the swarm.
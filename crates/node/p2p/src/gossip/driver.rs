@@ -1,9 +1,9 @@
 //! Consensus-layer gossipsub driver for Optimism.
 
-use alloy_primitives::{Address, hex};
+use alloy_primitives::Address;
 use derive_more::Debug;
 use discv5::Enr;
-use futures::{AsyncReadExt, AsyncWriteExt, stream::StreamExt};
+use futures::stream::StreamExt;
 use kona_genesis::RollupConfig;
 use kona_peers::{EnrValidation, PeerMonitoring, enr_to_multiaddr};
 use libp2p::{
@@ -19,11 +19,12 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, mpsc};
 
 use crate::{
     Behaviour, BlockHandler, ConnectionGate, ConnectionGater, Event, GossipDriverBuilder, Handler,
-    PublishError,
+    PayloadRequest, PublishError, SyncClient, SyncRateLimiter, payload_by_number_protocol,
+    sync::serve_payload_requests,
 };
 
 /// A driver for a [`Swarm`] instance.
@@ -50,6 +51,17 @@ pub struct GossipDriver<G: ConnectionGate> {
     /// TODO(@theochap, `<https://github.com/op-rs/kona/issues/2141>`): remove the sync-req-resp protocol once the `op-node` phases it out.
     #[debug(skip)]
     pub sync_protocol: Option<IncomingStreams>,
+    /// The [`SyncClient`] used to request missing payloads from peers.
+    pub sync_client: SyncClient,
+    /// The receiver for the [`PayloadRequest`]s received from peers.
+    ///
+    /// This is an option to allow the consumer of the gossip driver to take the receiver and
+    /// answer the requests.
+    pub payload_requests: Option<mpsc::Receiver<PayloadRequest>>,
+    /// The sender for the [`PayloadRequest`]s received from peers.
+    payload_request_sender: mpsc::Sender<PayloadRequest>,
+    /// The rate limits applied to the `payload_by_number` requests served to peers.
+    sync_server_limiter: Arc<std::sync::Mutex<SyncRateLimiter>>,
     /// A mapping from [`PeerId`] to [`Multiaddr`].
     pub peerstore: HashMap<PeerId, libp2p::identify::Info>,
     /// If set, the gossip layer will monitor peer scores and ban peers that are below a given
@@ -67,6 +79,9 @@ impl<G> GossipDriver<G>
 where
     G: ConnectionGate,
 {
+    /// The capacity of the channel of [`PayloadRequest`]s received from peers.
+    pub const PAYLOAD_REQUEST_CHANNEL_SIZE: usize = 64;
+
     /// Returns the [`GossipDriverBuilder`] that can be used to construct the [`GossipDriver`].
     pub const fn builder(
         rollup_config: RollupConfig,
@@ -86,6 +101,13 @@ where
         sync_protocol: IncomingStreams,
         gate: G,
     ) -> Self {
+        let sync_client = SyncClient::new(
+            sync_handler.clone(),
+            payload_by_number_protocol(handler.rollup_config.l2_chain_id.id()),
+            Arc::new(handler.rollup_config.clone()),
+        );
+        let (payload_request_sender, payload_requests) =
+            mpsc::channel(Self::PAYLOAD_REQUEST_CHANNEL_SIZE);
         Self {
             swarm,
             addr,
@@ -95,6 +117,10 @@ where
             peer_connection_start: Default::default(),
             sync_handler,
             sync_protocol: Some(sync_protocol),
+            sync_client,
+            payload_requests: Some(payload_requests),
+            payload_request_sender,
+            sync_server_limiter: Arc::new(std::sync::Mutex::new(SyncRateLimiter::server())),
             connection_gate: gate,
             ping: Arc::new(Mutex::new(Default::default())),
         }
@@ -130,53 +156,23 @@ where
 
     /// Handles the sync request/response protocol.
     ///
-    /// This is a mock handler that supports the `payload_by_number` protocol.
-    /// It always returns: not found (1), version (0). `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
-    ///
-    /// ## Note
+    /// Serves the `payload_by_number` protocol.
+    /// `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
     ///
-    /// This is used to ensure op-nodes are not penalizing kona-nodes for not supporting it.
-    /// This feature is being deprecated by the op-node team. Once it is fully removed from the
-    /// op-node's implementation we will remove this handler.
+    /// Requests are forwarded as [`PayloadRequest`]s through [`Self::payload_requests`]. Requests
+    /// that cannot be forwarded or are not answered in time receive an error response.
     pub(super) fn sync_protocol_handler(&mut self) {
-        let Some(mut sync_protocol) = self.sync_protocol.take() else {
+        let Some(sync_protocol) = self.sync_protocol.take() else {
             return;
         };
 
         // Spawn a new task to handle the sync request/response protocol.
-        tokio::spawn(async move {
-            loop {
-                let Some((peer_id, mut inbound_stream)) = sync_protocol.next().await else {
-                    warn!(target: "gossip", "The sync protocol stream has ended");
-                    return;
-                };
-
-                info!(target: "gossip", "Received a sync request from {peer_id}, spawning a new task to handle it");
-
-                tokio::spawn(async move {
-                    let mut buffer = Vec::new();
-                    let Ok(bytes_received) = inbound_stream.read_to_end(&mut buffer).await else {
-                        error!(target: "gossip", "Failed to read the sync request from {peer_id}");
-                        return;
-                    };
-
-                    debug!(target: "gossip", bytes_received = bytes_received, peer_id = ?peer_id, payload = ?buffer, "Received inbound sync request");
-
-                    // We return: not found (1), version (0). `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
-                    // Response format: <response> = <res><version><payload>
-                    // No payload is returned.
-                    const OUTPUT: [u8; 2] = hex!("0100");
-
-                    // We only write that we're not supporting the sync request.
-                    if let Err(e) = inbound_stream.write_all(&OUTPUT).await {
-                        error!(target: "gossip", err = ?e, "Failed to write the sync response to {peer_id}");
-                        return;
-                    };
-
-                    debug!(target: "gossip", bytes_sent = OUTPUT.len(), peer_id = ?peer_id, "Sent outbound sync response");
-                });
-            }
-        });
+        tokio::spawn(serve_payload_requests(
+            sync_protocol,
+            self.payload_request_sender.clone(),
+            self.sync_server_limiter.clone(),
+            Arc::new(self.handler.rollup_config.clone()),
+        ));
     }
 
     /// Starts the libp2p Swarm.
@@ -390,7 +386,7 @@ where
     pub fn handle_event(&mut self, event: SwarmEvent<Event>) -> Option<OpNetworkPayloadEnvelope> {
         match event {
             SwarmEvent::Behaviour(behavior_event) => {
-                return self.handle_gossip_event(behavior_event)
+                return self.handle_gossip_event(behavior_event);
             }
             SwarmEvent::ConnectionEstablished { peer_id, .. } => {
                 let peer_count = self.swarm.connected_peers().count();
@@ -462,6 +458,11 @@ where
                     pings.lock().await.remove(&peer_id);
                 });
 
+                self.sync_client.remove_peer(&peer_id);
+                if let Ok(mut limiter) = self.sync_server_limiter.lock() {
+                    limiter.remove_peer(&peer_id);
+                }
+
                 // If the connection was initiated by us, remove the peer from the current dials
                 // set so that we can dial it again.
                 self.connection_gate.remove_dial(&peer_id);
@@ -29,6 +29,13 @@ pub use gossip::{
     PublishError, SEEN_MESSAGES_TTL, default_config, default_config_builder,
 };
 
+mod sync;
+pub use sync::{
+    PAYLOAD_BY_NUMBER_REQUEST_SIZE, PayloadByNumberResult, PayloadRequest, SyncClient,
+    SyncClientError, SyncCodecError, SyncRateLimiter, TokenBucket, decode_request, decode_response,
+    encode_error_response, encode_request, encode_response, payload_by_number_protocol,
+};
+
 mod discv5;
 pub use discv5::{
     Discv5Builder, Discv5BuilderError, Discv5Driver, Discv5Handler, HandlerRequest, LocalNode,
@@ -51,6 +51,9 @@ impl Metrics {
     pub const GOSSIP_PEER_CONNECTION_DURATION_SECONDS: &str =
         "kona_node_gossip_peer_connection_duration_seconds";
 
+    /// Identifier for the gauge that tracks `payload_by_number` requests, served and sent.
+    pub const PAYLOAD_BY_NUMBER: &str = "kona_node_payload_by_number_requests";
+
     /// Initializes metrics for the P2P stack.
     ///
     /// This does two things:
@@ -105,6 +108,10 @@ impl Metrics {
             Self::GOSSIP_PEER_CONNECTION_DURATION_SECONDS,
             "Duration of peer connections in seconds"
         );
+        metrics::describe_counter!(
+            Self::PAYLOAD_BY_NUMBER,
+            "Requests served and sent over the payload_by_number protocol"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -169,5 +176,12 @@ impl Metrics {
 
         // Banned Peers
         kona_macros::set!(gauge, Self::BANNED_PEERS, 0);
+
+        // Payload by number requests
+        kona_macros::set!(counter, Self::PAYLOAD_BY_NUMBER, "type", "server_served", 0);
+        kona_macros::set!(counter, Self::PAYLOAD_BY_NUMBER, "type", "server_throttled", 0);
+        kona_macros::set!(counter, Self::PAYLOAD_BY_NUMBER, "type", "client_valid", 0);
+        kona_macros::set!(counter, Self::PAYLOAD_BY_NUMBER, "type", "client_rejected", 0);
+        kona_macros::set!(counter, Self::PAYLOAD_BY_NUMBER, "type", "client_error", 0);
     }
 }
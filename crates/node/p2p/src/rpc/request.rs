@@ -260,6 +260,7 @@ impl P2pRpcRequest {
 
         // Clone the ping map
         let pings = Arc::clone(&gossip.ping);
+        let req_resp_scores = gossip.sync_client.scores();
 
         #[derive(Default)]
         struct PeerMetadata {
@@ -439,10 +440,7 @@ impl P2pRpcRequest {
                                     // See `<https://github.com/libp2p/rust-libp2p/issues/6058>`
                                     behavioral_penalty: Default::default(),
                                 },
-                                // We only support a shim implementation for the req/resp
-                                // protocol so we're not
-                                // computing scores for it.
-                                req_resp: Default::default(),
+                                req_resp: req_resp_scores.get(peer_id).copied().unwrap_or_default(),
                             },
                         },
                     )
@@ -0,0 +1,300 @@
+//! Requests missing payloads from peers over `payload_by_number`.
+
+use alloy_primitives::B256;
+use derive_more::Debug;
+use futures::{AsyncReadExt, AsyncWriteExt};
+use kona_genesis::RollupConfig;
+use libp2p::{PeerId, StreamProtocol};
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::{SyncCodecError, SyncRateLimiter, decode_response, encode_request};
+use crate::{BlockHandler, BlockInvalidError, MAX_GOSSIP_SIZE, ReqRespScores};
+
+/// The maximum time to wait for a peer to answer a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The score penalty of each rejected payload when ranking peers, relative to a single valid or
+/// failed response.
+const REJECTED_PAYLOAD_PENALTY: f64 = 10.0;
+
+/// An error requesting a payload from a peer.
+#[derive(Debug, thiserror::Error)]
+pub enum SyncClientError {
+    /// The request was rate limited.
+    #[error("Request rate limited")]
+    RateLimited,
+    /// The stream to the peer could not be opened.
+    #[error("Failed to open stream: {0}")]
+    OpenStream(#[from] libp2p_stream::OpenStreamError),
+    /// The request could not be sent or the response could not be read.
+    #[error("Stream error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The peer did not answer in time.
+    #[error("Request timed out")]
+    Timeout,
+    /// The response could not be decoded.
+    #[error(transparent)]
+    Codec(#[from] SyncCodecError),
+    /// The peer returned a payload for a different block.
+    #[error(
+        "Unexpected payload. Expected {expected_number} ({expected_hash}), got {number} ({hash})"
+    )]
+    UnexpectedPayload {
+        /// The requested block number.
+        expected_number: u64,
+        /// The expected block hash.
+        expected_hash: B256,
+        /// The received block number.
+        number: u64,
+        /// The received block hash.
+        hash: B256,
+    },
+    /// The returned payload is invalid.
+    #[error(transparent)]
+    InvalidPayload(#[from] BlockInvalidError),
+}
+
+impl SyncClientError {
+    /// Returns true if the peer returned a payload that failed validation.
+    pub const fn is_rejected_payload(&self) -> bool {
+        matches!(self, Self::UnexpectedPayload { .. } | Self::InvalidPayload(_))
+    }
+}
+
+/// A client for the `payload_by_number` request/response protocol.
+///
+/// The client is cheap to clone: clones share the rate limits and the [`ReqRespScores`] tracked
+/// for each peer.
+#[derive(Debug, Clone)]
+pub struct SyncClient {
+    /// The [`libp2p_stream::Control`] used to open streams to peers.
+    #[debug(skip)]
+    control: libp2p_stream::Control,
+    /// The `payload_by_number` protocol.
+    protocol: StreamProtocol,
+    /// The [`RollupConfig`] used to validate the payloads.
+    rollup_config: Arc<RollupConfig>,
+    /// The rate limits for outbound requests.
+    limiter: Arc<Mutex<SyncRateLimiter>>,
+    /// The [`ReqRespScores`] of each peer that was sent requests.
+    scores: Arc<Mutex<HashMap<PeerId, ReqRespScores>>>,
+}
+
+impl SyncClient {
+    /// The maximum number of blocks requested to fill a single gap.
+    pub const MAX_GAP: u64 = 256;
+
+    /// Creates a new [`SyncClient`].
+    pub fn new(
+        control: libp2p_stream::Control,
+        protocol: StreamProtocol,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Self {
+        Self {
+            control,
+            protocol,
+            rollup_config,
+            limiter: Arc::new(Mutex::new(SyncRateLimiter::client())),
+            scores: Default::default(),
+        }
+    }
+
+    /// Returns a snapshot of the [`ReqRespScores`] of each peer.
+    pub fn scores(&self) -> HashMap<PeerId, ReqRespScores> {
+        self.scores.lock().map(|scores| scores.clone()).unwrap_or_default()
+    }
+
+    /// Returns the given peers ordered by their [`ReqRespScores`], so that peers with the most
+    /// valid and fewest failed responses come first. Rejected payloads are penalized more heavily
+    /// than failed responses, but every peer stays eligible.
+    pub fn rank_peers(&self, peers: &[PeerId]) -> Vec<PeerId> {
+        let scores = self.scores();
+        let score = |peer: &PeerId| {
+            scores.get(peer).map_or(0.0, |s| {
+                s.valid_responses -
+                    s.error_responses -
+                    s.rejected_payloads * REJECTED_PAYLOAD_PENALTY
+            })
+        };
+        let mut ranked = peers.to_vec();
+        ranked.sort_by(|a, b| score(b).total_cmp(&score(a)));
+        ranked
+    }
+
+    /// Removes the state kept for a disconnected peer.
+    pub fn remove_peer(&self, peer: &PeerId) {
+        if let Ok(mut limiter) = self.limiter.lock() {
+            limiter.remove_peer(peer);
+        }
+        if let Ok(mut scores) = self.scores.lock() {
+            scores.remove(peer);
+        }
+    }
+
+    /// Requests the payload of the block with the given number and hash from a peer.
+    ///
+    /// The returned payload is validated against the expected block hash, and the peer's
+    /// [`ReqRespScores`] are updated with the outcome of the request.
+    pub async fn request_payload(
+        &self,
+        peer: PeerId,
+        number: u64,
+        hash: B256,
+    ) -> Result<OpExecutionPayloadEnvelope, SyncClientError> {
+        let result = self.request_and_validate(peer, number, hash).await;
+        if matches!(result, Err(SyncClientError::RateLimited)) {
+            return result;
+        }
+
+        if let Ok(mut scores) = self.scores.lock() {
+            let scores = scores.entry(peer).or_default();
+            match &result {
+                Ok(_) => scores.valid_responses += 1.0,
+                Err(err) if err.is_rejected_payload() => scores.rejected_payloads += 1.0,
+                Err(_) => scores.error_responses += 1.0,
+            }
+        }
+        kona_macros::inc!(
+            counter,
+            crate::Metrics::PAYLOAD_BY_NUMBER,
+            match &result {
+                Ok(_) => "client_valid",
+                Err(err) if err.is_rejected_payload() => "client_rejected",
+                Err(_) => "client_error",
+            }
+        );
+        result
+    }
+
+    /// Fills the gap of blocks in `from..=to`, where `to_hash` is the trusted hash of block `to`.
+    ///
+    /// Blocks are requested in descending order so that each received payload is validated
+    /// against the parent hash of its (already validated) child. The requests of each block are
+    /// rotated across the given peers, as ranked by [`Self::rank_peers`], until one of them
+    /// returns a valid payload.
+    ///
+    /// Returns the payloads that were retrieved, in ascending order. These are the blocks from
+    /// the first block that could not be retrieved (exclusive) up to `to`.
+    pub async fn fill_gap(
+        &self,
+        peers: &[PeerId],
+        from: u64,
+        to: u64,
+        to_hash: B256,
+    ) -> Vec<OpExecutionPayloadEnvelope> {
+        let from = from.max(to.saturating_sub(Self::MAX_GAP - 1));
+        let peers = self.rank_peers(peers);
+        let mut payloads = Vec::new();
+        let mut expected_hash = to_hash;
+
+        'blocks: for (i, number) in (from..=to).rev().enumerate() {
+            for peer in peers.iter().cycle().skip(i).take(peers.len()) {
+                match self.request_payload(*peer, number, expected_hash).await {
+                    Ok(payload) => {
+                        expected_hash = payload.execution_payload.parent_hash();
+                        payloads.push(payload);
+                        continue 'blocks;
+                    }
+                    Err(err) => {
+                        debug!(target: "gossip", ?peer, number, ?err, "Failed to retrieve payload");
+                    }
+                }
+            }
+            warn!(target: "gossip", number, "Failed to retrieve payload from any peer");
+            break;
+        }
+
+        payloads.reverse();
+        payloads
+    }
+
+    async fn request_and_validate(
+        &self,
+        peer: PeerId,
+        number: u64,
+        hash: B256,
+    ) -> Result<OpExecutionPayloadEnvelope, SyncClientError> {
+        let delay = self
+            .limiter
+            .lock()
+            .ok()
+            .and_then(|mut limiter| limiter.reserve(peer, Instant::now()))
+            .ok_or(SyncClientError::RateLimited)?;
+        tokio::time::sleep(delay).await;
+
+        let response = tokio::time::timeout(REQUEST_TIMEOUT, self.request(peer, number))
+            .await
+            .map_err(|_| SyncClientError::Timeout)??;
+        let payload = decode_response(&self.rollup_config, number, &response)?;
+
+        let (received_number, received_hash) =
+            (payload.execution_payload.block_number(), payload.execution_payload.block_hash());
+        if received_number != number || received_hash != hash {
+            return Err(SyncClientError::UnexpectedPayload {
+                expected_number: number,
+                expected_hash: hash,
+                number: received_number,
+                hash: received_hash,
+            });
+        }
+        BlockHandler::validate_block_hash(
+            &self.rollup_config,
+            &payload.execution_payload,
+            payload.parent_beacon_block_root,
+        )?;
+
+        Ok(payload)
+    }
+
+    async fn request(&self, peer: PeerId, number: u64) -> Result<Vec<u8>, SyncClientError> {
+        let mut stream = self.control.clone().open_stream(peer, self.protocol.clone()).await?;
+
+        stream.write_all(&encode_request(number)).await?;
+        // Close the write side of the stream to signal the end of the request.
+        stream.close().await?;
+
+        let mut response = Vec::new();
+        stream.take(MAX_GOSSIP_SIZE as u64).read_to_end(&mut response).await?;
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_peers() {
+        let client = SyncClient::new(
+            libp2p_stream::Behaviour::new().new_control(),
+            crate::payload_by_number_protocol(10),
+            Arc::new(RollupConfig::default()),
+        );
+        let (good, unknown, failing, rejecting) =
+            (PeerId::random(), PeerId::random(), PeerId::random(), PeerId::random());
+        {
+            let mut scores = client.scores.lock().unwrap();
+            scores.insert(good, ReqRespScores { valid_responses: 3.0, ..Default::default() });
+            scores.insert(failing, ReqRespScores { error_responses: 2.0, ..Default::default() });
+            scores.insert(
+                rejecting,
+                ReqRespScores {
+                    valid_responses: 10.0,
+                    rejected_payloads: 2.0,
+                    ..Default::default()
+                },
+            );
+        }
+
+        // Peers that returned rejected payloads are ranked last, but are not excluded.
+        assert_eq!(
+            client.rank_peers(&[failing, rejecting, unknown, good]),
+            vec![good, unknown, failing, rejecting]
+        );
+    }
+}
@@ -0,0 +1,397 @@
+//! Wire encoding of the `payload_by_number` request/response protocol.
+//!
+//! See: <https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>
+
+use alloy_primitives::{B256, Signature, U256};
+use kona_genesis::RollupConfig;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayload, OpExecutionPayloadEnvelope, OpNetworkPayloadEnvelope, PayloadHash,
+};
+use std::io::{Read, Write};
+
+use crate::MAX_GOSSIP_SIZE;
+
+/// The size of the signature that prefixes payloads gossiped over the blocks topics.
+const SIGNATURE_SIZE: usize = 65;
+
+/// The size of a `payload_by_number` request: the little-endian encoded block number.
+pub const PAYLOAD_BY_NUMBER_REQUEST_SIZE: usize = 8;
+
+/// The size of the response header: the result byte followed by the little-endian `u32` version.
+const RESPONSE_HEADER_SIZE: usize = 5;
+
+/// The response version of a bare execution payload, V1 before Canyon and V2 before Ecotone.
+const PAYLOAD_VERSION: u32 = 0;
+
+/// The response version of an execution payload envelope, V3 from Ecotone and V4 from Isthmus.
+const ENVELOPE_VERSION: u32 = 1;
+
+/// The result code that prefixes every `payload_by_number` response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+#[repr(u8)]
+pub enum PayloadByNumberResult {
+    /// The payload was found and follows the result code.
+    #[display("success")]
+    Success = 0,
+    /// The requested payload was not found.
+    #[display("not found")]
+    NotFound = 1,
+    /// The request was invalid.
+    #[display("invalid request")]
+    InvalidRequest = 2,
+    /// The request failed for an unknown reason.
+    #[display("unknown")]
+    Unknown = 3,
+}
+
+impl From<u8> for PayloadByNumberResult {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Success,
+            1 => Self::NotFound,
+            2 => Self::InvalidRequest,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// An error encoding or decoding a `payload_by_number` message.
+#[derive(Debug, thiserror::Error)]
+pub enum SyncCodecError {
+    /// The message is too short.
+    #[error("Message too short: expected at least {expected} bytes, got {received}")]
+    TooShort {
+        /// The minimum expected size.
+        expected: usize,
+        /// The received size.
+        received: usize,
+    },
+    /// The peer answered with a non-success result code.
+    #[error("Peer responded with result: {0}")]
+    Result(PayloadByNumberResult),
+    /// The response version does not match the hardfork of the requested block.
+    #[error("Unexpected response version: expected {expected}, got {received}")]
+    UnexpectedVersion {
+        /// The version expected for the requested block.
+        expected: u32,
+        /// The received version.
+        received: u32,
+    },
+    /// The snappy compression or decompression failed.
+    #[error("Snappy error: {0}")]
+    Snappy(#[from] std::io::Error),
+    /// The raw snappy compression or decompression failed.
+    #[error("Raw snappy error: {0}")]
+    RawSnappy(#[from] snap::Error),
+    /// The payload could not be encoded.
+    #[error("Failed to encode payload: {0}")]
+    Encode(#[from] op_alloy_rpc_types_engine::PayloadEnvelopeEncodeError),
+    /// The payload could not be decoded.
+    #[error("Failed to decode payload: {0}")]
+    Decode(#[from] op_alloy_rpc_types_engine::PayloadEnvelopeError),
+}
+
+/// Encodes a `payload_by_number` request for the given block number.
+pub const fn encode_request(number: u64) -> [u8; PAYLOAD_BY_NUMBER_REQUEST_SIZE] {
+    number.to_le_bytes()
+}
+
+/// Decodes a `payload_by_number` request into the requested block number.
+pub fn decode_request(data: &[u8]) -> Result<u64, SyncCodecError> {
+    let bytes: [u8; PAYLOAD_BY_NUMBER_REQUEST_SIZE] = data.try_into().map_err(|_| {
+        SyncCodecError::TooShort { expected: PAYLOAD_BY_NUMBER_REQUEST_SIZE, received: data.len() }
+    })?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Encodes an unsuccessful `payload_by_number` response.
+///
+/// The version is always zero since no payload follows the result code.
+pub const fn encode_error_response(result: PayloadByNumberResult) -> [u8; RESPONSE_HEADER_SIZE] {
+    let mut out = [0u8; RESPONSE_HEADER_SIZE];
+    out[0] = result as u8;
+    out
+}
+
+/// Returns the timestamp of the L2 block with the given number.
+fn block_timestamp(cfg: &RollupConfig, number: u64) -> u64 {
+    let blocks = number.saturating_sub(cfg.genesis.l2.number);
+    cfg.genesis.l2_time.saturating_add(blocks.saturating_mul(cfg.block_time))
+}
+
+/// Encodes a successful `payload_by_number` response for the given payload.
+///
+/// Response format: `<result><version><payload>`, where the version is a little-endian `u32` and
+/// the payload is snappy framed SSZ. As in the op-node, blocks before Ecotone are sent as a bare
+/// execution payload (version 0), and later blocks as an execution payload envelope (version 1).
+/// The payload encoding is chosen from the hardforks active at the block's timestamp.
+pub fn encode_response(
+    cfg: &RollupConfig,
+    envelope: &OpExecutionPayloadEnvelope,
+) -> Result<Vec<u8>, SyncCodecError> {
+    // The SSZ encoding used by req/resp is the gossip encoding without the signature prefix, so
+    // the payload is encoded as an unsigned gossip message and the signature stripped.
+    let network = OpNetworkPayloadEnvelope {
+        payload: envelope.execution_payload.clone(),
+        signature: Signature::new(U256::ZERO, U256::ZERO, false),
+        payload_hash: PayloadHash(B256::ZERO),
+        parent_beacon_block_root: envelope.parent_beacon_block_root,
+    };
+    let timestamp = envelope.execution_payload.timestamp();
+    let (version, compressed) = if cfg.is_isthmus_active(timestamp) {
+        (ENVELOPE_VERSION, network.encode_v4()?)
+    } else if cfg.is_ecotone_active(timestamp) {
+        (ENVELOPE_VERSION, network.encode_v3()?)
+    } else if cfg.is_canyon_active(timestamp) {
+        (PAYLOAD_VERSION, network.encode_v2()?)
+    } else {
+        (PAYLOAD_VERSION, network.encode_v1()?)
+    };
+    let ssz = snap::raw::Decoder::new().decompress_vec(&compressed)?;
+
+    let mut out = Vec::with_capacity(RESPONSE_HEADER_SIZE + ssz.len());
+    out.push(PayloadByNumberResult::Success as u8);
+    out.extend_from_slice(&version.to_le_bytes());
+    let mut encoder = snap::write::FrameEncoder::new(&mut out);
+    encoder.write_all(&ssz[SIGNATURE_SIZE..])?;
+    encoder.flush()?;
+    drop(encoder);
+    Ok(out)
+}
+
+/// Decodes a `payload_by_number` response for the L2 block with the given number into the
+/// execution payload envelope.
+///
+/// The expected version and payload encoding are chosen from the hardforks active at the
+/// timestamp of the requested block, as in the op-node.
+pub fn decode_response(
+    cfg: &RollupConfig,
+    number: u64,
+    data: &[u8],
+) -> Result<OpExecutionPayloadEnvelope, SyncCodecError> {
+    let Some(&result) = data.first() else {
+        return Err(SyncCodecError::TooShort { expected: RESPONSE_HEADER_SIZE, received: 0 });
+    };
+    let result = PayloadByNumberResult::from(result);
+    if result != PayloadByNumberResult::Success {
+        return Err(SyncCodecError::Result(result));
+    }
+    if data.len() < RESPONSE_HEADER_SIZE {
+        return Err(SyncCodecError::TooShort {
+            expected: RESPONSE_HEADER_SIZE,
+            received: data.len(),
+        });
+    }
+    let version = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
+    let timestamp = block_timestamp(cfg, number);
+    let expected =
+        if cfg.is_ecotone_active(timestamp) { ENVELOPE_VERSION } else { PAYLOAD_VERSION };
+    if version != expected {
+        return Err(SyncCodecError::UnexpectedVersion { expected, received: version });
+    }
+
+    // Prefix the SSZ payload with an empty signature to reuse the gossip decoding.
+    let mut ssz = vec![0u8; SIGNATURE_SIZE];
+    snap::read::FrameDecoder::new(&data[RESPONSE_HEADER_SIZE..])
+        .take(MAX_GOSSIP_SIZE as u64)
+        .read_to_end(&mut ssz)?;
+    let compressed = snap::raw::Encoder::new().compress_vec(&ssz)?;
+
+    let network = if cfg.is_isthmus_active(timestamp) {
+        OpNetworkPayloadEnvelope::decode_v4(&compressed)?
+    } else if cfg.is_ecotone_active(timestamp) {
+        OpNetworkPayloadEnvelope::decode_v3(&compressed)?
+    } else if cfg.is_canyon_active(timestamp) {
+        OpNetworkPayloadEnvelope::decode_v2(&compressed)?
+    } else {
+        OpNetworkPayloadEnvelope::decode_v1(&compressed)?
+    };
+    Ok(network.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, hex};
+    use alloy_rpc_types_engine::{ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3};
+    use arbitrary::Arbitrary;
+    use kona_genesis::HardForkConfig;
+    use op_alloy_rpc_types_engine::OpExecutionPayloadV4;
+    use rand::Rng;
+
+    fn unstructured_bytes() -> [u8; 4096] {
+        let mut bytes = [0u8; 4096];
+        rand::rng().fill(bytes.as_mut_slice());
+        bytes
+    }
+
+    /// Returns a rollup config with 2 second blocks, where the given hardforks are active from
+    /// genesis.
+    fn config(canyon: bool, ecotone: bool, isthmus: bool) -> RollupConfig {
+        let time = |active: bool| active.then_some(0);
+        RollupConfig {
+            block_time: 2,
+            hardforks: HardForkConfig {
+                canyon_time: time(canyon),
+                ecotone_time: time(ecotone),
+                isthmus_time: time(isthmus),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn roundtrip(cfg: &RollupConfig, envelope: &OpExecutionPayloadEnvelope, version: u8) {
+        let encoded = encode_response(cfg, envelope).unwrap();
+        assert_eq!(&encoded[..RESPONSE_HEADER_SIZE], &[0, version, 0, 0, 0]);
+        let number = envelope.execution_payload.block_number();
+        assert_eq!(&decode_response(cfg, number, &encoded).unwrap(), envelope);
+    }
+
+    #[test]
+    fn test_request_roundtrip() {
+        let encoded = encode_request(0x1234_5678);
+        assert_eq!(encoded, [0x78, 0x56, 0x34, 0x12, 0, 0, 0, 0]);
+        assert_eq!(decode_request(&encoded).unwrap(), 0x1234_5678);
+        assert!(matches!(
+            decode_request(&encoded[..4]),
+            Err(SyncCodecError::TooShort { expected: 8, received: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_error_response() {
+        let cfg = RollupConfig::default();
+        let encoded = encode_error_response(PayloadByNumberResult::NotFound);
+        assert_eq!(encoded, [1, 0, 0, 0, 0]);
+        assert!(matches!(
+            decode_response(&cfg, 0, &encoded),
+            Err(SyncCodecError::Result(PayloadByNumberResult::NotFound))
+        ));
+        assert!(matches!(decode_response(&cfg, 0, &[]), Err(SyncCodecError::TooShort { .. })));
+    }
+
+    #[test]
+    fn test_response_roundtrip_v1() {
+        let bytes = unstructured_bytes();
+        let envelope = OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V1(
+                ExecutionPayloadV1::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap(),
+            ),
+            parent_beacon_block_root: None,
+        };
+        roundtrip(&config(false, false, false), &envelope, 0);
+    }
+
+    #[test]
+    fn test_response_roundtrip_v2() {
+        let bytes = unstructured_bytes();
+        let envelope = OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V2(
+                ExecutionPayloadV2::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap(),
+            ),
+            parent_beacon_block_root: None,
+        };
+        roundtrip(&config(true, false, false), &envelope, 0);
+    }
+
+    #[test]
+    fn test_response_roundtrip_v3() {
+        let bytes = unstructured_bytes();
+        let envelope = OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V3(
+                ExecutionPayloadV3::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap(),
+            ),
+            parent_beacon_block_root: Some(B256::repeat_byte(0xaa)),
+        };
+        roundtrip(&config(true, true, false), &envelope, 1);
+    }
+
+    #[test]
+    fn test_response_roundtrip_v4() {
+        let bytes = unstructured_bytes();
+        let v3 = ExecutionPayloadV3::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap();
+        let envelope = OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V4(
+                OpExecutionPayloadV4::from_v3_with_withdrawals_root(v3, B256::repeat_byte(0xbb)),
+            ),
+            parent_beacon_block_root: Some(B256::repeat_byte(0xaa)),
+        };
+        roundtrip(&config(true, true, true), &envelope, 1);
+    }
+
+    #[test]
+    fn test_decode_op_node_ecotone_response() {
+        // The SSZ execution payload envelope that the op-node writes for Ecotone block 1000: the
+        // parent beacon block root, followed by the `ExecutionPayloadV3` with the extra data
+        // `kona`, two transactions and a single withdrawal.
+        let ssz = hex!(
+            "66666666666666666666666666666666666666666666666666666666666666661111111111111111"
+            "11111111111111111111111111111111111111111111111142000000000000000000000000000000"
+            "00000011222222222222222222222222222222222222222222222222222222222222222233333333"
+            "33333333333333333333333333333333333333333333333333333333000000000000000000000000"
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            "00000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            "000000004444444444444444444444444444444444444444444444444444444444444444e8030000"
+            "0000000080c3c901000000000852000000000000d0070000000000001002000000ca9a3b00000000"
+            "00000000000000000000000000000000000000000000000055555555555555555555555555555555"
+            "55555555555555555555555555555555140200002802000000000200000000000000000000000000"
+            "6b6f6e6108000000100000007ef8f8a0deadbeef02c0ffee07000000000000000300000000000000"
+            "420000000000000000000000000000000000001600ca9a3b00000000"
+        );
+        let mut response = vec![0, 1, 0, 0, 0];
+        let mut encoder = snap::write::FrameEncoder::new(&mut response);
+        encoder.write_all(&ssz).unwrap();
+        drop(encoder);
+
+        let cfg = config(true, true, false);
+        let envelope = decode_response(&cfg, 1000, &response).unwrap();
+        assert_eq!(envelope.parent_beacon_block_root, Some(B256::repeat_byte(0x66)));
+        let OpExecutionPayload::V3(payload) = &envelope.execution_payload else {
+            panic!("expected a V3 payload, got {:?}", envelope.execution_payload);
+        };
+        let v1 = &payload.payload_inner.payload_inner;
+        assert_eq!(v1.parent_hash, B256::repeat_byte(0x11));
+        assert_eq!(v1.fee_recipient, address!("4200000000000000000000000000000000000011"));
+        assert_eq!(v1.block_number, 1000);
+        assert_eq!(v1.gas_limit, 30_000_000);
+        assert_eq!(v1.gas_used, 21_000);
+        assert_eq!(v1.timestamp, 2000);
+        assert_eq!(v1.extra_data.as_ref(), b"kona");
+        assert_eq!(v1.base_fee_per_gas, U256::from(1_000_000_000u64));
+        assert_eq!(v1.block_hash, B256::repeat_byte(0x55));
+        assert_eq!(v1.transactions.len(), 2);
+        assert_eq!(v1.transactions[1].as_ref(), hex!("02c0ffee"));
+        let withdrawals = &payload.payload_inner.withdrawals;
+        assert_eq!(withdrawals.len(), 1);
+        assert_eq!(withdrawals[0].index, 7);
+        assert_eq!(withdrawals[0].amount, 1_000_000_000);
+        assert_eq!(payload.blob_gas_used, 131_072);
+
+        // The response is re-encoded identically.
+        assert_eq!(encode_response(&cfg, &envelope).unwrap(), response);
+
+        // Before Ecotone, a bare payload is expected.
+        assert!(matches!(
+            decode_response(&config(true, false, false), 1000, &response),
+            Err(SyncCodecError::UnexpectedVersion { expected: 0, received: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_unexpected_version() {
+        let mut encoded = encode_error_response(PayloadByNumberResult::Success).to_vec();
+        encoded[1] = 2;
+        let mut encoder = snap::write::FrameEncoder::new(&mut encoded);
+        encoder.write_all(&[0u8; 32]).unwrap();
+        drop(encoder);
+        assert!(matches!(
+            decode_response(&config(true, true, false), 0, &encoded),
+            Err(SyncCodecError::UnexpectedVersion { expected: 1, received: 2 })
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+//! Rate limits for the `payload_by_number` request/response protocol.
+
+use libp2p::PeerId;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// A token bucket rate limiter.
+///
+/// Tokens are refilled continuously at `rate` tokens per second, up to `burst` tokens.
+/// Reservations are always granted: when the bucket is empty the caller is told how long to
+/// wait before its reservation becomes available.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    /// The number of tokens refilled per second.
+    rate: f64,
+    /// The maximum number of tokens the bucket holds.
+    burst: f64,
+    /// The number of available tokens. Negative when reservations are pending.
+    tokens: f64,
+    /// The last time the bucket was refilled.
+    last: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a new full [`TokenBucket`].
+    pub const fn new(rate: f64, burst: u32, now: Instant) -> Self {
+        Self { rate, burst: burst as f64, tokens: burst as f64, last: now }
+    }
+
+    /// Reserves a single token, returning how long the caller must wait before using it.
+    pub fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.last = now.max(self.last);
+        self.tokens -= 1.0;
+
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+
+    /// Returns a token reserved with [`Self::reserve`] that was not used.
+    pub const fn cancel(&mut self) {
+        self.tokens = (self.tokens + 1.0).min(self.burst);
+    }
+}
+
+/// Rate limits `payload_by_number` requests both globally and per peer.
+#[derive(Debug, Clone)]
+pub struct SyncRateLimiter {
+    /// The global rate limit.
+    global: TokenBucket,
+    /// The per-peer rate limits.
+    peers: HashMap<PeerId, TokenBucket>,
+    /// The number of tokens refilled per second for each peer.
+    peer_rate: f64,
+    /// The maximum number of tokens each peer bucket holds.
+    peer_burst: u32,
+    /// The maximum time a request may be delayed before it is rejected.
+    max_delay: Duration,
+}
+
+impl SyncRateLimiter {
+    /// The global rate of requests served to peers, per second.
+    pub const SERVER_GLOBAL_RATE: f64 = 50.0;
+    /// The global burst of requests served to peers.
+    pub const SERVER_GLOBAL_BURST: u32 = 3;
+    /// The rate of requests served to a single peer, per second.
+    pub const SERVER_PEER_RATE: f64 = 1.0;
+    /// The burst of requests served to a single peer.
+    pub const SERVER_PEER_BURST: u32 = 2;
+    /// The global rate of requests sent to peers, per second.
+    pub const CLIENT_GLOBAL_RATE: f64 = 10.0;
+    /// The global burst of requests sent to peers.
+    pub const CLIENT_GLOBAL_BURST: u32 = 5;
+    /// The rate of requests sent to a single peer, per second.
+    pub const CLIENT_PEER_RATE: f64 = 2.0;
+    /// The burst of requests sent to a single peer.
+    pub const CLIENT_PEER_BURST: u32 = 3;
+    /// The maximum time a request may be delayed before it is rejected.
+    pub const MAX_DELAY: Duration = Duration::from_secs(20);
+
+    /// Creates a new [`SyncRateLimiter`].
+    pub fn new(
+        global_rate: f64,
+        global_burst: u32,
+        peer_rate: f64,
+        peer_burst: u32,
+        max_delay: Duration,
+    ) -> Self {
+        Self {
+            global: TokenBucket::new(global_rate, global_burst, Instant::now()),
+            peers: HashMap::new(),
+            peer_rate,
+            peer_burst,
+            max_delay,
+        }
+    }
+
+    /// Creates the [`SyncRateLimiter`] used to serve requests from peers.
+    pub fn server() -> Self {
+        Self::new(
+            Self::SERVER_GLOBAL_RATE,
+            Self::SERVER_GLOBAL_BURST,
+            Self::SERVER_PEER_RATE,
+            Self::SERVER_PEER_BURST,
+            Self::MAX_DELAY,
+        )
+    }
+
+    /// Creates the [`SyncRateLimiter`] used to send requests to peers.
+    pub fn client() -> Self {
+        Self::new(
+            Self::CLIENT_GLOBAL_RATE,
+            Self::CLIENT_GLOBAL_BURST,
+            Self::CLIENT_PEER_RATE,
+            Self::CLIENT_PEER_BURST,
+            Self::MAX_DELAY,
+        )
+    }
+
+    /// Reserves a request for the given peer.
+    ///
+    /// Returns how long the request must be delayed, or `None` if the delay exceeds the maximum
+    /// delay, in which case nothing is reserved and the request should be rejected.
+    pub fn reserve(&mut self, peer: PeerId, now: Instant) -> Option<Duration> {
+        let peer_bucket = self
+            .peers
+            .entry(peer)
+            .or_insert_with(|| TokenBucket::new(self.peer_rate, self.peer_burst, now));
+        let delay = self.global.reserve(now).max(peer_bucket.reserve(now));
+
+        if delay > self.max_delay {
+            self.global.cancel();
+            peer_bucket.cancel();
+            return None;
+        }
+        Some(delay)
+    }
+
+    /// Removes the rate limit state of a peer.
+    pub fn remove_peer(&mut self, peer: &PeerId) {
+        self.peers.remove(peer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_burst_then_delay() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, 2, now);
+
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::from_millis(500));
+        assert_eq!(bucket.reserve(now), Duration::from_secs(1));
+
+        // The bucket refills over time, paying back the pending reservations first.
+        assert_eq!(bucket.reserve(now + Duration::from_secs(1)), Duration::from_millis(500));
+        assert_eq!(bucket.reserve(now + Duration::from_secs(10)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_token_bucket_cancel() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(1.0, 1, now);
+
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::from_secs(1));
+        bucket.cancel();
+        assert_eq!(bucket.reserve(now), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_rate_limiter_per_peer() {
+        let now = Instant::now();
+        let mut limiter = SyncRateLimiter::new(100.0, 100, 1.0, 1, Duration::from_secs(2));
+        let (alice, bob) = (PeerId::random(), PeerId::random());
+
+        assert_eq!(limiter.reserve(alice, now), Some(Duration::ZERO));
+        assert_eq!(limiter.reserve(alice, now), Some(Duration::from_secs(1)));
+        assert_eq!(limiter.reserve(alice, now), Some(Duration::from_secs(2)));
+        // Exceeds the maximum delay.
+        assert_eq!(limiter.reserve(alice, now), None);
+
+        // Other peers are not affected.
+        assert_eq!(limiter.reserve(bob, now), Some(Duration::ZERO));
+
+        // Removing the peer resets its rate limit.
+        limiter.remove_peer(&alice);
+        assert_eq!(limiter.reserve(alice, now), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_rate_limiter_global() {
+        let now = Instant::now();
+        let mut limiter = SyncRateLimiter::new(1.0, 1, 100.0, 100, Duration::from_secs(1));
+
+        assert_eq!(limiter.reserve(PeerId::random(), now), Some(Duration::ZERO));
+        assert_eq!(limiter.reserve(PeerId::random(), now), Some(Duration::from_secs(1)));
+        assert_eq!(limiter.reserve(PeerId::random(), now), None);
+    }
+}
@@ -0,0 +1,36 @@
+//! The `payload_by_number` request/response protocol.
+//!
+//! Peers use this protocol to request the execution payloads of unsafe blocks they missed over
+//! gossip. See: <https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>
+//!
+//! ## Key Components
+//!
+//! - [`SyncClient`]: Requests and validates missing payloads from peers, tracking their
+//!   [`crate::ReqRespScores`].
+//! - [`PayloadRequest`]: A peer's request, served by the [`crate::GossipDriver`] from the payloads
+//!   provided by its consumer.
+//! - [`SyncRateLimiter`]: Global and per-peer rate limits applied to requests in both directions.
+
+use libp2p::StreamProtocol;
+
+mod codec;
+pub use codec::{
+    PAYLOAD_BY_NUMBER_REQUEST_SIZE, PayloadByNumberResult, SyncCodecError, decode_request,
+    decode_response, encode_error_response, encode_request, encode_response,
+};
+
+mod limiter;
+pub use limiter::{SyncRateLimiter, TokenBucket};
+
+mod server;
+pub use server::PayloadRequest;
+pub(crate) use server::serve_payload_requests;
+
+mod client;
+pub use client::{SyncClient, SyncClientError};
+
+/// Returns the `payload_by_number` [`StreamProtocol`] for the given L2 chain id.
+pub fn payload_by_number_protocol(l2_chain_id: u64) -> StreamProtocol {
+    StreamProtocol::try_from_owned(format!("/opstack/req/payload_by_number/{l2_chain_id}/0/"))
+        .expect("the protocol starts with a slash")
+}
@@ -0,0 +1,129 @@
+//! Serves `payload_by_number` requests from peers.
+
+use futures::{AsyncReadExt, AsyncWriteExt, StreamExt};
+use kona_genesis::RollupConfig;
+use libp2p::PeerId;
+use libp2p_stream::IncomingStreams;
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc, oneshot};
+
+use super::{
+    PAYLOAD_BY_NUMBER_REQUEST_SIZE, PayloadByNumberResult, SyncRateLimiter, decode_request,
+    encode_error_response, encode_response,
+};
+
+/// The maximum time to wait for a peer to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum time to wait for a requested payload to be retrieved.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A peer's request for the execution payload of the canonical L2 block at a given number.
+///
+/// Sent by the [`crate::GossipDriver`] to the consumer of
+/// [`crate::GossipDriver::payload_requests`], which answers it through the response channel.
+#[derive(Debug)]
+pub struct PayloadRequest {
+    /// The peer that sent the request.
+    pub peer: PeerId,
+    /// The number of the requested L2 block.
+    pub number: u64,
+    /// The channel to send the payload through. `None` if the payload is not available.
+    pub response: oneshot::Sender<Option<OpExecutionPayloadEnvelope>>,
+}
+
+/// Serves the inbound `payload_by_number` streams until the stream of incoming streams ends.
+///
+/// Requests are rate limited globally and per peer using the given [`SyncRateLimiter`], whose
+/// peers are removed by the [`crate::GossipDriver`] as they disconnect. Throttled requests are
+/// delayed, and requests that would be delayed for too long are dropped.
+pub(crate) async fn serve_payload_requests(
+    mut streams: IncomingStreams,
+    requests: mpsc::Sender<PayloadRequest>,
+    limiter: Arc<Mutex<SyncRateLimiter>>,
+    rollup_config: Arc<RollupConfig>,
+) {
+    loop {
+        let Some((peer, mut stream)) = streams.next().await else {
+            warn!(target: "gossip", "The sync protocol stream has ended");
+            return;
+        };
+
+        let reserved =
+            limiter.lock().ok().and_then(|mut limiter| limiter.reserve(peer, Instant::now()));
+        let Some(delay) = reserved else {
+            debug!(target: "gossip", ?peer, "Rate limit exceeded, dropping sync request");
+            kona_macros::inc!(counter, crate::Metrics::PAYLOAD_BY_NUMBER, "server_throttled");
+            continue;
+        };
+
+        let requests = requests.clone();
+        let rollup_config = rollup_config.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+
+            let mut buffer = Vec::with_capacity(PAYLOAD_BY_NUMBER_REQUEST_SIZE);
+            let read =
+                (&mut stream).take(PAYLOAD_BY_NUMBER_REQUEST_SIZE as u64).read_to_end(&mut buffer);
+            let response = match tokio::time::timeout(REQUEST_TIMEOUT, read).await {
+                Ok(Ok(_)) => match decode_request(&buffer) {
+                    Ok(number) => respond(peer, number, &requests, &rollup_config).await,
+                    Err(err) => {
+                        debug!(target: "gossip", ?peer, ?err, "Received invalid sync request");
+                        encode_error_response(PayloadByNumberResult::InvalidRequest).to_vec()
+                    }
+                },
+                Ok(Err(err)) => {
+                    debug!(target: "gossip", ?peer, ?err, "Failed to read sync request");
+                    return;
+                }
+                Err(_) => {
+                    debug!(target: "gossip", ?peer, "Timed out reading sync request");
+                    return;
+                }
+            };
+
+            if let Err(err) = stream.write_all(&response).await {
+                debug!(target: "gossip", ?peer, ?err, "Failed to write sync response");
+                return;
+            }
+            if let Err(err) = stream.close().await {
+                debug!(target: "gossip", ?peer, ?err, "Failed to close sync stream");
+            }
+            kona_macros::inc!(counter, crate::Metrics::PAYLOAD_BY_NUMBER, "server_served");
+        });
+    }
+}
+
+/// Retrieves the requested payload and encodes the response.
+async fn respond(
+    peer: PeerId,
+    number: u64,
+    requests: &mpsc::Sender<PayloadRequest>,
+    rollup_config: &RollupConfig,
+) -> Vec<u8> {
+    debug!(target: "gossip", ?peer, number, "Received sync request");
+
+    let (tx, rx) = oneshot::channel();
+    if requests.try_send(PayloadRequest { peer, number, response: tx }).is_err() {
+        debug!(target: "gossip", ?peer, number, "Sync request queue unavailable");
+        return encode_error_response(PayloadByNumberResult::Unknown).to_vec();
+    }
+
+    let payload = match tokio::time::timeout(RESPONSE_TIMEOUT, rx).await {
+        Ok(Ok(Some(payload))) => payload,
+        Ok(Ok(None)) => return encode_error_response(PayloadByNumberResult::NotFound).to_vec(),
+        Ok(Err(_)) | Err(_) => {
+            return encode_error_response(PayloadByNumberResult::Unknown).to_vec();
+        }
+    };
+
+    encode_response(rollup_config, &payload).unwrap_or_else(|err| {
+        warn!(target: "gossip", ?err, number, "Failed to encode sync response");
+        encode_error_response(PayloadByNumberResult::Unknown).to_vec()
+    })
+}
@@ -1,6 +1,7 @@
 //! Contains the RPC Configuration.
 
-use std::{net::SocketAddr, path::PathBuf};
+use kona_engine::SafeHeadDb;
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
 
 /// The RPC configuration.
 #[derive(Debug, Clone)]
@@ -18,6 +19,10 @@ pub struct RpcBuilder {
     pub ws_enabled: bool,
     /// Enable development RPC endpoints
     pub dev_enabled: bool,
+    /// The [`SafeHeadDb`] backing the `optimism_safeHeadAtL1Block` method.
+    ///
+    /// If [`None`], the method is not supported.
+    pub safe_head_db: Option<Arc<SafeHeadDb>>,
 }
 
 impl RpcBuilder {
@@ -45,4 +50,9 @@ impl RpcBuilder {
     pub fn set_addr(self, addr: SocketAddr) -> Self {
         Self { socket: addr, ..self }
     }
+
+    /// Sets the [`SafeHeadDb`] on the [`RpcBuilder`].
+    pub fn with_safe_head_db(self, safe_head_db: Option<Arc<SafeHeadDb>>) -> Self {
+        Self { safe_head_db, ..self }
+    }
 }
@@ -27,15 +27,16 @@ impl DevEngineRpc {
         Self { engine_query_sender }
     }
 
-    /// Gets an engine queue length watcher for subscriptions.
-    async fn engine_queue_length_watcher(
+    /// Gets a queue length watcher for subscriptions, using the given query.
+    async fn queue_length_watcher(
         &self,
+        query: impl FnOnce(
+            tokio::sync::oneshot::Sender<tokio::sync::watch::Receiver<usize>>,
+        ) -> EngineQueries,
     ) -> Result<tokio::sync::watch::Receiver<usize>, jsonrpsee::core::SubscriptionError> {
         let (query_tx, query_rx) = tokio::sync::oneshot::channel();
 
-        if let Err(e) =
-            self.engine_query_sender.send(EngineQueries::QueueLengthReceiver(query_tx)).await
-        {
+        if let Err(e) = self.engine_query_sender.send(query(query_tx)).await {
             tracing::warn!(target: "rpc::dev", ?e, "Failed to send engine state receiver query. The engine query handler is likely closed.");
             return Err(jsonrpsee::core::SubscriptionError::from(
                 "Internal error. Failed to send engine state receiver query. The engine query handler is likely closed.",
@@ -45,6 +46,53 @@ impl DevEngineRpc {
         query_rx.await.map_err(|_| jsonrpsee::core::SubscriptionError::from("Internal error. Failed to receive engine task receiver query. The engine query handler is likely closed."))
     }
 
+    /// Forwards the queue length updates of the watcher to the subscription sink.
+    async fn forward_queue_length_updates(
+        sink: PendingSubscriptionSink,
+        mut subscription: tokio::sync::watch::Receiver<usize>,
+    ) -> SubscriptionResult {
+        let sink = sink.accept().await?;
+
+        let mut current_queue_length = *subscription.borrow();
+
+        Self::send_queue_length_update(&sink, &current_queue_length).await?;
+
+        while let Ok(new_queue_length) = subscription
+            .wait_for(|queue_length| queue_length != &current_queue_length)
+            .await
+            .map(|state| *state)
+        {
+            Self::send_queue_length_update(&sink, &new_queue_length).await?;
+            current_queue_length = new_queue_length;
+        }
+
+        Ok(())
+    }
+
+    /// Gets a queue length using the given query.
+    async fn queue_length(
+        &self,
+        query: impl FnOnce(tokio::sync::oneshot::Sender<usize>) -> EngineQueries,
+    ) -> RpcResult<usize> {
+        let (query_tx, query_rx) = tokio::sync::oneshot::channel();
+
+        self.engine_query_sender.send(query(query_tx)).await.map_err(|_| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                ErrorCode::InternalError.code(),
+                "Engine query channel closed",
+                None::<()>,
+            )
+        })?;
+
+        query_rx.await.map_err(|_| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to receive queue length",
+                None::<()>,
+            )
+        })
+    }
+
     async fn send_queue_length_update(
         sink: &SubscriptionSink,
         queue_length: &usize,
@@ -69,46 +117,30 @@ impl DevEngineApiServer for DevEngineRpc {
         &self,
         sink: PendingSubscriptionSink,
     ) -> SubscriptionResult {
-        let sink = sink.accept().await?;
-
-        let mut subscription = self.engine_queue_length_watcher().await?;
-
-        let mut current_queue_length = *subscription.borrow();
-
-        Self::send_queue_length_update(&sink, &current_queue_length).await?;
-
-        while let Ok(new_queue_length) = subscription
-            .wait_for(|queue_length| queue_length != &current_queue_length)
-            .await
-            .map(|state| *state)
-        {
-            Self::send_queue_length_update(&sink, &new_queue_length).await?;
-            current_queue_length = new_queue_length;
-        }
+        let subscription = self.queue_length_watcher(EngineQueries::QueueLengthReceiver).await?;
+        Self::forward_queue_length_updates(sink, subscription).await?;
 
         tracing::warn!(target: "rpc::dev::engine_queue_size", "Subscription to engine queue size has been closed.");
         Ok(())
     }
 
     async fn dev_task_queue_length(&self) -> RpcResult<usize> {
-        let (query_tx, query_rx) = tokio::sync::oneshot::channel();
+        self.queue_length(EngineQueries::TaskQueueLength).await
+    }
 
-        self.engine_query_sender.send(EngineQueries::TaskQueueLength(query_tx)).await.map_err(
-            |_| {
-                jsonrpsee::types::ErrorObjectOwned::owned(
-                    ErrorCode::InternalError.code(),
-                    "Engine query channel closed",
-                    None::<()>,
-                )
-            },
-        )?;
+    async fn dev_subscribe_unsafe_payload_queue_length(
+        &self,
+        sink: PendingSubscriptionSink,
+    ) -> SubscriptionResult {
+        let subscription =
+            self.queue_length_watcher(EngineQueries::UnsafePayloadQueueLengthReceiver).await?;
+        Self::forward_queue_length_updates(sink, subscription).await?;
 
-        query_rx.await.map_err(|_| {
-            jsonrpsee::types::ErrorObjectOwned::owned(
-                ErrorCode::InternalError.code(),
-                "Failed to receive task queue length",
-                None::<()>,
-            )
-        })
+        tracing::warn!(target: "rpc::dev::unsafe_payload_queue_size", "Subscription to unsafe payload queue size has been closed.");
+        Ok(())
+    }
+
+    async fn dev_unsafe_payload_queue_length(&self) -> RpcResult<usize> {
+        self.queue_length(EngineQueries::UnsafePayloadQueueLength).await
     }
 }
@@ -145,6 +145,10 @@ pub trait Ws {
     /// Subscribes to the stream of unsafe head updates.
     #[subscription(name = "subscribe_unsafe_head", item = kona_protocol::L2BlockInfo)]
     async fn ws_unsafe_head_updates(&self) -> SubscriptionResult;
+
+    /// Subscribes to the stream of events emitted by the stages of the derivation pipeline.
+    #[subscription(name = "subscribe_derivation_events", item = kona_derive::DerivationEvent)]
+    async fn ws_derivation_events(&self) -> SubscriptionResult;
 }
 
 /// Development RPC API for engine state introspection.
@@ -159,6 +163,14 @@ pub trait DevEngineApi {
     /// Get the current number of tasks in the engine queue.
     #[method(name = "taskQueueLength")]
     async fn dev_task_queue_length(&self) -> RpcResult<usize>;
+
+    /// Subscribe to updates of the number of unsafe payloads buffered ahead of the unsafe head.
+    #[subscription(name = "subscribe_unsafe_payload_queue_size", item = usize)]
+    async fn dev_subscribe_unsafe_payload_queue_length(&self) -> SubscriptionResult;
+
+    /// Get the current number of unsafe payloads buffered ahead of the unsafe head.
+    #[method(name = "unsafePayloadQueueLength")]
+    async fn dev_unsafe_payload_queue_length(&self) -> RpcResult<usize>;
 }
 
 /// The admin namespace for the consensus node.
@@ -8,9 +8,10 @@ use jsonrpsee::{
     core::RpcResult,
     types::{ErrorCode, ErrorObject},
 };
-use kona_engine::{EngineQueries, EngineQuerySender, EngineState};
+use kona_engine::{EngineQueries, EngineQuerySender, EngineState, SafeHeadDb};
 use kona_genesis::RollupConfig;
 use kona_protocol::SyncStatus;
+use std::sync::Arc;
 
 use crate::{
     L1State, L1WatcherQueries, OutputResponse, RollupNodeApiServer, SafeHeadResponse,
@@ -26,6 +27,8 @@ pub struct RollupRpc {
     pub engine_sender: EngineQuerySender,
     /// The channel to send [`crate::L1WatcherQueries`]s.
     pub l1_watcher_sender: L1WatcherQuerySender,
+    /// The [`SafeHeadDb`] backing `optimism_safeHeadAtL1Block`, if enabled.
+    pub safe_head_db: Option<Arc<SafeHeadDb>>,
 }
 
 impl RollupRpc {
@@ -37,7 +40,43 @@ impl RollupRpc {
         engine_sender: EngineQuerySender,
         l1_watcher_sender: L1WatcherQuerySender,
     ) -> Self {
-        Self { engine_sender, l1_watcher_sender }
+        Self { engine_sender, l1_watcher_sender, safe_head_db: None }
+    }
+
+    /// Sets the [`SafeHeadDb`] backing `optimism_safeHeadAtL1Block`.
+    pub fn with_safe_head_db(self, safe_head_db: Option<Arc<SafeHeadDb>>) -> Self {
+        Self { safe_head_db, ..self }
+    }
+
+    /// Resolves the given [`BlockNumberOrTag`] to an L1 block number, using the L1 watcher's
+    /// view of the L1 chain for tags.
+    async fn resolve_l1_block_number(&self, block_num: BlockNumberOrTag) -> RpcResult<u64> {
+        let tag = match block_num {
+            BlockNumberOrTag::Number(number) => return Ok(number),
+            BlockNumberOrTag::Earliest => return Ok(0),
+            tag => tag,
+        };
+
+        let (l1_sync_status_send, l1_sync_status_recv) = tokio::sync::oneshot::channel();
+        self.l1_watcher_sender
+            .send(L1WatcherQueries::L1State(l1_sync_status_send))
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+        let l1_state =
+            l1_sync_status_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        let block = match tag {
+            BlockNumberOrTag::Safe => l1_state.safe_l1,
+            BlockNumberOrTag::Finalized => l1_state.finalized_l1,
+            _ => l1_state.head_l1,
+        };
+        block.map(|block| block.number).ok_or_else(|| {
+            ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                format!("L1 block {tag} is not known yet"),
+                None::<()>,
+            )
+        })
     }
 
     // Important note: we zero-out the fields that can't be derived yet to follow op-node's
@@ -93,14 +132,38 @@ impl RollupNodeApiServer for RollupRpc {
         Ok(OutputResponse::from_v0(output_root, sync_status, l2_block_info))
     }
 
-    /// This RPC endpoint is not supported. It is not necessary to track the safe head for every L1
-    /// block post-interop anymore so we can remove this method from the rpc interface.
+    /// Returns the L2 safe head after all data of the latest L1 block at or before the given L1
+    /// block was processed.
+    ///
+    /// This RPC endpoint is only supported if the node records safe heads in a [`SafeHeadDb`]. It
+    /// is not necessary to track the safe head for every L1 block post-interop anymore.
     async fn op_safe_head_at_l1_block(
         &self,
-        _block_num: BlockNumberOrTag,
+        block_num: BlockNumberOrTag,
     ) -> RpcResult<SafeHeadResponse> {
         kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "op_safeHeadAtL1Block");
-        return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+
+        let Some(safe_head_db) = self.safe_head_db.clone() else {
+            return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        };
+
+        let l1_number = self.resolve_l1_block_number(block_num).await?;
+        // The lookup is a binary search over the database file, so keep it off the runtime.
+        let entry = tokio::task::spawn_blocking(move || safe_head_db.safe_head_at_l1(l1_number))
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?
+            .map_err(|err| {
+                ErrorObject::owned(ErrorCode::InternalError.code(), err.to_string(), None::<()>)
+            })?;
+        let Some(entry) = entry else {
+            return Err(ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                format!("No safe head recorded at or before L1 block {l1_number}"),
+                None::<()>,
+            ));
+        };
+
+        Ok(SafeHeadResponse { l1_block: entry.l1_block, safe_head: entry.safe_head })
     }
 
     async fn op_sync_status(&self) -> RpcResult<SyncStatus> {
@@ -3,8 +3,10 @@
 use jsonrpsee::{
     PendingSubscriptionSink, SubscriptionSink, core::SubscriptionResult, tracing::warn,
 };
+use kona_derive::DerivationEvent;
 use kona_engine::{EngineQueries, EngineQuerySender, EngineState};
 use kona_protocol::L2BlockInfo;
+use tokio::sync::broadcast;
 
 use jsonrpsee::core::to_json_raw_value;
 
@@ -15,12 +17,17 @@ use crate::jsonrpsee::WsServer;
 pub struct WsRPC {
     /// The engine query sender.
     engine_query_sender: EngineQuerySender,
+    /// The sender that the derivation pipeline's events are broadcast on.
+    derivation_events: broadcast::Sender<DerivationEvent>,
 }
 
 impl WsRPC {
     /// Constructs a new [`WsRPC`] instance.
-    pub const fn new(engine_query_sender: EngineQuerySender) -> Self {
-        Self { engine_query_sender }
+    pub const fn new(
+        engine_query_sender: EngineQuerySender,
+        derivation_events: broadcast::Sender<DerivationEvent>,
+    ) -> Self {
+        Self { engine_query_sender, derivation_events }
     }
 
     async fn engine_state_watcher(
@@ -124,4 +131,36 @@ impl WsServer for WsRPC {
         warn!(target: "rpc::ws", "Subscription to unsafe head updates has been closed.");
         Ok(())
     }
+
+    async fn ws_derivation_events(&self, sink: PendingSubscriptionSink) -> SubscriptionResult {
+        let sink = sink.accept().await?;
+
+        let mut subscription = self.derivation_events.subscribe();
+
+        loop {
+            let event = match subscription.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(target: "rpc::ws", skipped, "Derivation event subscriber lagged behind.");
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            sink.send(to_json_raw_value(&event).map_err(|_| {
+                jsonrpsee::core::SubscriptionError::from(
+                    "Internal error. Impossible to convert derivation event to json",
+                )
+            })?)
+            .await
+            .map_err(|_| {
+                jsonrpsee::core::SubscriptionError::from(
+                    "Failed to send derivation event. Subscription likely dropped.",
+                )
+            })?;
+        }
+
+        warn!(target: "rpc::ws", "Subscription to derivation events has been closed.");
+        Ok(())
+    }
 }
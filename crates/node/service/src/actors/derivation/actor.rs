@@ -2,26 +2,32 @@
 
 use std::sync::Arc;
 
-use crate::{InteropMode, Metrics, NodeActor, actors::CancellableContext};
+use crate::{
+    BroadcastEventSink, CheckpointConfig, DERIVATION_EVENTS_CAPACITY, InteropMode, Metrics,
+    NodeActor, PipelineCheckpointer, actors::CancellableContext,
+};
 use alloy_provider::RootProvider;
 use async_trait::async_trait;
 use kona_derive::{
-    ActivationSignal, Pipeline, PipelineError, PipelineErrorKind, ResetError, ResetSignal, Signal,
+    ActivationSignal, DerivationEvent, DerivationEventSink, Pipeline, PipelineCheckpoint,
+    PipelineError, PipelineErrorKind, PipelineResult, ResetError, ResetSignal, Signal,
     SignalReceiver, StepResult,
 };
 use kona_genesis::RollupConfig;
+use kona_hardforks::UpgradeRegistry;
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use kona_providers_alloy::{
-    AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlineBlobProvider,
-    OnlinePipeline,
+    AlloyChainProvider, AlloyL2ChainProvider, OnlineAltDAClient, OnlineBeaconClient,
+    OnlineBlobProvider, OnlinePipeline, OnlinePipelineOptions,
 };
 use op_alloy_network::Optimism;
 use thiserror::Error;
 use tokio::{
     select,
-    sync::{mpsc, oneshot, watch},
+    sync::{broadcast, mpsc, oneshot, watch},
 };
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+use url::Url;
 
 /// The [NodeActor] for the derivation sub-routine.
 ///
@@ -62,6 +68,8 @@ where
     ///
     /// Specs: <https://specs.optimism.io/protocol/derivation.html#l1-sync-payload-attributes-processing>
     derivation_signal_rx: mpsc::Receiver<Signal>,
+    /// The sender that the pipeline's [`DerivationEvent`]s are broadcast on.
+    derivation_events_tx: broadcast::Sender<DerivationEvent>,
 }
 
 /// The state for the derivation actor.
@@ -78,6 +86,9 @@ where
     /// A flag indicating whether or not derivation is waiting for a signal. When waiting for a
     /// signal, derivation cannot process any incoming events.
     pub waiting_for_signal: bool,
+    /// The optional [`PipelineCheckpointer`], which persists checkpoints of the pipeline and
+    /// restores them on boot.
+    pub checkpointer: Option<PipelineCheckpointer>,
 }
 
 /// The size of the cache used in the derivation pipeline's providers.
@@ -89,8 +100,11 @@ pub trait PipelineBuilder: Send + Sync + 'static {
     /// The type of pipeline to build.
     type Pipeline: Pipeline + SignalReceiver + Send + Sync + 'static;
 
-    /// Builds the derivation pipeline.
-    async fn build(self) -> DerivationState<Self::Pipeline>;
+    /// Builds the derivation pipeline, whose stages emit their events to the given sink.
+    async fn build(
+        self,
+        event_sink: Arc<dyn DerivationEventSink>,
+    ) -> PipelineResult<DerivationState<Self::Pipeline>>;
 }
 
 /// The configuration necessary to build the derivation actor.
@@ -106,13 +120,23 @@ pub struct DerivationBuilder {
     pub rollup_config: Arc<RollupConfig>,
     /// The interop mode.
     pub interop_mode: InteropMode,
+    /// The optional [`CheckpointConfig`] used to persist checkpoints of the pipeline.
+    pub checkpoint_config: Option<CheckpointConfig>,
+    /// The URL of the Alt-DA server. Required if the rollup uses Alt-DA.
+    pub altda_server: Option<Url>,
+    /// The [`UpgradeRegistry`] providing the network upgrade transactions, if not the built-in
+    /// hardforks.
+    pub upgrades: Option<UpgradeRegistry>,
 }
 
 #[async_trait]
 impl PipelineBuilder for DerivationBuilder {
     type Pipeline = OnlinePipeline;
 
-    async fn build(self) -> DerivationState<OnlinePipeline> {
+    async fn build(
+        self,
+        event_sink: Arc<dyn DerivationEventSink>,
+    ) -> PipelineResult<DerivationState<OnlinePipeline>> {
         // Create the caching L1/L2 EL providers for derivation.
         let l1_derivation_provider =
             AlloyChainProvider::new(self.l1_provider.clone(), DERIVATION_PROVIDER_CACHE_SIZE);
@@ -121,6 +145,12 @@ impl PipelineBuilder for DerivationBuilder {
             self.rollup_config.clone(),
             DERIVATION_PROVIDER_CACHE_SIZE,
         );
+        let options = OnlinePipelineOptions::default()
+            .with_altda_client(
+                self.altda_server.map(|url| OnlineAltDAClient::new_http(url.to_string())),
+            )
+            .with_upgrades(self.upgrades)
+            .with_event_sink(event_sink);
 
         let pipeline = match self.interop_mode {
             InteropMode::Polled => OnlinePipeline::new_polled(
@@ -128,16 +158,23 @@ impl PipelineBuilder for DerivationBuilder {
                 OnlineBlobProvider::init(self.l1_beacon.clone()).await,
                 l1_derivation_provider,
                 l2_derivation_provider,
-            ),
+                options,
+            )?,
             InteropMode::Indexed => OnlinePipeline::new_indexed(
                 self.rollup_config.clone(),
                 OnlineBlobProvider::init(self.l1_beacon.clone()).await,
                 l1_derivation_provider,
                 l2_derivation_provider,
-            ),
+                options,
+            )?,
         };
 
-        DerivationState::new(pipeline)
+        let mut state = DerivationState::new(pipeline);
+        if let Some(config) = self.checkpoint_config {
+            state.checkpointer =
+                Some(PipelineCheckpointer::load(config, self.l1_provider, self.l2_provider).await);
+        }
+        Ok(state)
     }
 }
 
@@ -157,6 +194,10 @@ pub struct DerivationInboundChannels {
     /// This channel should be used by the engine actor to send [`Signal`]s to the derivation
     /// pipeline. The signals are received by `DerivationActor::derivation_signal_rx`.
     pub derivation_signal_tx: mpsc::Sender<Signal>,
+    /// The sender that the pipeline's [`DerivationEvent`]s are broadcast on.
+    ///
+    /// Other actors subscribe to this channel to observe the pipeline's stages.
+    pub derivation_events_tx: broadcast::Sender<DerivationEvent>,
 }
 
 /// The communication context used by the derivation actor.
@@ -167,8 +208,8 @@ pub struct DerivationContext {
     /// Sends the derived [`OpAttributesWithParent`]s produced by the actor.
     pub derived_attributes_tx: mpsc::Sender<OpAttributesWithParent>,
     /// The reset request sender, used to handle [`PipelineErrorKind::Reset`] events and forward
-    /// them to the engine.
-    pub reset_request_tx: mpsc::Sender<()>,
+    /// them to the engine, along with the reorged L1 block if the reset follows an L1 reorg.
+    pub reset_request_tx: mpsc::Sender<Option<BlockInfo>>,
 }
 
 impl CancellableContext for DerivationContext {
@@ -183,13 +224,20 @@ where
 {
     /// Creates a new instance of the [DerivationState].
     pub const fn new(pipeline: P) -> Self {
-        Self { pipeline, derivation_idle: true, waiting_for_signal: false }
+        Self { pipeline, derivation_idle: true, waiting_for_signal: false, checkpointer: None }
     }
 
     /// Handles a [`Signal`] received over the derivation signal receiver channel.
+    ///
+    /// On the first [`Signal::Reset`] after boot, the pipeline is restored from the persisted
+    /// [`PipelineCheckpoint`] instead, if it still matches the canonical chain.
     async fn signal(&mut self, signal: Signal) {
-        if let Signal::Reset(ResetSignal { l1_origin, .. }) = signal {
+        if let Signal::Reset(reset @ ResetSignal { l1_origin, .. }) = signal {
             kona_macros::set!(counter, Metrics::DERIVATION_L1_ORIGIN, l1_origin.number);
+
+            if self.restore_checkpoint(&reset).await {
+                return;
+            }
         }
 
         match self.pipeline.signal(signal).await {
@@ -200,12 +248,64 @@ where
         }
     }
 
+    /// Attempts to restore the pipeline from the checkpoint loaded on boot, in place of the given
+    /// reset. Returns `true` if the pipeline was restored.
+    async fn restore_checkpoint(&mut self, reset: &ResetSignal) -> bool {
+        let Some(checkpointer) = self.checkpointer.as_mut() else {
+            return false;
+        };
+        let Some(checkpoint) = checkpointer.take_restorable(reset).await else {
+            return false;
+        };
+
+        match self.pipeline.restore(&checkpoint).await {
+            Ok(_) => {
+                kona_macros::set!(
+                    counter,
+                    Metrics::DERIVATION_L1_ORIGIN,
+                    checkpoint.l1_origin.number
+                );
+                info!(
+                    target: "derivation",
+                    l1_origin = checkpoint.l1_origin.number,
+                    l2_safe_head = checkpoint.l2_safe_head.block_info.number,
+                    "Restored derivation pipeline from checkpoint"
+                );
+                true
+            }
+            Err(e) => {
+                warn!(target: "derivation", ?e, "Failed to restore pipeline checkpoint, resetting");
+                false
+            }
+        }
+    }
+
+    /// Takes a [`PipelineCheckpoint`] of the pipeline and persists it, if the configured interval
+    /// has passed since the last persisted checkpoint.
+    async fn persist_checkpoint(&mut self, origin: u64, l2_safe_head: L2BlockInfo) {
+        let Some(checkpointer) = self.checkpointer.as_mut() else {
+            return;
+        };
+        if !checkpointer.should_persist(origin) {
+            return;
+        }
+
+        let mut checkpoint = PipelineCheckpoint::new(l2_safe_head);
+        if let Err(e) = self.pipeline.checkpoint(&mut checkpoint) {
+            debug!(target: "derivation", ?e, "Skipping pipeline checkpoint");
+            return;
+        }
+        if let Err(e) = checkpointer.persist(&checkpoint).await {
+            warn!(target: "derivation", ?e, "Failed to persist pipeline checkpoint");
+        }
+    }
+
     /// Attempts to step the derivation pipeline forward as much as possible in order to produce the
     /// next safe payload.
     async fn produce_next_attributes(
         &mut self,
         engine_l2_safe_head: &watch::Receiver<L2BlockInfo>,
-        reset_request_tx: &mpsc::Sender<()>,
+        reset_request_tx: &mpsc::Sender<Option<BlockInfo>>,
     ) -> Result<OpAttributesWithParent, DerivationError> {
         // As we start the safe head at the disputed block's parent, we step the pipeline until the
         // first attributes are produced. All batches at and before the safe head will be
@@ -220,6 +320,8 @@ where
 
                     kona_macros::set!(counter, Metrics::DERIVATION_L1_ORIGIN, origin);
                     debug!(target: "derivation", l1_block = origin, "Advanced L1 origin");
+
+                    self.persist_checkpoint(origin, l2_safe_head).await;
                 }
                 StepResult::OriginAdvanceErr(e) | StepResult::StepFailed(e) => {
                     match e {
@@ -261,6 +363,7 @@ where
                                     )
                                     .await?;
                             } else {
+                                let mut reorged = None;
                                 if let ResetError::ReorgDetected(expected, new) = e {
                                     warn!(
                                         target: "derivation",
@@ -268,6 +371,8 @@ where
                                     );
 
                                     kona_macros::inc!(counter, Metrics::L1_REORG_COUNT);
+                                    // The current origin no longer links to the L1 chain.
+                                    reorged = self.pipeline.origin();
                                 }
                                 // send the `reset` signal to the engine actor only when interop is
                                 // not active.
@@ -276,7 +381,7 @@ where
                                     .rollup_config()
                                     .is_interop_active(l2_safe_head.block_info.timestamp)
                                 {
-                                    reset_request_tx.send(()).await.map_err(|e| {
+                                    reset_request_tx.send(reorged).await.map_err(|e| {
                                         error!(target: "derivation", ?e, "Failed to send reset request");
                                         DerivationError::Sender(Box::new(e))
                                     })?;
@@ -319,7 +424,7 @@ where
         engine_l2_safe_head: &mut watch::Receiver<L2BlockInfo>,
         el_sync_complete_rx: &oneshot::Receiver<()>,
         derived_attributes_tx: &mpsc::Sender<OpAttributesWithParent>,
-        reset_request_tx: &mpsc::Sender<()>,
+        reset_request_tx: &mpsc::Sender<Option<BlockInfo>>,
     ) -> Result<(), DerivationError> {
         // Only attempt derivation once the engine finishes syncing.
         if !el_sync_complete_rx.is_terminated() {
@@ -396,12 +501,14 @@ where
             watch::channel(L2BlockInfo::default());
         let (el_sync_complete_tx, el_sync_complete_rx) = oneshot::channel();
         let (derivation_signal_tx, derivation_signal_rx) = mpsc::channel(16);
+        let (derivation_events_tx, _) = broadcast::channel(DERIVATION_EVENTS_CAPACITY);
         let actor = Self {
             state,
             l1_head_updates: l1_head_updates_rx,
             engine_l2_safe_head: engine_l2_safe_head_rx,
             el_sync_complete_rx,
             derivation_signal_rx,
+            derivation_events_tx: derivation_events_tx.clone(),
         };
 
         (
@@ -410,6 +517,7 @@ where
                 engine_l2_safe_head_tx,
                 el_sync_complete_tx,
                 derivation_signal_tx,
+                derivation_events_tx,
             },
             actor,
         )
@@ -438,7 +546,8 @@ where
             cancellation,
         }: Self::OutboundData,
     ) -> Result<(), Self::Error> {
-        let mut state = self.state.build().await;
+        let event_sink = Arc::new(BroadcastEventSink::new(self.derivation_events_tx.clone()));
+        let mut state = self.state.build(event_sink).await?;
 
         loop {
             select! {
@@ -0,0 +1,260 @@
+//! Persistence of [`PipelineCheckpoint`]s for the [`DerivationActor`].
+//!
+//! [`DerivationActor`]: super::DerivationActor
+
+use alloy_provider::{Provider, RootProvider};
+use alloy_transport::{RpcError, TransportErrorKind};
+use kona_derive::{PipelineCheckpoint, ResetSignal};
+use kona_protocol::BlockInfo;
+use op_alloy_network::Optimism;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// The default number of L1 blocks between persisted [`PipelineCheckpoint`]s.
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 32;
+
+/// The configuration for persisting [`PipelineCheckpoint`]s of the derivation pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointConfig {
+    /// The path of the file that the latest checkpoint is persisted to.
+    pub path: PathBuf,
+    /// The minimum number of L1 blocks between persisted checkpoints.
+    pub interval: u64,
+}
+
+impl CheckpointConfig {
+    /// Creates a new [`CheckpointConfig`] with the [`DEFAULT_CHECKPOINT_INTERVAL`].
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path, interval: DEFAULT_CHECKPOINT_INTERVAL }
+    }
+}
+
+/// An error from the [`PipelineCheckpointer`].
+#[derive(Debug, Error)]
+pub enum CheckpointError {
+    /// Failed to read or write the checkpoint file.
+    #[error("Checkpoint file error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Failed to encode or decode the checkpoint.
+    #[error("Checkpoint encoding error: {0}")]
+    Json(#[from] serde_json::Error),
+    /// Failed to fetch a block to check the checkpoint against.
+    #[error("Provider error: {0}")]
+    Provider(#[from] RpcError<TransportErrorKind>),
+}
+
+/// Persists [`PipelineCheckpoint`]s of the derivation pipeline, and hands out the checkpoint
+/// persisted by the previous run of the node if it can be restored.
+///
+/// A persisted checkpoint can only be restored once, on the first reset of the pipeline after
+/// boot, and only if it still matches the canonical L1 and L2 chains.
+#[derive(Debug)]
+pub struct PipelineCheckpointer {
+    /// The checkpoint configuration.
+    config: CheckpointConfig,
+    /// The L1 provider, used to check that the checkpoint's L1 origin is canonical.
+    l1_provider: RootProvider,
+    /// The L2 provider, used to check that the checkpoint's L2 safe head is canonical.
+    l2_provider: RootProvider<Optimism>,
+    /// The checkpoint loaded on boot, if it has not been restored or discarded yet.
+    pending: Option<PipelineCheckpoint>,
+    /// The L1 origin number of the last persisted checkpoint.
+    last_persisted: Option<u64>,
+}
+
+impl PipelineCheckpointer {
+    /// Creates a new [`PipelineCheckpointer`], loading the checkpoint persisted at the configured
+    /// path, if any.
+    pub async fn load(
+        config: CheckpointConfig,
+        l1_provider: RootProvider,
+        l2_provider: RootProvider<Optimism>,
+    ) -> Self {
+        let pending = match Self::read(&config.path).await {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => {
+                warn!(
+                    target: "derivation",
+                    ?e,
+                    path = ?config.path,
+                    "Failed to load pipeline checkpoint"
+                );
+                None
+            }
+        };
+        if let Some(checkpoint) = pending.as_ref() {
+            info!(
+                target: "derivation",
+                l1_origin = checkpoint.l1_origin.number,
+                l2_safe_head = checkpoint.l2_safe_head.block_info.number,
+                "Loaded pipeline checkpoint"
+            );
+        }
+
+        Self { config, l1_provider, l2_provider, pending, last_persisted: None }
+    }
+
+    /// Reads the checkpoint at the given path, returning [`None`] if the file does not exist.
+    async fn read(path: &Path) -> Result<Option<PipelineCheckpoint>, CheckpointError> {
+        match tokio::fs::read(path).await {
+            Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns `true` if a checkpoint should be persisted at the given L1 origin.
+    pub fn should_persist(&self, origin: u64) -> bool {
+        self.last_persisted.is_none_or(|last| origin >= last.saturating_add(self.config.interval))
+    }
+
+    /// Persists the checkpoint, replacing the previously persisted checkpoint.
+    pub async fn persist(
+        &mut self,
+        checkpoint: &PipelineCheckpoint,
+    ) -> Result<(), CheckpointError> {
+        // Write to a temporary file first, so that a crash never leaves a partial checkpoint.
+        let tmp = self.config.path.with_extension("tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec(checkpoint)?).await?;
+        tokio::fs::rename(&tmp, &self.config.path).await?;
+
+        self.last_persisted = Some(checkpoint.l1_origin.number);
+        debug!(
+            target: "derivation",
+            l1_origin = checkpoint.l1_origin.number,
+            l2_safe_head = checkpoint.l2_safe_head.block_info.number,
+            "Persisted pipeline checkpoint"
+        );
+        Ok(())
+    }
+
+    /// Takes the checkpoint loaded on boot, if it can be restored in place of the given reset.
+    ///
+    /// The checkpoint can be restored if:
+    /// - Its L2 safe head is at or behind the reset's L2 safe head, so that no batches for blocks
+    ///   past the reset's L2 safe head have been consumed.
+    /// - Its L1 origin is at or ahead of the reset's L1 origin. Otherwise, it brings no benefit.
+    /// - Its L1 origin and L2 safe head are still part of the canonical chains.
+    pub async fn take_restorable(&mut self, reset: &ResetSignal) -> Option<PipelineCheckpoint> {
+        let checkpoint = self.pending.take()?;
+
+        if checkpoint.l2_safe_head.block_info.number > reset.l2_safe_head.block_info.number ||
+            checkpoint.l1_origin.number < reset.l1_origin.number
+        {
+            info!(
+                target: "derivation",
+                l1_origin = checkpoint.l1_origin.number,
+                l2_safe_head = checkpoint.l2_safe_head.block_info.number,
+                "Pipeline checkpoint is out of range of the reset, discarding"
+            );
+            return None;
+        }
+
+        match self.is_canonical(&checkpoint).await {
+            Ok(true) => Some(checkpoint),
+            Ok(false) => {
+                info!(
+                    target: "derivation",
+                    l1_origin = checkpoint.l1_origin.number,
+                    "Pipeline checkpoint is no longer canonical, discarding"
+                );
+                None
+            }
+            Err(e) => {
+                warn!(target: "derivation", ?e, "Failed to check pipeline checkpoint, discarding");
+                None
+            }
+        }
+    }
+
+    /// Returns `true` if the checkpoint's L1 origin and L2 safe head are canonical.
+    async fn is_canonical(&self, checkpoint: &PipelineCheckpoint) -> Result<bool, CheckpointError> {
+        let l1_origin: Option<BlockInfo> = self
+            .l1_provider
+            .get_block_by_number(checkpoint.l1_origin.number.into())
+            .await?
+            .map(Into::into);
+        if l1_origin.is_none_or(|block| block.hash != checkpoint.l1_origin.hash) {
+            return Ok(false);
+        }
+
+        let l2_safe_head: Option<BlockInfo> = self
+            .l2_provider
+            .get_block_by_number(checkpoint.l2_safe_head.block_info.number.into())
+            .await?
+            .map(Into::into);
+        Ok(l2_safe_head.is_some_and(|block| block.hash == checkpoint.l2_safe_head.block_info.hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_protocol::L2BlockInfo;
+
+    fn new_checkpointer(
+        path: PathBuf,
+        pending: Option<PipelineCheckpoint>,
+    ) -> PipelineCheckpointer {
+        let url: url::Url = "http://127.0.0.1:1".parse().unwrap();
+        PipelineCheckpointer {
+            config: CheckpointConfig { path, interval: 10 },
+            l1_provider: RootProvider::new_http(url.clone()),
+            l2_provider: RootProvider::new_http(url),
+            pending,
+            last_persisted: None,
+        }
+    }
+
+    fn checkpoint(l1_origin: u64, l2_safe_head: u64) -> PipelineCheckpoint {
+        PipelineCheckpoint {
+            l1_origin: BlockInfo { number: l1_origin, ..Default::default() },
+            l2_safe_head: L2BlockInfo {
+                block_info: BlockInfo { number: l2_safe_head, ..Default::default() },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_load_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        let mut checkpointer = new_checkpointer(path.clone(), None);
+
+        assert!(checkpointer.should_persist(100));
+        let expected = checkpoint(100, 50);
+        checkpointer.persist(&expected).await.unwrap();
+        assert!(!checkpointer.should_persist(109));
+        assert!(checkpointer.should_persist(110));
+
+        let loaded = PipelineCheckpointer::load(
+            CheckpointConfig::new(path),
+            checkpointer.l1_provider.clone(),
+            checkpointer.l2_provider.clone(),
+        )
+        .await;
+        assert_eq!(loaded.pending, Some(expected));
+    }
+
+    #[tokio::test]
+    async fn test_take_restorable_out_of_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+
+        // The checkpoint's L2 safe head is ahead of the reset's L2 safe head.
+        let mut checkpointer = new_checkpointer(path.clone(), Some(checkpoint(100, 50)));
+        let reset = ResetSignal {
+            l2_safe_head: checkpoint(0, 40).l2_safe_head,
+            l1_origin: BlockInfo { number: 90, ..Default::default() },
+            system_config: None,
+        };
+        assert!(checkpointer.take_restorable(&reset).await.is_none());
+        assert!(checkpointer.pending.is_none());
+
+        // The checkpoint's L1 origin is behind the reset's L1 origin.
+        let mut checkpointer = new_checkpointer(path, Some(checkpoint(80, 30)));
+        assert!(checkpointer.take_restorable(&reset).await.is_none());
+    }
+}
@@ -0,0 +1,52 @@
+//! Contains the [`BroadcastEventSink`], which forwards derivation events to subscribers.
+
+use kona_derive::{DerivationEvent, DerivationEventSink};
+use tokio::sync::broadcast;
+
+/// The capacity of the derivation event channel. Subscribers that fall further behind than this
+/// miss the oldest events.
+pub const DERIVATION_EVENTS_CAPACITY: usize = 1024;
+
+/// A [`DerivationEventSink`] that broadcasts every [`DerivationEvent`] to the subscribers of a
+/// [`broadcast`] channel.
+#[derive(Debug, Clone)]
+pub struct BroadcastEventSink {
+    /// The sender of the broadcast channel.
+    sender: broadcast::Sender<DerivationEvent>,
+}
+
+impl BroadcastEventSink {
+    /// Creates a new [`BroadcastEventSink`] from the given sender.
+    pub const fn new(sender: broadcast::Sender<DerivationEvent>) -> Self {
+        Self { sender }
+    }
+}
+
+impl DerivationEventSink for BroadcastEventSink {
+    fn emit(&self, event: DerivationEvent) {
+        // Sending only fails when there are no subscribers, in which case the event is dropped.
+        let _ = self.sender.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_event_sink() {
+        let (sender, mut receiver) = broadcast::channel(DERIVATION_EVENTS_CAPACITY);
+        let sink = BroadcastEventSink::new(sender);
+
+        let event = DerivationEvent::InvalidFrameData { origin: 1 };
+        sink.emit(event.clone());
+        assert_eq!(receiver.try_recv().unwrap(), event);
+    }
+
+    #[test]
+    fn test_broadcast_event_sink_no_subscribers() {
+        let (sender, receiver) = broadcast::channel(DERIVATION_EVENTS_CAPACITY);
+        drop(receiver);
+        BroadcastEventSink::new(sender).emit(DerivationEvent::InvalidFrameData { origin: 1 });
+    }
+}
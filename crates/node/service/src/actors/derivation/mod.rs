@@ -0,0 +1,15 @@
+//! The `DerivationActor` and its components.
+
+mod checkpoint;
+pub use checkpoint::{
+    CheckpointConfig, CheckpointError, DEFAULT_CHECKPOINT_INTERVAL, PipelineCheckpointer,
+};
+
+mod events;
+pub use events::{BroadcastEventSink, DERIVATION_EVENTS_CAPACITY};
+
+mod actor;
+pub use actor::{
+    DerivationActor, DerivationBuilder, DerivationContext, DerivationError,
+    DerivationInboundChannels, DerivationState, InboundDerivationMessage, PipelineBuilder,
+};
@@ -1,14 +1,14 @@
 //! The [`EngineActor`].
 
-use super::{EngineError, L2Finalizer};
+use super::{EngineError, L2Finalizer, SafeHeadRecorder, UnsafePayloadQueue};
 use alloy_rpc_types_engine::JwtSecret;
 use async_trait::async_trait;
 use futures::future::OptionFuture;
 use kona_derive::{ResetSignal, Signal};
 use kona_engine::{
-    BuildTask, ConsolidateTask, Engine, EngineClient, EngineQueries,
+    BuildTask, ConsolidateTask, Engine, EngineClient, EngineEndpoint, EngineQueries,
     EngineState as InnerEngineState, EngineTask, EngineTaskError, EngineTaskErrorSeverity,
-    InsertTask,
+    InsertTask, SafeHeadDb,
 };
 use kona_genesis::RollupConfig;
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
@@ -17,11 +17,12 @@ use std::sync::Arc;
 use tokio::{
     sync::{mpsc, oneshot, watch},
     task::JoinHandle,
+    time::Instant,
 };
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 use url::Url;
 
-use crate::{NodeActor, NodeMode, actors::CancellableContext};
+use crate::{NodeActor, NodeMode, SyncMode, actors::CancellableContext};
 
 /// The [`EngineActor`] is responsible for managing the operations sent to the execution layer's
 /// Engine API. To accomplish this, it uses the [`Engine`] task queue to order Engine API
@@ -34,8 +35,9 @@ pub struct EngineActor {
     attributes_rx: mpsc::Receiver<OpAttributesWithParent>,
     /// A channel to receive [`OpExecutionPayloadEnvelope`] from the network actor.
     unsafe_block_rx: mpsc::Receiver<OpExecutionPayloadEnvelope>,
-    /// A channel to receive reset requests.
-    reset_request_rx: mpsc::Receiver<()>,
+    /// A channel to receive reset requests, with the reorged L1 block if the reset follows an L1
+    /// reorg.
+    reset_request_rx: mpsc::Receiver<Option<BlockInfo>>,
     /// Handler for inbound queries to the engine.
     inbound_queries: mpsc::Receiver<EngineQueries>,
     /// A channel to receive build requests from the sequencer actor.
@@ -69,8 +71,9 @@ pub struct EngineInboundData {
     /// trigger [`BuildTask`] tasks which should insert the block newly built to the engine
     /// state upon completion.
     pub unsafe_block_tx: mpsc::Sender<OpExecutionPayloadEnvelope>,
-    /// A channel to send reset requests.
-    pub reset_request_tx: mpsc::Sender<()>,
+    /// A channel to send reset requests, with the reorged L1 block if the reset follows an L1
+    /// reorg.
+    pub reset_request_tx: mpsc::Sender<Option<BlockInfo>>,
     /// Handler to send inbound queries to the engine.
     pub inbound_queries_tx: mpsc::Sender<EngineQueries>,
     /// A channel that sends new finalized L1 blocks intermittently.
@@ -84,6 +87,12 @@ pub struct EngineBuilder {
     pub config: Arc<RollupConfig>,
     /// The engine rpc url.
     pub engine_url: Url,
+    /// The engine endpoints of secondary execution clients, each with its own jwt secret.
+    /// When the primary execution client fails, they are promoted to primary in order.
+    pub failover_engines: Vec<EngineEndpoint>,
+    /// Whether new payloads are mirrored to all execution clients, so that secondaries can be
+    /// promoted without syncing.
+    pub mirror_new_payload: bool,
     /// The L1 rpc url.
     pub l1_rpc_url: Url,
     /// The engine jwt secret.
@@ -92,6 +101,14 @@ pub struct EngineBuilder {
     /// When the node is in sequencer mode, the engine actor will receive requests to build blocks
     /// from the sequencer actor.
     pub mode: NodeMode,
+    /// The [`SyncMode`] of the node.
+    /// In consensus-layer sync mode, derivation starts without waiting for the EL to sync, and
+    /// only unsafe blocks that extend the current unsafe head are inserted.
+    pub sync_mode: SyncMode,
+    /// The [`SafeHeadDb`] that the L1 block each safe head was derived from is recorded in.
+    ///
+    /// If [`None`], safe heads are not recorded.
+    pub safe_head_db: Option<Arc<SafeHeadDb>>,
 }
 
 impl EngineBuilder {
@@ -99,7 +116,12 @@ impl EngineBuilder {
     /// updates.
     fn build_state(self) -> EngineActorState {
         let client = self.client();
-        let state = InnerEngineState::default();
+        // In consensus-layer sync mode, the EL is not relied on to sync itself, so it is
+        // considered synced right away, and the initial reset starts derivation from its head.
+        let state = InnerEngineState {
+            el_sync_finished: self.sync_mode.is_consensus_layer(),
+            ..Default::default()
+        };
         let (engine_state_send, _) = tokio::sync::watch::channel(state);
         let (engine_queue_length_send, _) = tokio::sync::watch::channel(0);
 
@@ -107,18 +129,21 @@ impl EngineBuilder {
             rollup: self.config,
             client,
             engine: Engine::new(state, engine_state_send, engine_queue_length_send),
+            safe_heads: self.safe_head_db.map(SafeHeadRecorder::new),
+            sync_mode: self.sync_mode,
+            unsafe_payloads: UnsafePayloadQueue::new(UnsafePayloadQueue::DEFAULT_CAPACITY),
         }
     }
 
     /// Returns the [`EngineClient`].
     pub fn client(&self) -> Arc<EngineClient> {
-        EngineClient::new_http(
-            self.engine_url.clone(),
-            self.l1_rpc_url.clone(),
-            self.config.clone(),
-            self.jwt_secret,
-        )
-        .into()
+        let engines =
+            std::iter::once(EngineEndpoint::new(self.engine_url.clone(), self.jwt_secret))
+                .chain(self.failover_engines.iter().cloned())
+                .collect();
+        EngineClient::new_http_failover(engines, self.l1_rpc_url.clone(), self.config.clone())
+            .with_new_payload_mirroring(self.mirror_new_payload)
+            .into()
     }
 }
 
@@ -131,6 +156,12 @@ pub(super) struct EngineActorState {
     pub(super) client: Arc<EngineClient>,
     /// The [`Engine`] task queue.
     pub(super) engine: Engine,
+    /// The [`SafeHeadRecorder`], if safe heads are recorded.
+    pub(super) safe_heads: Option<SafeHeadRecorder>,
+    /// The [`SyncMode`] of the node.
+    pub(super) sync_mode: SyncMode,
+    /// The [`UnsafePayloadQueue`] buffering unsafe payloads received ahead of the unsafe head.
+    pub(super) unsafe_payloads: UnsafePayloadQueue,
 }
 
 /// The communication context used by the engine actor.
@@ -205,6 +236,7 @@ impl EngineActorState {
     ) -> JoinHandle<()> {
         let state_recv = self.engine.state_subscribe();
         let queue_length_recv = self.engine.queue_length_subscribe();
+        let unsafe_payloads_recv = self.unsafe_payloads.depth_subscribe();
         let engine_client = self.client.clone();
         let rollup_config = self.rollup.clone();
 
@@ -214,7 +246,13 @@ impl EngineActorState {
                     trace!(target: "engine", ?req, "Received engine query request.");
 
                     if let Err(e) = req
-                        .handle(&state_recv, &queue_length_recv, &engine_client, &rollup_config)
+                        .handle(
+                            &state_recv,
+                            &queue_length_recv,
+                            &unsafe_payloads_recv,
+                            &engine_client,
+                            &rollup_config,
+                        )
                         .await
                     {
                         warn!(target: "engine", err = ?e, "Failed to handle engine query request.");
@@ -224,6 +262,23 @@ impl EngineActorState {
         })
     }
 
+    /// Starts a task to periodically health check the execution clients, if the [`EngineClient`]
+    /// is backed by several.
+    fn start_health_check_task(&self) -> Option<JoinHandle<()>> {
+        if self.client.backend_count() < 2 {
+            return None;
+        }
+
+        let client = self.client.clone();
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(EngineClient::HEALTH_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                client.check_health().await;
+            }
+        }))
+    }
+
     /// Resets the inner [`Engine`] and propagates the reset to the derivation actor.
     pub(super) async fn reset(
         &mut self,
@@ -235,6 +290,14 @@ impl EngineActorState {
         let (l2_safe_head, l1_origin, system_config) =
             self.engine.reset(self.client.clone(), self.rollup.clone()).await?;
 
+        // Drop the recorded safe heads past the reset safe head.
+        if let Some(safe_heads) = self.safe_heads.as_mut() {
+            safe_heads.safe_head_reset(&l2_safe_head);
+        }
+
+        // Drop the buffered unsafe payloads, which may not extend the reset unsafe head.
+        self.unsafe_payloads.clear();
+
         // Signal the derivation actor to reset.
         let signal = ResetSignal { l2_safe_head, l1_origin, system_config: Some(system_config) };
         match derivation_signal_tx.send(signal.signal()).await {
@@ -339,8 +402,9 @@ impl EngineActorState {
         Ok(())
     }
 
-    /// Attempts to update the safe head via the watch channel.
-    fn maybe_update_safe_head(&self, engine_l2_safe_head_tx: &watch::Sender<L2BlockInfo>) {
+    /// Attempts to update the safe head via the watch channel, recording the new safe head if it
+    /// was updated.
+    fn maybe_update_safe_head(&mut self, engine_l2_safe_head_tx: &watch::Sender<L2BlockInfo>) {
         let state_safe_head = self.engine.state().sync_state.safe_head();
         let update = |head: &mut L2BlockInfo| {
             if head != &state_safe_head {
@@ -351,6 +415,12 @@ impl EngineActorState {
         };
         let sent = engine_l2_safe_head_tx.send_if_modified(update);
         trace!(target: "engine", ?sent, "Attempted L2 Safe Head Update");
+
+        if sent {
+            if let Some(safe_heads) = self.safe_heads.as_mut() {
+                safe_heads.safe_head_updated(&state_safe_head);
+            }
+        }
     }
 }
 
@@ -380,6 +450,10 @@ impl NodeActor for EngineActor {
         // Start the engine query server in a separate task to avoid blocking the main task.
         let handle = state.start_query_task(self.inbound_queries);
 
+        // Health check the execution clients in the background, failing over if the primary
+        // becomes unhealthy.
+        let health_check_handle = state.start_health_check_task();
+
         // The sync complete tx is consumed after the first successful send. Hence we need to wrap
         // it in an `Option` to ensure we satisfy the borrow checker.
         let mut sync_complete_tx = Some(sync_complete_tx);
@@ -403,6 +477,23 @@ impl NodeActor for EngineActor {
                 });
             }
 
+            // Insert the next buffered unsafe payload if it extends the unsafe head. Payloads are
+            // enqueued one at a time, as the engine task queue does not order insert tasks
+            // relative to each other.
+            let unsafe_head = state.engine.state().sync_state.unsafe_head();
+            if let Some(envelope) = state.unsafe_payloads.pop_next(&unsafe_head) {
+                let task = EngineTask::Insert(InsertTask::new(
+                    state.client.clone(),
+                    state.rollup.clone(),
+                    envelope,
+                    false, // The payload is not derived in this case. This is an unsafe block.
+                ));
+                state.engine.enqueue(task);
+                continue;
+            }
+            let unsafe_payloads_stale =
+                OptionFuture::from(state.unsafe_payloads.stale_at().map(tokio::time::sleep_until));
+
             tokio::select! {
                 biased;
 
@@ -410,16 +501,25 @@ impl NodeActor for EngineActor {
                     warn!(target: "engine", "EngineActor received shutdown signal. Aborting engine query task.");
 
                     handle.abort();
+                    if let Some(health_check_handle) = health_check_handle {
+                        health_check_handle.abort();
+                    }
 
                     return Ok(());
                 }
                 reset = self.reset_request_rx.recv() => {
-                    if reset.is_none() {
+                    let Some(reorged) = reset else {
                         error!(target: "engine", "Reset request receiver closed unexpectedly");
                         cancellation.cancel();
                         return Err(EngineError::ChannelClosed);
+                    };
+                    warn!(target: "engine", ?reorged, "Received reset request");
+                    // Drop the recorded safe heads derived from reorged L1 blocks.
+                    if let Some(reorged) = reorged {
+                        if let Some(safe_heads) = state.safe_heads.as_mut() {
+                            safe_heads.l1_reorg(&reorged);
+                        }
                     }
-                    warn!(target: "engine", "Received reset request");
                     state
                         .reset(&derivation_signal_tx, &engine_l2_safe_head_tx, &mut self.finalizer)
                         .await?;
@@ -447,6 +547,65 @@ impl NodeActor for EngineActor {
                         cancellation.cancel();
                         return Err(EngineError::ChannelClosed);
                     };
+
+                    let unsafe_head = state.engine.state().sync_state.unsafe_head().block_info;
+                    let number = envelope.execution_payload.block_number();
+                    if state.engine.state().el_sync_finished &&
+                        envelope.execution_payload.parent_hash() != unsafe_head.hash
+                    {
+                        // Unsafe blocks received ahead of the unsafe head are buffered until the
+                        // gap closes, rather than inserted right away, which would trigger EL
+                        // sync.
+                        let ahead = number.saturating_sub(unsafe_head.number);
+                        if ahead > 1 && ahead <= state.unsafe_payloads.capacity() as u64 {
+                            trace!(
+                                target: "engine",
+                                number,
+                                unsafe_head = unsafe_head.number,
+                                "Buffering unsafe block received ahead of the unsafe head"
+                            );
+                            state.unsafe_payloads.push(envelope);
+                            continue;
+                        }
+
+                        // In consensus-layer sync mode, other unsafe blocks that don't extend the
+                        // unsafe head are dropped, as inserting them would trigger EL sync.
+                        if state.sync_mode.is_consensus_layer() {
+                            debug!(
+                                target: "engine",
+                                number,
+                                unsafe_head = unsafe_head.number,
+                                "Dropping unsafe block that does not extend the unsafe head"
+                            );
+                            continue;
+                        }
+                    }
+
+                    let task = EngineTask::Insert(InsertTask::new(
+                        state.client.clone(),
+                        state.rollup.clone(),
+                        envelope,
+                        false, // The payload is not derived in this case. This is an unsafe block.
+                    ));
+                    state.engine.enqueue(task);
+                }
+                Some(_) = unsafe_payloads_stale => {
+                    let Some(envelope) = state.unsafe_payloads.take_stale(Instant::now()) else {
+                        continue;
+                    };
+
+                    // The gap to the buffered unsafe blocks did not close. In consensus-layer sync
+                    // mode they are dropped, otherwise the highest one is inserted to trigger EL
+                    // sync.
+                    if state.sync_mode.is_consensus_layer() {
+                        debug!(target: "engine", "Dropping stale buffered unsafe blocks");
+                        continue;
+                    }
+                    warn!(
+                        target: "engine",
+                        number = envelope.execution_payload.block_number(),
+                        "Buffered unsafe blocks are stale, inserting the highest one"
+                    );
                     let task = EngineTask::Insert(InsertTask::new(
                         state.client.clone(),
                         state.rollup.clone(),
@@ -462,6 +621,9 @@ impl NodeActor for EngineActor {
                         return Err(EngineError::ChannelClosed);
                     };
                     self.finalizer.enqueue_for_finalization(&attributes);
+                    if let Some(safe_heads) = state.safe_heads.as_mut() {
+                        safe_heads.enqueue(&attributes);
+                    }
 
                     let task = EngineTask::Consolidate(ConsolidateTask::new(
                         state.client.clone(),
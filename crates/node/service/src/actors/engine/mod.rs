@@ -8,3 +8,9 @@ pub use error::EngineError;
 
 mod finalizer;
 pub use finalizer::L2Finalizer;
+
+mod safe_heads;
+pub use safe_heads::SafeHeadRecorder;
+
+mod unsafe_payloads;
+pub use unsafe_payloads::UnsafePayloadQueue;
@@ -0,0 +1,208 @@
+//! The [`SafeHeadRecorder`].
+
+use alloy_eips::BlockNumHash;
+use kona_engine::SafeHeadDb;
+use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, mpsc},
+    thread,
+};
+use tokio::sync::oneshot;
+
+/// An internal type alias for L2 block numbers.
+type L2BlockNumber = u64;
+
+/// A write to the [`SafeHeadDb`], applied by the writer thread of the [`SafeHeadRecorder`].
+#[derive(Debug)]
+enum SafeHeadWrite {
+    /// The L2 safe head was derived from the L1 block.
+    Updated {
+        /// The L1 block the safe head was derived from.
+        l1_block: BlockNumHash,
+        /// The L2 safe head.
+        safe_head: BlockNumHash,
+    },
+    /// The L2 safe head was reset.
+    Reset(BlockNumHash),
+    /// The L1 block with the given number was reorged.
+    L1Reorg(u64),
+    /// Notifies the sender once all previous writes are applied.
+    Flush(oneshot::Sender<()>),
+}
+
+/// The [`SafeHeadRecorder`] is responsible for recording the L1 block that each promoted L2 safe
+/// head was derived from in the [`SafeHeadDb`].
+///
+/// Writes are applied in order by a dedicated thread, so that the blocking file I/O of the
+/// [`SafeHeadDb`] does not stall the engine actor.
+#[derive(Debug)]
+pub struct SafeHeadRecorder {
+    /// The sender of writes to the writer thread.
+    writer: mpsc::Sender<SafeHeadWrite>,
+    /// A map of `L2 block number -> L1 block derived from` for derived [`OpAttributesWithParent`]
+    /// that have not been promoted to the safe head yet.
+    awaiting_safety: BTreeMap<L2BlockNumber, BlockInfo>,
+}
+
+impl SafeHeadRecorder {
+    /// Creates a new [`SafeHeadRecorder`] that records safe heads in the given [`SafeHeadDb`].
+    ///
+    /// Spawns the writer thread, which exits once the [`SafeHeadRecorder`] is dropped.
+    pub fn new(db: Arc<SafeHeadDb>) -> Self {
+        let (writer, writes) = mpsc::channel();
+        thread::Builder::new()
+            .name(String::from("safe-db-writer"))
+            .spawn(move || Self::write(&db, writes))
+            .expect("failed to spawn the safe head database writer thread");
+        Self { writer, awaiting_safety: BTreeMap::new() }
+    }
+
+    /// Enqueues a derived [`OpAttributesWithParent`], so that the L1 block it was derived from is
+    /// recorded once its L2 block is promoted to the safe head.
+    pub fn enqueue(&mut self, attributes: &OpAttributesWithParent) {
+        if let Some(derived_from) = attributes.derived_from {
+            self.awaiting_safety.insert(attributes.block_number(), derived_from);
+        }
+    }
+
+    /// Records the new L2 safe head, if it was derived from enqueued attributes.
+    pub fn safe_head_updated(&mut self, safe_head: &L2BlockInfo) {
+        let number = safe_head.block_info.number;
+        if let Some(derived_from) = self.awaiting_safety.get(&number) {
+            self.send(SafeHeadWrite::Updated {
+                l1_block: derived_from.id(),
+                safe_head: safe_head.block_info.id(),
+            });
+        }
+        self.awaiting_safety.retain(|&n, _| n > number);
+    }
+
+    /// Truncates the recorded safe heads following a reset of the L2 safe head.
+    pub fn safe_head_reset(&mut self, safe_head: &L2BlockInfo) {
+        self.awaiting_safety.clear();
+        self.send(SafeHeadWrite::Reset(safe_head.block_info.id()));
+    }
+
+    /// Truncates the recorded safe heads following a reorg of the given L1 block.
+    pub fn l1_reorg(&mut self, l1_block: &BlockInfo) {
+        self.awaiting_safety.retain(|_, derived_from| derived_from.number < l1_block.number);
+        self.send(SafeHeadWrite::L1Reorg(l1_block.number));
+    }
+
+    /// Waits until all writes sent so far are applied to the [`SafeHeadDb`].
+    pub async fn flush(&self) {
+        let (tx, rx) = oneshot::channel();
+        self.send(SafeHeadWrite::Flush(tx));
+        let _ = rx.await;
+    }
+
+    /// Sends a write to the writer thread.
+    fn send(&self, write: SafeHeadWrite) {
+        if self.writer.send(write).is_err() {
+            error!(target: "engine", "Safe head database writer thread exited");
+        }
+    }
+
+    /// Applies the received writes to the [`SafeHeadDb`], until the sender is dropped.
+    fn write(db: &SafeHeadDb, writes: mpsc::Receiver<SafeHeadWrite>) {
+        for write in writes {
+            let result = match write {
+                SafeHeadWrite::Updated { l1_block, safe_head } => {
+                    db.safe_head_updated(l1_block, safe_head)
+                }
+                SafeHeadWrite::Reset(safe_head) => db.safe_head_reset(safe_head),
+                SafeHeadWrite::L1Reorg(l1_number) => db.l1_reorg(l1_number),
+                SafeHeadWrite::Flush(tx) => {
+                    let _ = tx.send(());
+                    Ok(())
+                }
+            };
+            if let Err(err) = result {
+                warn!(target: "engine", ?err, "Failed to write to the safe head database");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use op_alloy_rpc_types_engine::OpPayloadAttributes;
+
+    fn attributes(number: u64, derived_from: u64) -> OpAttributesWithParent {
+        OpAttributesWithParent::new(
+            OpPayloadAttributes::default(),
+            L2BlockInfo {
+                block_info: BlockInfo { number: number - 1, ..Default::default() },
+                ..Default::default()
+            },
+            Some(BlockInfo {
+                number: derived_from,
+                hash: B256::repeat_byte(derived_from as u8),
+                ..Default::default()
+            }),
+            false,
+        )
+    }
+
+    fn safe_head(number: u64) -> L2BlockInfo {
+        L2BlockInfo {
+            block_info: BlockInfo {
+                number,
+                hash: B256::repeat_byte(number as u8),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_safe_heads() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(SafeHeadDb::open(dir.path().join("safe_db")).unwrap());
+        let mut recorder = SafeHeadRecorder::new(db.clone());
+
+        recorder.enqueue(&attributes(1, 10));
+        recorder.enqueue(&attributes(2, 10));
+        recorder.enqueue(&attributes(3, 11));
+
+        // The safe head advances past multiple blocks at once.
+        recorder.safe_head_updated(&safe_head(2));
+        recorder.flush().await;
+        let entry = db.safe_head_at_l1(10).unwrap().unwrap();
+        assert_eq!(entry.safe_head, safe_head(2).block_info.id());
+        assert_eq!(recorder.awaiting_safety.len(), 1);
+
+        recorder.safe_head_updated(&safe_head(3));
+        recorder.flush().await;
+        assert_eq!(db.safe_head_at_l1(11).unwrap().unwrap().safe_head.number, 3);
+
+        // A reset drops both the recorded and awaiting safe heads past the reset safe head.
+        recorder.enqueue(&attributes(4, 12));
+        recorder.safe_head_reset(&safe_head(2));
+        recorder.flush().await;
+        assert!(recorder.awaiting_safety.is_empty());
+        assert_eq!(db.len().unwrap(), 1);
+        assert_eq!(db.safe_head_at_l1(11).unwrap().unwrap().safe_head.number, 2);
+    }
+
+    #[tokio::test]
+    async fn test_l1_reorg() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(SafeHeadDb::open(dir.path().join("safe_db")).unwrap());
+        let mut recorder = SafeHeadRecorder::new(db.clone());
+
+        recorder.enqueue(&attributes(1, 10));
+        recorder.enqueue(&attributes(2, 11));
+        recorder.enqueue(&attributes(3, 12));
+        recorder.safe_head_updated(&safe_head(2));
+
+        // A reorg drops the recorded and awaiting safe heads of the reorged L1 blocks.
+        recorder.l1_reorg(&BlockInfo { number: 11, ..Default::default() });
+        recorder.flush().await;
+        assert!(recorder.awaiting_safety.is_empty());
+        assert!(db.is_empty().unwrap());
+    }
+}
@@ -0,0 +1,367 @@
+//! The [`UnsafePayloadQueue`].
+
+use kona_protocol::L2BlockInfo;
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::{collections::BTreeMap, time::Duration};
+use tokio::{sync::watch, time::Instant};
+
+/// An internal type alias for L2 block numbers.
+type L2BlockNumber = u64;
+
+/// The [`UnsafePayloadQueue`] buffers unsafe payloads received ahead of the unsafe head, ordered
+/// by height, until the gap to the unsafe head closes.
+///
+/// The queue holds at most one payload per height. When a payload conflicts with a buffered
+/// payload of the same height, the most recently received payload wins and the buffered payloads
+/// of the replaced branch are dropped.
+///
+/// If the gap does not close within [`Self::STALE_TIMEOUT`], the buffered payloads are stale and
+/// are taken out of the queue with [`Self::take_stale`].
+#[derive(Debug)]
+pub struct UnsafePayloadQueue {
+    /// The buffered payloads, keyed by block number.
+    payloads: BTreeMap<L2BlockNumber, OpExecutionPayloadEnvelope>,
+    /// The maximum number of buffered payloads.
+    capacity: usize,
+    /// A channel that publishes the number of buffered payloads.
+    depth: watch::Sender<usize>,
+    /// The last time the queue made progress, while payloads are buffered.
+    progress: Option<Instant>,
+}
+
+impl UnsafePayloadQueue {
+    /// The default maximum number of buffered payloads.
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// The time after which buffered payloads are stale if the gap has not closed.
+    pub const STALE_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Creates a new [`UnsafePayloadQueue`] that buffers at most `capacity` payloads.
+    pub fn new(capacity: usize) -> Self {
+        Self { payloads: BTreeMap::new(), capacity, depth: watch::channel(0).0, progress: None }
+    }
+
+    /// Returns the maximum number of buffered payloads.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of buffered payloads.
+    pub fn len(&self) -> usize {
+        self.payloads.len()
+    }
+
+    /// Returns true if no payloads are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.payloads.is_empty()
+    }
+
+    /// Subscribes to updates of the number of buffered payloads.
+    pub fn depth_subscribe(&self) -> watch::Receiver<usize> {
+        self.depth.subscribe()
+    }
+
+    /// Buffers a payload received ahead of the unsafe head.
+    ///
+    /// Payloads already buffered are ignored. If the queue is full, the highest payload is
+    /// evicted to make room, unless the new payload is the highest itself.
+    pub fn push(&mut self, envelope: OpExecutionPayloadEnvelope) {
+        let number = envelope.execution_payload.block_number();
+        let hash = envelope.execution_payload.block_hash();
+
+        match self.payloads.get(&number) {
+            Some(buffered) if buffered.execution_payload.block_hash() == hash => {
+                trace!(target: "engine", number, %hash, "Ignoring duplicate unsafe payload");
+                return;
+            }
+            Some(_) => {
+                // The new payload replaces a buffered branch. Drop the buffered descendants, as
+                // they can't extend the new payload.
+                debug!(target: "engine", number, %hash, "Replacing conflicting unsafe payload");
+                self.drop_from(number, "conflict");
+            }
+            None => {}
+        }
+
+        // Drop the buffered ancestors that the new payload does not extend.
+        let parent_hash = envelope.execution_payload.parent_hash();
+        if self
+            .payloads
+            .get(&number.saturating_sub(1))
+            .is_some_and(|parent| parent.execution_payload.block_hash() != parent_hash)
+        {
+            debug!(target: "engine", number, %hash, "Dropping conflicting unsafe payloads");
+            let dropped = self.payloads.range(..number).count();
+            self.payloads.retain(|&n, _| n >= number);
+            Self::record_dropped(dropped, "conflict");
+        }
+
+        // Drop the buffered descendants that don't extend the new payload.
+        if self
+            .payloads
+            .get(&(number + 1))
+            .is_some_and(|child| child.execution_payload.parent_hash() != hash)
+        {
+            self.drop_from(number + 1, "conflict");
+        }
+
+        if self.payloads.len() >= self.capacity {
+            match self.payloads.last_key_value() {
+                Some((&highest, _)) if highest > number => {
+                    self.payloads.pop_last();
+                    Self::record_dropped(1, "capacity");
+                }
+                _ => {
+                    debug!(target: "engine", number, "Unsafe payload queue full, dropping payload");
+                    Self::record_dropped(1, "capacity");
+                    return;
+                }
+            }
+        }
+
+        self.payloads.insert(number, envelope);
+        self.progress.get_or_insert_with(Instant::now);
+        self.update_depth();
+    }
+
+    /// Pops the buffered payload that extends the given unsafe head, if any.
+    ///
+    /// Payloads at or below the unsafe head are pruned. If the payload following the unsafe head
+    /// does not extend it, the buffered payloads are from a conflicting branch and are dropped.
+    pub fn pop_next(&mut self, unsafe_head: &L2BlockInfo) -> Option<OpExecutionPayloadEnvelope> {
+        let head = unsafe_head.block_info;
+
+        let stale = self.payloads.range(..=head.number).count();
+        if stale > 0 {
+            self.payloads = self.payloads.split_off(&(head.number + 1));
+            Self::record_dropped(stale, "stale");
+        }
+
+        let next = match self.payloads.first_key_value() {
+            Some((&number, envelope)) if number == head.number + 1 => {
+                if envelope.execution_payload.parent_hash() == head.hash {
+                    self.payloads.pop_first().map(|(_, envelope)| envelope)
+                } else {
+                    debug!(
+                        target: "engine",
+                        number,
+                        unsafe_head = %head.hash,
+                        "Dropping unsafe payloads that do not extend the unsafe head"
+                    );
+                    self.drop_from(number, "conflict");
+                    None
+                }
+            }
+            _ => None,
+        };
+        if next.is_some() {
+            self.progress = Some(Instant::now());
+        }
+
+        self.update_depth();
+        next
+    }
+
+    /// Returns the time at which the buffered payloads become stale, if any are buffered.
+    pub fn stale_at(&self) -> Option<Instant> {
+        self.progress.map(|progress| progress + Self::STALE_TIMEOUT)
+    }
+
+    /// Takes the highest buffered payload and drops the others if the buffered payloads are stale
+    /// at the given time, so that the caller can fall back to inserting it directly.
+    pub fn take_stale(&mut self, now: Instant) -> Option<OpExecutionPayloadEnvelope> {
+        if self.stale_at().is_none_or(|stale_at| now < stale_at) {
+            return None;
+        }
+        let highest = self.payloads.pop_last().map(|(_, envelope)| envelope);
+        Self::record_dropped(self.payloads.len(), "timeout");
+        self.payloads.clear();
+        self.update_depth();
+        highest
+    }
+
+    /// Drops all buffered payloads.
+    pub fn clear(&mut self) {
+        Self::record_dropped(self.payloads.len(), "cleared");
+        self.payloads.clear();
+        self.update_depth();
+    }
+
+    /// Drops the buffered payloads at or above the given height.
+    fn drop_from(&mut self, number: L2BlockNumber, reason: &'static str) {
+        let dropped = self.payloads.split_off(&number).len();
+        Self::record_dropped(dropped, reason);
+    }
+
+    /// Records dropped payloads in the metrics.
+    fn record_dropped(count: usize, reason: &'static str) {
+        if count == 0 {
+            return;
+        }
+        trace!(target: "engine", count, reason, "Dropped buffered unsafe payloads");
+        kona_macros::inc!(
+            counter,
+            crate::Metrics::UNSAFE_PAYLOAD_QUEUE_DROPPED,
+            count as u64,
+            "reason" => reason
+        );
+    }
+
+    /// Publishes the number of buffered payloads.
+    fn update_depth(&mut self) {
+        let depth = self.payloads.len();
+        if depth == 0 {
+            self.progress = None;
+        }
+        if self.depth.send_if_modified(|current| std::mem::replace(current, depth) != depth) {
+            kona_macros::set!(gauge, crate::Metrics::UNSAFE_PAYLOAD_QUEUE_DEPTH, depth as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256, Bloom, Bytes, U256};
+    use alloy_rpc_types_engine::ExecutionPayloadV1;
+    use kona_protocol::BlockInfo;
+    use op_alloy_rpc_types_engine::OpExecutionPayload;
+
+    fn payload(number: u64, hash: u8, parent_hash: u8) -> OpExecutionPayloadEnvelope {
+        OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V1(ExecutionPayloadV1 {
+                parent_hash: B256::repeat_byte(parent_hash),
+                fee_recipient: Address::ZERO,
+                state_root: B256::ZERO,
+                receipts_root: B256::ZERO,
+                logs_bloom: Bloom::ZERO,
+                prev_randao: B256::ZERO,
+                block_number: number,
+                gas_limit: 0,
+                gas_used: 0,
+                timestamp: 0,
+                extra_data: Bytes::new(),
+                base_fee_per_gas: U256::ZERO,
+                block_hash: B256::repeat_byte(hash),
+                transactions: Vec::new(),
+            }),
+            parent_beacon_block_root: None,
+        }
+    }
+
+    fn head(number: u64, hash: u8) -> L2BlockInfo {
+        L2BlockInfo {
+            block_info: BlockInfo { number, hash: B256::repeat_byte(hash), ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    fn numbers(queue: &UnsafePayloadQueue) -> Vec<u64> {
+        queue.payloads.keys().copied().collect()
+    }
+
+    #[test]
+    fn test_drains_in_order_as_gaps_close() {
+        let mut queue = UnsafePayloadQueue::new(UnsafePayloadQueue::DEFAULT_CAPACITY);
+        let depth = queue.depth_subscribe();
+
+        queue.push(payload(4, 4, 3));
+        queue.push(payload(2, 2, 1));
+        queue.push(payload(2, 2, 1));
+        assert_eq!(numbers(&queue), vec![2, 4]);
+        assert_eq!(*depth.borrow(), 2);
+
+        assert_eq!(queue.pop_next(&head(1, 1)).unwrap().execution_payload.block_number(), 2);
+        // Block 3 is missing.
+        assert!(queue.pop_next(&head(2, 2)).is_none());
+
+        queue.push(payload(3, 3, 2));
+        assert_eq!(queue.pop_next(&head(2, 2)).unwrap().execution_payload.block_number(), 3);
+        assert_eq!(queue.pop_next(&head(3, 3)).unwrap().execution_payload.block_number(), 4);
+        assert!(queue.is_empty());
+        assert_eq!(*depth.borrow(), 0);
+    }
+
+    #[test]
+    fn test_prunes_stale_payloads() {
+        let mut queue = UnsafePayloadQueue::new(UnsafePayloadQueue::DEFAULT_CAPACITY);
+        queue.push(payload(2, 2, 1));
+        queue.push(payload(3, 3, 2));
+        queue.push(payload(5, 5, 4));
+
+        assert!(queue.pop_next(&head(3, 3)).is_none());
+        assert_eq!(numbers(&queue), vec![5]);
+    }
+
+    #[test]
+    fn test_conflicting_branches() {
+        let mut queue = UnsafePayloadQueue::new(UnsafePayloadQueue::DEFAULT_CAPACITY);
+        queue.push(payload(2, 2, 1));
+        queue.push(payload(3, 3, 2));
+        queue.push(payload(4, 4, 3));
+        queue.push(payload(6, 6, 5));
+
+        // A new block 3 replaces the buffered branch from block 3 and up, and its parent
+        // conflicts with the buffered block 2.
+        queue.push(payload(3, 0x33, 0x22));
+        assert_eq!(numbers(&queue), vec![3]);
+
+        // A child that does not extend the buffered block 3 replaces it.
+        queue.push(payload(4, 0x44, 0x23));
+        assert_eq!(numbers(&queue), vec![4]);
+
+        // A parent that the buffered child does not extend drops the child.
+        queue.push(payload(3, 0x33, 0x22));
+        assert_eq!(numbers(&queue), vec![3]);
+    }
+
+    #[test]
+    fn test_drops_payloads_not_extending_unsafe_head() {
+        let mut queue = UnsafePayloadQueue::new(UnsafePayloadQueue::DEFAULT_CAPACITY);
+        queue.push(payload(2, 2, 1));
+        queue.push(payload(3, 3, 2));
+
+        assert!(queue.pop_next(&head(1, 0x11)).is_none());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut queue = UnsafePayloadQueue::new(2);
+        queue.push(payload(3, 3, 2));
+        queue.push(payload(5, 5, 4));
+
+        // The highest payload is evicted for a lower one.
+        queue.push(payload(4, 4, 3));
+        assert_eq!(numbers(&queue), vec![3, 4]);
+
+        // Payloads above the highest buffered payload are dropped.
+        queue.push(payload(6, 6, 5));
+        assert_eq!(numbers(&queue), vec![3, 4]);
+
+        queue.clear();
+        assert!(queue.is_empty());
+        assert!(queue.stale_at().is_none());
+    }
+
+    #[test]
+    fn test_take_stale() {
+        let mut queue = UnsafePayloadQueue::new(UnsafePayloadQueue::DEFAULT_CAPACITY);
+        assert!(queue.take_stale(Instant::now()).is_none());
+
+        queue.push(payload(2, 2, 1));
+        queue.push(payload(4, 4, 3));
+        queue.push(payload(5, 5, 4));
+        let stale_at = queue.stale_at().unwrap();
+        assert!(queue.take_stale(stale_at - Duration::from_secs(1)).is_none());
+
+        // Progress postpones the staleness of the remaining payloads.
+        assert!(queue.pop_next(&head(1, 1)).is_some());
+        assert!(queue.stale_at().unwrap() >= stale_at);
+
+        let stale_at = queue.stale_at().unwrap();
+        assert_eq!(queue.take_stale(stale_at).unwrap().execution_payload.block_number(), 5);
+        assert!(queue.is_empty());
+        assert!(queue.stale_at().is_none());
+    }
+}
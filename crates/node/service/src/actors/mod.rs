@@ -8,6 +8,7 @@ pub use traits::{CancellableContext, NodeActor};
 mod engine;
 pub use engine::{
     EngineActor, EngineBuilder, EngineContext, EngineError, EngineInboundData, L2Finalizer,
+    SafeHeadRecorder, UnsafePayloadQueue,
 };
 
 mod rpc;
@@ -15,8 +16,10 @@ pub use rpc::{RpcActor, RpcActorError, RpcContext};
 
 mod derivation;
 pub use derivation::{
-    DerivationActor, DerivationBuilder, DerivationContext, DerivationError,
-    DerivationInboundChannels, DerivationState, InboundDerivationMessage, PipelineBuilder,
+    BroadcastEventSink, CheckpointConfig, CheckpointError, DEFAULT_CHECKPOINT_INTERVAL,
+    DERIVATION_EVENTS_CAPACITY, DerivationActor, DerivationBuilder, DerivationContext,
+    DerivationError, DerivationInboundChannels, DerivationState, InboundDerivationMessage,
+    PipelineBuilder, PipelineCheckpointer,
 };
 
 mod l1_watcher_rpc;
@@ -1,18 +1,24 @@
 use alloy_primitives::Address;
 use async_trait::async_trait;
-use kona_p2p::P2pRpcRequest;
+use kona_engine::{EngineQueries, EngineQuerySender, EngineState};
+use kona_p2p::{P2pRpcRequest, SyncClient};
 use kona_rpc::NetworkAdminQuery;
 use kona_sources::BlockSignerError;
 use libp2p::TransportError;
 use op_alloy_rpc_types_engine::{OpExecutionPayloadEnvelope, OpNetworkPayloadEnvelope};
 use thiserror::Error;
-use tokio::{self, select, sync::mpsc};
+use tokio::{
+    self, select,
+    sync::{mpsc, oneshot, watch},
+    task::JoinHandle,
+};
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 
 use crate::{
     CancellableContext, NodeActor,
     actors::network::{
         builder::NetworkBuilder, driver::NetworkDriverError, error::NetworkBuilderError,
+        handler::NetworkHandler,
     },
 };
 
@@ -101,6 +107,12 @@ impl NetworkActor {
 pub struct NetworkContext {
     /// The channel used by the sequencer actor for sending unsafe blocks to the network.
     pub blocks: mpsc::Sender<OpExecutionPayloadEnvelope>,
+    /// The channel used to query the engine.
+    ///
+    /// If set, the network actor answers peers' `payload_by_number` requests from the engine,
+    /// and requests the payloads missing between the engine's unsafe head and the blocks
+    /// received over gossip from peers.
+    pub engine_query: Option<EngineQuerySender>,
     /// Cancels the network actor.
     pub cancellation: CancellationToken,
 }
@@ -126,6 +138,9 @@ pub enum NetworkActorError {
     /// The network driver was missing its unsafe block receiver.
     #[error("Missing unsafe block receiver in network driver")]
     MissingUnsafeBlockReceiver,
+    /// The gossip driver was missing its payload request receiver.
+    #[error("Missing payload request receiver in gossip driver")]
+    MissingPayloadRequestReceiver,
     /// The network driver was missing its unsafe block signer sender.
     #[error("Missing unsafe block signer in network driver")]
     MissingUnsafeBlockSigner,
@@ -150,13 +165,27 @@ impl NodeActor for NetworkActor {
 
     async fn start(
         mut self,
-        NetworkContext { blocks, cancellation }: Self::OutboundData,
+        NetworkContext { blocks, engine_query, cancellation }: Self::OutboundData,
     ) -> Result<(), Self::Error> {
         let mut handler = self.builder.build()?.start().await?;
 
         // New unsafe block channel.
         let (unsafe_block_tx, mut unsafe_block_rx) = tokio::sync::mpsc::unbounded_channel();
 
+        // Requests for payloads received from peers.
+        let mut payload_requests = handler
+            .gossip
+            .payload_requests
+            .take()
+            .ok_or(NetworkActorError::MissingPayloadRequestReceiver)?;
+
+        // Subscribe to the engine state to detect gaps in the unsafe chain.
+        let engine_state = match engine_query.as_ref() {
+            Some(engine_query) => Some(Self::engine_state_receiver(engine_query).await?),
+            None => None,
+        };
+        let mut gap_filling: Option<JoinHandle<()>> = None;
+
         loop {
             select! {
                 _ = cancellation.cancelled() => {
@@ -228,11 +257,42 @@ impl NodeActor for NetworkActor {
                     };
 
                     if let Some(payload) = handler.gossip.handle_event(event) {
-                        if unsafe_block_tx.send(payload.into()).is_err() {
+                        let payload: OpExecutionPayloadEnvelope = payload.into();
+
+                        let in_flight = !unsafe_block_rx.is_empty() ||
+                            blocks.capacity() < blocks.max_capacity();
+                        Self::fill_gap(
+                            &handler,
+                            engine_state.as_ref(),
+                            &mut gap_filling,
+                            in_flight,
+                            &payload,
+                            &unsafe_block_tx,
+                        );
+
+                        if unsafe_block_tx.send(payload).is_err() {
                             warn!(target: "node::p2p", "Failed to send unsafe block to network handler");
                         }
                     }
                 },
+                request = payload_requests.recv() => {
+                    let Some(request) = request else {
+                        error!(target: "node::p2p", "The payload request channel has closed");
+                        return Err(NetworkActorError::ChannelClosed);
+                    };
+
+                    // Dropping the response channel answers the peer with an error.
+                    let Some(engine_query) = engine_query.as_ref() else {
+                        continue;
+                    };
+                    let query = EngineQueries::PayloadByNumber {
+                        number: request.number,
+                        sender: request.response,
+                    };
+                    if engine_query.try_send(query).is_err() {
+                        debug!(target: "node::p2p", peer = ?request.peer, "Failed to query payload from the engine");
+                    }
+                },
                 enr = handler.enr_receiver.recv() => {
                     let Some(enr) = enr else {
                         error!(target: "node::p2p", "The enr receiver channel has closed");
@@ -261,6 +321,83 @@ impl NodeActor for NetworkActor {
     }
 }
 
+impl NetworkActor {
+    /// Requests the payloads missing between the engine's unsafe head and a block received over
+    /// gossip, unless a previous gap is still being filled, blocks are still `in_flight` to the
+    /// engine, or the execution layer is syncing.
+    ///
+    /// Gaps larger than [`SyncClient::MAX_GAP`] cannot be connected to the unsafe head, and are
+    /// left to the execution layer sync or derivation.
+    ///
+    /// The missing payloads are forwarded to the engine in ascending order, followed by the
+    /// received block so that it is applied on top of the filled gap.
+    fn fill_gap(
+        handler: &NetworkHandler,
+        engine_state: Option<&watch::Receiver<EngineState>>,
+        gap_filling: &mut Option<JoinHandle<()>>,
+        in_flight: bool,
+        payload: &OpExecutionPayloadEnvelope,
+        unsafe_block_tx: &mpsc::UnboundedSender<OpExecutionPayloadEnvelope>,
+    ) {
+        // The engine's unsafe head lags the blocks that it has not processed yet.
+        if in_flight || gap_filling.as_ref().is_some_and(|task| !task.is_finished()) {
+            return;
+        }
+        let Some(unsafe_head) = engine_state.and_then(|state| {
+            let state = state.borrow();
+            state.el_sync_finished.then_some(state.sync_state.unsafe_head())
+        }) else {
+            return;
+        };
+        let number = payload.execution_payload.block_number();
+        let from = unsafe_head.block_info.number + 1;
+        if number <= from {
+            return;
+        }
+        if number - from > SyncClient::MAX_GAP {
+            debug!(
+                target: "node::p2p",
+                from,
+                to = number - 1,
+                "Unsafe payload gap too large to fill"
+            );
+            return;
+        }
+
+        info!(target: "node::p2p", from, to = number - 1, "Requesting missing unsafe payloads");
+        let peers = handler.gossip.swarm.connected_peers().copied().collect::<Vec<_>>();
+        let client = handler.gossip.sync_client.clone();
+        let unsafe_block_tx = unsafe_block_tx.clone();
+        let payload = payload.clone();
+
+        *gap_filling = Some(tokio::spawn(async move {
+            let parent_hash = payload.execution_payload.parent_hash();
+            let payloads = client.fill_gap(&peers, from, number - 1, parent_hash).await;
+            if payloads.is_empty() {
+                return;
+            }
+            for payload in payloads.into_iter().chain(std::iter::once(payload)) {
+                if unsafe_block_tx.send(payload).is_err() {
+                    warn!(target: "node::p2p", "Failed to send unsafe block to network handler");
+                    return;
+                }
+            }
+        }));
+    }
+
+    /// Subscribes to the [`EngineState`] through the engine query channel.
+    async fn engine_state_receiver(
+        engine_query: &EngineQuerySender,
+    ) -> Result<watch::Receiver<EngineState>, NetworkActorError> {
+        let (tx, rx) = oneshot::channel();
+        engine_query
+            .send(EngineQueries::StateReceiver(tx))
+            .await
+            .map_err(|_| NetworkActorError::ChannelClosed)?;
+        rx.await.map_err(|_| NetworkActorError::ChannelClosed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -14,9 +14,10 @@ use jsonrpsee::{
     core::RegisterMethodError,
     server::{Server, ServerHandle, middleware::http::ProxyGetRequestLayer},
 };
+use kona_derive::DerivationEvent;
 use kona_engine::EngineQueries;
 use kona_rpc::{L1WatcherQueries, P2pRpc, RollupRpc, RpcBuilder};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 
 /// An error returned by the [`RpcActor`].
@@ -63,6 +64,8 @@ pub struct RpcContext {
     pub l1_watcher_queries: mpsc::Sender<L1WatcherQueries>,
     /// The engine query sender.
     pub engine_query: mpsc::Sender<EngineQueries>,
+    /// The sender that the derivation pipeline's events are broadcast on.
+    pub derivation_events: broadcast::Sender<DerivationEvent>,
     /// The cancellation token, shared between all tasks.
     pub cancellation: CancellationToken,
 }
@@ -121,6 +124,7 @@ impl NodeActor for RpcActor {
             engine_query,
             network_admin,
             sequencer_admin,
+            derivation_events,
         }: Self::OutboundData,
     ) -> Result<(), Self::Error> {
         let mut modules = RpcModule::new(());
@@ -140,7 +144,8 @@ impl NodeActor for RpcActor {
         )?;
 
         // Create context for communication between actors.
-        let rollup_rpc = RollupRpc::new(engine_query.clone(), l1_watcher_queries);
+        let rollup_rpc = RollupRpc::new(engine_query.clone(), l1_watcher_queries)
+            .with_safe_head_db(self.config.safe_head_db.clone());
         modules.merge(rollup_rpc.into_rpc())?;
 
         // Add development RPC module for engine state introspection if enabled
@@ -150,7 +155,7 @@ impl NodeActor for RpcActor {
         }
 
         if self.config.ws_enabled() {
-            modules.merge(WsRPC::new(engine_query).into_rpc())?;
+            modules.merge(WsRPC::new(engine_query, derivation_events).into_rpc())?;
         }
 
         let restarts = self.config.restart_count();
@@ -199,6 +204,7 @@ mod tests {
             admin_persistence: None,
             ws_enabled: false,
             dev_enabled: false,
+            safe_head_db: None,
         };
         let result = launch(&launcher, RpcModule::new(())).await;
         assert!(result.is_ok());
@@ -213,6 +219,7 @@ mod tests {
             admin_persistence: None,
             ws_enabled: false,
             dev_enabled: false,
+            safe_head_db: None,
         };
         let mut modules = RpcModule::new(());
 
@@ -8,6 +8,7 @@ use alloy_provider::RootProvider;
 use async_trait::async_trait;
 use kona_derive::{AttributesBuilder, PipelineErrorKind, StatefulAttributesBuilder};
 use kona_genesis::RollupConfig;
+use kona_hardforks::UpgradeRegistry;
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider};
 use kona_rpc::SequencerAdminQuery;
@@ -121,6 +122,9 @@ pub struct SequencerBuilder {
     pub l1_provider: RootProvider,
     /// The L2 provider.
     pub l2_provider: RootProvider<Optimism>,
+    /// The [`UpgradeRegistry`] providing the network upgrade transactions, if not the built-in
+    /// hardforks.
+    pub upgrades: Option<UpgradeRegistry>,
 }
 
 impl AttributesBuilderConfig for SequencerBuilder {
@@ -134,11 +138,15 @@ impl AttributesBuilderConfig for SequencerBuilder {
             self.rollup_cfg.clone(),
             DERIVATION_PROVIDER_CACHE_SIZE,
         );
-        StatefulAttributesBuilder::new(
+        let builder = StatefulAttributesBuilder::new(
             self.rollup_cfg,
             l2_derivation_provider,
             l1_derivation_provider,
-        )
+        );
+        match self.upgrades {
+            Some(upgrades) => builder.with_upgrades(upgrades),
+            None => builder,
+        }
     }
 }
 
@@ -160,7 +168,7 @@ pub struct SequencerContext {
     /// Watch channel to observe the L1 head of the chain.
     pub l1_head_rx: watch::Receiver<Option<BlockInfo>>,
     /// Sender to request the engine to reset.
-    pub reset_request_tx: mpsc::Sender<()>,
+    pub reset_request_tx: mpsc::Sender<Option<BlockInfo>>,
     /// Sender to request the execution layer to build a payload attributes on top of the
     /// current unsafe head.
     pub build_request_tx:
@@ -236,7 +244,7 @@ impl<AB: AttributesBuilder> SequencerActorState<AB> {
                 unsafe_head_l1_origin = ?unsafe_head.l1_origin,
                 "Cannot build new L2 block on inconsistent L1 origin, resetting engine"
             );
-            if let Err(err) = ctx.reset_request_tx.send(()).await {
+            if let Err(err) = ctx.reset_request_tx.send(None).await {
                 error!(target: "sequencer", ?err, "Failed to reset engine");
                 ctx.cancellation.cancel();
                 return Err(SequencerActorError::ChannelClosed);
@@ -261,7 +269,7 @@ impl<AB: AttributesBuilder> SequencerActorState<AB> {
                     // Do nothing and allow a retry.
                 }
                 Err(PipelineErrorKind::Reset(_)) => {
-                    if let Err(err) = ctx.reset_request_tx.send(()).await {
+                    if let Err(err) = ctx.reset_request_tx.send(None).await {
                         error!(target: "sequencer", ?err, "Failed to reset engine");
                         ctx.cancellation.cancel();
                         return Err(SequencerActorError::ChannelClosed);
@@ -420,7 +428,7 @@ impl<AB: AttributesBuilder> SequencerActorState<AB> {
         unsafe_head_rx: &mut watch::Receiver<L2BlockInfo>,
     ) -> Result<(), SequencerActorError> {
         // Schedule a reset of the engine, in order to initialize the engine state.
-        if let Err(err) = ctx.reset_request_tx.send(()).await {
+        if let Err(err) = ctx.reset_request_tx.send(None).await {
             error!(target: "sequencer", ?err, "Failed to send reset request to engine");
             ctx.cancellation.cancel();
             return Err(SequencerActorError::ChannelClosed);
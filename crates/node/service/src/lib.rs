@@ -10,20 +10,25 @@
 extern crate tracing;
 
 mod service;
-pub use service::{InteropMode, NodeMode, RollupNode, RollupNodeBuilder, RollupNodeService};
+pub use service::{
+    InteropMode, NodeMode, RollupNode, RollupNodeBuilder, RollupNodeService, SyncMode,
+    UnknownSyncMode,
+};
 
 mod actors;
 pub use actors::{
-    AttributesBuilderConfig, CancellableContext, ConductorClient, ConductorError,
-    DelayedL1OriginSelectorProvider, DerivationActor, DerivationBuilder, DerivationContext,
-    DerivationError, DerivationInboundChannels, DerivationState, EngineActor, EngineBuilder,
-    EngineContext, EngineError, EngineInboundData, InboundDerivationMessage, L1OriginSelector,
-    L1OriginSelectorError, L1OriginSelectorProvider, L1WatcherRpc, L1WatcherRpcContext,
-    L1WatcherRpcError, L1WatcherRpcInboundChannels, L1WatcherRpcState, L2Finalizer, NetworkActor,
-    NetworkActorError, NetworkBuilder, NetworkBuilderError, NetworkConfig, NetworkContext,
-    NetworkDriver, NetworkDriverError, NetworkHandler, NetworkInboundData, NodeActor,
-    PipelineBuilder, RpcActor, RpcActorError, RpcContext, SequencerActor, SequencerActorError,
-    SequencerBuilder, SequencerConfig, SequencerContext, SequencerInboundData,
+    AttributesBuilderConfig, BroadcastEventSink, CancellableContext, CheckpointConfig,
+    CheckpointError, ConductorClient, ConductorError, DEFAULT_CHECKPOINT_INTERVAL,
+    DERIVATION_EVENTS_CAPACITY, DelayedL1OriginSelectorProvider, DerivationActor,
+    DerivationBuilder, DerivationContext, DerivationError, DerivationInboundChannels,
+    DerivationState, EngineActor, EngineBuilder, EngineContext, EngineError, EngineInboundData,
+    InboundDerivationMessage, L1OriginSelector, L1OriginSelectorError, L1OriginSelectorProvider,
+    L1WatcherRpc, L1WatcherRpcContext, L1WatcherRpcError, L1WatcherRpcInboundChannels,
+    L1WatcherRpcState, L2Finalizer, NetworkActor, NetworkActorError, NetworkBuilder,
+    NetworkBuilderError, NetworkConfig, NetworkContext, NetworkDriver, NetworkDriverError,
+    NetworkHandler, NetworkInboundData, NodeActor, PipelineBuilder, PipelineCheckpointer, RpcActor,
+    RpcActorError, RpcContext, SafeHeadRecorder, SequencerActor, SequencerActorError,
+    SequencerBuilder, SequencerConfig, SequencerContext, SequencerInboundData, UnsafePayloadQueue,
 };
 
 mod metrics;
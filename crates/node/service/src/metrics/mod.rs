@@ -29,6 +29,12 @@ impl Metrics {
     pub const SEQUENCER_CONDUCTOR_COMMITMENT_DURATION: &str =
         "kona_node_sequencer_conductor_commitment_duration";
 
+    /// Gauge for the number of unsafe payloads buffered ahead of the unsafe head.
+    pub const UNSAFE_PAYLOAD_QUEUE_DEPTH: &str = "kona_node_unsafe_payload_queue_depth";
+
+    /// Identifier for the counter of unsafe payloads dropped from the unsafe payload queue.
+    pub const UNSAFE_PAYLOAD_QUEUE_DROPPED: &str = "kona_node_unsafe_payload_queue_dropped";
+
     /// Initializes metrics for the node service.
     ///
     /// This does two things:
@@ -75,6 +81,19 @@ impl Metrics {
             Self::SEQUENCER_CONDUCTOR_COMMITMENT_DURATION,
             "Duration of the sequencer conductor commitment"
         );
+
+        // Unsafe payload queue depth
+        metrics::describe_gauge!(
+            Self::UNSAFE_PAYLOAD_QUEUE_DEPTH,
+            "Number of unsafe payloads buffered ahead of the unsafe head"
+        );
+
+        // Unsafe payload queue dropped payloads
+        metrics::describe_counter!(
+            Self::UNSAFE_PAYLOAD_QUEUE_DROPPED,
+            metrics::Unit::Count,
+            "Unsafe payloads dropped from the unsafe payload queue"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -86,5 +105,8 @@ impl Metrics {
 
         // Derivation critical error
         kona_macros::set!(counter, Self::DERIVATION_CRITICAL_ERROR, 0);
+
+        // Unsafe payload queue depth
+        kona_macros::set!(gauge, Self::UNSAFE_PAYLOAD_QUEUE_DEPTH, 0);
     }
 }
@@ -115,6 +115,7 @@ pub trait RollupNodeService {
                 l1_head_updates_tx,
                 engine_l2_safe_head_tx,
                 el_sync_complete_tx,
+                derivation_events_tx,
             },
             derivation,
         ) = Self::DerivationActor::build(self.derivation_builder());
@@ -143,6 +144,9 @@ pub trait RollupNodeService {
             network,
         ) = Self::NetworkActor::build(self.network_builder());
 
+        // The network actor answers peers' payload requests from the engine.
+        let network_engine_query = engine_rpc.clone();
+
         // Create the RPC server actor.
         let (_, rpc) = self.rpc_builder().map(Self::RpcActor::build).unzip();
 
@@ -164,6 +168,7 @@ pub trait RollupNodeService {
                         sequencer_admin: sequencer_inbound_data.as_ref().map(|s| s.admin_query_tx.clone()),
                         l1_watcher_queries: da_watcher_rpc,
                         engine_query: engine_rpc,
+                        derivation_events: derivation_events_tx,
                     }
                 )),
                 sequencer.map(|s| (
@@ -180,7 +185,11 @@ pub trait RollupNodeService {
                 ),
                 Some((
                     network,
-                    NetworkContext { blocks: unsafe_block_tx, cancellation: cancellation.clone() }
+                    NetworkContext {
+                        blocks: unsafe_block_tx,
+                        engine_query: Some(network_engine_query),
+                        cancellation: cancellation.clone(),
+                    }
                 )),
                 Some((
                     da_watcher,
@@ -10,7 +10,7 @@ mod standard;
 pub use standard::{RollupNode, RollupNodeBuilder};
 
 mod mode;
-pub use mode::{InteropMode, NodeMode};
+pub use mode::{InteropMode, NodeMode, SyncMode, UnknownSyncMode};
 
 pub(crate) mod util;
 pub(crate) use util::spawn_and_wait;
@@ -1,5 +1,7 @@
 //! Contains enums that configure the mode for the node to operate in.
 
+use std::str::FromStr;
+
 /// The [`NodeMode`] enum represents the modes of operation for the [`RollupNodeService`].
 ///
 /// [`RollupNodeService`]: crate::RollupNodeService
@@ -47,3 +49,48 @@ pub enum InteropMode {
     #[display("Indexed")]
     Indexed,
 }
+
+/// The [`SyncMode`] enum represents how the node brings the execution layer up to the tip of the
+/// chain.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, derive_more::Display, strum::EnumIter)]
+pub enum SyncMode {
+    /// The execution layer syncs itself (e.g. via snap sync) towards the unsafe blocks received
+    /// over gossip, and derivation only starts once it has finished syncing.
+    #[display("execution-layer")]
+    #[default]
+    ExecutionLayer,
+    /// The execution layer is not relied on to sync itself. Derivation starts right away from the
+    /// execution layer's current head (the rollup genesis on a fresh execution layer), and every
+    /// block is inserted into the execution layer by the node.
+    #[display("consensus-layer")]
+    ConsensusLayer,
+}
+
+impl SyncMode {
+    /// Returns `true` if [`Self`] is [`Self::ExecutionLayer`].
+    pub const fn is_execution_layer(&self) -> bool {
+        matches!(self, Self::ExecutionLayer)
+    }
+
+    /// Returns `true` if [`Self`] is [`Self::ConsensusLayer`].
+    pub const fn is_consensus_layer(&self) -> bool {
+        matches!(self, Self::ConsensusLayer)
+    }
+}
+
+impl FromStr for SyncMode {
+    type Err = UnknownSyncMode;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "execution-layer" => Ok(Self::ExecutionLayer),
+            "consensus-layer" => Ok(Self::ConsensusLayer),
+            _ => Err(UnknownSyncMode(s.to_string())),
+        }
+    }
+}
+
+/// An error returned when parsing an unknown [`SyncMode`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown sync mode: {0}")]
+pub struct UnknownSyncMode(pub String);
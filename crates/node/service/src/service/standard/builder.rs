@@ -1,6 +1,9 @@
 //! Contains the builder for the [`RollupNode`].
 
-use crate::{EngineBuilder, InteropMode, NetworkConfig, NodeMode, RollupNode, SequencerConfig};
+use crate::{
+    CheckpointConfig, EngineBuilder, InteropMode, NetworkConfig, NodeMode, RollupNode,
+    SequencerConfig, SyncMode,
+};
 use alloy_primitives::Bytes;
 use alloy_provider::RootProvider;
 use alloy_rpc_client::RpcClient;
@@ -15,7 +18,9 @@ use std::sync::Arc;
 use tower::ServiceBuilder;
 use url::Url;
 
+use kona_engine::{EngineEndpoint, SafeHeadDb};
 use kona_genesis::RollupConfig;
+use kona_hardforks::UpgradeRegistry;
 use kona_providers_alloy::OnlineBeaconClient;
 use kona_rpc::RpcBuilder;
 
@@ -30,6 +35,10 @@ pub struct RollupNodeBuilder {
     l1_beacon_api_url: Option<Url>,
     /// The L2 engine RPC URL.
     l2_engine_rpc_url: Option<Url>,
+    /// The L2 engine endpoints of secondary execution clients to fail over to.
+    l2_engine_failover_endpoints: Vec<EngineEndpoint>,
+    /// Whether new payloads are mirrored to all L2 execution clients.
+    mirror_new_payload: bool,
     /// The JWT secret.
     jwt_secret: Option<JwtSecret>,
     /// The [`NetworkConfig`].
@@ -40,8 +49,18 @@ pub struct RollupNodeBuilder {
     sequencer_config: Option<SequencerConfig>,
     /// The mode to run the node in.
     mode: NodeMode,
+    /// The mode to sync the execution layer in.
+    sync_mode: SyncMode,
     /// Whether to run the node in interop mode.
     interop_mode: InteropMode,
+    /// The [`CheckpointConfig`] for the derivation pipeline.
+    checkpoint_config: Option<CheckpointConfig>,
+    /// The [`SafeHeadDb`] that safe heads are recorded in.
+    safe_head_db: Option<Arc<SafeHeadDb>>,
+    /// The URL of the Alt-DA server.
+    altda_server_url: Option<Url>,
+    /// The [`UpgradeRegistry`] providing the network upgrade transactions.
+    upgrades: Option<UpgradeRegistry>,
 }
 
 impl RollupNodeBuilder {
@@ -55,6 +74,11 @@ impl RollupNodeBuilder {
         Self { mode, ..self }
     }
 
+    /// Sets the [`SyncMode`] on the [`RollupNodeBuilder`].
+    pub fn with_sync_mode(self, sync_mode: SyncMode) -> Self {
+        Self { sync_mode, ..self }
+    }
+
     /// Appends an L1 EL provider RPC URL to the builder.
     pub fn with_l1_provider_rpc_url(self, l1_provider_rpc_url: Url) -> Self {
         Self { l1_provider_rpc_url: Some(l1_provider_rpc_url), ..self }
@@ -70,6 +94,23 @@ impl RollupNodeBuilder {
         Self { l2_engine_rpc_url: Some(l2_engine_rpc_url), ..self }
     }
 
+    /// Sets the L2 engine endpoints of secondary execution clients on the [`RollupNodeBuilder`].
+    ///
+    /// When the primary execution client fails, the secondaries are promoted to primary in order.
+    /// Each [`EngineEndpoint`] is authenticated with its own JWT secret.
+    pub fn with_l2_engine_failover_endpoints(
+        self,
+        l2_engine_failover_endpoints: Vec<EngineEndpoint>,
+    ) -> Self {
+        Self { l2_engine_failover_endpoints, ..self }
+    }
+
+    /// Sets whether new payloads are mirrored to all L2 execution clients on the
+    /// [`RollupNodeBuilder`].
+    pub fn with_mirror_new_payload(self, mirror_new_payload: bool) -> Self {
+        Self { mirror_new_payload, ..self }
+    }
+
     /// Appends a JWT secret to the builder.
     pub fn with_jwt_secret(self, jwt_secret: JwtSecret) -> Self {
         Self { jwt_secret: Some(jwt_secret), ..self }
@@ -90,6 +131,35 @@ impl RollupNodeBuilder {
         Self { sequencer_config: Some(sequencer_config), ..self }
     }
 
+    /// Sets the [`CheckpointConfig`] for the derivation pipeline on the [`RollupNodeBuilder`].
+    ///
+    /// If [`None`], checkpoints of the derivation pipeline are not persisted.
+    pub fn with_checkpoint_config(self, checkpoint_config: Option<CheckpointConfig>) -> Self {
+        Self { checkpoint_config, ..self }
+    }
+
+    /// Sets the URL of the Alt-DA server on the [`RollupNodeBuilder`].
+    ///
+    /// Required if the rollup uses Alt-DA.
+    pub fn with_altda_server_url(self, altda_server_url: Option<Url>) -> Self {
+        Self { altda_server_url, ..self }
+    }
+
+    /// Sets the [`UpgradeRegistry`] on the [`RollupNodeBuilder`].
+    ///
+    /// If [`None`], the network upgrade transactions of the built-in hardforks are used.
+    pub fn with_upgrade_registry(self, upgrades: Option<UpgradeRegistry>) -> Self {
+        Self { upgrades, ..self }
+    }
+
+    /// Sets the [`SafeHeadDb`] on the [`RollupNodeBuilder`].
+    ///
+    /// If set, the L1 block each safe head was derived from is recorded in the [`SafeHeadDb`],
+    /// which backs the `optimism_safeHeadAtL1Block` RPC method.
+    pub fn with_safe_head_db(self, safe_head_db: Option<Arc<SafeHeadDb>>) -> Self {
+        Self { safe_head_db, ..self }
+    }
+
     /// Assembles the [`RollupNode`] service.
     ///
     /// ## Panics
@@ -125,8 +195,12 @@ impl RollupNodeBuilder {
             config: Arc::clone(&rollup_config),
             l1_rpc_url,
             engine_url,
+            failover_engines: self.l2_engine_failover_endpoints,
+            mirror_new_payload: self.mirror_new_payload,
             jwt_secret,
             mode: self.mode,
+            sync_mode: self.sync_mode,
+            safe_head_db: self.safe_head_db.clone(),
         };
 
         let p2p_config = self.p2p_config.expect("P2P config not set");
@@ -139,9 +213,12 @@ impl RollupNodeBuilder {
             l1_beacon,
             l2_provider,
             engine_builder,
-            rpc_builder: self.rpc_config,
+            rpc_builder: self.rpc_config.map(|rpc| rpc.with_safe_head_db(self.safe_head_db)),
             p2p_config,
             sequencer_config,
+            checkpoint_config: self.checkpoint_config,
+            altda_server: self.altda_server_url,
+            upgrades: self.upgrades,
         }
     }
 }
@@ -1,8 +1,8 @@
 //! Contains the [`RollupNode`] implementation.
 use crate::{
-    DerivationActor, DerivationBuilder, EngineActor, EngineBuilder, InteropMode, L1WatcherRpc,
-    L1WatcherRpcState, NetworkActor, NetworkBuilder, NetworkConfig, NodeMode, RollupNodeBuilder,
-    RollupNodeService, RpcActor, SequencerConfig,
+    CheckpointConfig, DerivationActor, DerivationBuilder, EngineActor, EngineBuilder, InteropMode,
+    L1WatcherRpc, L1WatcherRpcState, NetworkActor, NetworkBuilder, NetworkConfig, NodeMode,
+    RollupNodeBuilder, RollupNodeService, RpcActor, SequencerConfig,
     actors::{SequencerActor, SequencerBuilder},
 };
 use alloy_provider::RootProvider;
@@ -10,8 +10,10 @@ use async_trait::async_trait;
 use kona_derive::StatefulAttributesBuilder;
 use op_alloy_network::Optimism;
 use std::sync::Arc;
+use url::Url;
 
 use kona_genesis::RollupConfig;
+use kona_hardforks::UpgradeRegistry;
 use kona_providers_alloy::{
     AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlinePipeline,
 };
@@ -39,6 +41,12 @@ pub struct RollupNode {
     pub(crate) p2p_config: NetworkConfig,
     /// The [`SequencerConfig`] for the node.
     pub(crate) sequencer_config: SequencerConfig,
+    /// The optional [`CheckpointConfig`] for the derivation pipeline.
+    pub(crate) checkpoint_config: Option<CheckpointConfig>,
+    /// The URL of the Alt-DA server.
+    pub(crate) altda_server: Option<Url>,
+    /// The [`UpgradeRegistry`] providing the network upgrade transactions.
+    pub(crate) upgrades: Option<UpgradeRegistry>,
 }
 
 impl RollupNode {
@@ -80,6 +88,7 @@ impl RollupNodeService for RollupNode {
             rollup_cfg: self.config.clone(),
             l1_provider: self.l1_provider.clone(),
             l2_provider: self.l2_provider.clone(),
+            upgrades: self.upgrades.clone(),
         }
     }
 
@@ -98,6 +107,9 @@ impl RollupNodeService for RollupNode {
             l2_provider: self.l2_provider.clone(),
             rollup_config: self.config.clone(),
             interop_mode: self.interop_mode,
+            checkpoint_config: self.checkpoint_config.clone(),
+            altda_server: self.altda_server.clone(),
+            upgrades: self.upgrades.clone(),
         }
     }
 }
@@ -226,7 +226,11 @@ where
                 }
             }
 
-            let mut attributes = match self.pipeline.produce_payload(tip_cursor.l2_safe_head).await
+            let mut attributes = match kona_macros::profile!(
+                "derivation",
+                self.pipeline.produce_payload(tip_cursor.l2_safe_head)
+            )
+            .await
             {
                 Ok(attrs) => attrs.take_inner(),
                 Err(PipelineErrorKind::Critical(PipelineError::EndOfSource)) => {
@@ -253,7 +257,12 @@ where
             };
 
             self.executor.update_safe_head(tip_cursor.l2_safe_head_header.clone());
-            let outcome = match self.executor.execute_payload(attributes.clone()).await {
+            let outcome = match kona_macros::profile!(
+                "block_execution",
+                self.executor.execute_payload(attributes.clone())
+            )
+            .await
+            {
                 Ok(outcome) => outcome,
                 Err(e) => {
                     error!(target: "client", "Failed to execute L2 block: {}", e);
@@ -277,7 +286,12 @@ where
 
                         // Retry the execution.
                         self.executor.update_safe_head(tip_cursor.l2_safe_head_header.clone());
-                        match self.executor.execute_payload(attributes.clone()).await {
+                        match kona_macros::profile!(
+                            "block_execution",
+                            self.executor.execute_payload(attributes.clone())
+                        )
+                        .await
+                        {
                             Ok(header) => header,
                             Err(e) => {
                                 error!(
@@ -316,11 +330,11 @@ where
                 &block,
                 &self.pipeline.rollup_config().genesis,
             )?;
-            let tip_cursor = TipCursor::new(
-                l2_info,
-                outcome.header.clone(),
-                self.executor.compute_output_root().map_err(DriverError::Executor)?,
-            );
+            let output_root = {
+                kona_macros::profile_scope!("output_root");
+                self.executor.compute_output_root().map_err(DriverError::Executor)?
+            };
+            let tip_cursor = TipCursor::new(l2_info, outcome.header.clone(), output_root);
 
             // Advance the derivation pipeline cursor
             drop(pipeline_cursor);
@@ -32,12 +32,12 @@ where
         parent_hash: B256,
         block_env: &BlockEnv,
         ex_result: &BlockExecutionResult<OpReceiptEnvelope>,
-        bundle: BundleState,
+        bundle: &BundleState,
     ) -> ExecutorResult<Sealed<Header>> {
         let timestamp = block_env.timestamp.saturating_to::<u64>();
 
         // Compute the roots for the block header.
-        let state_root = self.trie_db.state_root(&bundle)?;
+        let state_root = self.trie_db.state_root(bundle)?;
         let transactions_root = ordered_trie_with_encoder(
             // SAFETY: The OP Stack protocol will never generate a payload attributes with an empty
             // transactions field. Panicking here is the desired behavior, as it indicates a severe
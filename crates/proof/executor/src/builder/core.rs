@@ -4,21 +4,32 @@
 //! for OP Stack L2 chains that operates in a stateless manner, pulling required state
 //! data from a [TrieDB] during execution rather than maintaining full state.
 
-use crate::{ExecutorError, ExecutorResult, TrieDB, TrieDBError, TrieDBProvider};
-use alloc::{string::ToString, vec::Vec};
+use crate::{
+    ExecutionTrace, ExecutorError, ExecutorResult, TrieDB, TrieDBError, TrieDBProvider,
+    trace::trace_transactions,
+};
+use alloc::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
 use alloy_consensus::{Header, Sealed, crypto::RecoveryError};
 use alloy_evm::{
     EvmFactory, FromRecoveredTx, FromTxWithEncoded,
-    block::{BlockExecutionResult, BlockExecutor, BlockExecutorFactory},
+    block::{BlockExecutionResult, BlockExecutor, BlockExecutorFactory, StateChangeSource},
 };
 use alloy_op_evm::{OpBlockExecutionCtx, OpBlockExecutorFactory, block::OpAlloyReceiptBuilder};
+use alloy_primitives::keccak256;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
 use kona_mpt::TrieHinter;
 use op_alloy_consensus::{OpReceiptEnvelope, OpTxEnvelope};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use op_revm::OpSpecId;
-use revm::database::{State, states::bundle_state::BundleRetention};
+use revm::{
+    database::{State, states::bundle_state::BundleRetention},
+    state::EvmState,
+};
+use spin::Mutex;
+
+/// The state changes recorded during a traced block execution, keyed by their source.
+type RecordedStateChanges = Arc<Mutex<Vec<(StateChangeSource, EvmState)>>>;
 
 /// Stateless OP Stack L2 block builder that derives state from trie proofs during execution.
 ///
@@ -206,6 +217,39 @@ where
         &mut self,
         attrs: OpPayloadAttributes,
     ) -> ExecutorResult<BlockBuildingOutcome> {
+        self.build_block_inner(attrs, false).map(|(outcome, _)| outcome)
+    }
+
+    /// Builds and executes a new L2 block using the provided payload attributes, identically to
+    /// [Self::build_block], and additionally returns an [ExecutionTrace] of the block.
+    ///
+    /// The trace contains the gas used, receipt and touched accounts of every transaction, as well
+    /// as the block-level state diff derived from the [`TrieDB`]. It is intended for debugging
+    /// state root mismatches against a reference execution client, and comes at the cost of
+    /// executing the transactions individually and unblinding the old state trie along all
+    /// modified paths.
+    ///
+    /// # Arguments
+    /// * `attrs` - Payload attributes containing transactions and block metadata
+    ///
+    /// # Returns
+    /// * `Ok((BlockBuildingOutcome, ExecutionTrace))` - The built block and its execution trace
+    /// * `Err(ExecutorError)` - Block building or execution failure
+    pub fn build_block_with_trace(
+        &mut self,
+        attrs: OpPayloadAttributes,
+    ) -> ExecutorResult<(BlockBuildingOutcome, ExecutionTrace)> {
+        let (outcome, trace) = self.build_block_inner(attrs, true)?;
+        Ok((outcome, trace.ok_or(ExecutorError::MissingExecutionTrace)?))
+    }
+
+    /// Builds and executes a new L2 block using the provided payload attributes, optionally
+    /// tracing its execution.
+    fn build_block_inner(
+        &mut self,
+        attrs: OpPayloadAttributes,
+        trace: bool,
+    ) -> ExecutorResult<(BlockBuildingOutcome, Option<ExecutionTrace>)> {
         // Step 1. Set up the execution environment.
         let base_fee_params =
             Self::active_base_fee_params(self.config, self.trie_db.parent_block_header(), &attrs)?;
@@ -249,14 +293,40 @@ where
             // This field is unused for individual block building jobs.
             extra_data: Default::default(),
         };
-        let executor = self.factory.create_executor(evm, ctx);
+        let mut executor = self.factory.create_executor(evm, ctx);
 
         // Step 3. Execute the block containing the transactions within the payload attributes.
         let transactions = attrs
             .recovered_transactions_with_encoded()
             .collect::<Result<Vec<_>, RecoveryError>>()
             .map_err(ExecutorError::Recovery)?;
-        let ex_result = executor.execute_block(transactions.iter())?;
+        let (ex_result, recorded) = if trace {
+            // Record the state changes of every system call and transaction, as well as the gas
+            // used by each transaction.
+            let changes = RecordedStateChanges::default();
+            let hook_changes = Arc::clone(&changes);
+            executor.set_state_hook(Some(Box::new(
+                move |source: StateChangeSource, state: &EvmState| {
+                    hook_changes.lock().push((source, state.clone()));
+                },
+            )));
+
+            executor.apply_pre_execution_changes()?;
+            let gas_used = transactions
+                .iter()
+                .map(|tx| executor.execute_transaction(tx))
+                .collect::<Result<Vec<_>, _>>()?;
+            let ex_result = executor.apply_post_execution_changes()?;
+
+            // Every executed transaction touches at least its sender, so an empty recording means
+            // that the state hook was never invoked.
+            if !transactions.is_empty() && changes.lock().is_empty() {
+                return Err(ExecutorError::MissingExecutionTrace);
+            }
+            (ex_result, Some((changes, gas_used)))
+        } else {
+            (executor.execute_block(transactions.iter())?, None)
+        };
 
         info!(
             target: "block_builder",
@@ -268,7 +338,7 @@ where
         // Step 4. Merge state transitions and seal the block.
         state.merge_transitions(BundleRetention::Reverts);
         let bundle = state.take_bundle();
-        let header = self.seal_block(&attrs, parent_hash, &block_env, &ex_result, bundle)?;
+        let header = self.seal_block(&attrs, parent_hash, &block_env, &ex_result, &bundle)?;
 
         info!(
             target: "block_builder",
@@ -280,9 +350,35 @@ where
             "Sealed new block",
         );
 
+        // Step 5. If tracing is enabled, assemble the execution trace. The state diff must be
+        // computed before the parent block header is updated.
+        let trace = if let Some((changes, gas_used)) = recorded {
+            let tx_hashes = attrs.transactions.iter().flatten().map(keccak256).collect();
+            let trie_db = &self.trie_db;
+            let transactions = trace_transactions(
+                core::mem::take(&mut *changes.lock()),
+                &bundle,
+                |address| Ok(trie_db.get_parent_trie_account(address)?.as_ref().map(Into::into)),
+                tx_hashes,
+                gas_used,
+                &ex_result.receipts,
+            )?;
+            let state_diff = self.trie_db.state_diff(&bundle)?;
+
+            info!(
+                target: "block_builder",
+                transactions = transactions.len(),
+                changed_accounts = state_diff.accounts.len(),
+                "Assembled execution trace",
+            );
+            Some(ExecutionTrace { transactions, state_diff })
+        } else {
+            None
+        };
+
         // Update the parent block hash in the state database, preparing for the next block.
         self.trie_db.set_parent_block_header(header.clone());
-        Ok((header, ex_result).into())
+        Ok(((header, ex_result).into(), trace))
     }
 }
 
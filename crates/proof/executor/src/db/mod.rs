@@ -1,13 +1,16 @@
 //! This module contains an implementation of an in-memory Trie DB for [`revm`], that allows for
 //! incremental updates through fetching node preimages on the fly during execution.
 
-use crate::errors::{TrieDBError, TrieDBResult};
+use crate::{
+    AccountDiff, StateDiff, StorageDiff,
+    errors::{TrieDBError, TrieDBResult},
+};
 use alloc::{string::ToString, vec::Vec};
 use alloy_consensus::{EMPTY_ROOT_HASH, Header, Sealed};
 use alloy_primitives::{Address, B256, U256, keccak256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_trie::TrieAccount;
-use kona_mpt::{Nibbles, TrieHinter, TrieNode, TrieNodeError};
+use kona_mpt::{Nibbles, TrieDiff, TrieHinter, TrieNode, TrieNodeError, diff_tries};
 use revm::{
     Database,
     database::{BundleState, states::StorageSlot},
@@ -149,6 +152,7 @@ where
     /// - `Ok(B256)`: The new state root hash of the trie DB.
     /// - `Err(_)`: If the state root hash could not be computed.
     pub fn state_root(&mut self, bundle: &BundleState) -> TrieDBResult<B256> {
+        kona_macros::profile_scope!("trie_state_root");
         debug!(target: "client_executor", "Recomputing state root");
 
         // Update the accounts in the trie with the changeset.
@@ -166,6 +170,107 @@ where
         Ok(root)
     }
 
+    /// Computes the [StateDiff] between the state trie of the parent block and the current state
+    /// trie. Should be called after the block's [BundleState] changeset has been applied with
+    /// [Self::state_root], and before the parent block header is updated.
+    ///
+    /// Only the trie nodes along the modified paths are unblinded. The preimages of the hashed
+    /// addresses and storage slots are resolved from the passed [BundleState], where known.
+    ///
+    /// ## Takes
+    /// - `bundle`: The [BundleState] changeset that was applied to the trie DB.
+    ///
+    /// ## Returns
+    /// - `Ok(StateDiff)`: The state diff of the block.
+    /// - `Err(_)`: If the state diff could not be computed.
+    pub fn state_diff(&self, bundle: &BundleState) -> TrieDBResult<StateDiff> {
+        let addresses = bundle
+            .state()
+            .keys()
+            .map(|address| (keccak256(address), *address))
+            .collect::<HashMap<_, _>>();
+
+        let parent_root = TrieNode::new_blinded(self.parent_block_header.state_root);
+        let accounts = diff_tries(&parent_root, &self.root_node, &self.fetcher)
+            .map(|diff| -> TrieDBResult<AccountDiff> {
+                let diff = diff?;
+                let hashed_address = B256::from_slice(&diff.key().pack());
+                let address = addresses.get(&hashed_address).copied();
+                let (old, new) = match diff {
+                    TrieDiff::Inserted { value, .. } => (None, Some(value)),
+                    TrieDiff::Modified { old, new, .. } => (Some(old), Some(new)),
+                    TrieDiff::Deleted { value, .. } => (Some(value), None),
+                };
+                let decode = |rlp: &[u8]| {
+                    TrieAccount::decode(&mut &rlp[..]).map_err(TrieNodeError::RLPError)
+                };
+                let old = old.as_deref().map(decode).transpose()?;
+                let new = new.as_deref().map(decode).transpose()?;
+
+                let storage = self.storage_diff(
+                    address,
+                    old.as_ref().map_or(EMPTY_ROOT_HASH, |account| account.storage_root),
+                    new.as_ref().map_or(EMPTY_ROOT_HASH, |account| account.storage_root),
+                    bundle,
+                )?;
+                Ok(AccountDiff { hashed_address, address, old, new, storage })
+            })
+            .collect::<TrieDBResult<Vec<_>>>()?;
+
+        Ok(StateDiff { accounts })
+    }
+
+    /// Computes the [StorageDiff]s between the old and new storage tries of an account.
+    fn storage_diff(
+        &self,
+        address: Option<Address>,
+        old_root: B256,
+        new_root: B256,
+        bundle: &BundleState,
+    ) -> TrieDBResult<Vec<StorageDiff>> {
+        if old_root == new_root {
+            return Ok(Vec::new());
+        }
+
+        // Prefer the cached storage trie of the account, which holds the modified nodes in memory.
+        let new_node = address
+            .and_then(|address| self.storage_roots.get(&address).cloned())
+            .unwrap_or_else(|| TrieNode::new_blinded(new_root));
+        let slots = address
+            .and_then(|address| bundle.account(&address))
+            .map(|account| {
+                account
+                    .storage
+                    .keys()
+                    .map(|slot| (keccak256(slot.to_be_bytes::<32>()), *slot))
+                    .collect::<HashMap<_, _>>()
+            })
+            .unwrap_or_default();
+
+        let decode = |rlp: Option<&[u8]>| {
+            rlp.map_or(Ok(U256::ZERO), |mut rlp| {
+                U256::decode(&mut rlp).map_err(TrieNodeError::RLPError)
+            })
+        };
+        diff_tries(&TrieNode::new_blinded(old_root), &new_node, &self.fetcher)
+            .map(|diff| -> TrieDBResult<StorageDiff> {
+                let diff = diff?;
+                let hashed_slot = B256::from_slice(&diff.key().pack());
+                let (old, new) = match &diff {
+                    TrieDiff::Inserted { value, .. } => (None, Some(value.as_ref())),
+                    TrieDiff::Modified { old, new, .. } => (Some(old.as_ref()), Some(new.as_ref())),
+                    TrieDiff::Deleted { value, .. } => (Some(value.as_ref()), None),
+                };
+                Ok(StorageDiff {
+                    hashed_slot,
+                    slot: slots.get(&hashed_slot).copied(),
+                    old: decode(old)?,
+                    new: decode(new)?,
+                })
+            })
+            .collect()
+    }
+
     /// Fetches the [TrieAccount] of an account from the trie DB.
     ///
     /// ## Takes
@@ -199,6 +304,35 @@ where
             .map(Some)
     }
 
+    /// Fetches the [TrieAccount] of an account from the state trie of the parent block, ignoring
+    /// any changes that were applied to the trie DB since.
+    ///
+    /// ## Takes
+    /// - `address`: The address of the account.
+    ///
+    /// ## Returns
+    /// - `Ok(Some(TrieAccount))`: The [TrieAccount] of the account in the parent state.
+    /// - `Ok(None)`: If the account does not exist in the parent state.
+    /// - `Err(_)`: If the account could not be fetched.
+    pub fn get_parent_trie_account(&self, address: &Address) -> TrieDBResult<Option<TrieAccount>> {
+        // Send a hint to the host to fetch the account proof.
+        self.hinter
+            .hint_account_proof(*address, self.parent_block_header.number)
+            .map_err(|e| TrieDBError::Provider(e.to_string()))?;
+
+        let mut parent_root = TrieNode::new_blinded(self.parent_block_header.state_root);
+        let hashed_address_nibbles = Nibbles::unpack(keccak256(address.as_slice()));
+        let Some(trie_account_rlp) = parent_root.open(&hashed_address_nibbles, &self.fetcher)?
+        else {
+            return Ok(None);
+        };
+
+        TrieAccount::decode(&mut trie_account_rlp.as_ref())
+            .map_err(TrieNodeError::RLPError)
+            .map_err(Into::into)
+            .map(Some)
+    }
+
     /// Modifies the accounts in the storage trie with the given [BundleState] changeset.
     ///
     /// ## Takes
@@ -328,6 +462,8 @@ where
     type Error = TrieDBError;
 
     fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        kona_macros::profile_scope!("trie_account");
+
         // Fetch the account from the trie.
         let Some(trie_account) =
             self.get_trie_account(&address, self.parent_block_header.number)?
@@ -357,6 +493,8 @@ where
     }
 
     fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        kona_macros::profile_scope!("trie_storage");
+
         // Send a hint to the host to fetch the storage proof.
         self.hinter
             .hint_storage_proof(address, index, self.parent_block_header.number)
@@ -177,6 +177,15 @@ pub enum ExecutorError {
     /// - Incorrect executor lifecycle management
     #[error("Missing the executor")]
     MissingExecutor,
+    /// Execution trace not produced by a traced block execution.
+    ///
+    /// This error occurs when a block is built with tracing enabled, but the
+    /// state changes of its execution were not recorded.
+    ///
+    /// # Common Causes
+    /// - The state hook of the block executor was not invoked
+    #[error("Missing the execution trace")]
+    MissingExecutionTrace,
 }
 
 /// Result type alias for operations that may fail with [`ExecutorError`].
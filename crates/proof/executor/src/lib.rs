@@ -18,6 +18,12 @@ pub use db::{NoopTrieDBProvider, TrieDB, TrieDBProvider};
 mod builder;
 pub use builder::{BlockBuildingOutcome, StatelessL2Builder, compute_receipts_root};
 
+mod trace;
+pub use trace::{
+    AccountDiff, AccountState, AccountTrace, ExecutionTrace, StateDiff, StorageDiff, StorageTrace,
+    TransactionTrace,
+};
+
 mod errors;
 pub use errors::{ExecutorError, ExecutorResult, TrieDBError, TrieDBResult};
 
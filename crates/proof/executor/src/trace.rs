@@ -0,0 +1,289 @@
+//! Execution trace and state diff types produced by
+//! [StatelessL2Builder::build_block_with_trace].
+//!
+//! [StatelessL2Builder::build_block_with_trace]: crate::StatelessL2Builder::build_block_with_trace
+
+use crate::TrieDBResult;
+use alloc::vec::Vec;
+use alloy_evm::block::StateChangeSource;
+use alloy_primitives::{Address, B256, U256, map::HashMap};
+use alloy_trie::TrieAccount;
+use op_alloy_consensus::OpReceiptEnvelope;
+use revm::{
+    database::BundleState,
+    state::{AccountInfo, EvmState},
+};
+
+/// A structured trace of a block's execution, consisting of a per-transaction trace and the
+/// block-level state diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ExecutionTrace {
+    /// The traces of the transactions within the block, in execution order.
+    pub transactions: Vec<TransactionTrace>,
+    /// The state diff of the block, derived from the state trie.
+    pub state_diff: StateDiff,
+}
+
+/// The trace of a single transaction's execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TransactionTrace {
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// The gas used by the transaction.
+    pub gas_used: u64,
+    /// The receipt of the transaction.
+    pub receipt: OpReceiptEnvelope,
+    /// The accounts touched by the transaction, sorted by address.
+    pub accounts: Vec<AccountTrace>,
+}
+
+/// An account touched by a transaction, along with its state before and after the transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AccountTrace {
+    /// The address of the account.
+    pub address: Address,
+    /// The state of the account before the transaction, or `None` if it did not exist.
+    pub old: Option<AccountState>,
+    /// The state of the account after the transaction, or `None` if it was destroyed.
+    pub new: Option<AccountState>,
+    /// The storage slots changed by the transaction, sorted by slot.
+    pub storage: Vec<StorageTrace>,
+}
+
+/// The state of an account, excluding its storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AccountState {
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The hash of the account's code.
+    pub code_hash: B256,
+}
+
+impl From<&AccountInfo> for AccountState {
+    fn from(info: &AccountInfo) -> Self {
+        Self { balance: info.balance, nonce: info.nonce, code_hash: info.code_hash }
+    }
+}
+
+impl From<&TrieAccount> for AccountState {
+    fn from(account: &TrieAccount) -> Self {
+        Self { balance: account.balance, nonce: account.nonce, code_hash: account.code_hash }
+    }
+}
+
+/// A storage slot changed by a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageTrace {
+    /// The storage slot.
+    pub slot: U256,
+    /// The value of the slot before the transaction.
+    pub old: U256,
+    /// The value of the slot after the transaction.
+    pub new: U256,
+}
+
+/// The block-level state diff, derived by diffing the parent state trie against the state trie
+/// after the block's changes have been applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateDiff {
+    /// The accounts that differ between the two state tries, sorted by hashed address.
+    pub accounts: Vec<AccountDiff>,
+}
+
+/// An account that differs between two state tries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AccountDiff {
+    /// The hashed address of the account, which is its key in the state trie.
+    pub hashed_address: B256,
+    /// The address of the account, if its preimage is known.
+    pub address: Option<Address>,
+    /// The account in the old state trie, or `None` if it was inserted.
+    pub old: Option<TrieAccount>,
+    /// The account in the new state trie, or `None` if it was deleted.
+    pub new: Option<TrieAccount>,
+    /// The storage slots that differ between the old and new storage tries, sorted by hashed
+    /// slot.
+    pub storage: Vec<StorageDiff>,
+}
+
+/// A storage slot that differs between two storage tries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StorageDiff {
+    /// The hashed slot, which is its key in the storage trie.
+    pub hashed_slot: B256,
+    /// The slot, if its preimage is known.
+    pub slot: Option<U256>,
+    /// The value of the slot in the old storage trie.
+    pub old: U256,
+    /// The value of the slot in the new storage trie.
+    pub new: U256,
+}
+
+/// Assembles the [TransactionTrace]s of a block from the state changes recorded during its
+/// execution.
+///
+/// The state of an account before its first change is taken from the block's [BundleState], or
+/// loaded through `pre_state` if the account is missing from the bundle, and from the preceding
+/// change afterwards.
+pub(crate) fn trace_transactions<F>(
+    changes: Vec<(StateChangeSource, EvmState)>,
+    bundle: &BundleState,
+    mut pre_state: F,
+    tx_hashes: Vec<B256>,
+    gas_used: Vec<u64>,
+    receipts: &[OpReceiptEnvelope],
+) -> TrieDBResult<Vec<TransactionTrace>>
+where
+    F: FnMut(&Address) -> TrieDBResult<Option<AccountState>>,
+{
+    let mut traces = tx_hashes
+        .into_iter()
+        .zip(gas_used)
+        .zip(receipts.iter().cloned())
+        .map(|((tx_hash, gas_used), receipt)| TransactionTrace {
+            tx_hash,
+            gas_used,
+            receipt,
+            accounts: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut latest = HashMap::<Address, Option<AccountState>>::default();
+    for (source, state) in changes {
+        let mut touched =
+            state.iter().filter(|(_, account)| account.is_touched()).collect::<Vec<_>>();
+        touched.sort_by_key(|(address, _)| **address);
+
+        for (address, account) in touched {
+            let new = (!account.is_selfdestructed()).then(|| AccountState::from(&account.info));
+            let old = match latest.insert(*address, new) {
+                Some(old) => old,
+                None => match bundle.account(address) {
+                    Some(bundle_account) => {
+                        bundle_account.original_info.as_ref().map(AccountState::from)
+                    }
+                    None => pre_state(address)?,
+                },
+            };
+
+            let StateChangeSource::Transaction(index) = source else {
+                continue;
+            };
+            let Some(trace) = traces.get_mut(index) else {
+                continue;
+            };
+
+            let mut storage = account
+                .storage
+                .iter()
+                .filter(|(_, slot)| slot.is_changed())
+                .map(|(slot, value)| StorageTrace {
+                    slot: *slot,
+                    old: value.original_value,
+                    new: value.present_value,
+                })
+                .collect::<Vec<_>>();
+            storage.sort_by_key(|storage| storage.slot);
+
+            trace.accounts.push(AccountTrace { address: *address, old, new, storage });
+        }
+    }
+
+    Ok(traces)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_consensus::{Receipt, ReceiptWithBloom};
+    use alloy_primitives::{Bloom, address};
+    use revm::state::{Account, EvmStorageSlot};
+
+    fn account(balance: u64) -> Account {
+        let mut account =
+            Account::from(AccountInfo { balance: U256::from(balance), ..Default::default() });
+        account.mark_touch();
+        account
+    }
+
+    #[test]
+    fn test_trace_transactions() {
+        let (alice, bob) = (
+            address!("0x00000000000000000000000000000000000000a1"),
+            address!("0x00000000000000000000000000000000000000b0"),
+        );
+
+        let mut first = account(10);
+        first
+            .storage
+            .insert(U256::from(1), EvmStorageSlot::new_changed(U256::ZERO, U256::from(7), 0));
+        let mut untouched = account(0);
+        untouched.unmark_touch();
+        let changes = vec![
+            (
+                StateChangeSource::Transaction(0),
+                EvmState::from_iter([(alice, first), (bob, untouched)]),
+            ),
+            (
+                StateChangeSource::Transaction(1),
+                EvmState::from_iter([(alice, account(5)), (bob, account(1))]),
+            ),
+        ];
+
+        // Neither account is in the bundle, so their pre-state is loaded.
+        let pre_state = |address: &Address| {
+            Ok((*address == alice).then_some(AccountState {
+                balance: U256::from(20),
+                nonce: 0,
+                code_hash: B256::ZERO,
+            }))
+        };
+        let receipt =
+            OpReceiptEnvelope::Legacy(ReceiptWithBloom::new(Receipt::default(), Bloom::ZERO));
+        let traces = trace_transactions(
+            changes,
+            &BundleState::default(),
+            pre_state,
+            vec![B256::repeat_byte(0x01), B256::repeat_byte(0x02)],
+            vec![21_000, 42_000],
+            &[receipt.clone(), receipt],
+        )
+        .unwrap();
+
+        let balance = |state: Option<AccountState>| state.map(|state| state.balance.to::<u64>());
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].gas_used, 21_000);
+        assert_eq!(traces[0].accounts.len(), 1);
+        assert_eq!(balance(traces[0].accounts[0].old), Some(20));
+        assert_eq!(balance(traces[0].accounts[0].new), Some(10));
+        assert_eq!(
+            traces[0].accounts[0].storage,
+            vec![StorageTrace { slot: U256::from(1), old: U256::ZERO, new: U256::from(7) }]
+        );
+
+        // The pre-state of an account is taken from its preceding change.
+        let accounts = &traces[1].accounts;
+        assert_eq!(
+            accounts.iter().map(|account| account.address).collect::<Vec<_>>(),
+            vec![alice, bob]
+        );
+        assert_eq!((balance(accounts[0].old), balance(accounts[0].new)), (Some(10), Some(5)));
+        assert_eq!((balance(accounts[1].old), balance(accounts[1].new)), (None, Some(1)));
+    }
+}
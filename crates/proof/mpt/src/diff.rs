@@ -0,0 +1,320 @@
+//! This module contains the [diff_tries] function, which lazily computes the leaf-level
+//! differences between two (partially blinded) [TrieNode]s.
+
+use crate::{BRANCH_CHILDREN, TrieLeafIter, TrieNode, TrieProvider, errors::TrieNodeResult};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::Bytes;
+use alloy_trie::Nibbles;
+use core::{cmp::Ordering, iter::Peekable};
+
+/// A single difference between the leaves of two tries, as yielded by [diff_tries].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieDiff {
+    /// The key is only present in the new trie.
+    Inserted {
+        /// The key of the leaf.
+        key: Nibbles,
+        /// The value of the leaf in the new trie.
+        value: Bytes,
+    },
+    /// The key is present in both tries, with different values.
+    Modified {
+        /// The key of the leaf.
+        key: Nibbles,
+        /// The value of the leaf in the old trie.
+        old: Bytes,
+        /// The value of the leaf in the new trie.
+        new: Bytes,
+    },
+    /// The key is only present in the old trie.
+    Deleted {
+        /// The key of the leaf.
+        key: Nibbles,
+        /// The value of the leaf in the old trie.
+        value: Bytes,
+    },
+}
+
+impl TrieDiff {
+    /// Returns the key of the leaf that differs.
+    pub const fn key(&self) -> &Nibbles {
+        match self {
+            Self::Inserted { key, .. } | Self::Modified { key, .. } | Self::Deleted { key, .. } => {
+                key
+            }
+        }
+    }
+}
+
+/// Computes the differences between the leaves of the `old` and `new` tries, in ascending key
+/// order.
+///
+/// The differences are computed lazily. Subtrees that are identical in both tries, such as blinded
+/// nodes with equal commitments, are skipped without being unblinded, so only the nodes along the
+/// modified paths are fetched through the [TrieProvider], once the iterator reaches them.
+///
+/// ## Takes
+/// - `old` - The root of the old trie
+/// - `new` - The root of the new trie
+/// - `fetcher` - The preimage fetcher for blinded nodes in either trie
+///
+/// ## Returns
+/// - A [TrieDiffIter] over the differences between the two tries. The iterator yields an error and
+///   stops if a blinded node along a modified path could not be unblinded.
+pub fn diff_tries<'a, F: TrieProvider>(
+    old: &TrieNode,
+    new: &TrieNode,
+    fetcher: &'a F,
+) -> TrieDiffIter<'a, F> {
+    let stack =
+        vec![DiffFrame::Nodes { path: Nibbles::default(), old: old.clone(), new: new.clone() }];
+    TrieDiffIter { fetcher, stack }
+}
+
+/// A [TrieDiffIter] lazily iterates over the [TrieDiff]s between two tries in ascending key
+/// order. It is created by [diff_tries].
+#[derive(Debug)]
+pub struct TrieDiffIter<'a, F: TrieProvider> {
+    /// The preimage fetcher for blinded nodes.
+    fetcher: &'a F,
+    /// The pairs of subtrees that are yet to be compared. The next pair to compare is at the top
+    /// of the stack.
+    stack: Vec<DiffFrame<'a, F>>,
+}
+
+/// A pair of subtrees located at the same path, that are yet to be compared by a [TrieDiffIter].
+#[derive(Debug)]
+enum DiffFrame<'a, F: TrieProvider> {
+    /// Two subtrees that are compared node by node while their shapes match.
+    Nodes {
+        /// The path of both subtrees.
+        path: Nibbles,
+        /// The old subtree.
+        old: TrieNode,
+        /// The new subtree.
+        new: TrieNode,
+    },
+    /// Two subtrees of diverging shapes, whose leaves are merged.
+    Leaves {
+        /// The path of both subtrees.
+        path: Nibbles,
+        /// The leaves of the old subtree, keyed relative to `path`.
+        old: Peekable<TrieLeafIter<'a, F>>,
+        /// The leaves of the new subtree, keyed relative to `path`.
+        new: Peekable<TrieLeafIter<'a, F>>,
+    },
+}
+
+impl<'a, F: TrieProvider> TrieDiffIter<'a, F> {
+    /// Compares the subtrees `old` and `new`, which are both located at `path`, pushing the pairs
+    /// of their children that differ onto the stack.
+    fn expand(
+        &mut self,
+        path: Nibbles,
+        mut old: TrieNode,
+        mut new: TrieNode,
+    ) -> TrieNodeResult<()> {
+        if old == new {
+            return Ok(());
+        }
+
+        old.unblind(self.fetcher)?;
+        new.unblind(self.fetcher)?;
+
+        match (old, new) {
+            (TrieNode::Branch { stack: old_stack }, TrieNode::Branch { stack: new_stack }) => {
+                let children = old_stack
+                    .into_iter()
+                    .zip(new_stack)
+                    .take(BRANCH_CHILDREN)
+                    .enumerate()
+                    .collect::<Vec<_>>();
+
+                // Push the children in reverse, such that the lowest nibble is compared first.
+                for (i, (old, new)) in children.into_iter().rev() {
+                    let mut child_path = path;
+                    child_path.push(i as u8);
+                    self.stack.push(DiffFrame::Nodes { path: child_path, old, new });
+                }
+            }
+            (
+                TrieNode::Extension { prefix: old_prefix, node: old_node },
+                TrieNode::Extension { prefix: new_prefix, node: new_node },
+            ) if old_prefix == new_prefix => {
+                let path = path.join(&old_prefix);
+                self.stack.push(DiffFrame::Nodes { path, old: *old_node, new: *new_node });
+            }
+            (old, new) => {
+                // The shapes of the subtrees diverge, so fall back to merging their leaves.
+                self.stack.push(DiffFrame::Leaves {
+                    path,
+                    old: TrieLeafIter::new(old, self.fetcher).peekable(),
+                    new: TrieLeafIter::new(new, self.fetcher).peekable(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: TrieProvider> Iterator for TrieDiffIter<'_, F> {
+    type Item = TrieNodeResult<TrieDiff>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(frame) = self.stack.pop() {
+            let result = match frame {
+                DiffFrame::Nodes { path, old, new } => self.expand(path, old, new).map(|_| None),
+                DiffFrame::Leaves { path, mut old, mut new } => {
+                    match merge_next(&path, &mut old, &mut new) {
+                        Some(result) => {
+                            self.stack.push(DiffFrame::Leaves { path, old, new });
+                            result
+                        }
+                        None => Ok(None),
+                    }
+                }
+            };
+
+            match result {
+                Ok(Some(diff)) => return Some(Ok(diff)),
+                Ok(None) => {}
+                Err(e) => {
+                    self.stack.clear();
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Takes the next leaf from either of two sorted leaf iterators, returning the difference between
+/// them, if any. Returns `None` once both iterators are exhausted.
+fn merge_next<I>(
+    path: &Nibbles,
+    old: &mut Peekable<I>,
+    new: &mut Peekable<I>,
+) -> Option<TrieNodeResult<Option<TrieDiff>>>
+where
+    I: Iterator<Item = TrieNodeResult<(Nibbles, Bytes)>>,
+{
+    // Errors are ordered first, so that they are taken from their iterator and returned.
+    let ordering = match (old.peek(), new.peek()) {
+        (None, None) => return None,
+        (Some(Err(_)), _) | (Some(_), None) => Ordering::Less,
+        (_, Some(Err(_))) | (None, Some(_)) => Ordering::Greater,
+        (Some(Ok((old_key, _))), Some(Ok((new_key, _)))) => old_key.cmp(new_key),
+    };
+
+    let diff = match ordering {
+        Ordering::Less => {
+            old.next()?.map(|(key, value)| Some(TrieDiff::Deleted { key: path.join(&key), value }))
+        }
+        Ordering::Greater => {
+            new.next()?.map(|(key, value)| Some(TrieDiff::Inserted { key: path.join(&key), value }))
+        }
+        Ordering::Equal => {
+            let (key, old_value) = old.next()?.ok()?;
+            let (_, new_value) = new.next()?.ok()?;
+            Ok((old_value != new_value).then(|| TrieDiff::Modified {
+                key: path.join(&key),
+                old: old_value,
+                new: new_value,
+            }))
+        }
+    };
+    Some(diff)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ProofTrieProvider;
+    use alloc::collections::{BTreeMap, BTreeSet};
+    use alloy_primitives::B256;
+    use alloy_trie::{HashBuilder, proof::ProofRetainer};
+    use proptest::{collection::btree_map, prelude::*};
+
+    /// Builds a blinded trie from the given entries, returning its root and all of its nodes.
+    fn build_trie(entries: &BTreeMap<B256, Bytes>) -> (TrieNode, Vec<Bytes>) {
+        let targets = entries.keys().map(Nibbles::unpack).collect();
+        let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, value) in entries {
+            hb.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = TrieNode::new_blinded(hb.root());
+        (root, hb.take_proof_nodes().into_inner().into_values().collect())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_diff_tries(
+            old in btree_map(any::<[u8; 32]>(), any::<[u8; 8]>(), 0..64),
+            changes in btree_map(any::<[u8; 32]>(), any::<[u8; 8]>(), 0..16),
+            deletions in any::<proptest::sample::Index>(),
+        ) {
+            let old = old
+                .into_iter()
+                .map(|(k, v)| (B256::from(k), Bytes::copy_from_slice(&v)))
+                .collect::<BTreeMap<_, _>>();
+
+            // Derive the new trie by deleting a prefix of the old entries, modifying the next
+            // entry, and inserting the changes.
+            let mut new = old.clone();
+            let deleted = if old.is_empty() { 0 } else { deletions.index(old.len()) };
+            let keys = old.keys().copied().collect::<Vec<_>>();
+            for key in &keys[..deleted] {
+                new.remove(key);
+            }
+            if let Some(key) = keys.get(deleted) {
+                new.insert(*key, Bytes::from_static(&[0xFF; 9]));
+            }
+            new.extend(changes.into_iter().map(|(k, v)| (B256::from(k), Bytes::copy_from_slice(&v))));
+
+            let mut expected = Vec::new();
+            for key in old.keys().chain(new.keys()).collect::<BTreeSet<_>>() {
+                let nibbles = Nibbles::unpack(key);
+                match (old.get(key), new.get(key)) {
+                    (Some(value), None) => {
+                        expected.push(TrieDiff::Deleted { key: nibbles, value: value.clone() })
+                    }
+                    (None, Some(value)) => {
+                        expected.push(TrieDiff::Inserted { key: nibbles, value: value.clone() })
+                    }
+                    (Some(old), Some(new)) if old != new => expected.push(TrieDiff::Modified {
+                        key: nibbles,
+                        old: old.clone(),
+                        new: new.clone(),
+                    }),
+                    _ => {}
+                }
+            }
+
+            let (old_root, old_nodes) = build_trie(&old);
+            let (new_root, new_nodes) = build_trie(&new);
+            let provider = ProofTrieProvider::new(old_nodes.into_iter().chain(new_nodes));
+
+            let diffs = diff_tries(&old_root, &new_root, &provider).collect::<TrieNodeResult<Vec<_>>>().unwrap();
+            prop_assert_eq!(diffs, expected);
+        }
+    }
+
+    #[test]
+    fn test_diff_tries_identical_skips_unblinding() {
+        let root = TrieNode::new_blinded(B256::repeat_byte(0xFF));
+        assert_eq!(diff_tries(&root, &root, &ProofTrieProvider::default()).next(), None);
+    }
+
+    #[test]
+    fn test_diff_tries_missing_node() {
+        let old = TrieNode::new_blinded(B256::repeat_byte(0xFF));
+        let new = TrieNode::new_blinded(B256::repeat_byte(0xEE));
+        let mut diffs = diff_tries(&old, &new, &ProofTrieProvider::default());
+        assert!(matches!(diffs.next(), Some(Err(_))));
+        assert_eq!(diffs.next(), None);
+    }
+}
@@ -1,6 +1,8 @@
 //! Errors for the `kona-derive` crate.
 
 use alloc::string::String;
+use alloy_primitives::B256;
+use alloy_trie::Nibbles;
 use thiserror::Error;
 
 /// A [Result] type alias where the error is [TrieNodeError].
@@ -43,3 +45,28 @@ pub enum OrderedListWalkerError {
     #[error("{0}")]
     TrieNode(#[from] TrieNodeError),
 }
+
+/// A [Result] type alias where the error is [ProofVerificationError].
+pub type ProofVerificationResult<T> = Result<T, ProofVerificationError>;
+
+/// An error type for merkle proof verification.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ProofVerificationError {
+    /// The proven value of a key does not match the expected value.
+    #[error("Proven value does not match the expected value for key {0:?}")]
+    ValueMismatch(Nibbles),
+    /// The root reconstructed from a range proof does not match the expected root.
+    #[error("Root mismatch: expected {expected}, got {actual}")]
+    RootMismatch {
+        /// The expected root.
+        expected: B256,
+        /// The reconstructed root.
+        actual: B256,
+    },
+    /// The entries of a range proof are not sorted, or lie outside of the range.
+    #[error("Range proof entries are not sorted or lie outside of the range")]
+    InvalidRange,
+    /// Trie node error, e.g. a node that is missing from the proof.
+    #[error("{0}")]
+    TrieNode(#[from] TrieNodeError),
+}
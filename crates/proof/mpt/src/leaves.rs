@@ -0,0 +1,224 @@
+//! This module contains the [TrieLeafIter] struct, which allows for lazily iterating over the
+//! leaves of a [TrieNode] in key order.
+
+use crate::{BRANCH_CHILDREN, TrieNode, TrieProvider, errors::TrieNodeResult};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::Bytes;
+use alloy_trie::Nibbles;
+use core::cmp::Ordering;
+
+/// A [TrieLeafIter] lazily iterates over the leaves of a (partially blinded) [TrieNode] in
+/// ascending key order. Blinded nodes are only unblinded through the [TrieProvider] once the
+/// iterator reaches them.
+#[derive(Debug, Clone)]
+pub struct TrieLeafIter<'a, F: TrieProvider> {
+    /// The root of the trie.
+    root: TrieNode,
+    /// The preimage fetcher for blinded nodes.
+    fetcher: &'a F,
+    /// The nodes that are yet to be visited, along with their paths. The next node to visit is
+    /// at the top of the stack.
+    stack: Vec<(Nibbles, TrieNode)>,
+    /// If set, iteration stops at the first leaf whose key does not start with the prefix.
+    prefix: Option<Nibbles>,
+}
+
+impl<'a, F: TrieProvider> TrieLeafIter<'a, F> {
+    /// Creates a new [TrieLeafIter] over the leaves of the trie rooted at `root`.
+    pub fn new(root: TrieNode, fetcher: &'a F) -> Self {
+        let stack = vec![(Nibbles::default(), root.clone())];
+        Self { root, fetcher, stack, prefix: None }
+    }
+
+    /// Repositions the iterator at the first leaf whose key is greater than or equal to `key`.
+    /// Only the blinded nodes on the path to `key` are unblinded.
+    pub fn seek(&mut self, key: &Nibbles) -> TrieNodeResult<()> {
+        self.stack.clear();
+        self.prefix = None;
+
+        let mut path = Nibbles::default();
+        let mut node = self.root.clone();
+        loop {
+            match node {
+                TrieNode::Empty => return Ok(()),
+                TrieNode::Blinded { .. } => node.unblind(self.fetcher)?,
+                TrieNode::Leaf { ref prefix, .. } => {
+                    if path.join(prefix) >= *key {
+                        self.stack.push((path, node));
+                    }
+                    return Ok(());
+                }
+                TrieNode::Extension { prefix, node: child } => {
+                    let full = path.join(&prefix);
+                    let len = full.len().min(key.len());
+                    match full.slice(..len).cmp(&key.slice(..len)) {
+                        Ordering::Less => return Ok(()),
+                        Ordering::Equal if full.len() < key.len() => {
+                            path = full;
+                            node = *child;
+                        }
+                        _ => {
+                            self.stack.push((full, *child));
+                            return Ok(());
+                        }
+                    }
+                }
+                TrieNode::Branch { stack } => {
+                    let Some(nibble) = key.get(path.len()) else {
+                        // The key is a prefix of the path, so every leaf below the branch is
+                        // greater than the key.
+                        self.stack.push((path, TrieNode::Branch { stack }));
+                        return Ok(());
+                    };
+
+                    let mut children = stack.into_iter().take(BRANCH_CHILDREN).collect::<Vec<_>>();
+                    node = core::mem::replace(&mut children[nibble as usize], TrieNode::Empty);
+                    self.push_children(
+                        &path,
+                        children.into_iter().skip(nibble as usize + 1),
+                        nibble + 1,
+                    );
+                    path.push(nibble);
+                }
+            }
+        }
+    }
+
+    /// Repositions the iterator at the first leaf whose key starts with `prefix`, and limits the
+    /// iteration to the leaves whose keys start with `prefix`.
+    pub fn seek_prefix(&mut self, prefix: &Nibbles) -> TrieNodeResult<()> {
+        self.seek(prefix)?;
+        self.prefix = Some(*prefix);
+        Ok(())
+    }
+
+    /// Pushes the given children of a branch node at `path` onto the stack, such that the child
+    /// with the lowest nibble is visited first. `first_nibble` is the nibble of the first child.
+    fn push_children<I>(&mut self, path: &Nibbles, children: I, first_nibble: u8)
+    where
+        I: DoubleEndedIterator<Item = TrieNode> + ExactSizeIterator,
+    {
+        for (i, child) in children.enumerate().rev() {
+            if child != TrieNode::Empty {
+                let mut child_path = *path;
+                child_path.push(first_nibble + i as u8);
+                self.stack.push((child_path, child));
+            }
+        }
+    }
+}
+
+impl<F: TrieProvider> Iterator for TrieLeafIter<'_, F> {
+    type Item = TrieNodeResult<(Nibbles, Bytes)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((path, mut node)) = self.stack.pop() {
+            match node {
+                TrieNode::Empty => {}
+                TrieNode::Blinded { .. } => {
+                    if let Err(e) = node.unblind(self.fetcher) {
+                        self.stack.clear();
+                        return Some(Err(e));
+                    }
+                    self.stack.push((path, node));
+                }
+                TrieNode::Leaf { prefix, value } => {
+                    let key = path.join(&prefix);
+                    if self.prefix.is_some_and(|prefix| !key.starts_with(&prefix)) {
+                        self.stack.clear();
+                        return None;
+                    }
+                    return Some(Ok((key, value)));
+                }
+                TrieNode::Extension { prefix, node } => {
+                    self.stack.push((path.join(&prefix), *node));
+                }
+                TrieNode::Branch { stack } => {
+                    self.push_children(&path, stack.into_iter().take(BRANCH_CHILDREN), 0);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl TrieNode {
+    /// Returns a [TrieLeafIter] that lazily iterates over the leaves of the trie rooted at `self`
+    /// in ascending key order.
+    pub fn leaves<'a, F: TrieProvider>(&self, fetcher: &'a F) -> TrieLeafIter<'a, F> {
+        TrieLeafIter::new(self.clone(), fetcher)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{NoopTrieProvider, ProofTrieProvider};
+    use alloc::collections::BTreeMap;
+    use alloy_primitives::B256;
+    use alloy_trie::{HashBuilder, proof::ProofRetainer};
+    use proptest::{collection::btree_map, prelude::*};
+
+    /// Builds a blinded trie from the given entries, returning its root and a provider for all of
+    /// its nodes.
+    fn build_trie(entries: &BTreeMap<B256, Bytes>) -> (TrieNode, ProofTrieProvider) {
+        let targets = entries.keys().map(Nibbles::unpack).collect();
+        let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, value) in entries {
+            hb.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = TrieNode::new_blinded(hb.root());
+        (root, ProofTrieProvider::new(hb.take_proof_nodes().into_inner().into_values()))
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_leaf_iter_in_order(
+            entries in btree_map(any::<[u8; 32]>(), any::<[u8; 8]>(), 1..128),
+            seek in any::<[u8; 32]>(),
+        ) {
+            let entries = entries
+                .into_iter()
+                .map(|(k, v)| (B256::from(k), Bytes::copy_from_slice(&v)))
+                .collect::<BTreeMap<_, _>>();
+            let (root, provider) = build_trie(&entries);
+
+            let expected =
+                entries.iter().map(|(k, v)| (Nibbles::unpack(k), v.clone())).collect::<Vec<_>>();
+            let leaves = root.leaves(&provider).collect::<TrieNodeResult<Vec<_>>>().unwrap();
+            prop_assert_eq!(&leaves, &expected);
+
+            // Seeking positions the iterator at the first key greater than or equal to the target.
+            let target = Nibbles::unpack(seek);
+            let mut iter = root.leaves(&provider);
+            iter.seek(&target).unwrap();
+            let sought = iter.collect::<TrieNodeResult<Vec<_>>>().unwrap();
+            let expected_sought =
+                expected.iter().filter(|(k, _)| *k >= target).cloned().collect::<Vec<_>>();
+            prop_assert_eq!(sought, expected_sought);
+
+            // Seeking a prefix limits the iteration to the keys with that prefix.
+            let prefix = expected[0].0.slice(..2);
+            let mut iter = root.leaves(&provider);
+            iter.seek_prefix(&prefix).unwrap();
+            let prefixed = iter.collect::<TrieNodeResult<Vec<_>>>().unwrap();
+            let expected_prefixed =
+                expected.iter().filter(|(k, _)| k.starts_with(&prefix)).cloned().collect::<Vec<_>>();
+            prop_assert_eq!(prefixed, expected_prefixed);
+        }
+    }
+
+    #[test]
+    fn test_leaf_iter_missing_node() {
+        let mut iter = TrieNode::new_blinded(B256::repeat_byte(0xFF)).leaves(&NoopTrieProvider);
+        assert_eq!(iter.next(), None);
+
+        let mut iter =
+            TrieNode::new_blinded(B256::repeat_byte(0xFF)).leaves(&ProofTrieProvider::default());
+        assert!(matches!(iter.next(), Some(Err(_))));
+        assert_eq!(iter.next(), None);
+    }
+}
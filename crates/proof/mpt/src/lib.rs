@@ -10,17 +10,33 @@
 extern crate alloc;
 
 mod errors;
-pub use errors::{OrderedListWalkerError, OrderedListWalkerResult, TrieNodeError, TrieNodeResult};
+pub use errors::{
+    OrderedListWalkerError, OrderedListWalkerResult, ProofVerificationError,
+    ProofVerificationResult, TrieNodeError, TrieNodeResult,
+};
 
 mod traits;
 pub use traits::{TrieHinter, TrieProvider};
 
 mod node;
+pub(crate) use node::BRANCH_CHILDREN;
 pub use node::TrieNode;
 
 mod list_walker;
 pub use list_walker::OrderedListWalker;
 
+mod proof;
+pub use proof::{
+    ProofTrieProvider, verify_account_proof, verify_multiproof, verify_proof, verify_range_proof,
+    verify_storage_proof,
+};
+
+mod leaves;
+pub use leaves::TrieLeafIter;
+
+mod diff;
+pub use diff::{TrieDiff, TrieDiffIter, diff_tries};
+
 mod noop;
 pub use noop::{NoopTrieHinter, NoopTrieProvider};
 
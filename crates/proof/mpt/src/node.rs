@@ -14,6 +14,9 @@ use alloy_trie::{EMPTY_ROOT_HASH, Nibbles};
 /// The length of the branch list when RLP encoded
 const BRANCH_LIST_LENGTH: usize = 17;
 
+/// The number of child nodes of a branch node, excluding its value.
+pub(crate) const BRANCH_CHILDREN: usize = BRANCH_LIST_LENGTH - 1;
+
 /// The length of a leaf or extension node's RLP encoded list
 const LEAF_OR_EXTENSION_LIST_LENGTH: usize = 2;
 
@@ -0,0 +1,394 @@
+//! Contains standalone verification of merkle proofs, multiproofs and range proofs against a
+//! trie root.
+
+use crate::{
+    TrieNode, TrieProvider,
+    errors::{ProofVerificationError, ProofVerificationResult, TrieNodeResult},
+};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_primitives::{Address, B256, Bytes, U256, keccak256};
+use alloy_rlp::Decodable;
+use alloy_trie::{Nibbles, TrieAccount};
+
+/// A [TrieProvider] that serves trie nodes from a set of proof nodes, keyed by their [keccak256]
+/// hash.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProofTrieProvider {
+    nodes: BTreeMap<B256, Bytes>,
+}
+
+impl ProofTrieProvider {
+    /// Creates a new [ProofTrieProvider] from the given RLP encoded proof nodes.
+    pub fn new<I, T>(nodes: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let nodes = nodes
+            .into_iter()
+            .map(|node| (keccak256(node.as_ref()), Bytes::copy_from_slice(node.as_ref())))
+            .collect();
+        Self { nodes }
+    }
+}
+
+impl TrieProvider for ProofTrieProvider {
+    type Error = String;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        let node = self.nodes.get(&key).ok_or_else(|| format!("Missing proof node: {key}"))?;
+        TrieNode::decode(&mut node.as_ref()).map_err(|e| e.to_string())
+    }
+}
+
+/// Verifies a merkle proof for a single key against the given root.
+///
+/// ## Takes
+/// - `root` - The root of the trie.
+/// - `key` - The nibbles representation of the key.
+/// - `expected_value` - The expected value at the key, or `None` to verify that the key is absent.
+/// - `proof` - The RLP encoded trie nodes on the path to the key.
+///
+/// ## Returns
+/// - `Ok(())` - The proof is valid.
+/// - `Err(_)` - The proof is incomplete, or does not prove the expected value.
+pub fn verify_proof<T: AsRef<[u8]>>(
+    root: B256,
+    key: Nibbles,
+    expected_value: Option<&[u8]>,
+    proof: &[T],
+) -> ProofVerificationResult<()> {
+    verify_multiproof(root, [(key, expected_value)], proof)
+}
+
+/// Verifies a multiproof for a set of keys against the given root. Proof nodes shared between the
+/// paths of several keys only need to be present once.
+///
+/// ## Takes
+/// - `root` - The root of the trie.
+/// - `entries` - The keys, along with their expected values. A value of `None` verifies that the
+///   key is absent.
+/// - `proof` - The RLP encoded trie nodes on the paths to all keys.
+///
+/// ## Returns
+/// - `Ok(())` - The proof is valid for all entries.
+/// - `Err(_)` - The proof is incomplete, or does not prove the expected value of an entry.
+pub fn verify_multiproof<'a, I, T>(
+    root: B256,
+    entries: I,
+    proof: &[T],
+) -> ProofVerificationResult<()>
+where
+    I: IntoIterator<Item = (Nibbles, Option<&'a [u8]>)>,
+    T: AsRef<[u8]>,
+{
+    let provider = ProofTrieProvider::new(proof);
+    let mut root_node = TrieNode::new_blinded(root);
+
+    for (key, expected_value) in entries {
+        let value = root_node.open(&key, &provider)?;
+        if value.map(|v| &v[..]) != expected_value {
+            return Err(ProofVerificationError::ValueMismatch(key));
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies a range proof, proving that the given entries are exactly the leaves of the trie
+/// within the inclusive key range `[start, end]`.
+///
+/// The proof must contain the trie nodes on the paths to both `start` and `end`. The trie is
+/// reconstructed from the boundary paths and the given entries, and its root is compared against
+/// the expected root.
+///
+/// ## Takes
+/// - `root` - The root of the trie.
+/// - `start` - The nibbles representation of the first key of the range.
+/// - `end` - The nibbles representation of the last key of the range.
+/// - `entries` - The leaves within the range, sorted by key in strictly ascending order.
+/// - `proof` - The RLP encoded trie nodes on the paths to `start` and `end`.
+///
+/// ## Returns
+/// - `Ok(())` - The proof is valid.
+/// - `Err(_)` - The proof is incomplete, the entries are malformed, or the entries are not exactly
+///   the leaves within the range.
+pub fn verify_range_proof<T: AsRef<[u8]>>(
+    root: B256,
+    start: Nibbles,
+    end: Nibbles,
+    entries: &[(Nibbles, Bytes)],
+    proof: &[T],
+) -> ProofVerificationResult<()> {
+    if start > end ||
+        entries.windows(2).any(|w| w[0].0 >= w[1].0) ||
+        entries.iter().any(|(key, _)| *key < start || *key > end)
+    {
+        return Err(ProofVerificationError::InvalidRange);
+    }
+
+    let provider = ProofTrieProvider::new(proof);
+    let mut root_node = TrieNode::new_blinded(root);
+
+    // Remove all leaves within the range from the trie, and re-insert the given entries.
+    prune_range(&mut root_node, Some(start), Some(end), &provider)?;
+    for (key, value) in entries {
+        root_node.insert(key, value.clone(), &provider)?;
+    }
+
+    let actual = root_node.blind();
+    if actual != root {
+        return Err(ProofVerificationError::RootMismatch { expected: root, actual });
+    }
+
+    Ok(())
+}
+
+/// Verifies an `eth_getProof`-style account proof against the given state root.
+///
+/// ## Takes
+/// - `state_root` - The root of the state trie.
+/// - `address` - The address of the account.
+/// - `account` - The expected account, or `None` to verify that the account does not exist.
+/// - `proof` - The RLP encoded trie nodes on the path to the account.
+///
+/// ## Returns
+/// - `Ok(())` - The proof is valid.
+/// - `Err(_)` - The proof is incomplete, or does not prove the expected account.
+pub fn verify_account_proof<T: AsRef<[u8]>>(
+    state_root: B256,
+    address: Address,
+    account: Option<&TrieAccount>,
+    proof: &[T],
+) -> ProofVerificationResult<()> {
+    let encoded = account.map(alloy_rlp::encode);
+    verify_proof(state_root, Nibbles::unpack(keccak256(address)), encoded.as_deref(), proof)
+}
+
+/// Verifies an `eth_getProof`-style storage proof against the given storage root. A zero value
+/// verifies that the slot is absent from the storage trie.
+///
+/// ## Takes
+/// - `storage_root` - The root of the account's storage trie.
+/// - `slot` - The storage slot.
+/// - `value` - The expected value of the storage slot.
+/// - `proof` - The RLP encoded trie nodes on the path to the storage slot.
+///
+/// ## Returns
+/// - `Ok(())` - The proof is valid.
+/// - `Err(_)` - The proof is incomplete, or does not prove the expected value.
+pub fn verify_storage_proof<T: AsRef<[u8]>>(
+    storage_root: B256,
+    slot: B256,
+    value: U256,
+    proof: &[T],
+) -> ProofVerificationResult<()> {
+    let encoded = (!value.is_zero()).then(|| alloy_rlp::encode(value));
+    verify_proof(storage_root, Nibbles::unpack(keccak256(slot)), encoded.as_deref(), proof)
+}
+
+/// Removes all leaves within the inclusive range `[start, end]` from the trie rooted at `node`.
+/// The bounds are relative to the position of `node` within the trie, and an absent bound means
+/// that the whole subtrie lies on the inner side of it.
+///
+/// Only the nodes on the paths to the bounds are unblinded. Subtries that lie entirely within the
+/// range are removed without being unblinded.
+fn prune_range<F: TrieProvider>(
+    node: &mut TrieNode,
+    start: Option<Nibbles>,
+    end: Option<Nibbles>,
+    fetcher: &F,
+) -> TrieNodeResult<()> {
+    if start.is_none() && end.is_none() {
+        *node = TrieNode::Empty;
+        return Ok(());
+    }
+
+    match node {
+        TrieNode::Empty => Ok(()),
+        TrieNode::Blinded { .. } => {
+            node.unblind(fetcher)?;
+            prune_range(node, start, end, fetcher)
+        }
+        TrieNode::Leaf { prefix, .. } => {
+            let above_start = start.is_none_or(|start| *prefix >= start);
+            let below_end = end.is_none_or(|end| *prefix <= end);
+            if above_start && below_end {
+                *node = TrieNode::Empty;
+            }
+            Ok(())
+        }
+        TrieNode::Extension { prefix, node: child } => {
+            let len = prefix.len();
+            let (Some(start), Some(end)) =
+                (narrow_lower_bound(prefix, start, len), narrow_upper_bound(prefix, end, len))
+            else {
+                // The extension lies entirely outside of the range.
+                return Ok(());
+            };
+
+            if start.is_none() && end.is_none() {
+                *node = TrieNode::Empty;
+                Ok(())
+            } else {
+                prune_range(child, start, end, fetcher)
+            }
+        }
+        TrieNode::Branch { stack } => {
+            for (nibble, child) in stack.iter_mut().enumerate().take(16) {
+                let nibble = Nibbles::from_nibbles([nibble as u8]);
+                if let (Some(start), Some(end)) =
+                    (narrow_lower_bound(&nibble, start, 1), narrow_upper_bound(&nibble, end, 1))
+                {
+                    prune_range(child, start, end, fetcher)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Narrows the lower bound of a range to the subtrie below the given path segment of length
+/// `len`. Returns `None` if the subtrie lies entirely below the bound, `Some(None)` if it lies
+/// entirely above it, and `Some(Some(_))` with the remaining bound if the segment lies on the path
+/// to the bound.
+fn narrow_lower_bound(
+    segment: &Nibbles,
+    bound: Option<Nibbles>,
+    len: usize,
+) -> Option<Option<Nibbles>> {
+    let Some(bound) = bound else { return Some(None) };
+    let head = bound.slice(..len.min(bound.len()));
+    match segment.cmp(&head) {
+        core::cmp::Ordering::Less => None,
+        core::cmp::Ordering::Greater => Some(None),
+        core::cmp::Ordering::Equal => Some(Some(bound.slice(len.min(bound.len())..))),
+    }
+}
+
+/// Narrows the upper bound of a range to the subtrie below the given path segment of length
+/// `len`. Returns `None` if the subtrie lies entirely above the bound, `Some(None)` if it lies
+/// entirely below it, and `Some(Some(_))` with the remaining bound if the segment lies on the path
+/// to the bound.
+fn narrow_upper_bound(
+    segment: &Nibbles,
+    bound: Option<Nibbles>,
+    len: usize,
+) -> Option<Option<Nibbles>> {
+    let Some(bound) = bound else { return Some(None) };
+    let head = bound.slice(..len.min(bound.len()));
+    match segment.cmp(&head) {
+        core::cmp::Ordering::Greater => None,
+        core::cmp::Ordering::Less => Some(None),
+        core::cmp::Ordering::Equal => Some(Some(bound.slice(len.min(bound.len())..))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+    use alloy_trie::{HashBuilder, proof::ProofRetainer};
+    use proptest::{collection::btree_map, prelude::*};
+
+    /// Builds a trie from the given sorted entries, returning its root and the proof nodes on the
+    /// paths to the given targets.
+    fn build_trie(entries: &BTreeMap<B256, Bytes>, targets: Vec<Nibbles>) -> (B256, Vec<Bytes>) {
+        let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, value) in entries {
+            hb.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hb.root();
+        (root, hb.take_proof_nodes().into_inner().into_values().collect())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_verify_multiproof(
+            entries in btree_map(any::<[u8; 32]>(), any::<[u8; 8]>(), 2..64),
+            absent in any::<[u8; 32]>(),
+        ) {
+            let entries = entries
+                .into_iter()
+                .map(|(k, v)| (B256::from(k), Bytes::copy_from_slice(&v)))
+                .collect::<BTreeMap<_, _>>();
+            prop_assume!(!entries.contains_key(&B256::from(absent)));
+
+            let mut targets = entries.keys().map(Nibbles::unpack).collect::<Vec<_>>();
+            targets.push(Nibbles::unpack(absent));
+            let (root, proof) = build_trie(&entries, targets);
+
+            let mut proven = entries
+                .iter()
+                .map(|(k, v)| (Nibbles::unpack(k), Some(v.as_ref())))
+                .collect::<Vec<_>>();
+            proven.push((Nibbles::unpack(absent), None));
+            prop_assert!(verify_multiproof(root, proven.clone(), &proof).is_ok());
+
+            // A proof for a different value must be rejected.
+            proven[0].1 = Some(&[0xFF; 9][..]);
+            prop_assert!(verify_multiproof(root, proven, &proof).is_err());
+        }
+
+        #[test]
+        fn test_verify_range_proof(
+            entries in btree_map(any::<[u8; 32]>(), any::<[u8; 8]>(), 4..64),
+            bounds in (any::<usize>(), any::<usize>()),
+        ) {
+            let entries = entries
+                .into_iter()
+                .map(|(k, v)| (B256::from(k), Bytes::copy_from_slice(&v)))
+                .collect::<BTreeMap<_, _>>();
+            let keys = entries.keys().copied().collect::<Vec<_>>();
+            let (a, b) = (bounds.0 % keys.len(), bounds.1 % keys.len());
+            let (start, end) = (Nibbles::unpack(keys[a.min(b)]), Nibbles::unpack(keys[a.max(b)]));
+
+            let (root, proof) = build_trie(&entries, vec![start, end]);
+            let range = entries
+                .iter()
+                .map(|(k, v)| (Nibbles::unpack(k), v.clone()))
+                .filter(|(k, _)| *k >= start && *k <= end)
+                .collect::<Vec<_>>();
+            prop_assert!(verify_range_proof(root, start, end, &range, &proof).is_ok());
+
+            // Omitting a leaf within the range must be rejected.
+            let mut omitted = range.clone();
+            omitted.remove(omitted.len() / 2);
+            prop_assert!(verify_range_proof(root, start, end, &omitted, &proof).is_err());
+        }
+    }
+
+    #[test]
+    fn test_verify_account_and_storage_proof() {
+        let address = Address::repeat_byte(0xAA);
+        let slot = B256::repeat_byte(0x01);
+        let value = U256::from(0xBEEF);
+
+        let storage = BTreeMap::from([(keccak256(slot), alloy_rlp::encode(value).into())]);
+        let (storage_root, storage_proof) =
+            build_trie(&storage, vec![Nibbles::unpack(keccak256(slot))]);
+
+        let account = TrieAccount { nonce: 1, storage_root, ..Default::default() };
+        let state = BTreeMap::from([(keccak256(address), alloy_rlp::encode(account).into())]);
+        let (state_root, account_proof) =
+            build_trie(&state, vec![Nibbles::unpack(keccak256(address))]);
+
+        verify_account_proof(state_root, address, Some(&account), &account_proof).unwrap();
+        verify_storage_proof(storage_root, slot, value, &storage_proof).unwrap();
+        verify_storage_proof(storage_root, B256::ZERO, U256::ZERO, &storage_proof).unwrap();
+
+        assert!(verify_account_proof(state_root, address, None, &account_proof).is_err());
+        assert!(verify_storage_proof(storage_root, slot, U256::ZERO, &storage_proof).is_err());
+        assert!(matches!(
+            verify_account_proof(state_root, address, Some(&account), &Vec::<Bytes>::new()),
+            Err(ProofVerificationError::TrieNode(_))
+        ));
+    }
+}
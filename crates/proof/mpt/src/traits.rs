@@ -81,4 +81,22 @@ pub trait TrieHinter {
         parent_hash: B256,
         op_payload_attributes: &OpPayloadAttributes,
     ) -> Result<(), Self::Error>;
+
+    /// Hints the host to fetch the merged execution witness for a range of consecutive blocks,
+    /// built on top of the parent block's state. The hint is optional, and is a no-op by default.
+    ///
+    /// ## Takes
+    /// - `parent_hash` - The hash of the parent block of the first block in the range.
+    /// - `block_count` - The number of blocks in the range.
+    ///
+    /// ## Returns
+    /// - Ok(()): If the hint was successful.
+    /// - Err(Self::Error): If the hint was unsuccessful.
+    fn hint_execution_witness_range(
+        &self,
+        _parent_hash: B256,
+        _block_count: u64,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
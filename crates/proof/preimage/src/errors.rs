@@ -48,3 +48,11 @@ pub enum ChannelError {
 
 /// A [Result] type for the [ChannelError] enum.
 pub type ChannelResult<T> = Result<T, ChannelError>;
+
+/// A [SocketEndpointParseError] is returned when a [SocketEndpoint] cannot be parsed from a string.
+///
+/// [SocketEndpoint]: crate::SocketEndpoint
+#[cfg(feature = "net")]
+#[derive(Error, Debug)]
+#[error("Invalid socket endpoint `{0}`, expected `tcp://<ip>:<port>` or `unix://<path>`")]
+pub struct SocketEndpointParseError(pub String);
@@ -33,3 +33,10 @@ pub use traits::{
 mod native_channel;
 #[cfg(feature = "std")]
 pub use native_channel::{BidirectionalChannel, NativeChannel};
+
+#[cfg(feature = "net")]
+mod net_channel;
+#[cfg(feature = "net")]
+pub use net_channel::{
+    ChannelKind, SessionChannels, SessionHeader, SocketChannel, SocketEndpoint, SocketListener,
+};
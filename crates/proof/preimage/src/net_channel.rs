@@ -0,0 +1,611 @@
+//! Socket implementation of the [Channel] trait, backed by [tokio]'s TCP and Unix domain socket
+//! streams.
+//!
+//! A client session consists of two connections to the same [SocketEndpoint], one for the hint
+//! channel and one for the preimage channel. Each connection opens with a [SessionHeader], which
+//! allows a [SocketListener] to pair the two connections of a session.
+
+use crate::{
+    Channel,
+    errors::{ChannelError, ChannelResult, SocketEndpointParseError},
+};
+use async_trait::async_trait;
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fmt::{self, Display},
+    io::{self, ErrorKind},
+    net::SocketAddr,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf},
+    net::{TcpListener, TcpStream},
+    sync::Mutex,
+    task::JoinSet,
+    time::Instant,
+};
+
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+/// The address of a socket that a preimage server listens on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketEndpoint {
+    /// A TCP socket, formatted as `tcp://<ip>:<port>`.
+    Tcp(SocketAddr),
+    /// A Unix domain socket, formatted as `unix://<path>`.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for SocketEndpoint {
+    type Err = SocketEndpointParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(addr) = s.strip_prefix("tcp://") {
+            return addr.parse().map(Self::Tcp).map_err(|_| SocketEndpointParseError(s.to_string()));
+        }
+
+        #[cfg(unix)]
+        if let Some(path) = s.strip_prefix("unix://") {
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+
+        Err(SocketEndpointParseError(s.to_string()))
+    }
+}
+
+impl Display for SocketEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "tcp://{addr}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SocketEndpoint {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SocketEndpoint {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <String as serde::Deserialize<'de>>::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// The channel that a connection carries within a client session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ChannelKind {
+    /// The hint channel.
+    Hint = 0,
+    /// The preimage channel.
+    Preimage = 1,
+}
+
+impl TryFrom<u8> for ChannelKind {
+    type Error = io::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Hint),
+            1 => Ok(Self::Preimage),
+            _ => Err(io::Error::new(ErrorKind::InvalidData, "Invalid channel kind")),
+        }
+    }
+}
+
+/// The header sent by the client at the start of every connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionHeader {
+    /// The ID of the client session, chosen by the client.
+    pub session: u64,
+    /// The channel that the connection carries.
+    pub kind: ChannelKind,
+}
+
+impl SessionHeader {
+    /// The length of an encoded [SessionHeader].
+    pub const LEN: usize = 1 + 8;
+
+    /// Encodes the [SessionHeader] as `kind (1 byte) ++ session (8 bytes, big-endian)`.
+    pub fn encode(&self) -> [u8; Self::LEN] {
+        let mut buf = [0u8; Self::LEN];
+        buf[0] = self.kind as u8;
+        buf[1..].copy_from_slice(&self.session.to_be_bytes());
+        buf
+    }
+
+    /// Decodes a [SessionHeader] from its encoding.
+    pub fn decode(buf: &[u8; Self::LEN]) -> io::Result<Self> {
+        let kind = ChannelKind::try_from(buf[0])?;
+        let session = u64::from_be_bytes(buf[1..].try_into().expect("length checked"));
+        Ok(Self { session, kind })
+    }
+}
+
+/// The hint and preimage channels of a client session.
+#[derive(Debug, Clone)]
+pub struct SessionChannels {
+    /// The hint channel.
+    pub hint: SocketChannel,
+    /// The preimage channel.
+    pub preimage: SocketChannel,
+}
+
+impl SessionChannels {
+    /// Connects to the preimage server listening on the given [SocketEndpoint], opening the hint
+    /// and preimage channels of the session with the given ID.
+    pub async fn connect(endpoint: &SocketEndpoint, session: u64) -> io::Result<Self> {
+        let hint =
+            SocketChannel::connect(endpoint, SessionHeader { session, kind: ChannelKind::Hint })
+                .await?;
+        let preimage = SocketChannel::connect(
+            endpoint,
+            SessionHeader { session, kind: ChannelKind::Preimage },
+        )
+        .await?;
+        Ok(Self { hint, preimage })
+    }
+}
+
+/// The read half of a socket stream.
+type BoxedReader = Box<dyn AsyncRead + Send + Unpin>;
+
+/// The write half of a socket stream.
+type BoxedWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// A [Channel] over a TCP or Unix domain socket stream.
+#[derive(Clone)]
+pub struct SocketChannel {
+    /// The read half of the stream.
+    read: Arc<Mutex<BoxedReader>>,
+    /// The write half of the stream.
+    write: Arc<Mutex<BoxedWriter>>,
+}
+
+impl fmt::Debug for SocketChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketChannel").finish_non_exhaustive()
+    }
+}
+
+impl SocketChannel {
+    /// Creates a new [SocketChannel] over the given stream.
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (read, write): (ReadHalf<S>, WriteHalf<S>) = tokio::io::split(stream);
+        Self {
+            read: Arc::new(Mutex::new(Box::new(read))),
+            write: Arc::new(Mutex::new(Box::new(write))),
+        }
+    }
+
+    /// Connects to the given [SocketEndpoint], and opens the connection with the given
+    /// [SessionHeader].
+    pub async fn connect(endpoint: &SocketEndpoint, header: SessionHeader) -> io::Result<Self> {
+        let channel = match endpoint {
+            SocketEndpoint::Tcp(addr) => {
+                let stream = TcpStream::connect(addr).await?;
+                stream.set_nodelay(true)?;
+                Self::new(stream)
+            }
+            #[cfg(unix)]
+            SocketEndpoint::Unix(path) => Self::new(UnixStream::connect(path).await?),
+        };
+
+        let mut write = channel.write.lock().await;
+        write.write_all(&header.encode()).await?;
+        write.flush().await?;
+        drop(write);
+
+        Ok(channel)
+    }
+
+    /// Reads the [SessionHeader] that opens the connection.
+    async fn read_header(&self) -> io::Result<SessionHeader> {
+        let mut buf = [0u8; SessionHeader::LEN];
+        self.read.lock().await.read_exact(&mut buf).await?;
+        SessionHeader::decode(&buf)
+    }
+}
+
+#[async_trait]
+impl Channel for SocketChannel {
+    async fn read(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        self.read.lock().await.read(buf).await.map_err(|_| ChannelError::Closed)
+    }
+
+    async fn read_exact(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        self.read.lock().await.read_exact(buf).await.map_err(|e| match e.kind() {
+            ErrorKind::UnexpectedEof => ChannelError::UnexpectedEOF,
+            _ => ChannelError::Closed,
+        })
+    }
+
+    async fn write(&self, buf: &[u8]) -> ChannelResult<usize> {
+        let mut write = self.write.lock().await;
+        write.write_all(buf).await.map_err(|_| ChannelError::Closed)?;
+        write.flush().await.map_err(|_| ChannelError::Closed)?;
+        Ok(buf.len())
+    }
+}
+
+/// The socket listener of a [SocketListener].
+#[derive(Debug)]
+enum Listener {
+    /// A TCP listener.
+    Tcp(TcpListener),
+    /// A Unix domain socket listener.
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    /// Accepts a new connection.
+    async fn accept(&self) -> io::Result<SocketChannel> {
+        match self {
+            Self::Tcp(listener) => {
+                let (stream, _) = listener.accept().await?;
+                stream.set_nodelay(true)?;
+                Ok(SocketChannel::new(stream))
+            }
+            #[cfg(unix)]
+            Self::Unix(listener) => Ok(SocketChannel::new(listener.accept().await?.0)),
+        }
+    }
+}
+
+/// The result of reading the [SessionHeader] of an accepted connection.
+type Handshake = io::Result<(SessionHeader, SocketChannel)>;
+
+/// A listener that accepts client sessions on a [SocketEndpoint].
+#[derive(Debug)]
+pub struct SocketListener {
+    /// The underlying socket listener.
+    listener: Listener,
+    /// The tasks reading the [SessionHeader]s of accepted connections.
+    handshakes: JoinSet<Handshake>,
+    /// The channels of sessions for which only one of the two connections has been accepted,
+    /// along with the time they were accepted at.
+    pending: HashMap<u64, (ChannelKind, SocketChannel, Instant)>,
+}
+
+impl SocketListener {
+    /// The time that a connection has to send its [SessionHeader] before it is dropped.
+    pub const HEADER_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// The maximum number of accepted connections that have not sent their [SessionHeader] yet.
+    pub const MAX_PENDING_CONNECTIONS: usize = 64;
+
+    /// The maximum number of sessions for which only one of the two connections has been
+    /// accepted.
+    pub const MAX_PENDING_SESSIONS: usize = 64;
+
+    /// The time that the second connection of a session has to be accepted after the first one,
+    /// before the first one is dropped.
+    pub const PENDING_SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Binds a new [SocketListener] to the given [SocketEndpoint].
+    ///
+    /// A Unix domain socket left behind by a listener that is no longer running is removed before
+    /// binding.
+    pub async fn bind(endpoint: &SocketEndpoint) -> io::Result<Self> {
+        let listener = match endpoint {
+            SocketEndpoint::Tcp(addr) => Listener::Tcp(TcpListener::bind(addr).await?),
+            #[cfg(unix)]
+            SocketEndpoint::Unix(path) => {
+                remove_stale_socket(path).await?;
+                Listener::Unix(UnixListener::bind(path)?)
+            }
+        };
+        Ok(Self { listener, handshakes: JoinSet::new(), pending: HashMap::default() })
+    }
+
+    /// Returns the [SocketEndpoint] that the listener is bound to.
+    pub fn local_endpoint(&self) -> io::Result<SocketEndpoint> {
+        match &self.listener {
+            Listener::Tcp(listener) => listener.local_addr().map(SocketEndpoint::Tcp),
+            #[cfg(unix)]
+            Listener::Unix(listener) => listener
+                .local_addr()?
+                .as_pathname()
+                .map(|path| SocketEndpoint::Unix(path.to_path_buf()))
+                .ok_or_else(|| io::Error::other("Unnamed Unix socket")),
+        }
+    }
+
+    /// Accepts connections until both channels of a client session have been opened, and returns
+    /// the ID and channels of the session.
+    ///
+    /// The [SessionHeader]s of accepted connections are read concurrently. Connections that fail to
+    /// send a valid [SessionHeader] within [Self::HEADER_TIMEOUT] are dropped, as are connections
+    /// that exceed the pending limits and connections that duplicate a pending channel of their
+    /// session. Sessions whose second connection isn't accepted within
+    /// [Self::PENDING_SESSION_TIMEOUT] are dropped once the next connection is paired.
+    ///
+    /// This method is cancel safe.
+    pub async fn accept(&mut self) -> io::Result<(u64, SessionChannels)> {
+        loop {
+            tokio::select! {
+                accepted = self.listener.accept() => {
+                    let channel = accepted?;
+                    if self.handshakes.len() >= Self::MAX_PENDING_CONNECTIONS {
+                        warn!(target: "preimage_socket", "Too many pending connections");
+                        continue;
+                    }
+                    self.handshakes.spawn(Self::handshake(channel));
+                }
+                Some(joined) = self.handshakes.join_next() => {
+                    let (header, channel) = match joined.map_err(io::Error::other).and_then(|r| r) {
+                        Ok(handshake) => handshake,
+                        Err(e) => {
+                            warn!(target: "preimage_socket", "Dropping connection: {e}");
+                            continue;
+                        }
+                    };
+                    if let Some(session) = self.pair(header, channel) {
+                        return Ok(session);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads the [SessionHeader] of an accepted connection, timing out after
+    /// [Self::HEADER_TIMEOUT].
+    async fn handshake(channel: SocketChannel) -> Handshake {
+        match tokio::time::timeout(Self::HEADER_TIMEOUT, channel.read_header()).await {
+            Ok(header) => Ok((header?, channel)),
+            Err(_) => Err(io::Error::new(ErrorKind::TimedOut, "Timed out reading session header")),
+        }
+    }
+
+    /// Pairs a connection with the pending connection of its session, returning the ID and
+    /// channels of the session once both of its connections have been accepted.
+    fn pair(
+        &mut self,
+        header: SessionHeader,
+        channel: SocketChannel,
+    ) -> Option<(u64, SessionChannels)> {
+        self.pending.retain(|session, (_, _, accepted_at)| {
+            let stale = accepted_at.elapsed() >= Self::PENDING_SESSION_TIMEOUT;
+            if stale {
+                warn!(target: "preimage_socket", "Dropping incomplete session {session}");
+            }
+            !stale
+        });
+
+        let full = self.pending.len() >= Self::MAX_PENDING_SESSIONS;
+        match self.pending.entry(header.session) {
+            Entry::Occupied(entry) if entry.get().0 == header.kind => {
+                warn!(
+                    target: "preimage_socket",
+                    "Rejecting duplicate {:?} connection of session {}",
+                    header.kind,
+                    header.session
+                );
+                None
+            }
+            Entry::Occupied(entry) => {
+                let (_, other, _) = entry.remove();
+                let (hint, preimage) = match header.kind {
+                    ChannelKind::Hint => (channel, other),
+                    ChannelKind::Preimage => (other, channel),
+                };
+                Some((header.session, SessionChannels { hint, preimage }))
+            }
+            Entry::Vacant(_) if full => {
+                warn!(
+                    target: "preimage_socket",
+                    "Too many pending sessions, rejecting connection of session {}",
+                    header.session
+                );
+                None
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((header.kind, channel, Instant::now()));
+                None
+            }
+        }
+    }
+}
+
+/// Removes the Unix domain socket at the given path if no listener accepts connections on it, e.g.
+/// because the listener that bound it exited without unlinking it.
+#[cfg(unix)]
+async fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let is_socket = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata.file_type().is_socket(),
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let stale = matches!(
+        UnixStream::connect(path).await,
+        Err(e) if e.kind() == ErrorKind::ConnectionRefused
+    );
+    if is_socket && stale {
+        warn!(target: "preimage_socket", "Removing stale socket at {}", path.display());
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        HintReader, HintRouter, HintWriter, HintWriterClient, OracleReader, OracleServer,
+        PreimageFetcher, PreimageKey, PreimageKeyType, PreimageOracleClient,
+        errors::PreimageOracleResult,
+    };
+    use alloy_primitives::keccak256;
+
+    struct TestBackend;
+
+    #[async_trait]
+    impl PreimageFetcher for TestBackend {
+        async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+            Ok(key.key_value().to_be_bytes::<32>().to_vec())
+        }
+    }
+
+    #[async_trait]
+    impl HintRouter for TestBackend {
+        async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+            assert_eq!(hint, "test-hint");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_socket_endpoint_roundtrip() {
+        for endpoint in ["tcp://127.0.0.1:7000", "tcp://[::1]:7000"] {
+            assert_eq!(SocketEndpoint::from_str(endpoint).unwrap().to_string(), endpoint);
+        }
+        #[cfg(unix)]
+        assert_eq!(
+            SocketEndpoint::from_str("unix:///tmp/kona.sock").unwrap().to_string(),
+            "unix:///tmp/kona.sock"
+        );
+        assert!(SocketEndpoint::from_str("127.0.0.1:7000").is_err());
+        assert!(SocketEndpoint::from_str("tcp://localhost").is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_socket_sessions() {
+        let endpoint = SocketEndpoint::Tcp("127.0.0.1:0".parse().unwrap());
+        let mut listener = SocketListener::bind(&endpoint).await.unwrap();
+        let endpoint = listener.local_endpoint().unwrap();
+
+        let server = tokio::task::spawn(async move {
+            for _ in 0..2 {
+                let (_, channels) = listener.accept().await.unwrap();
+                tokio::task::spawn(async move {
+                    let hint_reader = HintReader::new(channels.hint);
+                    let oracle_server = OracleServer::new(channels.preimage);
+                    hint_reader.next_hint(&TestBackend).await.unwrap();
+                    while oracle_server.next_preimage_request(&TestBackend).await.is_ok() {}
+                });
+            }
+        });
+
+        let clients = (0..2u64).map(|session| {
+            let endpoint = endpoint.clone();
+            tokio::task::spawn(async move {
+                let channels = SessionChannels::connect(&endpoint, session).await.unwrap();
+                HintWriter::new(channels.hint).write("test-hint").await.unwrap();
+
+                let key =
+                    PreimageKey::new(*keccak256(session.to_be_bytes()), PreimageKeyType::Keccak256);
+                let preimage = OracleReader::new(channels.preimage).get(key).await.unwrap();
+                assert_eq!(preimage, key.key_value().to_be_bytes::<32>().to_vec());
+            })
+        });
+
+        for client in clients.collect::<Vec<_>>() {
+            client.await.unwrap();
+        }
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_socket_silent_connection() {
+        let endpoint = SocketEndpoint::Tcp("127.0.0.1:0".parse().unwrap());
+        let mut listener = SocketListener::bind(&endpoint).await.unwrap();
+        let endpoint = listener.local_endpoint().unwrap();
+
+        // A connection that never sends its header does not block the sessions behind it.
+        let SocketEndpoint::Tcp(addr) = endpoint else { unreachable!() };
+        let _silent = TcpStream::connect(addr).await.unwrap();
+        let client = tokio::task::spawn(async move {
+            SessionChannels::connect(&endpoint, 7).await.unwrap();
+        });
+
+        let (session, _) = listener.accept().await.unwrap();
+        assert_eq!(session, 7);
+        client.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_socket_listener_pairing() {
+        let endpoint = SocketEndpoint::Tcp("127.0.0.1:0".parse().unwrap());
+        let mut listener = SocketListener::bind(&endpoint).await.unwrap();
+        let channel = || SocketChannel::new(tokio::io::duplex(1).0);
+        let header = |session, kind| SessionHeader { session, kind };
+
+        assert!(listener.pair(header(7, ChannelKind::Hint), channel()).is_none());
+        // A duplicate connection of a pending channel is rejected.
+        assert!(listener.pair(header(7, ChannelKind::Hint), channel()).is_none());
+        assert_eq!(listener.pending.len(), 1);
+
+        let (session, _) = listener.pair(header(7, ChannelKind::Preimage), channel()).unwrap();
+        assert_eq!(session, 7);
+        assert!(listener.pending.is_empty());
+
+        // Connections of new sessions are rejected once the pending limit is reached.
+        for session in 0..SocketListener::MAX_PENDING_SESSIONS as u64 {
+            assert!(listener.pair(header(session, ChannelKind::Hint), channel()).is_none());
+        }
+        assert!(listener.pair(header(u64::MAX, ChannelKind::Hint), channel()).is_none());
+        assert_eq!(listener.pending.len(), SocketListener::MAX_PENDING_SESSIONS);
+        assert!(!listener.pending.contains_key(&u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_socket_listener_evicts_incomplete_sessions() {
+        tokio::time::pause();
+        let endpoint = SocketEndpoint::Tcp("127.0.0.1:0".parse().unwrap());
+        let mut listener = SocketListener::bind(&endpoint).await.unwrap();
+        let channel = || SocketChannel::new(tokio::io::duplex(1).0);
+        let header = |session, kind| SessionHeader { session, kind };
+
+        for session in 0..SocketListener::MAX_PENDING_SESSIONS as u64 {
+            assert!(listener.pair(header(session, ChannelKind::Hint), channel()).is_none());
+        }
+        assert!(listener.pair(header(u64::MAX, ChannelKind::Hint), channel()).is_none());
+        assert!(!listener.pending.contains_key(&u64::MAX));
+
+        // Once the incomplete sessions time out, new sessions are accepted again.
+        tokio::time::advance(SocketListener::PENDING_SESSION_TIMEOUT).await;
+        assert!(listener.pair(header(u64::MAX, ChannelKind::Hint), channel()).is_none());
+        assert_eq!(listener.pending.len(), 1);
+        let (session, _) =
+            listener.pair(header(u64::MAX, ChannelKind::Preimage), channel()).unwrap();
+        assert_eq!(session, u64::MAX);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_socket_stale_path() {
+        let dir = std::env::temp_dir().join(format!("kona-preimage-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let endpoint = SocketEndpoint::Unix(dir.join("stale.sock"));
+
+        // Dropping a listener leaves its socket behind.
+        drop(SocketListener::bind(&endpoint).await.unwrap());
+        let listener = SocketListener::bind(&endpoint).await.unwrap();
+
+        // A socket that a listener still accepts connections on is not removed.
+        assert!(SocketListener::bind(&endpoint).await.is_err());
+
+        drop(listener);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
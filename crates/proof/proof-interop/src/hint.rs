@@ -42,6 +42,9 @@ pub enum HintType {
     /// A hint that specifies bulk storage of all the code, state and keys generated by an
     /// execution witness.
     L2PayloadWitness,
+    /// A hint that specifies bulk storage of all the code, state and keys generated by the merged
+    /// execution witness of a range of consecutive blocks.
+    L2PayloadWitnessRange,
 }
 
 impl HintType {
@@ -78,6 +81,7 @@ impl FromStr for HintType {
             "l2-account-storage-proof" => Ok(Self::L2AccountStorageProof),
             "l2-block-data" => Ok(Self::L2BlockData),
             "l2-payload-witness" => Ok(Self::L2PayloadWitness),
+            "l2-payload-witness-range" => Ok(Self::L2PayloadWitnessRange),
             _ => Err(HintParsingError(value.to_string())),
         }
     }
@@ -102,6 +106,7 @@ impl From<HintType> for &str {
             HintType::L2AccountStorageProof => "l2-account-storage-proof",
             HintType::L2BlockData => "l2-block-data",
             HintType::L2PayloadWitness => "l2-payload-witness",
+            HintType::L2PayloadWitnessRange => "l2-payload-witness-range",
         }
     }
 }
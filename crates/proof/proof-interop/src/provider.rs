@@ -267,4 +267,20 @@ impl<C: CommsClient> TrieHinter for OracleInteropProvider<C> {
                 .await
         })
     }
+
+    fn hint_execution_witness_range(
+        &self,
+        parent_hash: B256,
+        block_count: u64,
+    ) -> Result<(), Self::Error> {
+        kona_proof::block_on(async move {
+            HintType::L2PayloadWitnessRange
+                .with_data(&[parent_hash.as_slice(), block_count.to_be_bytes().as_ref()])
+                .with_data(
+                    self.chain_id.read().map_or_else(Vec::new, |id| id.to_be_bytes().to_vec()),
+                )
+                .send(self.oracle.as_ref())
+                .await
+        })
+    }
 }
@@ -111,6 +111,12 @@ pub enum HintType {
     /// A hint that specifies bulk storage of all the code, state and keys generated by an
     /// execution witness.
     L2PayloadWitness,
+    /// A hint that specifies bulk storage of all the code, state and keys generated by the merged
+    /// execution witness of a range of consecutive blocks.
+    L2PayloadWitnessRange,
+    /// A hint that carries the JSON-encoded profile report of a profiling build of the client
+    /// program to the host.
+    ClientProfile,
 }
 
 impl HintType {
@@ -144,6 +150,8 @@ impl FromStr for HintType {
             "l2-account-proof" => Ok(Self::L2AccountProof),
             "l2-account-storage-proof" => Ok(Self::L2AccountStorageProof),
             "l2-payload-witness" => Ok(Self::L2PayloadWitness),
+            "l2-payload-witness-range" => Ok(Self::L2PayloadWitnessRange),
+            "client-profile" => Ok(Self::ClientProfile),
             _ => Err(HintParsingError(value.to_string())),
         }
     }
@@ -165,6 +173,8 @@ impl From<HintType> for &str {
             HintType::L2AccountProof => "l2-account-proof",
             HintType::L2AccountStorageProof => "l2-account-storage-proof",
             HintType::L2PayloadWitness => "l2-payload-witness",
+            HintType::L2PayloadWitnessRange => "l2-payload-witness-range",
+            HintType::ClientProfile => "client-profile",
         }
     }
 }
@@ -6,8 +6,9 @@ use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_derive::{
     ChainProvider, DataAvailabilityProvider, DerivationPipeline, L2ChainProvider, OriginProvider,
-    Pipeline, PipelineBuilder, PipelineErrorKind, PipelineResult, PolledAttributesQueueStage,
-    ResetSignal, Signal, SignalReceiver, StatefulAttributesBuilder, StepResult,
+    Pipeline, PipelineBuilder, PipelineCheckpoint, PipelineErrorKind, PipelineResult,
+    PolledAttributesQueueStage, ResetSignal, Signal, SignalReceiver, StatefulAttributesBuilder,
+    StepResult,
 };
 use kona_driver::{DriverPipeline, PipelineCursor};
 use kona_genesis::{RollupConfig, SystemConfig};
@@ -117,6 +118,16 @@ where
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
         self.pipeline.signal(signal).await
     }
+
+    /// Takes a checkpoint of the pipeline's stages.
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        self.pipeline.checkpoint(checkpoint)
+    }
+
+    /// Restores the pipeline's stages from a checkpoint.
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.pipeline.restore(checkpoint).await
+    }
 }
 
 impl<O, L1, L2, DA> OriginProvider for OraclePipeline<O, L1, L2, DA>
@@ -278,4 +278,22 @@ impl<T: CommsClient> TrieHinter for OracleL2ChainProvider<T> {
                 .await
         })
     }
+
+    fn hint_execution_witness_range(
+        &self,
+        parent_hash: B256,
+        block_count: u64,
+    ) -> Result<(), Self::Error> {
+        if block_count == 0 {
+            return Ok(());
+        }
+
+        crate::block_on(async move {
+            HintType::L2PayloadWitnessRange
+                .with_data(&[parent_hash.as_slice(), block_count.to_be_bytes().as_ref()])
+                .with_data(self.chain_id.map_or_else(Vec::new, |id| id.to_be_bytes().to_vec()))
+                .send(self.oracle.as_ref())
+                .await
+        })
+    }
 }
@@ -91,3 +91,21 @@ pub fn mmap(size: usize) -> IOResult<usize> {
 pub fn exit(code: usize) -> ! {
     ClientIO::exit(code)
 }
+
+/// Returns the current time of the kernel's monotonic clock, in nanoseconds. FPVM kernels derive
+/// the time from their step counter. If the kernel does not support clocks, `0` is returned.
+#[inline]
+#[cfg(all(feature = "profiling", any(target_arch = "mips64", target_arch = "riscv64")))]
+pub fn clock() -> u64 {
+    ClientIO::clock_gettime().unwrap_or_default()
+}
+
+/// Returns the time elapsed since the first call to this function, in nanoseconds.
+#[inline]
+#[cfg(all(feature = "profiling", not(any(target_arch = "mips64", target_arch = "riscv64"))))]
+pub fn clock() -> u64 {
+    use std::{sync::OnceLock, time::Instant};
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
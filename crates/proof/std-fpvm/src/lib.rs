@@ -17,6 +17,9 @@ pub mod io;
 #[cfg(feature = "tracing")]
 pub mod tracing;
 
+#[cfg(feature = "profiling")]
+pub mod profiler;
+
 pub mod malloc;
 
 mod traits;
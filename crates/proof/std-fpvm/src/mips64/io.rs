@@ -24,6 +24,9 @@ pub(crate) enum SyscallNumber {
     /// Similar behavior as Linux/MIPS for mapping memory on the host machine. Only accepts 2
     /// arguments for cannon.
     Mmap = 5009,
+    /// Similar behavior as Linux/MIPS. The kernel derives the time from its step counter.
+    #[cfg(feature = "profiling")]
+    ClockGettime = 5222,
 }
 
 impl BasicKernelInterface for Mips64IO {
@@ -66,3 +69,22 @@ impl BasicKernelInterface for Mips64IO {
         }
     }
 }
+
+#[cfg(feature = "profiling")]
+impl Mips64IO {
+    /// Returns the current time of the monotonic clock, in nanoseconds.
+    pub(crate) fn clock_gettime() -> IOResult<u64> {
+        /// The identifier of the monotonic clock.
+        const CLOCK_MONOTONIC: usize = 1;
+
+        let mut timespec = [0u64; 2];
+        unsafe {
+            crate::linux::from_ret(syscall::syscall2(
+                SyscallNumber::ClockGettime as usize,
+                CLOCK_MONOTONIC,
+                timespec.as_mut_ptr() as usize,
+            ))?;
+        }
+        Ok(timespec[0].saturating_mul(1_000_000_000).saturating_add(timespec[1]))
+    }
+}
@@ -0,0 +1,299 @@
+//! This module contains the [FpvmProfiler], a [Subscriber] that attributes the cost of the client
+//! program to the [tracing] spans that it enters, and aggregates it into a [ProfileReport].
+//!
+//! Costs are measured with [io::clock], which FPVM kernels derive from their step counter.
+
+use crate::{io, tracing::FpvmTracingSubscriber};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+use spin::Mutex;
+use tracing::{
+    Event, Level, Metadata, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+
+/// The name of the span field that, if present, is appended to the span name to form the name of
+/// the phase. This allows for a single span callsite to be broken down further, e.g. per
+/// precompile address.
+pub const PHASE_KEY_FIELD: &str = "phase_key";
+
+/// The global profiler state. The client program is single-threaded, so spans are entered and
+/// exited in a strictly nested order.
+static PROFILER: Mutex<ProfilerState> = Mutex::new(ProfilerState::new());
+
+/// The aggregated cost of the client program, broken down per phase.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileReport {
+    /// The profiled phases, sorted by descending exclusive cost.
+    pub phases: Vec<PhaseProfile>,
+}
+
+/// The aggregated cost of a single phase of the client program.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseProfile {
+    /// The name of the phase.
+    pub name: String,
+    /// The number of times that the phase was started.
+    pub calls: u64,
+    /// The total cost of the phase, including nested phases, in nanoseconds.
+    pub total_nanos: u64,
+    /// The cost of the phase, excluding nested phases, in nanoseconds.
+    pub self_nanos: u64,
+}
+
+/// Returns the [ProfileReport] of all phases that have been recorded by the [FpvmProfiler] so far.
+pub fn report() -> ProfileReport {
+    let mut phases = PROFILER.lock().phases.clone();
+    phases.sort_by(|a, b| b.self_nanos.cmp(&a.self_nanos).then_with(|| a.name.cmp(&b.name)));
+    ProfileReport { phases }
+}
+
+/// A [Subscriber] that records the cost of every span entered by the client program, and
+/// forwards events to a [FpvmTracingSubscriber].
+#[derive(Debug, Clone)]
+pub struct FpvmProfiler {
+    inner: FpvmTracingSubscriber,
+}
+
+impl FpvmProfiler {
+    /// Create a new [FpvmProfiler] that logs events with the specified minimum log level.
+    pub const fn new(min_level: Level) -> Self {
+        Self { inner: FpvmTracingSubscriber::new(min_level) }
+    }
+}
+
+impl Subscriber for FpvmProfiler {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut visitor = PhaseKeyVisitor::default();
+        span.record(&mut visitor);
+
+        let name = match visitor.key {
+            Some(key) => format!("{}::{key}", span.metadata().name()),
+            None => span.metadata().name().to_string(),
+        };
+        Id::from_u64(PROFILER.lock().new_span(name))
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        self.inner.event(event);
+    }
+
+    fn enter(&self, span: &Id) {
+        let now = io::clock();
+        let mut state = PROFILER.lock();
+        if let Some(phase) = state.spans.get(&span.into_u64()).map(|span| span.phase) {
+            state.enter(phase, now);
+        }
+    }
+
+    fn exit(&self, _span: &Id) {
+        let now = io::clock();
+        PROFILER.lock().exit(now);
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(span) = PROFILER.lock().spans.get_mut(&span.into_u64()) {
+            span.refs += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        PROFILER.lock().close_span(span.into_u64())
+    }
+}
+
+/// A span that has been created and not yet closed.
+#[derive(Debug)]
+struct SpanRef {
+    /// The index of the phase of the span.
+    phase: usize,
+    /// The number of open handles to the span.
+    refs: usize,
+}
+
+/// A span that is currently entered.
+#[derive(Debug)]
+struct Frame {
+    /// The index of the phase of the span.
+    phase: usize,
+    /// The time at which the span was entered.
+    start: u64,
+    /// The cost of the nested spans that have been entered and exited within the span.
+    children: u64,
+}
+
+/// The state of the [FpvmProfiler].
+#[derive(Debug)]
+struct ProfilerState {
+    /// The recorded phases.
+    phases: Vec<PhaseProfile>,
+    /// The indices of the recorded phases, keyed by name.
+    index: BTreeMap<String, usize>,
+    /// The open spans, keyed by their unique ID.
+    spans: BTreeMap<u64, SpanRef>,
+    /// The ID of the next span. IDs start at `1`, as `0` is not a valid span ID.
+    next_id: u64,
+    /// The stack of entered spans.
+    stack: Vec<Frame>,
+}
+
+impl ProfilerState {
+    /// Creates a new, empty [ProfilerState].
+    const fn new() -> Self {
+        Self {
+            phases: Vec::new(),
+            index: BTreeMap::new(),
+            spans: BTreeMap::new(),
+            next_id: 1,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Creates a new span of the phase with the given name, returning the unique ID of the span.
+    fn new_span(&mut self, name: String) -> u64 {
+        let phase = self.start_phase(name);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.spans.insert(id, SpanRef { phase, refs: 1 });
+        id
+    }
+
+    /// Drops a handle to the span with the given ID, returning `true` if the span was closed.
+    fn close_span(&mut self, id: u64) -> bool {
+        let Some(span) = self.spans.get_mut(&id) else {
+            return false;
+        };
+        span.refs -= 1;
+        if span.refs > 0 {
+            return false;
+        }
+        self.spans.remove(&id);
+        true
+    }
+
+    /// Records the start of a phase with the given name, returning the index of the phase.
+    fn start_phase(&mut self, name: String) -> usize {
+        let index = match self.index.get(&name) {
+            Some(index) => *index,
+            None => {
+                self.phases.push(PhaseProfile { name: name.clone(), ..Default::default() });
+                self.index.insert(name, self.phases.len() - 1);
+                self.phases.len() - 1
+            }
+        };
+        self.phases[index].calls += 1;
+        index
+    }
+
+    /// Records that the phase with the given index was entered at `now`.
+    fn enter(&mut self, phase: usize, now: u64) {
+        self.stack.push(Frame { phase, start: now, children: 0 });
+    }
+
+    /// Records that the innermost entered phase was exited at `now`.
+    fn exit(&mut self, now: u64) {
+        let Some(frame) = self.stack.pop() else {
+            return;
+        };
+
+        let elapsed = now.saturating_sub(frame.start);
+        if let Some(phase) = self.phases.get_mut(frame.phase) {
+            phase.total_nanos += elapsed;
+            phase.self_nanos += elapsed.saturating_sub(frame.children);
+        }
+        if let Some(parent) = self.stack.last_mut() {
+            parent.children += elapsed;
+        }
+    }
+}
+
+/// A [Visit] implementation that extracts the [PHASE_KEY_FIELD] of a span.
+#[derive(Default)]
+struct PhaseKeyVisitor {
+    key: Option<String>,
+}
+
+impl Visit for PhaseKeyVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn core::fmt::Debug) {
+        if field.name() == PHASE_KEY_FIELD {
+            self.key = Some(format!("{value:?}"));
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == PHASE_KEY_FIELD {
+            self.key = Some(value.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_phases() {
+        let mut state = ProfilerState::new();
+
+        let derivation = state.start_phase("derivation".to_string());
+        state.enter(derivation, 0);
+        for start in [10, 50] {
+            let stage = state.start_phase("frame_queue".to_string());
+            state.enter(stage, start);
+            state.exit(start + 20);
+        }
+        state.exit(100);
+
+        assert_eq!(
+            state.phases,
+            vec![
+                PhaseProfile {
+                    name: "derivation".to_string(),
+                    calls: 1,
+                    total_nanos: 100,
+                    self_nanos: 60,
+                },
+                PhaseProfile {
+                    name: "frame_queue".to_string(),
+                    calls: 2,
+                    total_nanos: 40,
+                    self_nanos: 40,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unique_span_ids() {
+        let mut state = ProfilerState::new();
+
+        let first = state.new_span("frame_queue".to_string());
+        let second = state.new_span("frame_queue".to_string());
+        assert_ne!(first, second);
+        assert_eq!(state.phases[0].calls, 2);
+
+        // A span is only closed once all of its handles are dropped.
+        state.spans.get_mut(&first).unwrap().refs += 1;
+        assert!(!state.close_span(first));
+        assert!(state.close_span(first));
+        assert!(!state.close_span(first));
+        assert!(state.spans.contains_key(&second));
+    }
+}
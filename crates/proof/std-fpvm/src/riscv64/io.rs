@@ -22,6 +22,9 @@ pub(crate) enum SyscallNumber {
     Write = 64,
     /// Similar behavior as Linux for mapping memory on the host machine.
     Mmap = 222,
+    /// Similar behavior as Linux. The kernel derives the time from its step counter.
+    #[cfg(feature = "profiling")]
+    ClockGettime = 113,
 }
 
 impl BasicKernelInterface for RiscV64IO {
@@ -69,3 +72,22 @@ impl BasicKernelInterface for RiscV64IO {
         }
     }
 }
+
+#[cfg(feature = "profiling")]
+impl RiscV64IO {
+    /// Returns the current time of the monotonic clock, in nanoseconds.
+    pub(crate) fn clock_gettime() -> IOResult<u64> {
+        /// The identifier of the monotonic clock.
+        const CLOCK_MONOTONIC: usize = 1;
+
+        let mut timespec = [0u64; 2];
+        unsafe {
+            crate::linux::from_ret(syscall::syscall2(
+                SyscallNumber::ClockGettime as usize,
+                CLOCK_MONOTONIC,
+                timespec.as_mut_ptr() as usize,
+            ))?;
+        }
+        Ok(timespec[0].saturating_mul(1_000_000_000).saturating_add(timespec[1]))
+    }
+}
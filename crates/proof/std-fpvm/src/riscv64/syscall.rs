@@ -32,6 +32,23 @@ pub(crate) unsafe fn syscall1(syscall_number: usize, arg1: usize) -> usize {
     ret
 }
 
+/// Issues a raw system call with 2 arguments. (e.g. clock_gettime)
+#[inline]
+#[cfg(feature = "profiling")]
+pub(crate) unsafe fn syscall2(syscall_number: usize, arg1: usize, arg2: usize) -> usize {
+    let mut ret: usize;
+    unsafe {
+        asm!(
+            "ecall",
+            in("a7") syscall_number,
+            inlateout("a0") arg1 => ret,
+            in("a1") arg2,
+            options(nostack, preserves_flags)
+        );
+    }
+    ret
+}
+
 /// Issues a raw system call with 3 arguments. (e.g. read, write)
 #[inline]
 pub(crate) unsafe fn syscall3(
@@ -0,0 +1,154 @@
+#![allow(missing_docs)]
+//! Contains benchmarks comparing the channel compression algorithms on recorded channels.
+//!
+//! Besides the recorded channel shipped with `kona-protocol` and the channel of the recorded OP
+//! Mainnet batcher transaction shipped with `kona-derive`, the channels found in the directory
+//! pointed to by the `KONA_BENCH_CHANNELS_DIR` environment variable are benchmarked. Each `.hex`
+//! file holds the hex-encoded data of a channel, as read from its frames, in any format that the
+//! [BatchReader] decodes.
+//!
+//! The compression ratio of each algorithm is printed before it is benchmarked.
+
+use alloy_consensus::{Transaction, TxEnvelope};
+use alloy_eips::eip2718::Decodable2718;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use kona_comp::{
+    ChannelCompressor, CompressionAlgo, CompressorWriter, VariantCompressor, ZstdLevel,
+};
+use kona_protocol::{BatchReader, Frame};
+use std::{fs, path::Path};
+
+/// The environment variable pointing to a directory of additional recorded channels.
+const CHANNELS_DIR_ENV: &str = "KONA_BENCH_CHANNELS_DIR";
+
+/// The algorithms that are compared.
+const ALGOS: [(&str, CompressionAlgo); 8] = [
+    ("zlib", CompressionAlgo::Zlib),
+    ("brotli-9", CompressionAlgo::Brotli9),
+    ("brotli-10", CompressionAlgo::Brotli10),
+    ("brotli-11", CompressionAlgo::Brotli11),
+    ("zstd-3", CompressionAlgo::Zstd(zstd_level(3))),
+    ("zstd-9", CompressionAlgo::Zstd(zstd_level(9))),
+    ("zstd-19", CompressionAlgo::Zstd(zstd_level(19))),
+    ("zstd-22", CompressionAlgo::Zstd(zstd_level(22))),
+];
+
+/// Returns the given zstd compression level.
+const fn zstd_level(level: i32) -> ZstdLevel {
+    match ZstdLevel::new(level) {
+        Ok(level) => level,
+        Err(_) => panic!("invalid zstd level"),
+    }
+}
+
+/// Decodes a hex-encoded channel, returning its decompressed RLP data.
+fn decode_channel(hex: &str) -> Vec<u8> {
+    let data = alloy_primitives::hex::decode(hex.trim()).expect("channel is hex encoded");
+    let mut reader = BatchReader::new(data, usize::MAX);
+    reader.decompress().expect("channel decompresses");
+    reader.decompressed
+}
+
+/// Decodes the zlib channel carried by the recorded OP Mainnet batcher transaction, returning its
+/// decompressed RLP data.
+///
+/// The zlib stream of the channel ends without a final block. Like `op-node`, which reads batches
+/// until the stream ends, the data decompressed up to the end of the stream is kept.
+fn decode_batcher_tx(raw: &[u8]) -> Vec<u8> {
+    let tx = TxEnvelope::decode_2718(&mut &raw[..]).expect("batcher transaction decodes");
+    let frames = Frame::parse_frames(tx.input()).expect("batcher transaction holds frames");
+    let data = frames.into_iter().flat_map(|frame| frame.data).collect::<Vec<_>>();
+    miniz_oxide::inflate::decompress_to_vec_zlib(&data).unwrap_or_else(|err| err.output)
+}
+
+/// Loads the recorded channels, as `(name, decompressed RLP data)` pairs.
+fn recorded_channels() -> Vec<(String, Vec<u8>)> {
+    let mut channels = vec![
+        (
+            "testdata/batch".to_string(),
+            decode_channel(include_str!("../../protocol/testdata/batch.hex")),
+        ),
+        (
+            "mainnet/batcher-tx".to_string(),
+            decode_batcher_tx(include_bytes!("../../derive/testdata/raw_batcher_tx.hex")),
+        ),
+    ];
+
+    if let Ok(dir) = std::env::var(CHANNELS_DIR_ENV) {
+        let mut paths = fs::read_dir(&dir)
+            .expect("channels directory is readable")
+            .map(|entry| entry.expect("directory entry is readable").path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "hex"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        for path in paths {
+            let hex = fs::read_to_string(&path).expect("channel file is readable");
+            channels.push((channel_name(&path), decode_channel(&hex)));
+        }
+    }
+    channels
+}
+
+/// Returns the name of a recorded channel file.
+fn channel_name(path: &Path) -> String {
+    path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// Compresses the given data into a channel with the given algorithm.
+fn compress(algo: CompressionAlgo, data: &[u8]) -> Vec<u8> {
+    let mut compressor = VariantCompressor::from(algo);
+    compressor.write(data).expect("data compresses");
+    compressor.close().expect("compressor closes");
+
+    let mut channel = compressor.channel_version().into_iter().collect::<Vec<_>>();
+    channel.extend(compressor.get_compressed());
+    channel
+}
+
+fn compression(c: &mut Criterion) {
+    let channels = recorded_channels();
+
+    let mut g = c.benchmark_group("compression");
+    g.sample_size(10);
+    for (channel, data) in &channels {
+        g.throughput(Throughput::Bytes(data.len() as u64));
+        for (name, algo) in ALGOS {
+            let compressed = compress(algo, data);
+            println!(
+                "{channel} {name}: {} -> {} bytes (ratio {:.4})",
+                data.len(),
+                compressed.len(),
+                compressed.len() as f64 / data.len() as f64
+            );
+
+            g.bench_with_input(BenchmarkId::new(name, channel), data, |b, data| {
+                b.iter(|| compress(algo, data));
+            });
+        }
+    }
+    g.finish();
+}
+
+fn decompression(c: &mut Criterion) {
+    let channels = recorded_channels();
+
+    let mut g = c.benchmark_group("decompression");
+    g.sample_size(10);
+    for (channel, data) in &channels {
+        g.throughput(Throughput::Bytes(data.len() as u64));
+        for (name, algo) in ALGOS {
+            let compressed = compress(algo, data);
+            g.bench_with_input(BenchmarkId::new(name, channel), &compressed, |b, compressed| {
+                b.iter(|| {
+                    let mut reader = BatchReader::new(compressed.clone(), usize::MAX);
+                    reader.decompress().expect("channel decompresses");
+                    reader.decompressed
+                });
+            });
+        }
+    }
+    g.finish();
+}
+
+criterion_group!(benches, compression, decompression);
+criterion_main!(benches);
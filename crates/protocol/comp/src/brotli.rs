@@ -42,6 +42,8 @@ pub struct BrotliCompressor {
     raw: Vec<u8>,
     /// Marks that the compressor is closed.
     closed: bool,
+    /// The number of compressed bytes that have been read.
+    read_offset: usize,
     /// The compression level.
     pub level: BrotliLevel,
 }
@@ -50,7 +52,7 @@ impl BrotliCompressor {
     /// Creates a new brotli compressor with the given compression level.
     pub fn new(level: impl Into<BrotliLevel>) -> Self {
         let level = level.into();
-        Self { compressed: Vec::new(), raw: Vec::new(), closed: false, level }
+        Self { compressed: Vec::new(), raw: Vec::new(), closed: false, read_offset: 0, level }
     }
 }
 
@@ -111,16 +113,18 @@ impl CompressorWriter for BrotliCompressor {
         self.closed = false;
         self.raw.clear();
         self.compressed.clear();
+        self.read_offset = 0;
     }
 
     fn read(&mut self, buf: &mut [u8]) -> CompressorResult<usize> {
-        let len = self.compressed.len().min(buf.len());
-        buf[..len].copy_from_slice(&self.compressed[..len]);
+        let len = self.len().min(buf.len());
+        buf[..len].copy_from_slice(&self.compressed[self.read_offset..self.read_offset + len]);
+        self.read_offset += len;
         Ok(len)
     }
 
     fn len(&self) -> usize {
-        self.compressed.len()
+        self.compressed.len().saturating_sub(self.read_offset)
     }
 }
 
@@ -1,7 +1,7 @@
 //! Contains the `ChannelOut` primitive for Optimism.
 
 use crate::{ChannelCompressor, CompressorError};
-use alloc::{vec, vec::Vec};
+use alloc::vec;
 use kona_genesis::RollupConfig;
 use kona_protocol::{Batch, ChannelId, Frame};
 use rand::{RngCore, SeedableRng, rngs::SmallRng};
@@ -83,9 +83,10 @@ where
             return Err(ChannelOutError::ChannelClosed);
         }
 
-        // Encode the batch.
-        let mut buf = vec![];
-        batch.encode(&mut buf).map_err(|_| ChannelOutError::BatchEncoding)?;
+        // Encode the batch, and wrap it in an RLP string as the channel reader expects.
+        let mut encoded = vec![];
+        batch.encode(&mut encoded).map_err(|_| ChannelOutError::BatchEncoding)?;
+        let buf = alloy_rlp::encode(encoded.as_slice());
 
         // Validate that the RLP length is within the channel's limits.
         let max_rlp_bytes_per_channel = self.config.max_rlp_bytes_per_channel(batch.timestamp());
@@ -94,6 +95,7 @@ where
         }
 
         self.compressor.write(&buf)?;
+        self.rlp_length += buf.len() as u64;
 
         Ok(())
     }
@@ -127,7 +129,7 @@ where
 
         // Construct an empty frame.
         let mut frame =
-            Frame { id: self.id, number: self.frame_number, is_last: self.closed, data: vec![] };
+            Frame { id: self.id, number: self.frame_number, is_last: false, data: vec![] };
 
         let mut max_size = max_size - FRAME_V0_OVERHEAD;
         if max_size > self.ready_bytes() {
@@ -135,9 +137,13 @@ where
         }
 
         // Read `max_size` bytes from the compressed data.
-        let mut data = Vec::with_capacity(max_size);
-        self.compressor.read(&mut data).map_err(ChannelOutError::Compression)?;
-        frame.data.extend_from_slice(data.as_slice());
+        let mut data = vec![0; max_size];
+        let read = self.compressor.read(&mut data).map_err(ChannelOutError::Compression)?;
+        data.truncate(read);
+        frame.data = data;
+
+        // The last frame of a closed channel drains the compressed data.
+        frame.is_last = self.closed && self.ready_bytes() == 0;
 
         // Update the compressed data.
         self.frame_number += 1;
@@ -148,8 +154,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{CompressorWriter, test_utils::MockCompressor};
-    use kona_protocol::{SingleBatch, SpanBatch};
+    use crate::{CompressorWriter, ZlibCompressor, test_utils::MockCompressor};
+    use kona_protocol::{BatchReader, SingleBatch, SpanBatch};
 
     #[test]
     fn test_output_frame_max_size_too_small() {
@@ -267,5 +273,26 @@ mod tests {
 
         let batch = Batch::Single(SingleBatch::default());
         assert_eq!(channel.add_batch(batch), Ok(()));
+        assert_eq!(channel.input_bytes(), channel.ready_bytes() as u64);
+    }
+
+    #[test]
+    fn test_channel_out_output_last_frame() {
+        let config = RollupConfig::default();
+        let mut channel = ChannelOut::new(ChannelId::default(), &config, ZlibCompressor::new());
+        channel.add_batch(Batch::Single(SingleBatch::default())).unwrap();
+        let compressed = channel.compressor.get_compressed();
+        let mut reader = BatchReader::new(compressed.clone(), usize::MAX);
+        assert_eq!(reader.next_batch(&config), Some(Batch::Single(SingleBatch::default())));
+        channel.close();
+
+        let first = channel.output_frame(FRAME_V0_OVERHEAD + 4).unwrap();
+        assert_eq!(first.data, compressed[..4]);
+        assert!(!first.is_last);
+
+        let second = channel.output_frame(usize::MAX).unwrap();
+        assert_eq!(second.number, 1);
+        assert_eq!(second.data, compressed[4..]);
+        assert!(second.is_last);
     }
 }
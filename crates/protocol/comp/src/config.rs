@@ -17,6 +17,6 @@ pub struct Config {
     /// will default to RatioKind.
     pub kind: CompressorType,
 
-    /// Type of compression algorithm to use. Must be one of [zlib, brotli-(9|10|11)]
+    /// Type of compression algorithm to use. Must be one of [zlib, brotli-(9|10|11), zstd, raw]
     pub compression_algo: CompressionAlgo,
 }
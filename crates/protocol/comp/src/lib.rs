@@ -24,11 +24,19 @@ pub use types::{CompressionAlgo, CompressorError, CompressorResult, CompressorTy
 mod zlib;
 pub use zlib::{ZlibCompressor, compress_zlib, decompress_zlib};
 
+mod raw;
+pub use raw::RawCompressor;
+
 #[cfg(feature = "std")]
 mod brotli;
 #[cfg(feature = "std")]
 pub use brotli::{BrotliCompressionError, BrotliCompressor, BrotliLevel, compress_brotli};
 
+#[cfg(feature = "zstd")]
+mod zstd;
+#[cfg(feature = "zstd")]
+pub use zstd::{DEFAULT_ZSTD_LEVEL, ZstdCompressor, ZstdLevel, compress_zstd};
+
 #[cfg(feature = "std")]
 mod variant;
 #[cfg(feature = "std")]
@@ -0,0 +1,82 @@
+//! Contains the raw passthrough compressor.
+
+use crate::{ChannelCompressor, CompressorResult, CompressorWriter};
+use alloc::vec::Vec;
+
+/// The raw compressor, which passes the channel data through uncompressed.
+///
+/// Raw channels are experimental, and only accepted by the derivation pipeline on chains that
+/// enable the experimental channel formats. They serve as a baseline when comparing compression
+/// algorithms.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct RawCompressor {
+    /// The uncompressed buffer.
+    buffer: Vec<u8>,
+    /// The number of bytes that have been read.
+    read_offset: usize,
+}
+
+impl RawCompressor {
+    /// Create a new raw compressor.
+    pub const fn new() -> Self {
+        Self { buffer: Vec::new(), read_offset: 0 }
+    }
+}
+
+impl CompressorWriter for RawCompressor {
+    fn write(&mut self, data: &[u8]) -> CompressorResult<usize> {
+        self.buffer.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> CompressorResult<()> {
+        Ok(())
+    }
+
+    fn close(&mut self) -> CompressorResult<()> {
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.read_offset = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len().saturating_sub(self.read_offset)
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> CompressorResult<usize> {
+        let len = self.len().min(buf.len());
+        buf[..len].copy_from_slice(&self.buffer[self.read_offset..self.read_offset + len]);
+        self.read_offset += len;
+        Ok(len)
+    }
+}
+
+impl ChannelCompressor for RawCompressor {
+    fn get_compressed(&self) -> Vec<u8> {
+        self.buffer.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_compressor_passthrough() {
+        let mut compressor = RawCompressor::new();
+        compressor.write(&[0xab; 16]).unwrap();
+        compressor.write(&[0xcd; 16]).unwrap();
+        assert_eq!(compressor.get_compressed(), [[0xab; 16], [0xcd; 16]].concat());
+
+        let mut buf = [0u8; 20];
+        assert_eq!(compressor.read(&mut buf).unwrap(), 20);
+        assert_eq!(compressor.len(), 12);
+
+        compressor.reset();
+        assert_eq!(compressor.len(), 0);
+    }
+}
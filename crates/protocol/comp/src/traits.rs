@@ -22,10 +22,10 @@ pub trait CompressorWriter {
     /// Resets the compressor.
     fn reset(&mut self);
 
-    /// Returns the length of the compressed data.
+    /// Returns the length of the compressed data that has not been read yet.
     fn len(&self) -> usize;
 
-    /// Reads the compressed data into the given buffer.
+    /// Reads the compressed data into the given buffer, consuming it.
     /// Returns the number of bytes read.
     fn read(&mut self, buf: &mut [u8]) -> CompressorResult<usize>;
 }
@@ -12,6 +12,12 @@ pub enum CompressorError {
     /// Brotli compression failed.
     #[error("brotli compression failed")]
     Brotli,
+    /// Zstd compression failed.
+    #[error("zstd compression failed")]
+    Zstd,
+    /// The zstd compression level is out of range.
+    #[error("invalid zstd compression level {0}, must be between 1 and 22")]
+    InvalidZstdLevel(i32),
 }
 
 /// The type of compressor to use.
@@ -38,6 +44,15 @@ pub enum CompressionAlgo {
     Brotli11,
     /// The zlib compression.
     Zlib,
+    /// The zstd compression at the given level.
+    ///
+    /// Experimental, see [kona_genesis::RollupConfig::is_experimental_compression_active].
+    #[cfg(feature = "zstd")]
+    Zstd(crate::ZstdLevel),
+    /// No compression, the channel data is passed through as-is.
+    ///
+    /// Experimental, see [kona_genesis::RollupConfig::is_experimental_compression_active].
+    Raw,
 }
 
 #[cfg(feature = "std")]
@@ -1,12 +1,15 @@
 //! A variant over the different implementations of [ChannelCompressor].
 
+#[cfg(feature = "zstd")]
+use crate::ZstdCompressor;
 use crate::{
     BrotliCompressor, ChannelCompressor, CompressionAlgo, CompressorResult, CompressorWriter,
-    ZlibCompressor,
+    RawCompressor, ZlibCompressor,
 };
 use kona_genesis::RollupConfig;
+use kona_protocol::BatchReader;
 
-/// The channel compressor wraps the brotli and zlib compressor types,
+/// The channel compressor wraps the brotli, zlib, zstd and raw compressor types,
 /// implementing the [ChannelCompressor] trait itself.
 #[derive(Debug, Clone)]
 pub enum VariantCompressor {
@@ -14,6 +17,11 @@ pub enum VariantCompressor {
     Brotli(BrotliCompressor),
     /// The zlib compressor.
     Zlib(ZlibCompressor),
+    /// The experimental zstd compressor.
+    #[cfg(feature = "zstd")]
+    Zstd(ZstdCompressor),
+    /// The experimental raw passthrough compressor.
+    Raw(RawCompressor),
 }
 
 impl VariantCompressor {
@@ -25,6 +33,18 @@ impl VariantCompressor {
             Self::Zlib(ZlibCompressor::new())
         }
     }
+
+    /// Returns the channel version byte that must prefix the compressed data for the
+    /// [BatchReader] to recognize it, if any. Zlib data is recognized by its header.
+    pub const fn channel_version(&self) -> Option<u8> {
+        match self {
+            Self::Brotli(_) => Some(BatchReader::CHANNEL_VERSION_BROTLI),
+            Self::Zlib(_) => None,
+            #[cfg(feature = "zstd")]
+            Self::Zstd(_) => Some(BatchReader::CHANNEL_VERSION_ZSTD),
+            Self::Raw(_) => Some(BatchReader::CHANNEL_VERSION_RAW),
+        }
+    }
 }
 
 impl CompressorWriter for VariantCompressor {
@@ -32,6 +52,9 @@ impl CompressorWriter for VariantCompressor {
         match self {
             Self::Brotli(compressor) => compressor.write(data),
             Self::Zlib(compressor) => compressor.write(data),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(compressor) => compressor.write(data),
+            Self::Raw(compressor) => compressor.write(data),
         }
     }
 
@@ -39,6 +62,9 @@ impl CompressorWriter for VariantCompressor {
         match self {
             Self::Brotli(compressor) => compressor.flush(),
             Self::Zlib(compressor) => compressor.flush(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(compressor) => compressor.flush(),
+            Self::Raw(compressor) => compressor.flush(),
         }
     }
 
@@ -46,6 +72,9 @@ impl CompressorWriter for VariantCompressor {
         match self {
             Self::Brotli(compressor) => compressor.close(),
             Self::Zlib(compressor) => compressor.close(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(compressor) => compressor.close(),
+            Self::Raw(compressor) => compressor.close(),
         }
     }
 
@@ -53,6 +82,9 @@ impl CompressorWriter for VariantCompressor {
         match self {
             Self::Brotli(compressor) => compressor.reset(),
             Self::Zlib(compressor) => compressor.reset(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(compressor) => compressor.reset(),
+            Self::Raw(compressor) => compressor.reset(),
         }
     }
 
@@ -60,6 +92,9 @@ impl CompressorWriter for VariantCompressor {
         match self {
             Self::Brotli(compressor) => compressor.len(),
             Self::Zlib(compressor) => compressor.len(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(compressor) => compressor.len(),
+            Self::Raw(compressor) => compressor.len(),
         }
     }
 
@@ -67,6 +102,9 @@ impl CompressorWriter for VariantCompressor {
         match self {
             Self::Brotli(compressor) => compressor.read(buf),
             Self::Zlib(compressor) => compressor.read(buf),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(compressor) => compressor.read(buf),
+            Self::Raw(compressor) => compressor.read(buf),
         }
     }
 }
@@ -76,6 +114,9 @@ impl ChannelCompressor for VariantCompressor {
         match self {
             Self::Brotli(compressor) => compressor.get_compressed(),
             Self::Zlib(compressor) => compressor.get_compressed(),
+            #[cfg(feature = "zstd")]
+            Self::Zstd(compressor) => compressor.get_compressed(),
+            Self::Raw(compressor) => compressor.get_compressed(),
         }
     }
 }
@@ -87,6 +128,45 @@ impl From<CompressionAlgo> for VariantCompressor {
             lvl @ CompressionAlgo::Brotli10 => Self::Brotli(BrotliCompressor::new(lvl)),
             lvl @ CompressionAlgo::Brotli11 => Self::Brotli(BrotliCompressor::new(lvl)),
             CompressionAlgo::Zlib => Self::Zlib(ZlibCompressor::new()),
+            #[cfg(feature = "zstd")]
+            CompressionAlgo::Zstd(level) => Self::Zstd(ZstdCompressor::new(level)),
+            CompressionAlgo::Raw => Self::Raw(RawCompressor::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_protocol::{Batch, SingleBatch};
+
+    #[test]
+    fn test_experimental_variants_roundtrip() {
+        let cfg = RollupConfig { experimental_compression_time: Some(0), ..Default::default() };
+        let batch = Batch::Single(SingleBatch { timestamp: 1, ..Default::default() });
+        let mut encoded = Vec::new();
+        batch.encode(&mut encoded).unwrap();
+        let rlp = alloy_rlp::encode(encoded.as_slice());
+
+        let algos = [
+            #[cfg(feature = "zstd")]
+            CompressionAlgo::Zstd(crate::ZstdLevel::MIN),
+            #[cfg(feature = "zstd")]
+            CompressionAlgo::Zstd(crate::ZstdLevel::new(19).unwrap()),
+            CompressionAlgo::Raw,
+        ];
+        for algo in algos {
+            let mut compressor = VariantCompressor::from(algo);
+            compressor.write(&rlp).unwrap();
+            let mut data = vec![compressor.channel_version().unwrap()];
+            data.extend(compressor.get_compressed());
+
+            let mut reader = BatchReader::new(data.clone(), usize::MAX);
+            assert_eq!(reader.next_batch(&cfg), Some(batch.clone()));
+
+            // The experimental channel formats are rejected unless enabled.
+            let mut reader = BatchReader::new(data, usize::MAX);
+            assert_eq!(reader.next_batch(&RollupConfig::default()), None);
         }
     }
 }
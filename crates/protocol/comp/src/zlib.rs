@@ -9,12 +9,12 @@ const BEST_ZLIB_COMPRESSION: u8 = 9;
 
 /// Method to compress data using ZLIB.
 pub fn compress_zlib(data: &[u8]) -> Vec<u8> {
-    miniz_oxide::deflate::compress_to_vec(data, BEST_ZLIB_COMPRESSION)
+    miniz_oxide::deflate::compress_to_vec_zlib(data, BEST_ZLIB_COMPRESSION)
 }
 
 /// Method to decompress data using ZLIB.
 pub fn decompress_zlib(data: &[u8]) -> Result<Vec<u8>, DecompressError> {
-    miniz_oxide::inflate::decompress_to_vec(data)
+    miniz_oxide::inflate::decompress_to_vec_zlib(data)
 }
 
 /// The ZLIB compressor.
@@ -25,12 +25,14 @@ pub struct ZlibCompressor {
     buffer: Vec<u8>,
     /// The compressed buffer.
     compressed: Vec<u8>,
+    /// The number of compressed bytes that have been read.
+    read_offset: usize,
 }
 
 impl ZlibCompressor {
     /// Create a new ZLIB compressor.
     pub const fn new() -> Self {
-        Self { buffer: Vec::new(), compressed: Vec::new() }
+        Self { buffer: Vec::new(), compressed: Vec::new(), read_offset: 0 }
     }
 }
 
@@ -53,15 +55,17 @@ impl CompressorWriter for ZlibCompressor {
     fn reset(&mut self) {
         self.buffer.clear();
         self.compressed.clear();
+        self.read_offset = 0;
     }
 
     fn len(&self) -> usize {
-        self.compressed.len()
+        self.compressed.len().saturating_sub(self.read_offset)
     }
 
     fn read(&mut self, buf: &mut [u8]) -> CompressorResult<usize> {
-        let len = self.compressed.len().min(buf.len());
-        buf[..len].copy_from_slice(&self.compressed[..len]);
+        let len = self.len().min(buf.len());
+        buf[..len].copy_from_slice(&self.compressed[self.read_offset..self.read_offset + len]);
+        self.read_offset += len;
         Ok(len)
     }
 }
@@ -71,3 +75,33 @@ impl ChannelCompressor for ZlibCompressor {
         self.compressed.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zlib_roundtrip() {
+        let data = [0xde, 0xad, 0xbe, 0xef].repeat(64);
+        let compressed = compress_zlib(&data);
+        // The zlib header must carry the deflate compression method for channel readers.
+        assert_eq!(compressed[0] & 0x0F, 8);
+        assert_eq!(decompress_zlib(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn test_zlib_compressor_read() {
+        let mut compressor = ZlibCompressor::new();
+        compressor.write(&[0xff; 128]).unwrap();
+        let compressed = compressor.get_compressed();
+        assert_eq!(compressor.len(), compressed.len());
+
+        let mut buf = [0u8; 4];
+        assert_eq!(compressor.read(&mut buf).unwrap(), 4);
+        assert_eq!(buf, compressed[..4]);
+        assert_eq!(compressor.len(), compressed.len() - 4);
+
+        compressor.reset();
+        assert_eq!(compressor.len(), 0);
+    }
+}
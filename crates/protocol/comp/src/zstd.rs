@@ -0,0 +1,179 @@
+//! Contains zstd compression utilities.
+
+use crate::{ChannelCompressor, CompressorError, CompressorResult, CompressorWriter};
+use std::vec::Vec;
+
+/// The default zstd compression level.
+pub const DEFAULT_ZSTD_LEVEL: ZstdLevel = ZstdLevel(3);
+
+/// A zstd compression level, between [ZstdLevel::MIN] and [ZstdLevel::MAX].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ZstdLevel(i32);
+
+impl ZstdLevel {
+    /// The fastest zstd compression level.
+    pub const MIN: Self = Self(1);
+    /// The best zstd compression level.
+    pub const MAX: Self = Self(22);
+
+    /// Creates a new [ZstdLevel], returning [CompressorError::InvalidZstdLevel] if the level is
+    /// out of range.
+    pub const fn new(level: i32) -> CompressorResult<Self> {
+        if level < Self::MIN.0 || level > Self::MAX.0 {
+            return Err(CompressorError::InvalidZstdLevel(level));
+        }
+        Ok(Self(level))
+    }
+
+    /// Returns the compression level.
+    pub const fn get(self) -> i32 {
+        self.0
+    }
+}
+
+impl Default for ZstdLevel {
+    fn default() -> Self {
+        DEFAULT_ZSTD_LEVEL
+    }
+}
+
+impl TryFrom<i32> for ZstdLevel {
+    type Error = CompressorError;
+
+    fn try_from(level: i32) -> CompressorResult<Self> {
+        Self::new(level)
+    }
+}
+
+/// Compresses the given bytes data into a single zstd frame, using the compressor implemented
+/// in the [`zstd`](https://crates.io/crates/zstd) crate.
+pub fn compress_zstd(input: &[u8], level: ZstdLevel) -> std::io::Result<Vec<u8>> {
+    zstd::bulk::compress(input, level.get())
+}
+
+/// The zstd compressor.
+///
+/// Zstd channels are experimental, and only accepted by the derivation pipeline on chains that
+/// enable the experimental channel formats.
+#[derive(Debug, Clone)]
+pub struct ZstdCompressor {
+    /// The compressed bytes.
+    compressed: Vec<u8>,
+    /// The raw bytes (need to store on reset).
+    raw: Vec<u8>,
+    /// Marks that the compressor is closed.
+    closed: bool,
+    /// The number of compressed bytes that have been read.
+    read_offset: usize,
+    /// The compression level.
+    pub level: ZstdLevel,
+}
+
+impl ZstdCompressor {
+    /// Creates a new zstd compressor with the given compression level.
+    pub const fn new(level: ZstdLevel) -> Self {
+        Self { compressed: Vec::new(), raw: Vec::new(), closed: false, read_offset: 0, level }
+    }
+}
+
+impl Default for ZstdCompressor {
+    fn default() -> Self {
+        Self::new(DEFAULT_ZSTD_LEVEL)
+    }
+}
+
+impl CompressorWriter for ZstdCompressor {
+    fn write(&mut self, data: &[u8]) -> CompressorResult<usize> {
+        if self.closed {
+            return Err(CompressorError::Zstd);
+        }
+
+        // First append the new data to the raw buffer.
+        self.raw.extend_from_slice(data);
+
+        // Compress the raw buffer.
+        self.compressed =
+            compress_zstd(&self.raw, self.level).map_err(|_| CompressorError::Zstd)?;
+
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> CompressorResult<()> {
+        Ok(())
+    }
+
+    fn close(&mut self) -> CompressorResult<()> {
+        self.flush()?;
+        self.closed = true;
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.closed = false;
+        self.raw.clear();
+        self.compressed.clear();
+        self.read_offset = 0;
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> CompressorResult<usize> {
+        let len = self.len().min(buf.len());
+        buf[..len].copy_from_slice(&self.compressed[self.read_offset..self.read_offset + len]);
+        self.read_offset += len;
+        Ok(len)
+    }
+
+    fn len(&self) -> usize {
+        self.compressed.len().saturating_sub(self.read_offset)
+    }
+}
+
+impl ChannelCompressor for ZstdCompressor {
+    fn get_compressed(&self) -> Vec<u8> {
+        self.compressed.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::hex;
+    use kona_genesis::MAX_RLP_BYTES_PER_CHANNEL_FJORD;
+    use kona_protocol::decompress_zstd;
+
+    #[test]
+    fn test_compress_zstd() {
+        let expected = hex!("28b52ffd005851000075ed184249e9bc19675e");
+        let decompressed = hex!("75ed184249e9bc19675e");
+
+        let mut compressor = ZstdCompressor::new(DEFAULT_ZSTD_LEVEL);
+        compressor.write(&decompressed).unwrap();
+        compressor.close().unwrap();
+        assert_eq!(compressor.get_compressed(), expected);
+        assert_eq!(compressor.write(&decompressed), Err(CompressorError::Zstd));
+    }
+
+    #[test]
+    fn test_zstd_roundtrip() {
+        let data = [0xde, 0xad, 0xbe, 0xef].repeat(1024);
+        for level in [1, 3, 19].map(|level| ZstdLevel::new(level).unwrap()) {
+            let mut compressor = ZstdCompressor::new(level);
+            compressor.write(&data[..2048]).unwrap();
+            compressor.write(&data[2048..]).unwrap();
+            let compressed = compressor.get_compressed();
+            assert!(compressed.len() < data.len());
+
+            let decompressed =
+                decompress_zstd(&compressed, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize).unwrap();
+            assert_eq!(decompressed, data);
+        }
+    }
+
+    #[test]
+    fn test_zstd_level_range() {
+        assert_eq!(ZstdLevel::new(0), Err(CompressorError::InvalidZstdLevel(0)));
+        assert_eq!(ZstdLevel::new(23), Err(CompressorError::InvalidZstdLevel(23)));
+        assert_eq!(ZstdLevel::try_from(-5), Err(CompressorError::InvalidZstdLevel(-5)));
+        assert_eq!(ZstdLevel::new(1), Ok(ZstdLevel::MIN));
+        assert_eq!(ZstdLevel::new(22).map(ZstdLevel::get), Ok(22));
+    }
+}
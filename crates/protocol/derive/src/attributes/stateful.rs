@@ -12,7 +12,7 @@ use alloy_rlp::Encodable;
 use alloy_rpc_types_engine::PayloadAttributes;
 use async_trait::async_trait;
 use kona_genesis::RollupConfig;
-use kona_hardforks::{Hardfork, Hardforks};
+use kona_hardforks::UpgradeRegistry;
 use kona_protocol::{
     DEPOSIT_EVENT_ABI_HASH, L1BlockInfoTx, L2BlockInfo, Predeploys, decode_deposit,
 };
@@ -31,6 +31,8 @@ where
     config_fetcher: L2P,
     /// The L1 receipts fetcher.
     receipts_fetcher: L1P,
+    /// The network upgrades, if they differ from the built-in hardforks.
+    upgrades: Option<UpgradeRegistry>,
 }
 
 impl<L1P, L2P> StatefulAttributesBuilder<L1P, L2P>
@@ -40,7 +42,19 @@ where
 {
     /// Create a new [`StatefulAttributesBuilder`] with the given epoch.
     pub const fn new(rcfg: Arc<RollupConfig>, sys_cfg_fetcher: L2P, receipts: L1P) -> Self {
-        Self { rollup_cfg: rcfg, config_fetcher: sys_cfg_fetcher, receipts_fetcher: receipts }
+        Self {
+            rollup_cfg: rcfg,
+            config_fetcher: sys_cfg_fetcher,
+            receipts_fetcher: receipts,
+            upgrades: None,
+        }
+    }
+
+    /// Sets the [`UpgradeRegistry`] that provides the network upgrade transactions, in place of
+    /// the built-in hardforks.
+    pub fn with_upgrades(mut self, upgrades: UpgradeRegistry) -> Self {
+        self.upgrades = Some(upgrades);
+        self
     }
 }
 
@@ -126,27 +140,11 @@ where
             ));
         }
 
-        let mut upgrade_transactions: Vec<Bytes> = vec![];
-        if self.rollup_cfg.is_ecotone_active(next_l2_time) &&
-            !self.rollup_cfg.is_ecotone_active(l2_parent.block_info.timestamp)
-        {
-            upgrade_transactions = Hardforks::ECOTONE.txs().collect();
-        }
-        if self.rollup_cfg.is_fjord_active(next_l2_time) &&
-            !self.rollup_cfg.is_fjord_active(l2_parent.block_info.timestamp)
-        {
-            upgrade_transactions.append(&mut Hardforks::FJORD.txs().collect());
-        }
-        if self.rollup_cfg.is_isthmus_active(next_l2_time) &&
-            !self.rollup_cfg.is_isthmus_active(l2_parent.block_info.timestamp)
-        {
-            upgrade_transactions.append(&mut Hardforks::ISTHMUS.txs().collect());
-        }
-        if self.rollup_cfg.is_interop_active(next_l2_time) &&
-            !self.rollup_cfg.is_interop_active(l2_parent.block_info.timestamp)
-        {
-            upgrade_transactions.append(&mut Hardforks::INTEROP.txs().collect());
-        }
+        let parent_time = l2_parent.block_info.timestamp;
+        let upgrade_transactions = match &self.upgrades {
+            Some(upgrades) => upgrades.txs(&self.rollup_cfg, parent_time, next_l2_time),
+            None => UpgradeRegistry::builtin_txs(&self.rollup_cfg, parent_time, next_l2_time),
+        };
 
         // Build and encode the L1 info transaction for the current payload.
         let (_, l1_info_tx_envelope) = L1BlockInfoTx::try_new_with_deposit_tx(
@@ -244,6 +242,7 @@ mod tests {
     use alloy_consensus::Header;
     use alloy_primitives::{B256, Log, LogData, U64, U256, address};
     use kona_genesis::{HardForkConfig, SystemConfig};
+    use kona_hardforks::{Hardfork, UpgradeActivation, UpgradeDescription, UpgradeTransaction};
     use kona_protocol::{BlockInfo, DepositError};
 
     fn generate_valid_log() -> Log {
@@ -620,4 +619,48 @@ mod tests {
         assert_eq!(payload.transactions.as_ref().unwrap().len(), 10);
         assert_eq!(payload, expected);
     }
+
+    #[tokio::test]
+    async fn test_prepare_payload_with_custom_upgrade() {
+        let block_time = 2;
+        let timestamp = 100;
+        let cfg = Arc::new(RollupConfig { block_time, ..Default::default() });
+        let l2_number = 1;
+        let mut fetcher = TestSystemConfigL2Fetcher::default();
+        fetcher.insert(l2_number, SystemConfig::default());
+        let mut provider = TestChainProvider::default();
+        let header = Header { timestamp, ..Default::default() };
+        let hash = header.hash_slow();
+        provider.insert_header(hash, header);
+
+        let upgrade = UpgradeDescription {
+            name: "Custom".into(),
+            activation: UpgradeActivation::Timestamp(102),
+            transactions: vec![UpgradeTransaction::Call {
+                intent: "Custom: Predeploy Call".into(),
+                from: Address::ZERO,
+                to: Predeploys::GAS_PRICE_ORACLE,
+                gas_limit: 50_000,
+                input: Bytes::default(),
+            }],
+        };
+        let registry = UpgradeRegistry::builtin().with_upgrade(upgrade.clone());
+        let mut builder =
+            StatefulAttributesBuilder::new(cfg, fetcher, provider).with_upgrades(registry);
+        let epoch = BlockNumHash { hash, number: l2_number };
+        let l2_parent = L2BlockInfo {
+            block_info: BlockInfo {
+                hash: B256::ZERO,
+                number: l2_number,
+                timestamp,
+                parent_hash: hash,
+            },
+            l1_origin: BlockNumHash { hash, number: l2_number },
+            seq_num: 0,
+        };
+        let payload = builder.prepare_payload_attributes(l2_parent, epoch).await.unwrap();
+        let txs = payload.transactions.unwrap();
+        assert_eq!(txs.len(), 2);
+        assert_eq!(txs[1], upgrade.txs().next().unwrap());
+    }
 }
@@ -14,4 +14,4 @@ mod pipeline;
 pub use pipeline::{PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError};
 
 mod sources;
-pub use sources::{BlobDecodingError, BlobProviderError};
+pub use sources::{AltDAError, BlobDecodingError, BlobProviderError};
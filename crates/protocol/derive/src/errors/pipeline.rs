@@ -4,7 +4,9 @@ use crate::BuilderError;
 use alloc::string::String;
 use alloy_primitives::B256;
 use kona_genesis::SystemConfigUpdateError;
-use kona_protocol::{DepositError, SpanBatchError};
+use kona_protocol::{
+    BatchDecodingError, BatchEncodingError, DepositError, FrameDecodingError, SpanBatchError,
+};
 use thiserror::Error;
 
 /// [crate::ensure] is a short-hand for bubbling up errors in the case of a condition not being met.
@@ -270,6 +272,19 @@ pub enum PipelineError {
     /// It indicates a protocol version mismatch or configuration issue.
     #[error("Unsupported signal")]
     UnsupportedSignal,
+    /// The pipeline is not at an L1 origin boundary.
+    ///
+    /// A [`PipelineCheckpoint`] can only be taken once the data of the previous L1 origin has
+    /// been fully consumed, and before the data of the current L1 origin is read.
+    ///
+    /// [`PipelineCheckpoint`]: crate::PipelineCheckpoint
+    #[error("Pipeline is not at an L1 origin boundary")]
+    NotAtOriginBoundary,
+    /// The [`PipelineCheckpoint`] holds state that cannot be restored into the pipeline.
+    ///
+    /// [`PipelineCheckpoint`]: crate::PipelineCheckpoint
+    #[error("Invalid pipeline checkpoint")]
+    InvalidCheckpoint,
 }
 
 impl PipelineError {
@@ -367,6 +382,15 @@ pub enum PipelineEncodingError {
     /// Span Batch Error.
     #[error("{0}")]
     SpanBatchError(#[from] SpanBatchError),
+    /// Frame Decoding Error.
+    #[error("Error decoding frame: {0}")]
+    FrameDecodingError(#[from] FrameDecodingError),
+    /// Batch Decoding Error.
+    #[error("Error decoding batch: {0}")]
+    BatchDecodingError(#[from] BatchDecodingError),
+    /// Batch Encoding Error.
+    #[error("Error encoding batch: {0}")]
+    BatchEncodingError(#[from] BatchEncodingError),
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@
 
 use crate::{PipelineError, PipelineErrorKind};
 use alloc::string::{String, ToString};
+use alloy_primitives::Bytes;
 use thiserror::Error;
 
 /// Blob Decoding Error
@@ -51,6 +52,54 @@ impl From<BlobProviderError> for PipelineErrorKind {
     }
 }
 
+/// An error returned while deriving data through an [`AltDASource`].
+///
+/// [`AltDASource`]: crate::AltDASource
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AltDAError {
+    /// The configured commitment type is unknown.
+    #[error("Unknown commitment type: {0}")]
+    UnknownCommitmentType(String),
+    /// The commitment type byte is unknown.
+    #[error("Invalid commitment type: {0}")]
+    InvalidCommitmentType(u8),
+    /// The commitment is too short for its type.
+    #[error("Invalid commitment length")]
+    InvalidCommitmentLength,
+    /// A challenge contract is configured without the challenge and resolve windows.
+    #[error("Challenge contract configured without challenge and resolve windows")]
+    MissingChallengeWindows,
+    /// The input for the commitment is missing from the DA server, but may still be resolved
+    /// on-chain within the challenge and resolve windows.
+    #[error("Input for commitment {0} is pending")]
+    PendingInput(Bytes),
+    /// The input for the commitment is missing from the DA server, and was never challenged within
+    /// the challenge window.
+    #[error("Input for commitment {0} is missing past the challenge window")]
+    MissingPastWindow(Bytes),
+    /// The DA server returned an input that does not match its commitment.
+    #[error("Input does not match commitment {0}")]
+    InputMismatch(Bytes),
+    /// Error pertaining to the DA server client.
+    #[error("{0}")]
+    Backend(String),
+}
+
+impl From<AltDAError> for PipelineErrorKind {
+    fn from(val: AltDAError) -> Self {
+        match val {
+            AltDAError::UnknownCommitmentType(_) |
+            AltDAError::InvalidCommitmentType(_) |
+            AltDAError::InvalidCommitmentLength |
+            AltDAError::MissingChallengeWindows => PipelineError::Provider(val.to_string()).crit(),
+            AltDAError::PendingInput(_) | AltDAError::InputMismatch(_) | AltDAError::Backend(_) => {
+                PipelineError::Provider(val.to_string()).temp()
+            }
+            AltDAError::MissingPastWindow(_) => PipelineError::Provider(val.to_string()).crit(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,4 +123,13 @@ mod tests {
             BlobProviderError::BlobDecoding(BlobDecodingError::InvalidFieldElement).into();
         assert!(matches!(err, PipelineErrorKind::Critical(_)));
     }
+
+    #[test]
+    fn test_from_altda_error() {
+        let err: PipelineErrorKind = AltDAError::PendingInput(Bytes::new()).into();
+        assert!(matches!(err, PipelineErrorKind::Temporary(_)));
+
+        let err: PipelineErrorKind = AltDAError::MissingPastWindow(Bytes::new()).into();
+        assert!(matches!(err, PipelineErrorKind::Critical(_)));
+    }
 }
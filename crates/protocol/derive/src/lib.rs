@@ -17,7 +17,7 @@ pub use attributes::StatefulAttributesBuilder;
 
 mod errors;
 pub use errors::{
-    BatchDecompressionError, BlobDecodingError, BlobProviderError, BuilderError,
+    AltDAError, BatchDecompressionError, BlobDecodingError, BlobProviderError, BuilderError,
     PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError,
 };
 
@@ -29,7 +29,11 @@ pub use pipeline::{
 };
 
 mod sources;
-pub use sources::{BlobData, BlobSource, CalldataSource, EthereumDataSource};
+pub use sources::{
+    ALTDA_DERIVATION_VERSION, AltDAChallenges, AltDACommitment, AltDACommitmentType, AltDASource,
+    BlobData, BlobSource, CalldataSource, Challenge, ChallengeStatus, EthereumDataSource,
+    MAX_ALTDA_INPUT_SIZE,
+};
 
 mod stages;
 pub use stages::{
@@ -41,13 +45,17 @@ pub use stages::{
 
 mod traits;
 pub use traits::{
-    AttributesBuilder, AttributesProvider, BatchValidationProviderDerive, BlobProvider,
-    ChainProvider, DataAvailabilityProvider, L2ChainProvider, NextAttributes, OriginAdvancer,
-    OriginProvider, Pipeline, ResetProvider, SignalReceiver,
+    AltDAClient, AttributesBuilder, AttributesProvider, BatchValidationProviderDerive,
+    BlobProvider, ChainProvider, DataAvailabilityProvider, DerivationEventSink, L2ChainProvider,
+    NextAttributes, OriginAdvancer, OriginProvider, Pipeline, ResetProvider, SignalReceiver,
 };
 
 mod types;
-pub use types::{ActivationSignal, PipelineResult, ResetSignal, Signal, StepResult};
+pub use types::{
+    ActivationSignal, BatchCheckpoint, ChannelCheckpoint, ChannelDropReason, DerivationEvent,
+    EventEmitter, FrameDropReason, PipelineCheckpoint, PipelineResult, ResetSignal, Signal,
+    StepResult,
+};
 
 mod metrics;
 pub use metrics::Metrics;
@@ -2,8 +2,8 @@
 
 use crate::{
     AttributesBuilder, AttributesQueue, BatchProvider, BatchStream, ChainProvider, ChannelProvider,
-    ChannelReader, DataAvailabilityProvider, DerivationPipeline, FrameQueue,
-    IndexedAttributesQueueStage, IndexedTraversal, L1Retrieval, L2ChainProvider,
+    ChannelReader, DataAvailabilityProvider, DerivationEventSink, DerivationPipeline, EventEmitter,
+    FrameQueue, IndexedAttributesQueueStage, IndexedTraversal, L1Retrieval, L2ChainProvider,
     PolledAttributesQueueStage, PollingTraversal,
 };
 use alloc::sync::Arc;
@@ -26,6 +26,7 @@ where
     builder: Option<B>,
     origin: Option<BlockInfo>,
     rollup_config: Option<Arc<RollupConfig>>,
+    events: EventEmitter,
 }
 
 impl<B, P, T, D> Default for PipelineBuilder<B, P, T, D>
@@ -43,6 +44,7 @@ where
             builder: None,
             origin: None,
             rollup_config: None,
+            events: EventEmitter::disabled(),
         }
     }
 }
@@ -95,6 +97,14 @@ where
         self
     }
 
+    /// Sets the sink that the pipeline's stages emit [`DerivationEvent`]s to.
+    ///
+    /// [`DerivationEvent`]: crate::DerivationEvent
+    pub fn event_sink(mut self, sink: Arc<dyn DerivationEventSink>) -> Self {
+        self.events = EventEmitter::new(sink);
+        self
+    }
+
     /// Builds a derivation pipeline with the [`PolledAttributesQueueStage`].
     pub fn build_polled(self) -> DerivationPipeline<PolledAttributesQueueStage<D, P, T, B>, T> {
         self.into()
@@ -126,15 +136,20 @@ where
         let mut l1_traversal = PollingTraversal::new(chain_provider, Arc::clone(&rollup_config));
         l1_traversal.block = Some(builder.origin.expect("origin must be set"));
         let l1_retrieval = L1Retrieval::new(l1_traversal, dap_source);
-        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config));
-        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue);
+        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config))
+            .with_events(builder.events.clone());
+        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue)
+            .with_events(builder.events.clone());
         let channel_reader = ChannelReader::new(channel_provider, Arc::clone(&rollup_config));
         let batch_stream =
-            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone());
+            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone())
+                .with_events(builder.events.clone());
         let batch_provider =
-            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone());
+            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone())
+                .with_events(builder.events.clone());
         let attributes =
-            AttributesQueue::new(rollup_config.clone(), batch_provider, attributes_builder);
+            AttributesQueue::new(rollup_config.clone(), batch_provider, attributes_builder)
+                .with_events(builder.events);
 
         // Create the pipeline.
         Self::new(attributes, rollup_config, l2_chain_provider)
@@ -161,15 +176,20 @@ where
         let mut l1_traversal = IndexedTraversal::new(chain_provider, Arc::clone(&rollup_config));
         l1_traversal.block = Some(builder.origin.expect("origin must be set"));
         let l1_retrieval = L1Retrieval::new(l1_traversal, dap_source);
-        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config));
-        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue);
+        let frame_queue = FrameQueue::new(l1_retrieval, Arc::clone(&rollup_config))
+            .with_events(builder.events.clone());
+        let channel_provider = ChannelProvider::new(Arc::clone(&rollup_config), frame_queue)
+            .with_events(builder.events.clone());
         let channel_reader = ChannelReader::new(channel_provider, Arc::clone(&rollup_config));
         let batch_stream =
-            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone());
+            BatchStream::new(channel_reader, rollup_config.clone(), l2_chain_provider.clone())
+                .with_events(builder.events.clone());
         let batch_provider =
-            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone());
+            BatchProvider::new(rollup_config.clone(), batch_stream, l2_chain_provider.clone())
+                .with_events(builder.events.clone());
         let attributes =
-            AttributesQueue::new(rollup_config.clone(), batch_provider, attributes_builder);
+            AttributesQueue::new(rollup_config.clone(), batch_provider, attributes_builder)
+                .with_events(builder.events);
 
         // Create the pipeline.
         Self::new(attributes, rollup_config, l2_chain_provider)
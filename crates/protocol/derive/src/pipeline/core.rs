@@ -2,8 +2,8 @@
 
 use crate::{
     ActivationSignal, L2ChainProvider, NextAttributes, OriginAdvancer, OriginProvider, Pipeline,
-    PipelineError, PipelineErrorKind, PipelineResult, ResetSignal, Signal, SignalReceiver,
-    StepResult,
+    PipelineCheckpoint, PipelineError, PipelineErrorKind, PipelineResult, ResetSignal, Signal,
+    SignalReceiver, StepResult,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use async_trait::async_trait;
@@ -130,6 +130,43 @@ where
         );
         Ok(())
     }
+
+    /// Takes a [`PipelineCheckpoint`] of the stages, by calling the [`SignalReceiver::checkpoint`]
+    /// method.
+    ///
+    /// The checkpoint can only be taken at an L1 origin boundary, once all prepared
+    /// [`OpAttributesWithParent`] have been consumed. Otherwise, a
+    /// [`PipelineError::NotAtOriginBoundary`] error is returned.
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        if !self.prepared.is_empty() {
+            return Err(PipelineError::NotAtOriginBoundary.temp());
+        }
+        self.attributes.checkpoint(checkpoint)
+    }
+
+    /// Restores the stages from a [`PipelineCheckpoint`].
+    ///
+    /// The stages are first reset to the checkpoint's L1 origin and L2 safe head, using the
+    /// checkpoint's [`SystemConfig`], and then restored with the [`SignalReceiver::restore`]
+    /// method.
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        let reset = ResetSignal {
+            l2_safe_head: checkpoint.l2_safe_head,
+            l1_origin: checkpoint.l1_origin,
+            system_config: Some(checkpoint.system_config),
+        };
+        match self.attributes.signal(reset.signal()).await {
+            Ok(()) | Err(PipelineErrorKind::Temporary(PipelineError::Eof)) => {}
+            Err(err) => {
+                error!(target: "pipeline", "Stage reset errored: {:?}", err);
+                return Err(err);
+            }
+        }
+        self.prepared.clear();
+        self.attributes.restore(checkpoint).await?;
+        trace!(target: "pipeline", "Stages restored");
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -178,7 +215,9 @@ where
             crate::metrics::Metrics::PIPELINE_STEP_BLOCK,
             cursor.block_info.number as f64
         );
-        match self.attributes.next_attributes(cursor).await {
+        match kona_macros::profile!("attributes_queue", self.attributes.next_attributes(cursor))
+            .await
+        {
             Ok(a) => {
                 trace!(target: "pipeline", "Prepared L2 attributes: {:?}", a);
                 kona_macros::inc!(
@@ -206,7 +245,10 @@ where
             Err(err) => match err {
                 PipelineErrorKind::Temporary(PipelineError::Eof) => {
                     trace!(target: "pipeline", "Pipeline advancing origin");
-                    if let Err(e) = self.attributes.advance_origin().await {
+                    if let Err(e) =
+                        kona_macros::profile!("advance_origin", self.attributes.advance_origin())
+                            .await
+                    {
                         return StepResult::OriginAdvanceErr(e);
                     }
                     StepResult::AdvancedOrigin
@@ -227,11 +269,17 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{DerivationPipeline, test_utils::*};
-    use alloc::{string::ToString, sync::Arc};
+    use crate::{
+        BatchCheckpoint, ChannelCheckpoint, DerivationPipeline, test_utils::*,
+        types::encode_single_batch,
+    };
+    use alloc::{string::ToString, sync::Arc, vec};
     use alloy_rpc_types_engine::PayloadAttributes;
     use kona_genesis::{RollupConfig, SystemConfig};
-    use kona_protocol::{L2BlockInfo, OpAttributesWithParent};
+    use kona_protocol::{
+        Batch, BatchWithInclusionBlock, Channel, Frame, L2BlockInfo, OpAttributesWithParent,
+        SingleBatch,
+    };
     use op_alloy_rpc_types_engine::OpPayloadAttributes;
 
     fn default_test_payload_attributes() -> OpAttributesWithParent {
@@ -364,4 +412,56 @@ mod tests {
         let result = pipeline.signal(ResetSignal::default().signal()).await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_derivation_pipeline_checkpoint_prepared_attributes() {
+        let mut pipeline = new_test_pipeline();
+        pipeline.prepared.push_back(default_test_payload_attributes());
+
+        let mut checkpoint = PipelineCheckpoint::default();
+        let err = pipeline.checkpoint(&mut checkpoint).unwrap_err();
+        assert_eq!(err, PipelineError::NotAtOriginBoundary.temp());
+    }
+
+    #[tokio::test]
+    async fn test_derivation_pipeline_checkpoint_restore_roundtrip() {
+        let id = [0xFF; 16];
+        let parent = BlockInfo { number: 9, timestamp: 88, ..Default::default() };
+        let l1_origin = BlockInfo { number: 10, timestamp: 100, ..Default::default() };
+        let mut channel = Channel::new(id, parent);
+        channel.add_frame(Frame::new(id, 0, vec![0xde, 0xad], false), parent).unwrap();
+        let batch = SingleBatch { epoch_num: 9, timestamp: 20, ..Default::default() };
+
+        let checkpoint = PipelineCheckpoint {
+            l1_origin,
+            l2_safe_head: L2BlockInfo {
+                block_info: BlockInfo { number: 5, timestamp: 18, ..Default::default() },
+                l1_origin: parent.id(),
+                seq_num: 0,
+            },
+            system_config: SystemConfig { gas_limit: 30_000_000, ..Default::default() },
+            frames: vec![Frame::new(id, 1, vec![0xbe, 0xef], true).encode().into()],
+            channels: vec![ChannelCheckpoint::from(&channel)],
+            batch_origin: Some(l1_origin),
+            l1_blocks: vec![parent, l1_origin],
+            batches: vec![
+                BatchCheckpoint::new(&BatchWithInclusionBlock::new(
+                    parent,
+                    Batch::Single(batch.clone()),
+                ))
+                .unwrap(),
+            ],
+            attributes_batch: Some(encode_single_batch(&batch)),
+            is_last_in_span: true,
+            ..Default::default()
+        };
+
+        let mut pipeline = new_test_pipeline();
+        pipeline.restore(&checkpoint).await.unwrap();
+        assert_eq!(pipeline.origin(), Some(l1_origin));
+
+        let mut restored = PipelineCheckpoint::new(checkpoint.l2_safe_head);
+        pipeline.checkpoint(&mut restored).unwrap();
+        assert_eq!(restored, checkpoint);
+    }
 }
@@ -0,0 +1,309 @@
+//! Contains the [AltDAChallenges] tracker, which follows the status of challenged commitments
+//! through the events of the `DataAvailabilityChallenge` contract.
+
+use crate::AltDACommitment;
+use alloy_consensus::{Receipt, Transaction, TxEnvelope};
+use alloy_primitives::{Address, B256, Bytes, U256, keccak256, map::HashMap};
+
+/// The signature of the `ChallengeStatusChanged` event of the `DataAvailabilityChallenge`
+/// contract.
+pub(crate) const CHALLENGE_STATUS_CHANGED_EVENT: &str =
+    "ChallengeStatusChanged(uint256,bytes,uint8)";
+
+/// The signature of the `resolve` function of the `DataAvailabilityChallenge` contract.
+pub(crate) const RESOLVE_FUNCTION: &str = "resolve(uint256,bytes,bytes)";
+
+/// The status of a challenge, as emitted by the `DataAvailabilityChallenge` contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ChallengeStatus {
+    /// The commitment has not been challenged.
+    Uninitialized = 0,
+    /// The commitment has been challenged, and awaits resolution.
+    Active = 1,
+    /// The challenge has been resolved by posting the input on-chain.
+    Resolved = 2,
+    /// The challenge was not resolved within the resolve window.
+    Expired = 3,
+}
+
+impl TryFrom<u8> for ChallengeStatus {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Uninitialized),
+            1 => Ok(Self::Active),
+            2 => Ok(Self::Resolved),
+            3 => Ok(Self::Expired),
+            _ => Err(value),
+        }
+    }
+}
+
+/// The state of a challenged commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    /// The status of the challenge.
+    pub status: ChallengeStatus,
+    /// The L1 block number in which the challenge was opened.
+    pub challenged_at: u64,
+    /// The input that resolved the challenge, if it has been resolved.
+    pub resolved_input: Option<Bytes>,
+}
+
+/// Tracks challenges of commitments through the `ChallengeStatusChanged` events of the
+/// `DataAvailabilityChallenge` contract, and expires active challenges that are not resolved
+/// within the resolve window.
+///
+/// Commitments are challenged by the L1 block number in which they were included. Both windows
+/// are measured in L1 blocks.
+#[derive(Debug, Clone)]
+pub struct AltDAChallenges {
+    /// The address of the `DataAvailabilityChallenge` contract.
+    challenge_address: Address,
+    /// The number of L1 blocks after inclusion in which a commitment can be challenged.
+    challenge_window: u64,
+    /// The number of L1 blocks after a challenge in which it can be resolved.
+    resolve_window: u64,
+    /// The challenges, keyed by the inclusion block number and encoded commitment.
+    challenges: HashMap<(u64, Bytes), Challenge>,
+    /// The number of the last L1 block whose events have been processed.
+    origin: Option<u64>,
+}
+
+impl AltDAChallenges {
+    /// Creates a new [AltDAChallenges] tracker.
+    pub fn new(challenge_address: Address, challenge_window: u64, resolve_window: u64) -> Self {
+        Self {
+            challenge_address,
+            challenge_window,
+            resolve_window,
+            challenges: HashMap::default(),
+            origin: None,
+        }
+    }
+
+    /// Returns the number of the last L1 block whose events have been processed.
+    pub const fn origin(&self) -> Option<u64> {
+        self.origin
+    }
+
+    /// Returns the number of L1 blocks after inclusion in which a commitment can be challenged.
+    pub const fn challenge_window(&self) -> u64 {
+        self.challenge_window
+    }
+
+    /// Returns the number of L1 blocks after a challenge in which it can be resolved.
+    pub const fn resolve_window(&self) -> u64 {
+        self.resolve_window
+    }
+
+    /// Returns the [Challenge] of the commitment included in the given L1 block, if any.
+    pub fn challenge(
+        &self,
+        inclusion_block: u64,
+        commitment: &AltDACommitment,
+    ) -> Option<&Challenge> {
+        self.challenges.get(&(inclusion_block, commitment.encode()))
+    }
+
+    /// Drops all tracked challenges. Tracking restarts at the next processed block.
+    pub fn clear(&mut self) {
+        self.challenges.clear();
+        self.origin = None;
+    }
+
+    /// Returns `true` if the challenge events of the L1 block `number` are needed.
+    pub fn needs_block(&self, number: u64) -> bool {
+        self.origin.is_none_or(|origin| number > origin)
+    }
+
+    /// Returns `true` if the given receipts contain any logs of the challenge contract, in which
+    /// case the transactions of the block are required by [Self::process_block].
+    pub fn has_events(&self, receipts: &[Receipt]) -> bool {
+        receipts.iter().flat_map(|r| r.logs.iter()).any(|log| log.address == self.challenge_address)
+    }
+
+    /// Processes the challenge events of the L1 block `number`, and expires the active challenges
+    /// whose resolve window has passed. Blocks must be processed in order.
+    ///
+    /// `txs` must hold the transactions of the block if [Self::has_events] returns `true` for its
+    /// receipts, and is otherwise unused.
+    pub fn process_block(&mut self, number: u64, receipts: &[Receipt], txs: &[TxEnvelope]) {
+        let event_topic = keccak256(CHALLENGE_STATUS_CHANGED_EVENT);
+
+        for (index, receipt) in receipts.iter().enumerate() {
+            for log in &receipt.logs {
+                if log.address != self.challenge_address ||
+                    log.topics().first() != Some(&event_topic)
+                {
+                    continue;
+                }
+
+                let Some(event) = decode_status_changed(log.topics(), &log.data.data) else {
+                    warn!(target: "altda", "Failed to decode challenge event in block {number}");
+                    continue;
+                };
+                let (inclusion_block, commitment, status) = event;
+
+                match status {
+                    ChallengeStatus::Active => {
+                        self.challenges.insert(
+                            (inclusion_block, commitment),
+                            Challenge { status, challenged_at: number, resolved_input: None },
+                        );
+                    }
+                    ChallengeStatus::Resolved => {
+                        let input = txs.get(index).and_then(|tx| decode_resolve_input(tx.input()));
+                        if input.is_none() {
+                            warn!(
+                                target: "altda",
+                                "Failed to decode resolve input in block {number}"
+                            );
+                        }
+                        let challenge =
+                            self.challenges.entry((inclusion_block, commitment)).or_insert(
+                                Challenge { status, challenged_at: number, resolved_input: None },
+                            );
+                        challenge.status = status;
+                        challenge.resolved_input = input;
+                    }
+                    ChallengeStatus::Expired | ChallengeStatus::Uninitialized => {
+                        if let Some(challenge) =
+                            self.challenges.get_mut(&(inclusion_block, commitment))
+                        {
+                            challenge.status = status;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Expire the active challenges whose resolve window has passed.
+        for challenge in self.challenges.values_mut() {
+            if challenge.status == ChallengeStatus::Active &&
+                number > challenge.challenged_at + self.resolve_window
+            {
+                challenge.status = ChallengeStatus::Expired;
+            }
+        }
+
+        // Prune challenges that can no longer be referenced by a commitment being derived.
+        let horizon = self.challenge_window + self.resolve_window;
+        self.challenges.retain(|(inclusion_block, _), _| inclusion_block + horizon >= number);
+
+        self.origin = Some(number);
+    }
+}
+
+/// Decodes a `ChallengeStatusChanged(uint256 indexed, bytes, uint8)` event into the inclusion
+/// block number, the encoded commitment and the new status.
+fn decode_status_changed(topics: &[B256], data: &[u8]) -> Option<(u64, Bytes, ChallengeStatus)> {
+    let inclusion_block = U256::from_be_bytes(topics.get(1)?.0).try_into().ok()?;
+    let commitment = abi_bytes(data, 0)?;
+    let status = ChallengeStatus::try_from(u8::try_from(abi_word(data, 1)?).ok()?).ok()?;
+    Some((inclusion_block, Bytes::copy_from_slice(commitment), status))
+}
+
+/// Decodes the `resolveData` argument of a `resolve(uint256, bytes, bytes)` call.
+fn decode_resolve_input(input: &[u8]) -> Option<Bytes> {
+    let (selector, args) = input.split_at_checked(4)?;
+    if selector != &keccak256(RESOLVE_FUNCTION)[..4] {
+        return None;
+    }
+    abi_bytes(args, 2).map(Bytes::copy_from_slice)
+}
+
+/// Reads the 32 byte ABI word at the given index.
+fn abi_word(data: &[u8], index: usize) -> Option<U256> {
+    let word = data.get(index * 32..(index + 1) * 32)?;
+    Some(U256::from_be_slice(word))
+}
+
+/// Reads the dynamic `bytes` argument whose offset is stored in the ABI word at the given index.
+fn abi_bytes(data: &[u8], index: usize) -> Option<&[u8]> {
+    let offset: usize = abi_word(data, index)?.try_into().ok()?;
+    let len: usize = abi_word(data.get(offset..)?, 0)?.try_into().ok()?;
+    let start = offset.checked_add(32)?;
+    data.get(start..start.checked_add(len)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{challenge_status_log, resolve_calldata};
+    use alloc::{vec, vec::Vec};
+    use alloy_consensus::{Signed, TxLegacy};
+    use alloy_primitives::{Signature, address};
+
+    const CHALLENGE_ADDRESS: Address = address!("12c6a7db25b20347ca6f5d47e56d5e8219871c6d");
+
+    fn receipt(logs: Vec<alloy_primitives::Log>) -> Receipt {
+        Receipt { logs, ..Default::default() }
+    }
+
+    fn resolve_tx(inclusion_block: u64, commitment: &AltDACommitment, input: &[u8]) -> TxEnvelope {
+        TxEnvelope::Legacy(Signed::new_unchecked(
+            TxLegacy {
+                input: resolve_calldata(inclusion_block, commitment, input),
+                ..Default::default()
+            },
+            Signature::test_signature(),
+            Default::default(),
+        ))
+    }
+
+    #[test]
+    fn test_challenge_resolved() {
+        let mut challenges = AltDAChallenges::new(CHALLENGE_ADDRESS, 10, 5);
+        let commitment = AltDACommitment::keccak(b"input");
+
+        challenges.process_block(1, &[], &[]);
+        assert!(challenges.challenge(1, &commitment).is_none());
+
+        let log = challenge_status_log(CHALLENGE_ADDRESS, 1, &commitment, ChallengeStatus::Active);
+        challenges.process_block(2, &[receipt(vec![log])], &[]);
+        let challenge = challenges.challenge(1, &commitment).unwrap();
+        assert_eq!(challenge.status, ChallengeStatus::Active);
+        assert_eq!(challenge.challenged_at, 2);
+
+        let log =
+            challenge_status_log(CHALLENGE_ADDRESS, 1, &commitment, ChallengeStatus::Resolved);
+        let receipts = [receipt(vec![]), receipt(vec![log])];
+        let txs = [resolve_tx(0, &commitment, b"other"), resolve_tx(1, &commitment, b"input")];
+        assert!(challenges.has_events(&receipts));
+        challenges.process_block(3, &receipts, &txs);
+
+        let challenge = challenges.challenge(1, &commitment).unwrap();
+        assert_eq!(challenge.status, ChallengeStatus::Resolved);
+        assert_eq!(challenge.resolved_input, Some(Bytes::from_static(b"input")));
+        assert_eq!(challenges.origin(), Some(3));
+    }
+
+    #[test]
+    fn test_challenge_expired() {
+        let mut challenges = AltDAChallenges::new(CHALLENGE_ADDRESS, 10, 5);
+        let commitment = AltDACommitment::keccak(b"input");
+
+        let log = challenge_status_log(CHALLENGE_ADDRESS, 1, &commitment, ChallengeStatus::Active);
+        challenges.process_block(2, &[receipt(vec![log])], &[]);
+        challenges.process_block(7, &[], &[]);
+        assert_eq!(challenges.challenge(1, &commitment).unwrap().status, ChallengeStatus::Active);
+
+        challenges.process_block(8, &[], &[]);
+        assert_eq!(challenges.challenge(1, &commitment).unwrap().status, ChallengeStatus::Expired);
+    }
+
+    #[test]
+    fn test_ignores_other_contracts() {
+        let mut challenges = AltDAChallenges::new(CHALLENGE_ADDRESS, 10, 5);
+        let commitment = AltDACommitment::keccak(b"input");
+
+        let log = challenge_status_log(Address::ZERO, 1, &commitment, ChallengeStatus::Active);
+        let receipts = [receipt(vec![log])];
+        assert!(!challenges.has_events(&receipts));
+        challenges.process_block(2, &receipts, &[]);
+        assert!(challenges.challenge(1, &commitment).is_none());
+    }
+}
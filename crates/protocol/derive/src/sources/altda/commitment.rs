@@ -0,0 +1,148 @@
+//! Contains the [AltDACommitment] type, which references batcher data posted to an Alt-DA server.
+
+use crate::AltDAError;
+use alloc::{string::ToString, vec::Vec};
+use alloy_primitives::{B256, Bytes, keccak256};
+use core::str::FromStr;
+
+/// The derivation version byte that prefixes batcher transaction data carrying an
+/// [AltDACommitment], rather than frames.
+pub const ALTDA_DERIVATION_VERSION: u8 = 0x01;
+
+/// The maximum size of an input that can be resolved on-chain, and thus the maximum size of an
+/// input that is accepted by the [AltDASource].
+///
+/// [AltDASource]: crate::AltDASource
+pub const MAX_ALTDA_INPUT_SIZE: usize = 130_672;
+
+/// The type of an [AltDACommitment], as configured by `da_commitment_type` in the rollup config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum AltDACommitmentType {
+    /// A keccak256 commitment to the input, which can be challenged on-chain.
+    Keccak = 0x00,
+    /// An opaque commitment, whose format is defined by the DA layer.
+    Generic = 0x01,
+}
+
+impl FromStr for AltDACommitmentType {
+    type Err = AltDAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "KeccakCommitment" => Ok(Self::Keccak),
+            "GenericCommitment" => Ok(Self::Generic),
+            _ => Err(AltDAError::UnknownCommitmentType(s.to_string())),
+        }
+    }
+}
+
+/// A commitment to an input posted to an Alt-DA server.
+///
+/// Within a batcher transaction, a commitment is encoded as
+/// `ALTDA_DERIVATION_VERSION ++ commitment_type ++ commitment_data`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AltDACommitment {
+    /// A keccak256 commitment to the input.
+    Keccak(B256),
+    /// An opaque commitment. The first byte is the DA layer byte.
+    Generic(Bytes),
+}
+
+impl AltDACommitment {
+    /// Creates a new keccak256 [AltDACommitment] to the given input.
+    pub fn keccak(input: &[u8]) -> Self {
+        Self::Keccak(keccak256(input))
+    }
+
+    /// Returns the [AltDACommitmentType] of the commitment.
+    pub const fn commitment_type(&self) -> AltDACommitmentType {
+        match self {
+            Self::Keccak(_) => AltDACommitmentType::Keccak,
+            Self::Generic(_) => AltDACommitmentType::Generic,
+        }
+    }
+
+    /// Decodes an [AltDACommitment] from `commitment_type ++ commitment_data`.
+    pub fn decode(data: &[u8]) -> Result<Self, AltDAError> {
+        let (ty, commitment) = data.split_first().ok_or(AltDAError::InvalidCommitmentLength)?;
+        match *ty {
+            ty if ty == AltDACommitmentType::Keccak as u8 => {
+                if commitment.len() != B256::len_bytes() {
+                    return Err(AltDAError::InvalidCommitmentLength);
+                }
+                Ok(Self::Keccak(B256::from_slice(commitment)))
+            }
+            ty if ty == AltDACommitmentType::Generic as u8 => {
+                if commitment.is_empty() {
+                    return Err(AltDAError::InvalidCommitmentLength);
+                }
+                Ok(Self::Generic(Bytes::copy_from_slice(commitment)))
+            }
+            ty => Err(AltDAError::InvalidCommitmentType(ty)),
+        }
+    }
+
+    /// Encodes the commitment as `commitment_type ++ commitment_data`.
+    pub fn encode(&self) -> Bytes {
+        let data = match self {
+            Self::Keccak(hash) => hash.as_slice(),
+            Self::Generic(data) => data.as_ref(),
+        };
+        let mut encoded = Vec::with_capacity(1 + data.len());
+        encoded.push(self.commitment_type() as u8);
+        encoded.extend_from_slice(data);
+        encoded.into()
+    }
+
+    /// Encodes the commitment as the data of a batcher transaction, prefixed with the
+    /// [ALTDA_DERIVATION_VERSION].
+    pub fn tx_data(&self) -> Bytes {
+        [&[ALTDA_DERIVATION_VERSION], self.encode().as_ref()].concat().into()
+    }
+
+    /// Returns `true` if the given input matches the commitment. Generic commitments cannot be
+    /// verified, and always match.
+    pub fn verify(&self, input: &[u8]) -> bool {
+        match self {
+            Self::Keccak(hash) => keccak256(input) == *hash,
+            Self::Generic(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commitment_roundtrip() {
+        let commitments = [
+            AltDACommitment::keccak(b"input"),
+            AltDACommitment::Generic(Bytes::from_static(&[0x0c, 0xde, 0xad])),
+        ];
+        for commitment in commitments {
+            let tx_data = commitment.tx_data();
+            assert_eq!(tx_data[0], ALTDA_DERIVATION_VERSION);
+            assert_eq!(AltDACommitment::decode(&tx_data[1..]).unwrap(), commitment);
+        }
+    }
+
+    #[test]
+    fn test_commitment_decode_invalid() {
+        assert_eq!(AltDACommitment::decode(&[]), Err(AltDAError::InvalidCommitmentLength));
+        assert_eq!(AltDACommitment::decode(&[0x00; 4]), Err(AltDAError::InvalidCommitmentLength));
+        assert_eq!(AltDACommitment::decode(&[0x01]), Err(AltDAError::InvalidCommitmentLength));
+        assert_eq!(
+            AltDACommitment::decode(&[0x02, 0xFF]),
+            Err(AltDAError::InvalidCommitmentType(2))
+        );
+    }
+
+    #[test]
+    fn test_commitment_verify() {
+        let commitment = AltDACommitment::keccak(b"input");
+        assert!(commitment.verify(b"input"));
+        assert!(!commitment.verify(b"other"));
+    }
+}
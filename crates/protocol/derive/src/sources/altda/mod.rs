@@ -0,0 +1,16 @@
+//! Contains the [AltDASource], a data source for chains that post their batcher data to an
+//! alternative data availability layer, along with the [AltDACommitment] and [AltDAChallenges]
+//! types that it is built on.
+
+mod commitment;
+pub use commitment::{
+    ALTDA_DERIVATION_VERSION, AltDACommitment, AltDACommitmentType, MAX_ALTDA_INPUT_SIZE,
+};
+
+mod challenges;
+pub use challenges::{AltDAChallenges, Challenge, ChallengeStatus};
+#[cfg(any(test, feature = "test-utils"))]
+pub(crate) use challenges::{CHALLENGE_STATUS_CHANGED_EVENT, RESOLVE_FUNCTION};
+
+mod source;
+pub use source::AltDASource;
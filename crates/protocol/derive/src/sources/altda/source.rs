@@ -0,0 +1,450 @@
+//! Contains the [AltDASource], which resolves the [AltDACommitment]s posted to L1 by the batcher
+//! into the inputs that they commit to.
+
+use crate::{
+    ALTDA_DERIVATION_VERSION, AltDAChallenges, AltDAClient, AltDACommitment, AltDACommitmentType,
+    AltDAError, ChainProvider, ChallengeStatus, DataAvailabilityProvider, MAX_ALTDA_INPUT_SIZE,
+    PipelineResult,
+};
+use alloc::{boxed::Box, vec::Vec};
+use alloy_primitives::{Address, Bytes};
+use async_trait::async_trait;
+use kona_genesis::AltDAConfig;
+use kona_protocol::BlockInfo;
+
+/// A data source that wraps another [DataAvailabilityProvider], and resolves the
+/// [AltDACommitment]s within its data through an [AltDAClient].
+///
+/// Data that is not prefixed with the [ALTDA_DERIVATION_VERSION] is passed through unchanged.
+/// Commitments of a type other than the configured [AltDACommitmentType], as well as malformed
+/// commitments, are skipped.
+///
+/// If the DA server does not hold the input for a keccak256 commitment, the source looks ahead on
+/// L1 through the `DataAvailabilityChallenge` contract:
+/// - If the commitment is challenged and the challenge is resolved, the input posted on-chain is
+///   returned.
+/// - If the challenge expires without being resolved, the commitment is skipped.
+/// - If the commitment is not challenged within the challenge window, derivation cannot continue,
+///   and a critical error is returned.
+#[derive(Debug, Clone)]
+pub struct AltDASource<C, D, A>
+where
+    C: ChainProvider + Send,
+    D: DataAvailabilityProvider + Send,
+    A: AltDAClient + Send,
+{
+    /// The chain provider used to follow challenges on L1.
+    pub chain_provider: C,
+    /// The data source that provides the batcher transaction data.
+    pub inner: D,
+    /// The DA server client.
+    pub client: A,
+    /// The type of commitments accepted by the source.
+    pub commitment_type: AltDACommitmentType,
+    /// The challenge tracker. If `None`, commitments cannot be challenged.
+    pub challenges: Option<AltDAChallenges>,
+    /// The commitment whose input is still being resolved.
+    pending: Option<AltDACommitment>,
+}
+
+impl<C, D, A> AltDASource<C, D, A>
+where
+    C: ChainProvider + Send,
+    D: DataAvailabilityProvider + Send,
+    A: AltDAClient + Send,
+{
+    /// Creates a new [AltDASource].
+    pub const fn new(
+        chain_provider: C,
+        inner: D,
+        client: A,
+        commitment_type: AltDACommitmentType,
+        challenges: Option<AltDAChallenges>,
+    ) -> Self {
+        Self { chain_provider, inner, client, commitment_type, challenges, pending: None }
+    }
+
+    /// Creates a new [AltDASource] from the [AltDAConfig] of the rollup.
+    ///
+    /// The commitment type defaults to [AltDACommitmentType::Keccak], and challenges are only
+    /// tracked if a challenge contract is configured, in which case the challenge and resolve
+    /// windows must be configured as well. The windows are measured in L1 blocks.
+    pub fn from_config(
+        chain_provider: C,
+        inner: D,
+        client: A,
+        cfg: &AltDAConfig,
+    ) -> Result<Self, AltDAError> {
+        let commitment_type = cfg
+            .da_commitment_type
+            .as_deref()
+            .map(str::parse)
+            .transpose()?
+            .unwrap_or(AltDACommitmentType::Keccak);
+        let challenges = match cfg.da_challenge_address {
+            Some(address) => {
+                let (Some(challenge_window), Some(resolve_window)) =
+                    (cfg.da_challenge_window, cfg.da_resolve_window)
+                else {
+                    return Err(AltDAError::MissingChallengeWindows);
+                };
+                Some(AltDAChallenges::new(address, challenge_window, resolve_window))
+            }
+            None => None,
+        };
+        Ok(Self::new(chain_provider, inner, client, commitment_type, challenges))
+    }
+
+    /// Processes the challenge events of the L1 blocks up to and including `number`. Tracking
+    /// starts at the first block that is processed.
+    async fn advance_challenges(&mut self, number: u64) -> PipelineResult<()> {
+        let Self { chain_provider, challenges, .. } = self;
+        let Some(challenges) = challenges else {
+            return Ok(());
+        };
+
+        let start = challenges.origin().map_or(number, |origin| origin + 1);
+        for block_number in start..=number {
+            let block =
+                chain_provider.block_info_by_number(block_number).await.map_err(Into::into)?;
+            let receipts = chain_provider.receipts_by_hash(block.hash).await.map_err(Into::into)?;
+            let txs = if challenges.has_events(&receipts) {
+                chain_provider
+                    .block_info_and_transactions_by_hash(block.hash)
+                    .await
+                    .map_err(Into::into)?
+                    .1
+            } else {
+                Vec::new()
+            };
+            challenges.process_block(block_number, &receipts, &txs);
+        }
+
+        Ok(())
+    }
+
+    /// Fetches the input for the given commitment, included in `block_ref`. Returns `None` if the
+    /// commitment should be skipped.
+    async fn fetch_input(
+        &mut self,
+        block_ref: &BlockInfo,
+        commitment: &AltDACommitment,
+    ) -> PipelineResult<Option<Bytes>> {
+        if let Some(input) = self.client.get_input(commitment).await.map_err(Into::into)? {
+            // Oversized inputs are skipped before they are verified, since clients may truncate
+            // them.
+            if commitment.commitment_type() == AltDACommitmentType::Keccak &&
+                input.len() > MAX_ALTDA_INPUT_SIZE
+            {
+                warn!(target: "altda", "Skipping oversized input of {} bytes", input.len());
+                return Ok(None);
+            }
+            if !commitment.verify(&input) {
+                return Err(AltDAError::InputMismatch(commitment.encode()).into());
+            }
+            return Ok(Some(input));
+        }
+
+        // Only keccak256 commitments can be challenged, and thus resolved on-chain.
+        let challengeable = commitment.commitment_type() == AltDACommitmentType::Keccak;
+        if !challengeable || self.challenges.is_none() {
+            return Err(AltDAError::PendingInput(commitment.encode()).into());
+        }
+
+        loop {
+            let Some(challenges) = self.challenges.as_ref() else {
+                return Err(AltDAError::PendingInput(commitment.encode()).into());
+            };
+            let origin = challenges.origin().unwrap_or(block_ref.number);
+
+            match challenges.challenge(block_ref.number, commitment) {
+                Some(challenge) if challenge.status == ChallengeStatus::Resolved => {
+                    return match challenge.resolved_input {
+                        Some(ref input) if commitment.verify(input) => Ok(Some(input.clone())),
+                        _ => Err(AltDAError::MissingPastWindow(commitment.encode()).into()),
+                    };
+                }
+                Some(challenge) if challenge.status == ChallengeStatus::Expired => {
+                    warn!(target: "altda", "Skipping commitment with expired challenge");
+                    return Ok(None);
+                }
+                Some(_) => {}
+                None if origin >= block_ref.number + challenges.challenge_window() => {
+                    return Err(AltDAError::MissingPastWindow(commitment.encode()).into());
+                }
+                None => {}
+            }
+
+            // Look ahead on L1 until the challenge is resolved, expires, or the challenge window
+            // passes.
+            self.advance_challenges(origin + 1).await?;
+        }
+    }
+}
+
+#[async_trait]
+impl<C, D, A> DataAvailabilityProvider for AltDASource<C, D, A>
+where
+    C: ChainProvider + Send,
+    D: DataAvailabilityProvider + Send,
+    A: AltDAClient + Send,
+{
+    type Item = Bytes;
+
+    async fn next(
+        &mut self,
+        block_ref: &BlockInfo,
+        batcher_address: Address,
+    ) -> PipelineResult<Self::Item> {
+        self.advance_challenges(block_ref.number).await?;
+
+        loop {
+            let commitment = match self.pending.take() {
+                Some(commitment) => commitment,
+                None => {
+                    let data: Bytes = self.inner.next(block_ref, batcher_address).await?.into();
+                    match data.first() {
+                        Some(&ALTDA_DERIVATION_VERSION) => {}
+                        Some(_) => return Ok(data),
+                        None => continue,
+                    }
+
+                    let commitment = match AltDACommitment::decode(&data[1..]) {
+                        Ok(commitment) => commitment,
+                        Err(e) => {
+                            warn!(target: "altda", "Skipping invalid commitment: {e}");
+                            continue;
+                        }
+                    };
+                    if commitment.commitment_type() != self.commitment_type {
+                        warn!(
+                            target: "altda",
+                            "Skipping commitment of type {:?}, expected {:?}",
+                            commitment.commitment_type(),
+                            self.commitment_type
+                        );
+                        continue;
+                    }
+                    commitment
+                }
+            };
+
+            match self.fetch_input(block_ref, &commitment).await {
+                Ok(Some(input)) => return Ok(input),
+                Ok(None) => continue,
+                Err(e) => {
+                    self.pending = Some(commitment);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+        self.pending = None;
+        if let Some(challenges) = self.challenges.as_mut() {
+            challenges.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        PipelineError, PipelineErrorKind,
+        test_utils::{
+            TestAltDAClient, TestChainProvider, TestDAP, challenge_status_log, resolve_calldata,
+        },
+    };
+    use alloc::vec;
+    use alloy_consensus::{Receipt, Signed, TxEnvelope, TxLegacy};
+    use alloy_primitives::{B256, Signature, address};
+
+    const CHALLENGE_ADDRESS: Address = address!("12c6a7db25b20347ca6f5d47e56d5e8219871c6d");
+
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo { number, hash: B256::with_last_byte(number as u8), ..Default::default() }
+    }
+
+    fn test_source(
+        data: Vec<Bytes>,
+        challenges: Option<AltDAChallenges>,
+    ) -> AltDASource<TestChainProvider, TestDAP, TestAltDAClient> {
+        let dap = TestDAP { results: data.into_iter().rev().map(Ok).collect() };
+        AltDASource::new(
+            TestChainProvider::default(),
+            dap,
+            TestAltDAClient::default(),
+            AltDACommitmentType::Keccak,
+            challenges,
+        )
+    }
+
+    fn insert_blocks(provider: &mut TestChainProvider, blocks: Vec<Vec<alloy_primitives::Log>>) {
+        for (number, logs) in (1..).zip(blocks) {
+            provider.insert_block(number, block(number));
+            provider
+                .insert_receipts(block(number).hash, vec![Receipt { logs, ..Default::default() }]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_passthrough_frames() {
+        let frames = Bytes::from_static(&[0x00, 0xde, 0xad]);
+        let mut source = test_source(vec![frames.clone()], None);
+        assert_eq!(source.next(&block(1), Address::ZERO).await.unwrap(), frames);
+    }
+
+    #[tokio::test]
+    async fn test_input_from_client() {
+        let input = Bytes::from_static(&[0x00, 0xbe, 0xef]);
+        let mut client = TestAltDAClient::default();
+        let commitment = client.insert(input.clone());
+
+        let mut source = test_source(vec![commitment.tx_data()], None);
+        source.client = client;
+        assert_eq!(source.next(&block(1), Address::ZERO).await.unwrap(), input);
+        let err = source.next(&block(1), Address::ZERO).await.unwrap_err();
+        assert_eq!(err, PipelineError::Eof.temp());
+    }
+
+    #[tokio::test]
+    async fn test_skips_truncated_oversized_input() {
+        let input = Bytes::from(vec![0xAA; MAX_ALTDA_INPUT_SIZE + 2]);
+        let commitment = AltDACommitment::keccak(&input);
+        let mut client = TestAltDAClient::default();
+        client.insert_with_commitment(commitment.clone(), input.slice(..=MAX_ALTDA_INPUT_SIZE));
+
+        let mut source = test_source(vec![commitment.tx_data()], None);
+        source.client = client;
+        let err = source.next(&block(1), Address::ZERO).await.unwrap_err();
+        assert_eq!(err, PipelineError::Eof.temp());
+    }
+
+    #[tokio::test]
+    async fn test_skips_invalid_commitments() {
+        let input = Bytes::from_static(&[0x00, 0xbe, 0xef]);
+        let mut client = TestAltDAClient::default();
+        let commitment = client.insert(input.clone());
+        let generic = AltDACommitment::Generic(Bytes::from_static(&[0x0c, 0xde, 0xad]));
+
+        let data = vec![
+            Bytes::new(),
+            generic.tx_data(),
+            Bytes::from_static(&[ALTDA_DERIVATION_VERSION, 0x05]),
+            commitment.tx_data(),
+        ];
+        let mut source = test_source(data, None);
+        source.client = client;
+        assert_eq!(source.next(&block(1), Address::ZERO).await.unwrap(), input);
+    }
+
+    #[tokio::test]
+    async fn test_pending_input() {
+        let input = Bytes::from_static(&[0x00, 0xbe, 0xef]);
+        let commitment = AltDACommitment::keccak(&input);
+
+        let mut source = test_source(vec![commitment.tx_data()], None);
+        let err = source.next(&block(1), Address::ZERO).await.unwrap_err();
+        assert!(matches!(err, PipelineErrorKind::Temporary(_)));
+
+        // The pending commitment is retried once the DA server holds its input.
+        source.client.insert(input.clone());
+        assert_eq!(source.next(&block(1), Address::ZERO).await.unwrap(), input);
+    }
+
+    #[tokio::test]
+    async fn test_resolved_on_chain() {
+        let input = Bytes::from_static(&[0x00, 0xbe, 0xef]);
+        let commitment = AltDACommitment::keccak(&input);
+
+        let challenges = AltDAChallenges::new(CHALLENGE_ADDRESS, 2, 2);
+        let mut source = test_source(vec![commitment.tx_data()], Some(challenges));
+        let active =
+            challenge_status_log(CHALLENGE_ADDRESS, 1, &commitment, ChallengeStatus::Active);
+        let resolved =
+            challenge_status_log(CHALLENGE_ADDRESS, 1, &commitment, ChallengeStatus::Resolved);
+        insert_blocks(&mut source.chain_provider, vec![vec![], vec![active], vec![resolved]]);
+
+        let resolve_tx = TxEnvelope::Legacy(Signed::new_unchecked(
+            TxLegacy { input: resolve_calldata(1, &commitment, &input), ..Default::default() },
+            Signature::test_signature(),
+            Default::default(),
+        ));
+        source.chain_provider.transactions.push((block(3).hash, vec![resolve_tx]));
+
+        assert_eq!(source.next(&block(1), Address::ZERO).await.unwrap(), input);
+    }
+
+    #[tokio::test]
+    async fn test_expired_challenge_skipped() {
+        let commitment = AltDACommitment::keccak(b"input");
+
+        let challenges = AltDAChallenges::new(CHALLENGE_ADDRESS, 2, 1);
+        let mut source = test_source(vec![commitment.tx_data()], Some(challenges));
+        let active =
+            challenge_status_log(CHALLENGE_ADDRESS, 1, &commitment, ChallengeStatus::Active);
+        insert_blocks(&mut source.chain_provider, vec![vec![], vec![active], vec![], vec![]]);
+
+        let err = source.next(&block(1), Address::ZERO).await.unwrap_err();
+        assert_eq!(err, PipelineError::Eof.temp());
+    }
+
+    #[tokio::test]
+    async fn test_missing_past_challenge_window() {
+        let commitment = AltDACommitment::keccak(b"input");
+
+        let challenges = AltDAChallenges::new(CHALLENGE_ADDRESS, 2, 1);
+        let mut source = test_source(vec![commitment.tx_data()], Some(challenges));
+        insert_blocks(&mut source.chain_provider, vec![vec![], vec![], vec![]]);
+
+        let err = source.next(&block(1), Address::ZERO).await.unwrap_err();
+        assert_eq!(err, AltDAError::MissingPastWindow(commitment.encode()).into());
+    }
+
+    #[test]
+    fn test_from_config_missing_windows() {
+        let cfg = AltDAConfig {
+            da_challenge_address: Some(CHALLENGE_ADDRESS),
+            da_challenge_window: Some(2),
+            ..Default::default()
+        };
+        let err = AltDASource::from_config(
+            TestChainProvider::default(),
+            TestDAP::default(),
+            TestAltDAClient::default(),
+            &cfg,
+        )
+        .unwrap_err();
+        assert_eq!(err, AltDAError::MissingChallengeWindows);
+
+        let cfg = AltDAConfig { da_resolve_window: Some(2), ..cfg };
+        let source = AltDASource::from_config(
+            TestChainProvider::default(),
+            TestDAP::default(),
+            TestAltDAClient::default(),
+            &cfg,
+        )
+        .unwrap();
+        assert_eq!(source.challenges.unwrap().resolve_window(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_clear_resets_challenges() {
+        let commitment = AltDACommitment::keccak(b"input");
+
+        let challenges = AltDAChallenges::new(CHALLENGE_ADDRESS, 2, 2);
+        let mut source = test_source(vec![commitment.tx_data()], Some(challenges));
+        insert_blocks(&mut source.chain_provider, vec![vec![], vec![]]);
+
+        // The pending commitment looks ahead until the L1 head.
+        assert!(source.next(&block(1), Address::ZERO).await.is_err());
+        assert_eq!(source.challenges.as_ref().unwrap().origin(), Some(2));
+
+        source.clear();
+        assert!(source.pending.is_none());
+        assert_eq!(source.challenges.as_ref().unwrap().origin(), None);
+    }
+}
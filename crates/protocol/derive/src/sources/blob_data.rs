@@ -2,12 +2,15 @@
 
 use crate::BlobDecodingError;
 use alloc::{boxed::Box, vec};
-use alloy_eips::eip4844::{BYTES_PER_BLOB, Blob, VERSIONED_HASH_VERSION_KZG};
+use alloy_eips::eip4844::{BYTES_PER_BLOB, Blob};
 use alloy_primitives::Bytes;
 
 /// The blob encoding version
 pub(crate) const BLOB_ENCODING_VERSION: u8 = 0;
 
+/// The offset of the encoding version byte in the blob
+pub(crate) const BLOB_ENCODING_VERSION_OFFSET: usize = 1;
+
 /// Maximum blob data size
 pub(crate) const BLOB_MAX_DATA_SIZE: usize = (4 * 31 + 3) * 1024 - 4; // 130044
 
@@ -24,13 +27,19 @@ pub struct BlobData {
 }
 
 impl BlobData {
+    /// Decodes the raw byte data encoded in the given [`Blob`].
+    /// Returns a [`BlobDecodingError`] if the blob is invalid.
+    pub fn decode_blob(blob: &Blob) -> Result<Bytes, BlobDecodingError> {
+        Self { data: Some(Bytes::copy_from_slice(blob.as_slice())), calldata: None }.decode()
+    }
+
     /// Decodes the blob into raw byte data.
     /// Returns a [`BlobDecodingError`] if the blob is invalid.
     pub(crate) fn decode(&self) -> Result<Bytes, BlobDecodingError> {
         let data = self.data.as_ref().ok_or(BlobDecodingError::MissingData)?;
 
         // Validate the blob encoding version
-        if data[VERSIONED_HASH_VERSION_KZG as usize] != BLOB_ENCODING_VERSION {
+        if data[BLOB_ENCODING_VERSION_OFFSET] != BLOB_ENCODING_VERSION {
             return Err(BlobDecodingError::InvalidEncodingVersion);
         }
 
@@ -209,6 +218,13 @@ mod tests {
         assert_eq!(blob_data.data, Some(expected));
     }
 
+    #[test]
+    fn test_decode_blob_invalid_encoding_version() {
+        let mut blob = Blob::ZERO;
+        blob[BLOB_ENCODING_VERSION_OFFSET] = 1;
+        assert_eq!(BlobData::decode_blob(&blob), Err(BlobDecodingError::InvalidEncodingVersion));
+    }
+
     #[test]
     fn test_blob_data_decode_missing_data() {
         let blob_data = BlobData::default();
@@ -224,7 +240,7 @@ mod tests {
     #[test]
     fn test_blob_data_decode_invalid_length() {
         let mut data = vec![0u8; 32];
-        data[VERSIONED_HASH_VERSION_KZG as usize] = BLOB_ENCODING_VERSION;
+        data[BLOB_ENCODING_VERSION_OFFSET] = BLOB_ENCODING_VERSION;
         data[2] = 0xFF;
         data[3] = 0xFF;
         data[4] = 0xFF;
@@ -235,7 +251,7 @@ mod tests {
     #[test]
     fn test_blob_data_decode() {
         let mut data = vec![0u8; alloy_eips::eip4844::BYTES_PER_BLOB];
-        data[VERSIONED_HASH_VERSION_KZG as usize] = BLOB_ENCODING_VERSION;
+        data[BLOB_ENCODING_VERSION_OFFSET] = BLOB_ENCODING_VERSION;
         data[2] = 0x00;
         data[3] = 0x00;
         data[4] = 0x01;
@@ -246,7 +262,7 @@ mod tests {
     #[test]
     fn test_blob_data_decode_invalid_field_element() {
         let mut data = vec![0u8; alloy_eips::eip4844::BYTES_PER_BLOB + 10];
-        data[VERSIONED_HASH_VERSION_KZG as usize] = BLOB_ENCODING_VERSION;
+        data[BLOB_ENCODING_VERSION_OFFSET] = BLOB_ENCODING_VERSION;
         data[2] = 0x00;
         data[3] = 0x00;
         data[4] = 0x01;
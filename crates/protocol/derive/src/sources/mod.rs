@@ -18,3 +18,11 @@ pub use blobs::BlobSource;
 
 mod calldata;
 pub use calldata::CalldataSource;
+
+mod altda;
+pub use altda::{
+    ALTDA_DERIVATION_VERSION, AltDAChallenges, AltDACommitment, AltDACommitmentType, AltDASource,
+    Challenge, ChallengeStatus, MAX_ALTDA_INPUT_SIZE,
+};
+#[cfg(any(test, feature = "test-utils"))]
+pub(crate) use altda::{CHALLENGE_STATUS_CHANGED_EVENT, RESOLVE_FUNCTION};
@@ -6,7 +6,10 @@ use crate::{
         AttributesBuilder, AttributesProvider, NextAttributes, OriginAdvancer, OriginProvider,
         SignalReceiver,
     },
-    types::{PipelineResult, Signal},
+    types::{
+        DerivationEvent, EventEmitter, PipelineCheckpoint, PipelineResult, Signal,
+        decode_single_batch, encode_single_batch,
+    },
 };
 use alloc::{boxed::Box, sync::Arc};
 use async_trait::async_trait;
@@ -44,6 +47,8 @@ where
     batch: Option<SingleBatch>,
     /// The attributes builder.
     builder: AB,
+    /// The emitter for derivation events.
+    events: EventEmitter,
 }
 
 impl<P, AB> AttributesQueue<P, AB>
@@ -53,13 +58,27 @@ where
 {
     /// Create a new [`AttributesQueue`] stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P, builder: AB) -> Self {
-        Self { cfg, prev, is_last_in_span: false, batch: None, builder }
+        Self {
+            cfg,
+            prev,
+            is_last_in_span: false,
+            batch: None,
+            builder,
+            events: EventEmitter::disabled(),
+        }
+    }
+
+    /// Sets the [`EventEmitter`] of the stage.
+    pub fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = events;
+        self
     }
 
     /// Loads a [`SingleBatch`] from the [`AttributesProvider`] if needed.
     pub async fn load_batch(&mut self, parent: L2BlockInfo) -> PipelineResult<SingleBatch> {
         if self.batch.is_none() {
-            let batch = self.prev.next_batch(parent).await?;
+            let batch =
+                kona_macros::profile!("batch_provider", self.prev.next_batch(parent)).await?;
             self.batch = Some(batch);
             self.is_last_in_span = self.prev.is_last_in_span();
         }
@@ -88,6 +107,12 @@ where
             }
         };
         let origin = self.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+        self.events.emit(DerivationEvent::AttributesPrepared {
+            origin: origin.number,
+            parent: parent.block_info.number,
+            timestamp: attributes.payload_attributes.timestamp,
+            transactions: attributes.transactions.as_ref().map_or(0, |txs| txs.len()),
+        });
         let populated_attributes =
             OpAttributesWithParent::new(attributes, parent, Some(origin), self.is_last_in_span);
         kona_macros::record!(
@@ -202,6 +227,23 @@ where
         }
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        checkpoint.attributes_batch = self.batch.as_ref().map(encode_single_batch);
+        checkpoint.is_last_in_span = self.is_last_in_span;
+        self.prev.checkpoint(checkpoint)
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.prev.restore(checkpoint).await?;
+        self.batch = checkpoint
+            .attributes_batch
+            .as_ref()
+            .map(|batch| decode_single_batch(batch))
+            .transpose()?;
+        self.is_last_in_span = checkpoint.is_last_in_span;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -2,8 +2,8 @@
 
 use super::NextBatchProvider;
 use crate::{
-    AttributesProvider, BatchQueue, BatchValidator, L2ChainProvider, OriginAdvancer,
-    OriginProvider, PipelineError, PipelineResult, Signal, SignalReceiver,
+    AttributesProvider, BatchQueue, BatchValidator, EventEmitter, L2ChainProvider, OriginAdvancer,
+    OriginProvider, PipelineCheckpoint, PipelineError, PipelineResult, Signal, SignalReceiver,
 };
 use alloc::{boxed::Box, sync::Arc};
 use async_trait::async_trait;
@@ -44,6 +44,8 @@ where
     ///
     /// Must be [`None`] if `prev` or `batch_queue` is [`Some`].
     batch_validator: Option<BatchValidator<P>>,
+    /// The emitter for derivation events, handed to the active stage.
+    events: EventEmitter,
 }
 
 impl<P, F> BatchProvider<P, F>
@@ -53,7 +55,20 @@ where
 {
     /// Creates a new [`BatchProvider`] with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P, provider: F) -> Self {
-        Self { cfg, provider, prev: Some(prev), batch_queue: None, batch_validator: None }
+        Self {
+            cfg,
+            provider,
+            prev: Some(prev),
+            batch_queue: None,
+            batch_validator: None,
+            events: EventEmitter::disabled(),
+        }
+    }
+
+    /// Sets the [`EventEmitter`] that is handed to the [`BatchQueue`] and [`BatchValidator`].
+    pub fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = events;
+        self
     }
 
     /// Attempts to update the active stage of the mux.
@@ -63,16 +78,21 @@ where
             // On the first call to `attempt_update`, we need to determine the active stage to
             // initialize the mux with.
             if self.cfg.is_holocene_active(origin.timestamp) {
-                self.batch_validator = Some(BatchValidator::new(self.cfg.clone(), prev));
+                self.batch_validator = Some(
+                    BatchValidator::new(self.cfg.clone(), prev).with_events(self.events.clone()),
+                );
             } else {
-                self.batch_queue =
-                    Some(BatchQueue::new(self.cfg.clone(), prev, self.provider.clone()));
+                self.batch_queue = Some(
+                    BatchQueue::new(self.cfg.clone(), prev, self.provider.clone())
+                        .with_events(self.events.clone()),
+                );
             }
         } else if self.batch_queue.is_some() && self.cfg.is_holocene_active(origin.timestamp) {
             // If the batch queue is active and Holocene is also active, transition to the batch
             // validator.
             let batch_queue = self.batch_queue.take().expect("Must have batch queue");
-            let mut bv = BatchValidator::new(self.cfg.clone(), batch_queue.prev);
+            let mut bv = BatchValidator::new(self.cfg.clone(), batch_queue.prev)
+                .with_events(self.events.clone());
             bv.l1_blocks = batch_queue.l1_blocks;
             self.batch_validator = Some(bv);
         } else if self.batch_validator.is_some() && !self.cfg.is_holocene_active(origin.timestamp) {
@@ -81,7 +101,8 @@ where
             // until Holocene re-activates.
             let batch_validator = self.batch_validator.take().expect("Must have batch validator");
             let mut bq =
-                BatchQueue::new(self.cfg.clone(), batch_validator.prev, self.provider.clone());
+                BatchQueue::new(self.cfg.clone(), batch_validator.prev, self.provider.clone())
+                    .with_events(self.events.clone());
             bq.l1_blocks = batch_validator.l1_blocks;
             self.batch_queue = Some(bq);
         }
@@ -143,6 +164,30 @@ where
             Err(PipelineError::NotEnoughData.temp())
         }
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        if let Some(batch_validator) = self.batch_validator.as_ref() {
+            batch_validator.checkpoint(checkpoint)
+        } else if let Some(batch_queue) = self.batch_queue.as_ref() {
+            batch_queue.checkpoint(checkpoint)
+        } else if let Some(prev) = self.prev.as_ref() {
+            prev.checkpoint(checkpoint)
+        } else {
+            Err(PipelineError::NotEnoughData.temp())
+        }
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.attempt_update()?;
+
+        if let Some(batch_validator) = self.batch_validator.as_mut() {
+            batch_validator.restore(checkpoint).await
+        } else if let Some(batch_queue) = self.batch_queue.as_mut() {
+            batch_queue.restore(checkpoint).await
+        } else {
+            Err(PipelineError::NotEnoughData.temp())
+        }
+    }
 }
 
 #[async_trait]
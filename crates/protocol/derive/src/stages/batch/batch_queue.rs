@@ -4,14 +4,18 @@ use super::NextBatchProvider;
 use crate::{
     errors::{PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError},
     traits::{AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    types::{
+        BatchCheckpoint, DerivationEvent, EventEmitter, PipelineCheckpoint, PipelineResult,
+        ResetSignal, Signal, decode_single_batch, encode_single_batch,
+    },
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_genesis::RollupConfig;
 use kona_protocol::{
-    Batch, BatchValidity, BatchWithInclusionBlock, BlockInfo, L2BlockInfo, SingleBatch,
+    Batch, BatchValidity, BatchValidityReason, BatchWithInclusionBlock, BlockInfo, L2BlockInfo,
+    SingleBatch,
 };
 
 /// [`BatchQueue`] is responsible for ordering unordered batches
@@ -55,6 +59,8 @@ where
     pub(crate) next_spans: Vec<SingleBatch>,
     /// Used to validate the batches.
     pub(crate) fetcher: BF,
+    /// The emitter for derivation events.
+    pub(crate) events: EventEmitter,
 }
 
 impl<P, BF> BatchQueue<P, BF>
@@ -73,6 +79,25 @@ where
             batches: Default::default(),
             next_spans: Default::default(),
             fetcher,
+            events: EventEmitter::disabled(),
+        }
+    }
+
+    /// Sets the [`EventEmitter`] of the stage.
+    pub fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Emits a [`DerivationEvent::BatchDropped`] event for the given batch.
+    fn emit_dropped(&self, batch: &BatchWithInclusionBlock, reason: BatchValidityReason) {
+        if let Some(origin) = self.origin {
+            self.events.emit(DerivationEvent::BatchDropped {
+                origin: origin.number,
+                timestamp: batch.batch.timestamp(),
+                validity: reason.validity(),
+                reason,
+            });
         }
     }
 
@@ -130,9 +155,9 @@ where
         let mut remaining = Vec::new();
         for i in 0..self.batches.len() {
             let batch = &self.batches[i];
-            let validity =
-                batch.check_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher).await;
-            match validity {
+            let reason =
+                batch.explain_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher).await;
+            match reason.validity() {
                 BatchValidity::Future => {
                     // Drop Future batches post-holocene.
                     //
@@ -142,6 +167,7 @@ where
                     } else {
                         self.prev.flush();
                         warn!(target: "batch_queue", "[HOLOCENE] Dropping future batch with parent: {}", parent.block_info.number);
+                        self.emit_dropped(batch, reason);
                     }
                 }
                 BatchValidity::Drop => {
@@ -149,6 +175,7 @@ where
                     // stage.
                     self.prev.flush();
                     warn!(target: "batch_queue", "Dropping batch with parent: {}", parent.block_info);
+                    self.emit_dropped(batch, reason);
                     continue;
                 }
                 BatchValidity::Accept => {
@@ -170,6 +197,7 @@ where
                     }
 
                     warn!(target: "batch_queue", "[HOLOCENE] Dropping outdated batch with parent: {}", parent.block_info.number);
+                    self.emit_dropped(batch, reason);
                     continue;
                 }
             }
@@ -178,6 +206,10 @@ where
 
         if let Some(nb) = next_batch {
             info!(target: "batch_queue", "Next batch found for timestamp {}", nb.batch.timestamp());
+            self.events.emit(DerivationEvent::BatchAccepted {
+                origin: origin.number,
+                timestamp: nb.batch.timestamp(),
+            });
             return Ok(nb.batch);
         }
 
@@ -213,6 +245,11 @@ where
         // generate a batch to ensure that we at least have one batch per epoch.
         if next_timestamp < next_epoch.timestamp || first_of_epoch {
             info!(target: "batch_queue", "Generating empty batch for epoch: {}", epoch.number);
+            self.events.emit(DerivationEvent::EmptyBatchGenerated {
+                origin: origin.number,
+                epoch: epoch.number,
+                timestamp: next_timestamp,
+            });
             return Ok(Batch::Single(SingleBatch {
                 parent_hash: parent.block_info.hash,
                 epoch_num: epoch.number,
@@ -242,16 +279,19 @@ where
         let origin = self.origin.ok_or(PipelineError::MissingOrigin.crit())?;
         let data = BatchWithInclusionBlock { inclusion_block: origin, batch };
         // If we drop the batch, validation logs the drop reason with WARN level.
-        let validity =
-            data.check_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher).await;
+        let reason =
+            data.explain_batch(&self.cfg, &self.l1_blocks, parent, &mut self.fetcher).await;
+        let validity = reason.validity();
         // Post-Holocene, future batches are dropped due to prevent gaps.
         let drop = validity.is_drop() ||
             (self.cfg.is_holocene_active(origin.timestamp) && validity.is_future());
         if drop {
             self.prev.flush();
+            self.emit_dropped(&data, reason);
             return Ok(());
         } else if validity.is_outdated() {
             // If the batch is outdated, we drop it without flushing the previous stage.
+            self.emit_dropped(&data, reason);
             return Ok(());
         }
         self.batches.push(data);
@@ -344,7 +384,9 @@ where
 
         // Load more data into the batch queue.
         let mut out_of_data = false;
-        match self.prev.next_batch(parent, &self.l1_blocks).await {
+        match kona_macros::profile!("batch_stream", self.prev.next_batch(parent, &self.l1_blocks))
+            .await
+        {
             Ok(b) => {
                 if !origin_behind {
                     self.add_batch(b, parent).await.ok();
@@ -397,6 +439,13 @@ where
                         return Err(e);
                     }
                 };
+                if let Some(origin) = self.origin {
+                    self.events.emit(DerivationEvent::SpanBatchExpanded {
+                        origin: origin.number,
+                        timestamp: sb.starting_timestamp(),
+                        batches: batches.len(),
+                    });
+                }
                 self.next_spans = batches;
                 let nb = match self
                     .pop_next_batch(parent)
@@ -458,6 +507,32 @@ where
         }
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        checkpoint.batch_origin = self.origin;
+        checkpoint.l1_blocks = self.l1_blocks.clone();
+        checkpoint.batches =
+            self.batches.iter().map(BatchCheckpoint::new).collect::<PipelineResult<_>>()?;
+        checkpoint.next_spans = self.next_spans.iter().map(encode_single_batch).collect();
+        self.prev.checkpoint(checkpoint)
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.prev.restore(checkpoint).await?;
+        self.origin = checkpoint.batch_origin;
+        self.l1_blocks = checkpoint.l1_blocks.clone();
+        self.batches = checkpoint
+            .batches
+            .iter()
+            .map(|batch| batch.to_batch(&self.cfg))
+            .collect::<PipelineResult<_>>()?;
+        self.next_spans = checkpoint
+            .next_spans
+            .iter()
+            .map(|batch| decode_single_batch(batch))
+            .collect::<PipelineResult<_>>()?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
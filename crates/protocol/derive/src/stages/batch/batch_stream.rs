@@ -1,8 +1,10 @@
 //! This module contains the `BatchStream` stage.
 
 use crate::{
-    L2ChainProvider, NextBatchProvider, OriginAdvancer, OriginProvider, PipelineEncodingError,
-    PipelineError, PipelineResult, Signal, SignalReceiver,
+    DerivationEvent, EventEmitter, L2ChainProvider, NextBatchProvider, OriginAdvancer,
+    OriginProvider, PipelineCheckpoint, PipelineEncodingError, PipelineError, PipelineResult,
+    Signal, SignalReceiver,
+    types::{decode_single_batch, decode_span_batch, encode_single_batch, encode_span_batch},
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use async_trait::async_trait;
@@ -48,6 +50,8 @@ where
     config: Arc<RollupConfig>,
     /// Used to validate the batches.
     fetcher: BF,
+    /// The emitter for derivation events.
+    events: EventEmitter,
 }
 
 impl<P, BF> BatchStream<P, BF>
@@ -57,7 +61,20 @@ where
 {
     /// Create a new [`BatchStream`] stage.
     pub const fn new(prev: P, config: Arc<RollupConfig>, fetcher: BF) -> Self {
-        Self { prev, span: None, buffer: VecDeque::new(), config, fetcher }
+        Self {
+            prev,
+            span: None,
+            buffer: VecDeque::new(),
+            config,
+            fetcher,
+            events: EventEmitter::disabled(),
+        }
+    }
+
+    /// Sets the [`EventEmitter`] of the stage.
+    pub fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = events;
+        self
     }
 
     /// Returns if the [`BatchStream`] stage is active based on the
@@ -87,11 +104,17 @@ where
         l1_origins: &[BlockInfo],
     ) -> PipelineResult<()> {
         if let Some(span) = self.span.take() {
-            self.buffer.extend(
-                span.get_singular_batches(l1_origins, parent).map_err(|e| {
-                    PipelineError::BadEncoding(PipelineEncodingError::from(e)).crit()
-                })?,
-            );
+            let batches = span
+                .get_singular_batches(l1_origins, parent)
+                .map_err(|e| PipelineError::BadEncoding(PipelineEncodingError::from(e)).crit())?;
+            if let Some(origin) = self.prev.origin() {
+                self.events.emit(DerivationEvent::SpanBatchExpanded {
+                    origin: origin.number,
+                    timestamp: span.starting_timestamp(),
+                    batches: batches.len(),
+                });
+            }
+            self.buffer.extend(batches);
         }
         let batch_count = self.buffer.len() as f64;
         kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_BATCH_BUFFER, batch_count);
@@ -129,7 +152,7 @@ where
         // through this stage to the BatchQueue stage.
         if !self.is_active()? {
             trace!(target: "batch_span", "BatchStream stage is inactive, pass-through.");
-            return self.prev.next_batch().await;
+            return kona_macros::profile!("channel_reader", self.prev.next_batch()).await;
         }
 
         // If the buffer is empty, attempt to pull a batch from the previous stage.
@@ -137,7 +160,7 @@ where
             // Safety: bubble up any errors from the batch reader.
             let batch_with_inclusion = BatchWithInclusionBlock::new(
                 self.origin().ok_or(PipelineError::MissingOrigin.crit())?,
-                self.prev.next_batch().await?,
+                kona_macros::profile!("channel_reader", self.prev.next_batch()).await?,
             );
 
             // If the next batch is a singular batch, it is immediately
@@ -148,8 +171,8 @@ where
                 Batch::Span(b) => {
                     #[cfg(feature = "metrics")]
                     let start = std::time::Instant::now();
-                    let (validity, _) = b
-                        .check_batch_prefix(
+                    let (reason, _) = b
+                        .explain_batch_prefix(
                             self.config.as_ref(),
                             l1_origins,
                             parent,
@@ -157,6 +180,7 @@ where
                             &mut self.fetcher,
                         )
                         .await;
+                    let validity = reason.validity();
                     kona_macros::record!(
                         histogram,
                         crate::metrics::Metrics::PIPELINE_CHECK_BATCH_PREFIX,
@@ -169,6 +193,14 @@ where
                         "validity" => validity.to_string(),
                     );
 
+                    if validity.is_drop() || validity.is_outdated() {
+                        self.events.emit(DerivationEvent::BatchDropped {
+                            origin: batch_with_inclusion.inclusion_block.number,
+                            timestamp: b.starting_timestamp(),
+                            validity,
+                            reason,
+                        });
+                    }
                     match validity {
                         BatchValidity::Accept => self.span = Some(b),
                         BatchValidity::Drop => {
@@ -231,6 +263,27 @@ where
         self.span.take();
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        checkpoint.stream_buffer = self.buffer.iter().map(encode_single_batch).collect();
+        checkpoint.stream_span = self.span.as_ref().map(encode_span_batch).transpose()?;
+        self.prev.checkpoint(checkpoint)
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.prev.restore(checkpoint).await?;
+        self.buffer = checkpoint
+            .stream_buffer
+            .iter()
+            .map(|batch| decode_single_batch(batch))
+            .collect::<PipelineResult<_>>()?;
+        self.span = checkpoint
+            .stream_span
+            .as_ref()
+            .map(|span| decode_span_batch(span, &self.config))
+            .transpose()?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -4,7 +4,9 @@ use super::NextBatchProvider;
 use crate::{
     errors::{PipelineError, PipelineErrorKind, ResetError},
     traits::{AttributesProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    types::{
+        DerivationEvent, EventEmitter, PipelineCheckpoint, PipelineResult, ResetSignal, Signal,
+    },
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
@@ -35,6 +37,8 @@ where
     /// If new L2 Block's L1 origin is not included in this list, fetch and
     /// push it to the list.
     pub(crate) l1_blocks: Vec<BlockInfo>,
+    /// The emitter for derivation events.
+    pub(crate) events: EventEmitter,
 }
 
 impl<P> BatchValidator<P>
@@ -43,7 +47,13 @@ where
 {
     /// Create a new [`BatchValidator`] stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev, origin: None, l1_blocks: Vec::new() }
+        Self { cfg, prev, origin: None, l1_blocks: Vec::new(), events: EventEmitter::disabled() }
+    }
+
+    /// Sets the [`EventEmitter`] of the stage.
+    pub fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = events;
+        self
     }
 
     /// Returns `true` if the pipeline origin is behind the parent origin.
@@ -172,6 +182,11 @@ where
         // generate a batch to ensure that we at least have one batch per epoch.
         if next_timestamp < next_epoch.timestamp || first_of_epoch {
             info!(target: "batch_validator", "Generating empty batch for epoch #{}", epoch.number);
+            self.events.emit(DerivationEvent::EmptyBatchGenerated {
+                origin: stage_origin.number,
+                epoch: epoch.number,
+                timestamp: next_timestamp,
+            });
             return Ok(SingleBatch {
                 parent_hash: parent.block_info.hash,
                 epoch_num: epoch.number,
@@ -205,7 +220,11 @@ where
         // If the origin is behind, we must drain previous stages to catch up.
         let stage_origin = self.origin.ok_or(PipelineError::MissingOrigin.crit())?;
         if self.origin_behind(&parent) || parent.l1_origin.number == stage_origin.number {
-            self.prev.next_batch(parent, self.l1_blocks.as_ref()).await?;
+            kona_macros::profile!(
+                "batch_stream",
+                self.prev.next_batch(parent, self.l1_blocks.as_ref())
+            )
+            .await?;
             return Err(PipelineError::NotEnoughData.temp());
         }
 
@@ -227,7 +246,12 @@ where
         }
 
         // Pull the next batch from the previous stage.
-        let next_batch = match self.prev.next_batch(parent, self.l1_blocks.as_ref()).await {
+        let next_batch = match kona_macros::profile!(
+            "batch_stream",
+            self.prev.next_batch(parent, self.l1_blocks.as_ref())
+        )
+        .await
+        {
             Ok(batch) => batch,
             Err(PipelineErrorKind::Temporary(PipelineError::Eof)) => {
                 return self.try_derive_empty_batch(&parent);
@@ -248,13 +272,27 @@ where
         next_batch.parent_hash = parent.block_info.hash;
 
         // Check the validity of the single batch before forwarding it.
-        match next_batch.check_batch(
+        let reason = next_batch.explain_batch(
             self.cfg.as_ref(),
             self.l1_blocks.as_ref(),
             parent,
             &stage_origin,
-        ) {
+        );
+        let validity = reason.validity();
+        if validity.is_drop() || validity.is_outdated() {
+            self.events.emit(DerivationEvent::BatchDropped {
+                origin: stage_origin.number,
+                timestamp: next_batch.timestamp,
+                validity,
+                reason,
+            });
+        }
+        match validity {
             BatchValidity::Accept => {
+                self.events.emit(DerivationEvent::BatchAccepted {
+                    origin: stage_origin.number,
+                    timestamp: next_batch.timestamp,
+                });
                 info!(target: "batch_validator", "Found next batch (epoch #{})", next_batch.epoch_num);
                 Ok(next_batch)
             }
@@ -321,6 +359,19 @@ where
         }
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        checkpoint.batch_origin = self.origin;
+        checkpoint.l1_blocks = self.l1_blocks.clone();
+        self.prev.checkpoint(checkpoint)
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.prev.restore(checkpoint).await?;
+        self.origin = checkpoint.batch_origin;
+        self.l1_blocks = checkpoint.l1_blocks.clone();
+        Ok(())
+    }
 }
 
 #[cfg(test)]
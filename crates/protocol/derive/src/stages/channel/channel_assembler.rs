@@ -4,7 +4,10 @@ use super::{ChannelReaderProvider, NextFrameProvider};
 use crate::{
     errors::PipelineError,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{
+        ChannelCheckpoint, ChannelDropReason, DerivationEvent, EventEmitter, FrameDropReason,
+        PipelineCheckpoint, PipelineResult, Signal,
+    },
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::{Bytes, hex};
@@ -32,6 +35,8 @@ where
     pub(crate) prev: P,
     /// The current [`Channel`] being assembled.
     pub(crate) channel: Option<Channel>,
+    /// The emitter for derivation events.
+    pub(crate) events: EventEmitter,
 }
 
 impl<P> ChannelAssembler<P>
@@ -40,7 +45,13 @@ where
 {
     /// Creates a new [`ChannelAssembler`] stage with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev, channel: None }
+        Self { cfg, prev, channel: None, events: EventEmitter::disabled() }
+    }
+
+    /// Sets the [`EventEmitter`] of the stage.
+    pub fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = events;
+        self
     }
 
     /// Returns whether or not the channel currently being assembled has timed out.
@@ -76,12 +87,17 @@ where
                     origin.number,
                     channel.open_block_number()
                 );
+                self.events.emit(DerivationEvent::ChannelTimedOut {
+                    origin: origin.number,
+                    channel_id: channel.id(),
+                    open_block: channel.open_block_number(),
+                });
                 self.channel = None;
             }
         }
 
         // Grab the next frame from the previous stage.
-        let next_frame = self.prev.next_frame().await?;
+        let next_frame = kona_macros::profile!("frame_queue", self.prev.next_frame()).await?;
 
         // Start a new channel if the frame number is 0.
         if next_frame.number == 0 {
@@ -91,6 +107,13 @@ where
                 hex::encode(next_frame.id),
                 origin.number
             );
+            if let Some(channel) = self.channel.as_ref() {
+                self.events.emit(DerivationEvent::ChannelDropped {
+                    origin: origin.number,
+                    channel_id: channel.id(),
+                    reason: ChannelDropReason::Superseded,
+                });
+            }
             self.channel = Some(Channel::new(next_frame.id, origin));
         }
 
@@ -112,6 +135,7 @@ where
                 hex::encode(channel.id()),
                 origin.number
             );
+            let (frame_id, frame_number) = (next_frame.id, next_frame.number);
             if channel.add_frame(next_frame, origin).is_err() {
                 error!(
                     target: "channel_assembler",
@@ -119,8 +143,19 @@ where
                     hex::encode(channel.id()),
                     origin.number
                 );
+                self.events.emit(DerivationEvent::FrameDropped {
+                    origin: origin.number,
+                    channel_id: frame_id,
+                    frame_number,
+                    reason: FrameDropReason::Rejected,
+                });
                 return Err(PipelineError::NotEnoughData.temp());
             }
+            self.events.emit(DerivationEvent::FrameAccepted {
+                origin: origin.number,
+                channel_id: frame_id,
+                frame_number,
+            });
 
             let size = channel.size() as f64;
             kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_CHANNEL_MEM, size);
@@ -142,6 +177,11 @@ where
                     hex::encode(channel.id()),
                     channel.size()
                 );
+                self.events.emit(DerivationEvent::ChannelDropped {
+                    origin: origin.number,
+                    channel_id: channel.id(),
+                    reason: ChannelDropReason::TooLarge,
+                });
                 self.channel = None;
                 return Err(PipelineError::NotEnoughData.temp());
             }
@@ -156,11 +196,23 @@ where
                     "Channel (ID: {}) ready for decompression.",
                     hex::encode(channel.id()),
                 );
+                self.events.emit(DerivationEvent::ChannelReady {
+                    origin: origin.number,
+                    channel_id: channel.id(),
+                    size: channel_bytes.len(),
+                });
 
                 // Reset the channel and return the compressed bytes.
                 self.channel = None;
                 return Ok(Some(channel_bytes));
             }
+        } else {
+            self.events.emit(DerivationEvent::FrameDropped {
+                origin: origin.number,
+                channel_id: next_frame.id,
+                frame_number: next_frame.number,
+                reason: FrameDropReason::NoChannel,
+            });
         }
 
         kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_CHANNEL_MEM, 0);
@@ -198,14 +250,30 @@ where
         self.channel = None;
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        checkpoint.channels = self.channel.iter().map(ChannelCheckpoint::from).collect();
+        self.prev.checkpoint(checkpoint)
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.prev.restore(checkpoint).await?;
+        // The channel assembler only ever holds a single channel.
+        self.channel = match checkpoint.channels.as_slice() {
+            [] => None,
+            [channel] => Some(channel.to_channel()?),
+            _ => return Err(PipelineError::InvalidCheckpoint.crit()),
+        };
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::ChannelAssembler;
     use crate::{
-        ChannelReaderProvider, PipelineError,
-        test_utils::{CollectingLayer, TestNextFrameProvider, TraceStorage},
+        ChannelReaderProvider, DerivationEvent, EventEmitter, FrameDropReason, PipelineError,
+        test_utils::{CollectingEventSink, CollectingLayer, TestNextFrameProvider, TraceStorage},
     };
     use alloc::{sync::Arc, vec};
     use kona_genesis::{
@@ -262,6 +330,49 @@ mod test {
         assert!(warning_logs[0].contains(warn_str));
     }
 
+    #[tokio::test]
+    async fn test_assembler_channel_timeout_events() {
+        let frames = [
+            crate::frame!(0xFF, 0, vec![0xDD; 50], false),
+            crate::frame!(0xFF, 1, vec![0xDD; 50], true),
+        ];
+        let mock = TestNextFrameProvider::new(frames.into_iter().rev().map(Ok).collect());
+        let cfg = Arc::new(RollupConfig::default());
+        let sink = CollectingEventSink::default();
+        let mut assembler =
+            ChannelAssembler::new(cfg, mock).with_events(EventEmitter::new(Arc::new(sink.clone())));
+        assembler.prev.block_info = Some(BlockInfo::default());
+
+        assert_eq!(assembler.next_data().await.unwrap_err(), PipelineError::NotEnoughData.temp());
+
+        let timeout = assembler.cfg.channel_timeout(0) + 1;
+        assembler.prev.block_info = Some(BlockInfo { number: timeout, ..Default::default() });
+        assert_eq!(assembler.next_data().await.unwrap_err(), PipelineError::NotEnoughData.temp());
+
+        // The second frame arrives after the channel timed out, and has no channel to join.
+        assert_eq!(
+            sink.events(),
+            vec![
+                DerivationEvent::FrameAccepted {
+                    origin: 0,
+                    channel_id: [0xFF; 16],
+                    frame_number: 0
+                },
+                DerivationEvent::ChannelTimedOut {
+                    origin: timeout,
+                    channel_id: [0xFF; 16],
+                    open_block: 0
+                },
+                DerivationEvent::FrameDropped {
+                    origin: timeout,
+                    channel_id: [0xFF; 16],
+                    frame_number: 1,
+                    reason: FrameDropReason::NoChannel
+                },
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_assembler_non_starting_frame() {
         let frames = [
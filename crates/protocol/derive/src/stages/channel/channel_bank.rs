@@ -1,8 +1,9 @@
 //! This module contains the `ChannelBank` struct.
 
 use crate::{
-    ChannelReaderProvider, NextFrameProvider, OriginAdvancer, OriginProvider, PipelineError,
-    PipelineErrorKind, PipelineResult, Signal, SignalReceiver,
+    ChannelCheckpoint, ChannelDropReason, ChannelReaderProvider, DerivationEvent, EventEmitter,
+    FrameDropReason, NextFrameProvider, OriginAdvancer, OriginProvider, PipelineCheckpoint,
+    PipelineError, PipelineErrorKind, PipelineResult, Signal, SignalReceiver,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use alloy_primitives::{Bytes, hex, map::HashMap};
@@ -41,6 +42,8 @@ where
     pub(crate) channel_queue: VecDeque<ChannelId>,
     /// The previous stage of the derivation pipeline.
     pub(crate) prev: P,
+    /// The emitter for derivation events.
+    pub(crate) events: EventEmitter,
 }
 
 impl<P> ChannelBank<P>
@@ -49,7 +52,19 @@ where
 {
     /// Create a new [`ChannelBank`] stage.
     pub fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, channels: HashMap::default(), channel_queue: VecDeque::new(), prev }
+        Self {
+            cfg,
+            channels: HashMap::default(),
+            channel_queue: VecDeque::new(),
+            prev,
+            events: EventEmitter::disabled(),
+        }
+    }
+
+    /// Sets the [`EventEmitter`] of the stage.
+    pub fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = events;
+        self
     }
 
     /// Returns the size of the channel bank by accumulating over all channels.
@@ -72,6 +87,11 @@ where
                 self.channel_queue.pop_front().ok_or(PipelineError::ChannelProviderEmpty.crit())?;
             let channel = self.channels.remove(&id).ok_or(PipelineError::ChannelNotFound.crit())?;
             total_size -= channel.size();
+            self.events.emit(DerivationEvent::ChannelDropped {
+                origin: origin.number,
+                channel_id: id,
+                reason: ChannelDropReason::BankFull,
+            });
         }
         Ok(())
     }
@@ -99,15 +119,32 @@ where
                 target: "channel_bank",
                 "Channel (ID: {}) timed out", hex::encode(frame.id)
             );
+            self.events.emit(DerivationEvent::FrameDropped {
+                origin: origin.number,
+                channel_id: frame.id,
+                frame_number: frame.number,
+                reason: FrameDropReason::ChannelTimedOut,
+            });
             return Ok(());
         }
 
         // Ingest the frame. If it fails, ignore the frame.
-        let frame_id = frame.id;
+        let (frame_id, frame_number) = (frame.id, frame.number);
         if current_channel.add_frame(frame, origin).is_err() {
             warn!(target: "channel_bank", "Failed to add frame to channel: {:?}", frame_id);
+            self.events.emit(DerivationEvent::FrameDropped {
+                origin: origin.number,
+                channel_id: frame_id,
+                frame_number,
+                reason: FrameDropReason::Rejected,
+            });
             return Ok(());
         }
+        self.events.emit(DerivationEvent::FrameAccepted {
+            origin: origin.number,
+            channel_id: frame_id,
+            frame_number,
+        });
 
         self.prune()
     }
@@ -134,6 +171,11 @@ where
                 target: "channel_bank",
                 "Channel (ID: {}) timed out", hex::encode(first)
             );
+            self.events.emit(DerivationEvent::ChannelTimedOut {
+                origin: origin.number,
+                channel_id: first,
+                open_block: channel.open_block_number(),
+            });
             self.channels.remove(&first);
             self.channel_queue.pop_front();
             return Ok(None);
@@ -173,7 +215,13 @@ where
         self.channels.remove(&channel_id);
         self.channel_queue.remove(index);
 
-        frame_data.ok_or(PipelineError::ChannelProviderEmpty.crit())
+        let frame_data = frame_data.ok_or(PipelineError::ChannelProviderEmpty.crit())?;
+        self.events.emit(DerivationEvent::ChannelReady {
+            origin: origin.number,
+            channel_id,
+            size: frame_data.len(),
+        });
+        Ok(frame_data)
     }
 }
 
@@ -203,7 +251,7 @@ where
         };
 
         // Load the data into the channel bank
-        let frame = match self.prev.next_frame().await {
+        let frame = match kona_macros::profile!("frame_queue", self.prev.next_frame()).await {
             Ok(f) => f,
             Err(e) => {
                 return Err(e);
@@ -235,6 +283,25 @@ where
         self.channel_queue = VecDeque::with_capacity(10);
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        checkpoint.channels = self
+            .channel_queue
+            .iter()
+            .filter_map(|id| self.channels.get(id))
+            .map(ChannelCheckpoint::from)
+            .collect();
+        self.prev.checkpoint(checkpoint)
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.prev.restore(checkpoint).await?;
+        for channel in &checkpoint.channels {
+            self.channel_queue.push_back(channel.id);
+            self.channels.insert(channel.id, channel.to_channel()?);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
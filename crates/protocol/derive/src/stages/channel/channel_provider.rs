@@ -4,7 +4,7 @@ use super::{ChannelAssembler, ChannelBank, ChannelReaderProvider, NextFrameProvi
 use crate::{
     errors::PipelineError,
     traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    types::{EventEmitter, PipelineCheckpoint, PipelineResult, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Bytes;
@@ -41,6 +41,8 @@ where
     ///
     /// Must be [`None`] if `prev` or `channel_bank` is [`Some`].
     channel_assembler: Option<ChannelAssembler<P>>,
+    /// The emitter for derivation events, handed to the active stage.
+    events: EventEmitter,
 }
 
 impl<P> ChannelProvider<P>
@@ -49,7 +51,19 @@ where
 {
     /// Creates a new [`ChannelProvider`] with the given configuration and previous stage.
     pub const fn new(cfg: Arc<RollupConfig>, prev: P) -> Self {
-        Self { cfg, prev: Some(prev), channel_bank: None, channel_assembler: None }
+        Self {
+            cfg,
+            prev: Some(prev),
+            channel_bank: None,
+            channel_assembler: None,
+            events: EventEmitter::disabled(),
+        }
+    }
+
+    /// Sets the [`EventEmitter`] that is handed to the [`ChannelBank`] and [`ChannelAssembler`].
+    pub fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = events;
+        self
     }
 
     /// Attempts to update the active stage of the mux.
@@ -59,16 +73,21 @@ where
             // On the first call to `attempt_update`, we need to determine the active stage to
             // initialize the mux with.
             if self.cfg.is_holocene_active(origin.timestamp) {
-                self.channel_assembler = Some(ChannelAssembler::new(self.cfg.clone(), prev));
+                self.channel_assembler = Some(
+                    ChannelAssembler::new(self.cfg.clone(), prev).with_events(self.events.clone()),
+                );
             } else {
-                self.channel_bank = Some(ChannelBank::new(self.cfg.clone(), prev));
+                self.channel_bank =
+                    Some(ChannelBank::new(self.cfg.clone(), prev).with_events(self.events.clone()));
             }
         } else if self.channel_bank.is_some() && self.cfg.is_holocene_active(origin.timestamp) {
             // If the channel bank is active and Holocene is also active, transition to the channel
             // assembler.
             let channel_bank = self.channel_bank.take().expect("Must have channel bank");
-            self.channel_assembler =
-                Some(ChannelAssembler::new(self.cfg.clone(), channel_bank.prev));
+            self.channel_assembler = Some(
+                ChannelAssembler::new(self.cfg.clone(), channel_bank.prev)
+                    .with_events(self.events.clone()),
+            );
         } else if self.channel_assembler.is_some() && !self.cfg.is_holocene_active(origin.timestamp)
         {
             // If the channel assembler is active, and Holocene is not active, it indicates an L1
@@ -76,7 +95,10 @@ where
             // until Holocene re-activates.
             let channel_assembler =
                 self.channel_assembler.take().expect("Must have channel assembler");
-            self.channel_bank = Some(ChannelBank::new(self.cfg.clone(), channel_assembler.prev));
+            self.channel_bank = Some(
+                ChannelBank::new(self.cfg.clone(), channel_assembler.prev)
+                    .with_events(self.events.clone()),
+            );
         }
         Ok(())
     }
@@ -133,6 +155,30 @@ where
             Err(PipelineError::NotEnoughData.temp())
         }
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        if let Some(channel_assembler) = self.channel_assembler.as_ref() {
+            channel_assembler.checkpoint(checkpoint)
+        } else if let Some(channel_bank) = self.channel_bank.as_ref() {
+            channel_bank.checkpoint(checkpoint)
+        } else if let Some(prev) = self.prev.as_ref() {
+            prev.checkpoint(checkpoint)
+        } else {
+            Err(PipelineError::NotEnoughData.temp())
+        }
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.attempt_update()?;
+
+        if let Some(channel_assembler) = self.channel_assembler.as_mut() {
+            channel_assembler.restore(checkpoint).await
+        } else if let Some(channel_bank) = self.channel_bank.as_mut() {
+            channel_bank.restore(checkpoint).await
+        } else {
+            Err(PipelineError::NotEnoughData.temp())
+        }
+    }
 }
 
 #[async_trait]
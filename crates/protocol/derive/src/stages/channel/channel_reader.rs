@@ -1,8 +1,8 @@
 //! This module contains the `ChannelReader` struct.
 
 use crate::{
-    BatchStreamProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult, Signal,
-    SignalReceiver,
+    BatchStreamProvider, OriginAdvancer, OriginProvider, PipelineCheckpoint, PipelineError,
+    PipelineResult, Signal, SignalReceiver,
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Bytes;
@@ -58,8 +58,9 @@ where
     /// Creates the batch reader from available channel data.
     async fn set_batch_reader(&mut self) -> PipelineResult<()> {
         if self.next_batch.is_none() {
-            let channel =
-                self.prev.next_data().await?.ok_or(PipelineError::ChannelReaderEmpty.temp())?;
+            let channel = kona_macros::profile!("channel_provider", self.prev.next_data())
+                .await?
+                .ok_or(PipelineError::ChannelReaderEmpty.temp())?;
 
             let origin = self.prev.origin().ok_or(PipelineError::MissingOrigin.crit())?;
             let max_rlp_bytes_per_channel = if self.cfg.is_fjord_active(origin.timestamp) {
@@ -124,6 +125,10 @@ where
                 let size = next_batch.decompressed.len() as f64;
                 let ty = if next_batch.brotli_used {
                     BatchReader::CHANNEL_VERSION_BROTLI
+                } else if next_batch.zstd_used {
+                    BatchReader::CHANNEL_VERSION_ZSTD
+                } else if next_batch.raw_used {
+                    BatchReader::CHANNEL_VERSION_RAW
                 } else {
                     BatchReader::ZLIB_DEFLATE_COMPRESSION_METHOD
                 };
@@ -192,6 +197,18 @@ where
         }
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        // A partially read channel cannot be checkpointed.
+        if self.next_batch.is_some() {
+            return Err(PipelineError::NotAtOriginBoundary.temp());
+        }
+        self.prev.checkpoint(checkpoint)
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.prev.restore(checkpoint).await
+    }
 }
 
 #[cfg(test)]
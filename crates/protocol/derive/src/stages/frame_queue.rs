@@ -1,8 +1,9 @@
 //! This module contains the [FrameQueue] stage of the derivation pipeline.
 
 use crate::{
-    NextFrameProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult, Signal,
-    SignalReceiver,
+    DerivationEvent, EventEmitter, FrameDropReason, NextFrameProvider, OriginAdvancer,
+    OriginProvider, PipelineCheckpoint, PipelineError, PipelineResult, Signal, SignalReceiver,
+    types::decode_frame,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use alloy_primitives::Bytes;
@@ -38,6 +39,8 @@ where
     queue: VecDeque<Frame>,
     /// The rollup config.
     rollup_config: Arc<RollupConfig>,
+    /// The emitter for derivation events.
+    events: EventEmitter,
 }
 
 impl<P> FrameQueue<P>
@@ -48,7 +51,26 @@ where
     ///
     /// [`L1Retrieval`]: crate::stages::L1Retrieval
     pub const fn new(prev: P, cfg: Arc<RollupConfig>) -> Self {
-        Self { prev, queue: VecDeque::new(), rollup_config: cfg }
+        Self { prev, queue: VecDeque::new(), rollup_config: cfg, events: EventEmitter::disabled() }
+    }
+
+    /// Sets the [`EventEmitter`] of the stage.
+    pub fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Removes the frame at the given index from the queue, emitting a
+    /// [`DerivationEvent::FrameDropped`] event.
+    fn drop_frame(&mut self, index: usize, origin: BlockInfo, reason: FrameDropReason) {
+        if let Some(frame) = self.queue.remove(index) {
+            self.events.emit(DerivationEvent::FrameDropped {
+                origin: origin.number,
+                channel_id: frame.id,
+                frame_number: frame.number,
+                reason,
+            });
+        }
     }
 
     /// Returns if holocene is active.
@@ -71,19 +93,19 @@ where
             // If the frames are in the same channel, and the frame numbers are not sequential,
             // drop the next frame.
             if extends_channel && prev_frame.number + 1 != next_frame.number {
-                self.queue.remove(i + 1);
+                self.drop_frame(i + 1, origin, FrameDropReason::NonSequential);
                 continue;
             }
 
             // If the frames are in the same channel, and the previous is last, drop the next frame.
             if extends_channel && prev_frame.is_last {
-                self.queue.remove(i + 1);
+                self.drop_frame(i + 1, origin, FrameDropReason::AfterLastFrame);
                 continue;
             }
 
             // If the frames are in different channels, the next frame must be first.
             if !extends_channel && next_frame.number != 0 {
-                self.queue.remove(i + 1);
+                self.drop_frame(i + 1, origin, FrameDropReason::NotFirstFrame);
                 continue;
             }
 
@@ -98,6 +120,14 @@ where
                 // Drain all frames from the previous channel.
                 let drained = self.queue.drain(first_frame..=i);
                 i = i.saturating_sub(drained.len());
+                for frame in drained {
+                    self.events.emit(DerivationEvent::FrameDropped {
+                        origin: origin.number,
+                        channel_id: frame.id,
+                        frame_number: frame.number,
+                        reason: FrameDropReason::IncompleteChannel,
+                    });
+                }
                 continue;
             }
 
@@ -112,7 +142,7 @@ where
             return Ok(());
         }
 
-        let data = match self.prev.next_data().await {
+        let data = match kona_macros::profile!("l1_retrieval", self.prev.next_data()).await {
             Ok(data) => data,
             Err(e) => {
                 debug!(target: "frame_queue", "Failed to retrieve data: {:?}", e);
@@ -125,6 +155,9 @@ where
             // There may be more frames in the queue for the
             // pipeline to advance, so don't return an error here.
             error!(target: "frame_queue", "Failed to parse frames from data.");
+            if let Some(origin) = self.origin() {
+                self.events.emit(DerivationEvent::InvalidFrameData { origin: origin.number });
+            }
             return Ok(());
         };
 
@@ -195,6 +228,21 @@ where
         self.queue = VecDeque::default();
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        checkpoint.frames = self.queue.iter().map(|frame| frame.encode().into()).collect();
+        self.prev.checkpoint(checkpoint)
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.prev.restore(checkpoint).await?;
+        self.queue = checkpoint
+            .frames
+            .iter()
+            .map(|frame| decode_frame(frame))
+            .collect::<PipelineResult<_>>()?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
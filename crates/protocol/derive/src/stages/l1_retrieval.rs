@@ -2,7 +2,8 @@
 
 use crate::{
     ActivationSignal, DataAvailabilityProvider, FrameQueueProvider, OriginAdvancer, OriginProvider,
-    PipelineError, PipelineErrorKind, PipelineResult, ResetSignal, Signal, SignalReceiver,
+    PipelineCheckpoint, PipelineError, PipelineErrorKind, PipelineResult, ResetSignal, Signal,
+    SignalReceiver,
 };
 use alloc::boxed::Box;
 use alloy_primitives::Address;
@@ -67,7 +68,7 @@ where
     P: L1RetrievalProvider + OriginAdvancer + OriginProvider + SignalReceiver + Send,
 {
     async fn advance_origin(&mut self) -> PipelineResult<()> {
-        self.prev.advance_origin().await
+        kona_macros::profile!("l1_traversal", self.prev.advance_origin()).await
     }
 }
 
@@ -91,7 +92,12 @@ where
         // SAFETY: The above check ensures that `next` is not None.
         let next = self.next.as_ref().expect("infallible");
 
-        match self.provider.next(next, self.prev.batcher_addr()).await {
+        match kona_macros::profile!(
+            "data_availability",
+            self.provider.next(next, self.prev.batcher_addr())
+        )
+        .await
+        {
             Ok(data) => Ok(data),
             Err(e) => {
                 if let PipelineErrorKind::Temporary(PipelineError::Eof) = e {
@@ -131,6 +137,21 @@ where
         }
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        // The data of the previous origin must have been fully consumed.
+        if self.next.is_some() {
+            return Err(PipelineError::NotAtOriginBoundary.temp());
+        }
+        self.prev.checkpoint(checkpoint)
+    }
+
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        self.prev.restore(checkpoint).await?;
+        // The reset re-opens the origin, which is instead pulled from the traversal stage once.
+        self.next = None;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -140,6 +161,22 @@ mod tests {
     use alloc::vec;
     use alloy_primitives::Bytes;
 
+    #[test]
+    fn test_l1_retrieval_checkpoint_not_at_origin_boundary() {
+        let traversal = TraversalTestHelper::new_populated();
+        let dap = TestDAP { results: vec![] };
+        let mut retrieval = L1Retrieval::new(traversal, dap);
+        retrieval.next = Some(BlockInfo::default());
+
+        let mut checkpoint = PipelineCheckpoint::default();
+        let err = retrieval.checkpoint(&mut checkpoint).unwrap_err();
+        assert_eq!(err, PipelineError::NotAtOriginBoundary.temp());
+
+        retrieval.next = None;
+        retrieval.checkpoint(&mut checkpoint).unwrap();
+        assert_eq!(checkpoint.l1_origin, retrieval.prev.block.unwrap());
+    }
+
     #[tokio::test]
     async fn test_l1_retrieval_flush_channel() {
         let traversal = TraversalTestHelper::new_populated();
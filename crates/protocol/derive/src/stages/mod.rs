@@ -37,3 +37,181 @@ pub use batch::{
 
 mod attributes_queue;
 pub use attributes_queue::AttributesQueue;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AttributesProvider, DerivationEvent, EventEmitter, OriginAdvancer, OriginProvider,
+        PipelineError, PipelineErrorKind, PipelineResult, Signal, SignalReceiver,
+        test_utils::{CollectingEventSink, TestL2ChainProvider},
+    };
+    use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec::Vec};
+    use alloy_primitives::Bytes;
+    use async_trait::async_trait;
+    use kona_genesis::{HardForkConfig, RollupConfig};
+    use kona_protocol::{
+        BlockInfo,
+        test_utils::{ChannelGenerator, GeneratedCase, GeneratedCaseKind},
+    };
+
+    /// The maximum number of steps to derive a generated case in.
+    const MAX_STEPS: usize = 1024;
+
+    /// A [`FrameQueueProvider`] over the L1 chain of a [`GeneratedCase`]: the L1 origin of its L2
+    /// blocks, followed by the block that includes its frames.
+    #[derive(Debug)]
+    struct GeneratedCaseProvider {
+        origins: VecDeque<BlockInfo>,
+        inclusion_block: BlockInfo,
+        data: Option<Bytes>,
+    }
+
+    impl GeneratedCaseProvider {
+        fn new(case: &GeneratedCase) -> Self {
+            Self {
+                origins: [case.l1_origin, case.inclusion_block].into(),
+                inclusion_block: case.inclusion_block,
+                data: Some(case.batcher_data()),
+            }
+        }
+    }
+
+    impl OriginProvider for GeneratedCaseProvider {
+        fn origin(&self) -> Option<BlockInfo> {
+            self.origins.front().copied()
+        }
+    }
+
+    #[async_trait]
+    impl OriginAdvancer for GeneratedCaseProvider {
+        async fn advance_origin(&mut self) -> PipelineResult<()> {
+            if self.origins.len() < 2 {
+                return Err(PipelineError::Eof.temp());
+            }
+            self.origins.pop_front();
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl FrameQueueProvider for GeneratedCaseProvider {
+        type Item = Bytes;
+
+        async fn next_data(&mut self) -> PipelineResult<Self::Item> {
+            if self.origin() != Some(self.inclusion_block) {
+                return Err(PipelineError::Eof.temp());
+            }
+            self.data.take().ok_or(PipelineError::Eof.temp())
+        }
+    }
+
+    #[async_trait]
+    impl SignalReceiver for GeneratedCaseProvider {
+        async fn signal(&mut self, _: Signal) -> PipelineResult<()> {
+            Ok(())
+        }
+    }
+
+    fn rollup_config(hardforks: HardForkConfig) -> RollupConfig {
+        RollupConfig {
+            block_time: 2,
+            max_sequencer_drift: 600,
+            seq_window_size: 100,
+            channel_timeout: 50,
+            hardforks,
+            ..Default::default()
+        }
+    }
+
+    /// Derives the case through the frame queue, channel and batch stages, checking that the
+    /// expected channels are read and that the safe head ends up at the expected block.
+    async fn check_case(cfg: &Arc<RollupConfig>, case: &GeneratedCase) {
+        let sink = CollectingEventSink::default();
+        let events = EventEmitter::new(Arc::new(sink.clone()));
+        let l2_provider = TestL2ChainProvider::new(
+            case.l2_blocks.clone(),
+            case.op_blocks.clone(),
+            Default::default(),
+        );
+
+        let frame_queue = FrameQueue::new(GeneratedCaseProvider::new(case), cfg.clone())
+            .with_events(events.clone());
+        let channel_provider =
+            ChannelProvider::new(cfg.clone(), frame_queue).with_events(events.clone());
+        let channel_reader = ChannelReader::new(channel_provider, cfg.clone());
+        let batch_stream = BatchStream::new(channel_reader, cfg.clone(), l2_provider.clone())
+            .with_events(events.clone());
+        let mut batch_provider =
+            BatchProvider::new(cfg.clone(), batch_stream, l2_provider).with_events(events);
+
+        let mut safe_head = case.l2_safe_head;
+        let mut exhausted = false;
+        for _ in 0..MAX_STEPS {
+            match batch_provider.next_batch(safe_head).await {
+                Ok(batch) => {
+                    let block = *case
+                        .l2_blocks
+                        .iter()
+                        .find(|b| b.block_info.timestamp == batch.timestamp)
+                        .expect("batches build blocks of the case");
+                    assert_eq!(block.block_info.parent_hash, safe_head.block_info.hash);
+                    safe_head = block;
+                }
+                Err(PipelineErrorKind::Temporary(PipelineError::NotEnoughData)) => {}
+                Err(PipelineErrorKind::Temporary(PipelineError::Eof)) => {
+                    if batch_provider.advance_origin().await.is_err() {
+                        exhausted = true;
+                        break;
+                    }
+                }
+                Err(e) => panic!("{:?}: unexpected derivation error: {e}", case.kind),
+            }
+        }
+        assert!(exhausted, "{:?}: derivation did not complete", case.kind);
+        assert_eq!(safe_head, case.expected_safe_head, "{:?}", case.kind);
+
+        let ready: Vec<_> = sink
+            .events()
+            .into_iter()
+            .filter_map(|event| match event {
+                DerivationEvent::ChannelReady { channel_id, .. } => Some(channel_id),
+                _ => None,
+            })
+            .collect();
+        let expected: Vec<_> = case.channels.iter().filter(|c| c.read).map(|c| c.id).collect();
+        assert_eq!(ready, expected, "{:?}", case.kind);
+    }
+
+    async fn check_all_kinds(hardforks: HardForkConfig) {
+        let cfg = Arc::new(rollup_config(hardforks));
+        for seed in 0..8 {
+            let mut generator = ChannelGenerator::new(cfg.as_ref().clone(), seed);
+            for kind in GeneratedCaseKind::ALL {
+                check_case(&cfg, &generator.generate(kind)).await;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generated_cases_pre_delta() {
+        check_all_kinds(HardForkConfig::default()).await;
+    }
+
+    #[tokio::test]
+    async fn test_generated_cases_delta() {
+        check_all_kinds(HardForkConfig { delta_time: Some(0), ..Default::default() }).await;
+    }
+
+    #[tokio::test]
+    async fn test_generated_cases_holocene_isthmus() {
+        check_all_kinds(HardForkConfig {
+            delta_time: Some(0),
+            fjord_time: Some(0),
+            holocene_time: Some(0),
+            isthmus_time: Some(0),
+            ..Default::default()
+        })
+        .await;
+    }
+}
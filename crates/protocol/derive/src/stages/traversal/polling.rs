@@ -2,7 +2,8 @@
 
 use crate::{
     ActivationSignal, ChainProvider, L1RetrievalProvider, OriginAdvancer, OriginProvider,
-    PipelineError, PipelineResult, ResetError, ResetSignal, Signal, SignalReceiver,
+    PipelineCheckpoint, PipelineError, PipelineResult, ResetError, ResetSignal, Signal,
+    SignalReceiver,
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Address;
@@ -170,6 +171,16 @@ impl<F: ChainProvider + Send> SignalReceiver for PollingTraversal<F> {
 
         Ok(())
     }
+
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        // The data of the origin must not have been handed to the next stage yet.
+        if self.done {
+            return Err(PipelineError::NotAtOriginBoundary.temp());
+        }
+        checkpoint.l1_origin = self.block.ok_or(PipelineError::MissingOrigin.crit())?;
+        checkpoint.system_config = self.system_config;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,88 @@
+//! Test utilities for the [AltDASource].
+//!
+//! [AltDASource]: crate::AltDASource
+
+use crate::{
+    AltDAClient, AltDACommitment, AltDAError, ChallengeStatus,
+    sources::{CHALLENGE_STATUS_CHANGED_EVENT, RESOLVE_FUNCTION},
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use alloy_primitives::{Address, Bytes, Log, U256, keccak256, map::HashMap};
+use async_trait::async_trait;
+
+/// An in-memory Alt-DA server, which stands in for a DA server in tests.
+#[derive(Debug, Clone, Default)]
+pub struct TestAltDAClient {
+    /// The stored inputs, keyed by their commitments.
+    pub inputs: HashMap<AltDACommitment, Bytes>,
+}
+
+impl TestAltDAClient {
+    /// Stores the given input, returning its keccak256 [AltDACommitment].
+    pub fn insert(&mut self, input: Bytes) -> AltDACommitment {
+        let commitment = AltDACommitment::keccak(&input);
+        self.inputs.insert(commitment.clone(), input);
+        commitment
+    }
+
+    /// Stores the given input under the given [AltDACommitment].
+    pub fn insert_with_commitment(&mut self, commitment: AltDACommitment, input: Bytes) {
+        self.inputs.insert(commitment, input);
+    }
+}
+
+#[async_trait]
+impl AltDAClient for TestAltDAClient {
+    type Error = AltDAError;
+
+    async fn get_input(
+        &mut self,
+        commitment: &AltDACommitment,
+    ) -> Result<Option<Bytes>, Self::Error> {
+        Ok(self.inputs.get(commitment).cloned())
+    }
+}
+
+/// Encodes a `ChallengeStatusChanged` event log of the `DataAvailabilityChallenge` contract.
+pub fn challenge_status_log(
+    challenge_address: Address,
+    inclusion_block: u64,
+    commitment: &AltDACommitment,
+    status: ChallengeStatus,
+) -> Log {
+    let commitment = commitment.encode();
+    let mut data = Vec::new();
+    data.extend_from_slice(&U256::from(0x40).to_be_bytes::<32>());
+    data.extend_from_slice(&U256::from(status as u8).to_be_bytes::<32>());
+    data.extend_from_slice(&abi_encode_bytes(&commitment));
+
+    Log::new_unchecked(
+        challenge_address,
+        vec![
+            keccak256(CHALLENGE_STATUS_CHANGED_EVENT),
+            U256::from(inclusion_block).to_be_bytes::<32>().into(),
+        ],
+        data.into(),
+    )
+}
+
+/// Encodes the calldata of a `resolve(uint256, bytes, bytes)` call to the
+/// `DataAvailabilityChallenge` contract.
+pub fn resolve_calldata(inclusion_block: u64, commitment: &AltDACommitment, input: &[u8]) -> Bytes {
+    let commitment = abi_encode_bytes(&commitment.encode());
+    let mut data = keccak256(RESOLVE_FUNCTION)[..4].to_vec();
+    data.extend_from_slice(&U256::from(inclusion_block).to_be_bytes::<32>());
+    data.extend_from_slice(&U256::from(0x60).to_be_bytes::<32>());
+    data.extend_from_slice(&U256::from(0x60 + commitment.len()).to_be_bytes::<32>());
+    data.extend_from_slice(&commitment);
+    data.extend_from_slice(&abi_encode_bytes(input));
+    data.into()
+}
+
+/// ABI-encodes a dynamic `bytes` value as its length followed by its zero-padded contents.
+fn abi_encode_bytes(value: &[u8]) -> Vec<u8> {
+    let mut encoded = U256::from(value.len()).to_be_bytes::<32>().to_vec();
+    encoded.extend_from_slice(value);
+    encoded.resize(32 + value.len().div_ceil(32) * 32, 0);
+    encoded
+}
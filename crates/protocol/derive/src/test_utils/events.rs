@@ -0,0 +1,22 @@
+//! An event sink that collects the events emitted by the pipeline's stages.
+
+use crate::{DerivationEvent, DerivationEventSink};
+use alloc::{sync::Arc, vec::Vec};
+use spin::Mutex;
+
+/// A [`DerivationEventSink`] that collects every emitted [`DerivationEvent`].
+#[derive(Debug, Default, Clone)]
+pub struct CollectingEventSink(pub Arc<Mutex<Vec<DerivationEvent>>>);
+
+impl CollectingEventSink {
+    /// Returns a copy of the collected events, in the order they were emitted.
+    pub fn events(&self) -> Vec<DerivationEvent> {
+        self.0.lock().clone()
+    }
+}
+
+impl DerivationEventSink for CollectingEventSink {
+    fn emit(&self, event: DerivationEvent) {
+        self.0.lock().push(event);
+    }
+}
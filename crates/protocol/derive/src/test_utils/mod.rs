@@ -19,6 +19,9 @@ pub use chain_providers::{TestChainProvider, TestL2ChainProvider, TestProviderEr
 mod data_availability_provider;
 pub use data_availability_provider::TestDAP;
 
+mod altda;
+pub use altda::{TestAltDAClient, challenge_status_log, resolve_calldata};
+
 mod batch_provider;
 pub use batch_provider::TestNextBatchProvider;
 
@@ -43,6 +46,9 @@ pub use frame_queue::TestFrameQueueProvider;
 mod tracing;
 pub use tracing::{CollectingLayer, TraceStorage};
 
+mod events;
+pub use events::CollectingEventSink;
+
 mod sys_config_fetcher;
 pub use sys_config_fetcher::{TestSystemConfigL2Fetcher, TestSystemConfigL2FetcherError};
 
@@ -1,7 +1,7 @@
 //! Contains traits that describe the functionality of various data sources used in the derivation
 //! pipeline's stages.
 
-use crate::{PipelineErrorKind, PipelineResult};
+use crate::{AltDACommitment, PipelineErrorKind, PipelineResult};
 use alloc::{boxed::Box, fmt::Debug, string::ToString, vec::Vec};
 use alloy_eips::eip4844::{Blob, IndexedBlobHash};
 use alloy_primitives::{Address, Bytes};
@@ -23,6 +23,21 @@ pub trait BlobProvider {
     ) -> Result<Vec<Box<Blob>>, Self::Error>;
 }
 
+/// The AltDAClient trait specifies the functionality of a DA server client, which serves the
+/// inputs referenced by the [`AltDACommitment`]s posted to L1 by the batcher.
+#[async_trait]
+pub trait AltDAClient {
+    /// The error type for the [`AltDAClient`].
+    type Error: Display + ToString + Into<PipelineErrorKind>;
+
+    /// Fetches the input for the given [`AltDACommitment`]. Returns `None` if the DA server does
+    /// not hold the input.
+    async fn get_input(
+        &mut self,
+        commitment: &AltDACommitment,
+    ) -> Result<Option<Bytes>, Self::Error>;
+}
+
 /// Describes the functionality of a data source that can provide data availability information.
 #[async_trait]
 pub trait DataAvailabilityProvider {
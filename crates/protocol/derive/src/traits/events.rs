@@ -0,0 +1,12 @@
+//! Contains the [`DerivationEventSink`] trait.
+
+use crate::DerivationEvent;
+use core::fmt::Debug;
+
+/// A sink for the [`DerivationEvent`]s emitted by the stages of the derivation pipeline.
+///
+/// Events are emitted synchronously from within the stages, so implementations must not block.
+pub trait DerivationEventSink: Debug + Send + Sync {
+    /// Receives an event emitted by a stage.
+    fn emit(&self, event: DerivationEvent);
+}
@@ -4,13 +4,31 @@ use alloc::boxed::Box;
 use async_trait::async_trait;
 use kona_protocol::BlockInfo;
 
-use crate::{PipelineResult, Signal};
+use crate::{PipelineCheckpoint, PipelineResult, Signal};
 
 /// Providers a way for the pipeline to accept a signal from the driver.
 #[async_trait]
 pub trait SignalReceiver {
     /// Receives a signal from the driver.
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()>;
+
+    /// Writes the stage's buffered state into the [`PipelineCheckpoint`], and then the state of
+    /// the previous stages.
+    ///
+    /// Checkpoints can only be taken at an L1 origin boundary. Stages that do not hold any state
+    /// may rely on the default implementation, which does nothing.
+    fn checkpoint(&self, _checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        Ok(())
+    }
+
+    /// Restores the stage's buffered state from the [`PipelineCheckpoint`], after the previous
+    /// stages have been restored.
+    ///
+    /// Stages are reset to the checkpoint's L1 origin before they are restored. Stages that do not
+    /// hold any state may rely on the default implementation, which does nothing.
+    async fn restore(&mut self, _checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        Ok(())
+    }
 }
 
 /// Provides a method for accessing the pipeline's current L1 origin.
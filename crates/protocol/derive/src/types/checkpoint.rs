@@ -0,0 +1,211 @@
+//! Checkpoint types for the `kona-derive` pipeline.
+//!
+//! A [`PipelineCheckpoint`] is a snapshot of the state buffered by the pipeline's stages, taken at
+//! an L1 origin boundary. Restoring a checkpoint lets the pipeline resume derivation from the
+//! checkpointed L1 origin, instead of walking back a full channel timeout of L1 blocks.
+
+use crate::{PipelineEncodingError, PipelineError, PipelineResult};
+use alloc::vec::Vec;
+use alloy_primitives::Bytes;
+use kona_genesis::{RollupConfig, SystemConfig};
+use kona_protocol::{
+    Batch, BatchWithInclusionBlock, BlockInfo, Channel, ChannelId, Frame, L2BlockInfo, SingleBatch,
+    SpanBatch,
+};
+
+/// A snapshot of the derivation pipeline's stage state at an L1 origin boundary.
+///
+/// Frames and batches are held in their wire encoding, so that the checkpoint can be persisted
+/// without depending on the encoding of the protocol types.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipelineCheckpoint {
+    /// The L1 origin of the pipeline. The data in this block has not yet been read.
+    pub l1_origin: BlockInfo,
+    /// The L2 safe head that the pipeline was stepped on when the checkpoint was taken.
+    pub l2_safe_head: L2BlockInfo,
+    /// The [`SystemConfig`] of the traversal stage.
+    pub system_config: SystemConfig,
+    /// The encoded [`Frame`]s buffered in the frame queue.
+    pub frames: Vec<Bytes>,
+    /// The channels buffered in the channel bank or channel assembler, in FIFO order.
+    pub channels: Vec<ChannelCheckpoint>,
+    /// The L1 origin of the batch queue or batch validator.
+    pub batch_origin: Option<BlockInfo>,
+    /// The window of L1 blocks tracked by the batch queue or batch validator.
+    pub l1_blocks: Vec<BlockInfo>,
+    /// The encoded [`Batch`]es buffered in the batch queue, with their inclusion blocks.
+    pub batches: Vec<BatchCheckpoint>,
+    /// The encoded [`SingleBatch`]es cached by the batch queue from a span batch.
+    pub next_spans: Vec<Bytes>,
+    /// The encoded [`SingleBatch`]es buffered in the batch stream.
+    pub stream_buffer: Vec<Bytes>,
+    /// The encoded span [`Batch`] staged in the batch stream.
+    pub stream_span: Option<Bytes>,
+    /// The encoded [`SingleBatch`] being processed by the attributes queue.
+    pub attributes_batch: Option<Bytes>,
+    /// Whether the batch being processed by the attributes queue is the last in its span.
+    pub is_last_in_span: bool,
+}
+
+impl PipelineCheckpoint {
+    /// Creates a new, empty [`PipelineCheckpoint`] for the given L2 safe head.
+    ///
+    /// The remaining state is filled in by the stages through
+    /// [`SignalReceiver::checkpoint`](crate::SignalReceiver::checkpoint).
+    pub fn new(l2_safe_head: L2BlockInfo) -> Self {
+        Self { l2_safe_head, ..Default::default() }
+    }
+}
+
+/// A snapshot of a [`Channel`] buffered in the pipeline.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelCheckpoint {
+    /// The [`ChannelId`].
+    pub id: ChannelId,
+    /// The L1 block that the channel was opened at.
+    pub open_block: BlockInfo,
+    /// The highest L1 block that a frame of the channel was included in.
+    pub inclusion_block: BlockInfo,
+    /// The encoded [`Frame`]s of the channel, ordered by frame number.
+    pub frames: Vec<Bytes>,
+}
+
+impl From<&Channel> for ChannelCheckpoint {
+    fn from(channel: &Channel) -> Self {
+        Self {
+            id: channel.id(),
+            open_block: channel.open_block(),
+            inclusion_block: channel.highest_l1_inclusion_block(),
+            frames: channel.frames().into_iter().map(|frame| frame.encode().into()).collect(),
+        }
+    }
+}
+
+impl ChannelCheckpoint {
+    /// Rebuilds the [`Channel`] from the checkpoint.
+    pub fn to_channel(&self) -> PipelineResult<Channel> {
+        let mut channel = Channel::new(self.id, self.open_block);
+        for frame in &self.frames {
+            channel
+                .add_frame(decode_frame(frame)?, self.inclusion_block)
+                .map_err(|_| PipelineError::InvalidCheckpoint.crit())?;
+        }
+        Ok(channel)
+    }
+}
+
+/// A snapshot of a [`BatchWithInclusionBlock`] buffered in the pipeline.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchCheckpoint {
+    /// The L1 block that the batch was included in.
+    pub inclusion_block: BlockInfo,
+    /// The encoded [`Batch`].
+    pub batch: Bytes,
+}
+
+impl BatchCheckpoint {
+    /// Creates a new [`BatchCheckpoint`] from the given [`BatchWithInclusionBlock`].
+    pub fn new(batch: &BatchWithInclusionBlock) -> PipelineResult<Self> {
+        Ok(Self { inclusion_block: batch.inclusion_block, batch: encode_batch(&batch.batch)? })
+    }
+
+    /// Rebuilds the [`BatchWithInclusionBlock`] from the checkpoint.
+    pub fn to_batch(&self, cfg: &RollupConfig) -> PipelineResult<BatchWithInclusionBlock> {
+        Ok(BatchWithInclusionBlock::new(self.inclusion_block, decode_batch(&self.batch, cfg)?))
+    }
+}
+
+/// Decodes an encoded [`Frame`] held in a checkpoint.
+pub(crate) fn decode_frame(data: &[u8]) -> PipelineResult<Frame> {
+    Frame::decode(data)
+        .map(|(_, frame)| frame)
+        .map_err(|e| PipelineError::BadEncoding(e.into()).crit())
+}
+
+/// Encodes a [`Batch`] to be held in a checkpoint.
+pub(crate) fn encode_batch(batch: &Batch) -> PipelineResult<Bytes> {
+    let mut out = Vec::new();
+    batch.encode(&mut out).map_err(|e| PipelineError::BadEncoding(e.into()).crit())?;
+    Ok(out.into())
+}
+
+/// Decodes an encoded [`Batch`] held in a checkpoint.
+pub(crate) fn decode_batch(data: &[u8], cfg: &RollupConfig) -> PipelineResult<Batch> {
+    Batch::decode(&mut &data[..], cfg).map_err(|e| PipelineError::BadEncoding(e.into()).crit())
+}
+
+/// Encodes a [`SingleBatch`] to be held in a checkpoint.
+pub(crate) fn encode_single_batch(batch: &SingleBatch) -> Bytes {
+    alloy_rlp::encode(batch).into()
+}
+
+/// Decodes an encoded [`SingleBatch`] held in a checkpoint.
+pub(crate) fn decode_single_batch(data: &[u8]) -> PipelineResult<SingleBatch> {
+    <SingleBatch as alloy_rlp::Decodable>::decode(&mut &data[..])
+        .map_err(|e| PipelineError::BadEncoding(PipelineEncodingError::AlloyRlpError(e)).crit())
+}
+
+/// Encodes a [`SpanBatch`] to be held in a checkpoint.
+pub(crate) fn encode_span_batch(batch: &SpanBatch) -> PipelineResult<Bytes> {
+    encode_batch(&Batch::Span(batch.clone()))
+}
+
+/// Decodes an encoded [`SpanBatch`] held in a checkpoint.
+pub(crate) fn decode_span_batch(data: &[u8], cfg: &RollupConfig) -> PipelineResult<SpanBatch> {
+    match decode_batch(data, cfg)? {
+        Batch::Span(span) => Ok(span),
+        Batch::Single(_) => Err(PipelineError::InvalidBatchType.crit()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_channel_checkpoint_roundtrip() {
+        let id = [0xFF; 16];
+        let open_block = BlockInfo { number: 10, ..Default::default() };
+        let inclusion_block = BlockInfo { number: 12, ..Default::default() };
+        let mut channel = Channel::new(id, open_block);
+        channel.add_frame(Frame::new(id, 1, vec![0xbe, 0xef], true), inclusion_block).unwrap();
+        channel.add_frame(Frame::new(id, 0, vec![0xde, 0xad], false), open_block).unwrap();
+
+        let checkpoint = ChannelCheckpoint::from(&channel);
+        assert_eq!(checkpoint.frames.len(), 2);
+        assert_eq!(checkpoint.inclusion_block, inclusion_block);
+
+        let restored = checkpoint.to_channel().unwrap();
+        assert_eq!(restored.id(), id);
+        assert_eq!(restored.open_block(), open_block);
+        assert_eq!(restored.highest_l1_inclusion_block(), inclusion_block);
+        assert!(restored.is_ready());
+        assert_eq!(restored.frame_data(), channel.frame_data());
+    }
+
+    #[test]
+    fn test_batch_checkpoint_roundtrip() {
+        let cfg = RollupConfig::default();
+        let batch = BatchWithInclusionBlock::new(
+            BlockInfo { number: 5, ..Default::default() },
+            Batch::Single(SingleBatch { epoch_num: 4, timestamp: 2, ..Default::default() }),
+        );
+
+        let checkpoint = BatchCheckpoint::new(&batch).unwrap();
+        assert_eq!(checkpoint.to_batch(&cfg).unwrap(), batch);
+    }
+
+    #[test]
+    fn test_single_batch_checkpoint_roundtrip() {
+        let batch = SingleBatch { epoch_num: 4, timestamp: 2, ..Default::default() };
+        assert_eq!(decode_single_batch(&encode_single_batch(&batch)).unwrap(), batch);
+        assert!(
+            decode_span_batch(&encode_batch(&Batch::Single(batch)).unwrap(), &Default::default())
+                .is_err()
+        );
+    }
+}
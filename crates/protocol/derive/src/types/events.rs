@@ -0,0 +1,183 @@
+//! Events emitted by the stages of the derivation pipeline.
+
+use crate::DerivationEventSink;
+use alloc::sync::Arc;
+use kona_protocol::{BatchValidity, BatchValidityReason, ChannelId};
+
+/// A structured event emitted by a stage of the derivation pipeline.
+///
+/// Every event carries the number of the pipeline's L1 origin at the time it was emitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum DerivationEvent {
+    /// Batcher data could not be parsed into frames, and was discarded by the `FrameQueue`.
+    InvalidFrameData {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+    },
+    /// A frame was added to a channel.
+    FrameAccepted {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The ID of the frame's channel.
+        channel_id: ChannelId,
+        /// The number of the frame within its channel.
+        frame_number: u16,
+    },
+    /// A frame was dropped.
+    FrameDropped {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The ID of the frame's channel.
+        channel_id: ChannelId,
+        /// The number of the frame within its channel.
+        frame_number: u16,
+        /// Why the frame was dropped.
+        reason: FrameDropReason,
+    },
+    /// A channel is complete, and its data was forwarded to the `ChannelReader`.
+    ChannelReady {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The ID of the channel.
+        channel_id: ChannelId,
+        /// The size of the channel's frame data, in bytes.
+        size: usize,
+    },
+    /// A channel timed out before it was complete, and was discarded.
+    ChannelTimedOut {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The ID of the channel.
+        channel_id: ChannelId,
+        /// The number of the L1 block that the channel was opened at.
+        open_block: u64,
+    },
+    /// A channel was discarded before it was complete.
+    ChannelDropped {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The ID of the channel.
+        channel_id: ChannelId,
+        /// Why the channel was dropped.
+        reason: ChannelDropReason,
+    },
+    /// A batch was accepted for the next L2 block.
+    BatchAccepted {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The timestamp of the batch.
+        timestamp: u64,
+    },
+    /// A batch was dropped.
+    BatchDropped {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The timestamp of the batch.
+        timestamp: u64,
+        /// The validity that the batch was dropped with.
+        validity: BatchValidity,
+        /// Why the batch was dropped.
+        reason: BatchValidityReason,
+    },
+    /// A span batch was expanded into single batches.
+    SpanBatchExpanded {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The timestamp of the first batch in the span.
+        timestamp: u64,
+        /// The number of single batches in the span.
+        batches: usize,
+    },
+    /// An empty batch was generated, because the sequencing window of its epoch expired.
+    EmptyBatchGenerated {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The L1 origin of the batch.
+        epoch: u64,
+        /// The timestamp of the batch.
+        timestamp: u64,
+    },
+    /// Payload attributes were prepared for the next L2 block.
+    AttributesPrepared {
+        /// The L1 origin of the pipeline.
+        origin: u64,
+        /// The number of the parent L2 block.
+        parent: u64,
+        /// The timestamp of the L2 block.
+        timestamp: u64,
+        /// The number of transactions in the attributes, including deposits.
+        transactions: usize,
+    },
+}
+
+/// Why a frame was dropped by the derivation pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FrameDropReason {
+    /// The frame does not directly follow the previous frame of its channel.
+    NonSequential,
+    /// The frame follows the last frame of its channel.
+    AfterLastFrame,
+    /// The frame opens a new channel, but is not the first frame of the channel.
+    NotFirstFrame,
+    /// The frame's channel was superseded by a new channel before it was complete.
+    IncompleteChannel,
+    /// The frame's channel has timed out.
+    ChannelTimedOut,
+    /// The frame could not be added to its channel.
+    Rejected,
+    /// No channel is being assembled for the frame.
+    NoChannel,
+}
+
+/// Why a channel was dropped by the derivation pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ChannelDropReason {
+    /// The channel bank exceeded its maximum size, and the oldest channel was pruned.
+    BankFull,
+    /// The channel exceeded the maximum RLP bytes per channel.
+    TooLarge,
+    /// A new channel was opened before the channel was complete.
+    Superseded,
+}
+
+/// Emits [`DerivationEvent`]s to an optional [`DerivationEventSink`].
+///
+/// Stages hold an [`EventEmitter`] that is disabled by default, so that emitting an event is a
+/// no-op unless a sink is installed through the [`PipelineBuilder`].
+///
+/// [`PipelineBuilder`]: crate::PipelineBuilder
+#[derive(Debug, Clone, Default)]
+pub struct EventEmitter {
+    /// The sink that events are emitted to, if any.
+    sink: Option<Arc<dyn DerivationEventSink>>,
+}
+
+impl EventEmitter {
+    /// Creates a new, disabled [`EventEmitter`].
+    pub const fn disabled() -> Self {
+        Self { sink: None }
+    }
+
+    /// Creates a new [`EventEmitter`] that emits events to the given sink.
+    pub fn new(sink: Arc<dyn DerivationEventSink>) -> Self {
+        Self { sink: Some(sink) }
+    }
+
+    /// Returns `true` if a sink is installed.
+    pub const fn is_enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    /// Emits the event to the sink, if one is installed.
+    pub fn emit(&self, event: DerivationEvent) {
+        if let Some(sink) = self.sink.as_ref() {
+            sink.emit(event);
+        }
+    }
+}
@@ -5,3 +5,13 @@ pub use results::{PipelineResult, StepResult};
 
 mod signals;
 pub use signals::{ActivationSignal, ResetSignal, Signal};
+
+mod events;
+pub use events::{ChannelDropReason, DerivationEvent, EventEmitter, FrameDropReason};
+
+mod checkpoint;
+pub use checkpoint::{BatchCheckpoint, ChannelCheckpoint, PipelineCheckpoint};
+pub(crate) use checkpoint::{
+    decode_batch, decode_frame, decode_single_batch, decode_span_batch, encode_batch,
+    encode_single_batch, encode_span_batch,
+};
@@ -162,6 +162,7 @@ impl ChainConfig {
                 .unwrap_or_default(),
             superchain_config_address: None,
             blobs_enabled_l1_timestamp: None,
+            experimental_compression_time: None,
             da_challenge_address: self
                 .alt_da
                 .as_ref()
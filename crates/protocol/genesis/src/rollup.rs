@@ -80,6 +80,12 @@ pub struct RollupConfig {
         serde(rename = "blobs_data", skip_serializing_if = "Option::is_none")
     )]
     pub blobs_enabled_l1_timestamp: Option<u64>,
+    /// `experimental_compression_time` sets the activation time of the experimental channel
+    /// formats: zstd compressed and uncompressed (raw) channels. Meant for research devnets only.
+    /// Active if `experimental_compression_time` != None && L2 block timestamp >=
+    /// Some(experimental_compression_time), inactive otherwise.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub experimental_compression_time: Option<u64>,
     /// `da_challenge_address` is the L1 address that the data availability challenge contract is
     /// stored at.
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
@@ -124,6 +130,7 @@ impl<'a> arbitrary::Arbitrary<'a> for RollupConfig {
             protocol_versions_address: Address::arbitrary(u)?,
             superchain_config_address: Option::<Address>::arbitrary(u)?,
             blobs_enabled_l1_timestamp: Option::<u64>::arbitrary(u)?,
+            experimental_compression_time: Option::<u64>::arbitrary(u)?,
             da_challenge_address: Option::<Address>::arbitrary(u)?,
             interop_message_expiry_window: u.arbitrary()?,
             chain_op_config,
@@ -151,6 +158,7 @@ impl Default for RollupConfig {
             protocol_versions_address: Address::ZERO,
             superchain_config_address: None,
             blobs_enabled_l1_timestamp: None,
+            experimental_compression_time: None,
             da_challenge_address: None,
             interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
             alt_da_config: None,
@@ -320,6 +328,11 @@ impl RollupConfig {
             !self.is_interop_active(timestamp.saturating_sub(self.block_time))
     }
 
+    /// Returns true if the experimental channel formats are accepted at the given timestamp.
+    pub fn is_experimental_compression_active(&self, timestamp: u64) -> bool {
+        self.experimental_compression_time.is_some_and(|t| timestamp >= t)
+    }
+
     /// Returns true if a DA Challenge proxy Address is provided in the rollup config and the
     /// address is not zero.
     pub fn is_alt_da_enabled(&self) -> bool {
@@ -663,6 +676,16 @@ mod tests {
         assert!(!config.is_interop_active(9));
     }
 
+    #[test]
+    fn test_experimental_compression_active() {
+        let mut config = RollupConfig::default();
+        assert!(!config.is_experimental_compression_active(0));
+        config.experimental_compression_time = Some(10);
+        assert!(config.is_experimental_compression_active(10));
+        assert!(!config.is_experimental_compression_active(9));
+        assert!(!config.is_fjord_active(10));
+    }
+
     #[test]
     fn test_is_first_fork_block() {
         let cfg = RollupConfig {
@@ -864,6 +887,7 @@ mod tests {
             protocol_versions_address: Address::ZERO,
             superchain_config_address: None,
             blobs_enabled_l1_timestamp: None,
+            experimental_compression_time: None,
             da_challenge_address: None,
             interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
             chain_op_config: OP_MAINNET_BASE_FEE_CONFIG,
@@ -1,12 +1,12 @@
 //! Module containing a [`TxDeposit`] builder for the Ecotone network upgrade transactions.
 
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
 use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::{Address, B256, Bytes, TxKind, U256, address, hex};
 use kona_protocol::Predeploys;
 use op_alloy_consensus::{TxDeposit, UpgradeDepositSource};
 
-use crate::Hardfork;
+use crate::{Hardfork, UpgradeActivation, UpgradeDescription, UpgradeTransaction};
 
 /// The Ecotone network upgrade transactions.
 #[derive(Debug, Default, Clone, Copy)]
@@ -108,6 +108,56 @@ impl Ecotone {
             .into()
     }
 
+    /// Returns the [`UpgradeDescription`] of the Ecotone network upgrade.
+    pub fn description() -> UpgradeDescription {
+        UpgradeDescription {
+            name: String::from("Ecotone"),
+            activation: UpgradeActivation::Ecotone,
+            transactions: vec![
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Ecotone: L1 Block Deployment"),
+                    deployer: Self::L1_BLOCK_DEPLOYER,
+                    nonce: 0,
+                    gas_limit: 375_000,
+                    bytecode: Self::l1_block_deployment_bytecode(),
+                },
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Ecotone: Gas Price Oracle Deployment"),
+                    deployer: Self::GAS_PRICE_ORACLE_DEPLOYER,
+                    nonce: 0,
+                    gas_limit: 1_000_000,
+                    bytecode: Self::ecotone_gas_price_oracle_deployment_bytecode(),
+                },
+                UpgradeTransaction::UpgradeProxy {
+                    intent: String::from("Ecotone: L1 Block Proxy Update"),
+                    proxy: Predeploys::L1_BLOCK_INFO,
+                    implementation: Self::NEW_L1_BLOCK,
+                    gas_limit: 50_000,
+                },
+                UpgradeTransaction::UpgradeProxy {
+                    intent: String::from("Ecotone: Gas Price Oracle Proxy Update"),
+                    proxy: Predeploys::GAS_PRICE_ORACLE,
+                    implementation: Self::GAS_PRICE_ORACLE,
+                    gas_limit: 50_000,
+                },
+                UpgradeTransaction::Call {
+                    intent: String::from("Ecotone: Gas Price Oracle Set Ecotone"),
+                    from: Self::DEPOSITOR_ACCOUNT,
+                    to: Predeploys::GAS_PRICE_ORACLE,
+                    gas_limit: 80_000,
+                    input: Self::ENABLE_ECOTONE_INPUT.into(),
+                },
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Ecotone: beacon block roots contract deployment"),
+                    deployer: Self::EIP4788_FROM,
+                    nonce: 0,
+                    gas_limit: 250_000,
+                    bytecode: Self::eip4788_creation_data(),
+                },
+            ],
+        }
+    }
+
     /// Returns the list of [`TxDeposit`]s for the Ecotone network upgrade.
     pub fn deposits() -> impl Iterator<Item = TxDeposit> {
         ([
@@ -304,4 +354,12 @@ mod tests {
             assert_eq!(ecotone_upgrade_tx[i], *expected);
         }
     }
+
+    #[test]
+    fn test_ecotone_description() {
+        let description = Ecotone::description();
+        description.verify(&Ecotone.txs().collect::<Vec<_>>()).unwrap();
+        assert_eq!(description.transactions[0].deployed_address(), Some(Ecotone::NEW_L1_BLOCK));
+        assert_eq!(description.transactions[1].deployed_address(), Some(Ecotone::GAS_PRICE_ORACLE));
+    }
 }
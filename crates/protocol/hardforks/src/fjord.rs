@@ -1,12 +1,12 @@
 //! Module containing a [`TxDeposit`] builder for the Fjord network upgrade transactions.
 
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
 use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::{Address, B256, Bytes, TxKind, U256, address, hex};
 use kona_protocol::Predeploys;
 use op_alloy_consensus::{TxDeposit, UpgradeDepositSource};
 
-use crate::Hardfork;
+use crate::{Hardfork, UpgradeActivation, UpgradeDescription, UpgradeTransaction};
 
 /// The Fjord network upgrade transactions.
 #[derive(Debug, Default, Clone, Copy)]
@@ -63,6 +63,36 @@ impl Fjord {
             .into()
     }
 
+    /// Returns the [`UpgradeDescription`] of the Fjord network upgrade.
+    pub fn description() -> UpgradeDescription {
+        UpgradeDescription {
+            name: String::from("Fjord"),
+            activation: UpgradeActivation::Fjord,
+            transactions: vec![
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Fjord: Gas Price Oracle Deployment"),
+                    deployer: Self::GAS_PRICE_ORACLE_FJORD_DEPLOYER,
+                    nonce: 0,
+                    gas_limit: 1_450_000,
+                    bytecode: Self::gas_price_oracle_deployment_bytecode(),
+                },
+                UpgradeTransaction::UpgradeProxy {
+                    intent: String::from("Fjord: Gas Price Oracle Proxy Update"),
+                    proxy: Predeploys::GAS_PRICE_ORACLE,
+                    implementation: Self::FJORD_GAS_PRICE_ORACLE,
+                    gas_limit: 50_000,
+                },
+                UpgradeTransaction::Call {
+                    intent: String::from("Fjord: Gas Price Oracle Set Fjord"),
+                    from: Self::L1_INFO_DEPOSITER,
+                    to: Predeploys::GAS_PRICE_ORACLE,
+                    gas_limit: 90_000,
+                    input: Self::SET_FJORD_METHOD_SIGNATURE.into(),
+                },
+            ],
+        }
+    }
+
     /// Returns the list of [`TxDeposit`]s for the Fjord network upgrade.
     pub fn deposits() -> impl Iterator<Item = TxDeposit> {
         ([
@@ -180,4 +210,14 @@ mod tests {
             Fjord::GAS_PRICE_ORACLE_CODE_HASH,
         );
     }
+
+    #[test]
+    fn test_fjord_description() {
+        let description = Fjord::description();
+        description.verify(&Fjord.txs().collect::<Vec<_>>()).unwrap();
+        assert_eq!(
+            description.transactions[0].deployed_address(),
+            Some(Fjord::FJORD_GAS_PRICE_ORACLE)
+        );
+    }
 }
@@ -4,13 +4,13 @@
 //!
 //! [specs]: https://specs.optimism.io/interop/derivation.html#network-upgrade-transactions
 
-use alloc::string::String;
+use alloc::{string::String, vec};
 use alloy_eips::Encodable2718;
 use alloy_primitives::{Address, B256, Bytes, TxKind, U256, address, b256, hex};
 use kona_protocol::Predeploys;
 use op_alloy_consensus::{TxDeposit, UpgradeDepositSource};
 
-use crate::Hardfork;
+use crate::{Hardfork, UpgradeActivation, UpgradeDescription, UpgradeTransaction};
 
 /// The Interop network upgrade transactions.
 #[derive(Debug, Default, Clone, Copy)]
@@ -83,6 +83,42 @@ impl Interop {
             .into()
     }
 
+    /// Returns the [`UpgradeDescription`] of the Interop network upgrade.
+    pub fn description() -> UpgradeDescription {
+        UpgradeDescription {
+            name: String::from("Interop"),
+            activation: UpgradeActivation::Interop,
+            transactions: vec![
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Interop: CrossL2Inbox Deployment"),
+                    deployer: Self::CROSS_L2_INBOX_DEPLOYER,
+                    nonce: 0,
+                    gas_limit: 420_000,
+                    bytecode: Self::cross_l2_inbox_deployment_bytecode(),
+                },
+                UpgradeTransaction::UpgradeProxy {
+                    intent: String::from("Interop: CrossL2Inbox Proxy Update"),
+                    proxy: Predeploys::CROSS_L2_INBOX,
+                    implementation: Self::NEW_CROSS_L2_INBOX_IMPL,
+                    gas_limit: 50_000,
+                },
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Interop: L2ToL2CrossDomainMessenger Deployment"),
+                    deployer: Self::L2_TO_L2_XDM_DEPLOYER,
+                    nonce: 0,
+                    gas_limit: 1_100_000,
+                    bytecode: Self::l2_to_l2_xdm_deployment_bytecode(),
+                },
+                UpgradeTransaction::UpgradeProxy {
+                    intent: String::from("Interop: L2ToL2CrossDomainMessenger Proxy Update"),
+                    proxy: Predeploys::L2_TO_L2_XDM,
+                    implementation: Self::NEW_L2_TO_L2_XDM_IMPL,
+                    gas_limit: 50_000,
+                },
+            ],
+        }
+    }
+
     /// Returns the list of [`TxDeposit`]s for the network upgrade.
     pub fn deposits() -> impl Iterator<Item = TxDeposit> {
         ([
@@ -220,4 +256,18 @@ mod test {
             assert_eq!(interop_upgrade_tx[i], *expected);
         }
     }
+
+    #[test]
+    fn test_interop_description() {
+        let description = Interop::description();
+        description.verify(&Interop.txs().collect::<Vec<_>>()).unwrap();
+        assert_eq!(
+            description.transactions[0].deployed_address(),
+            Some(Interop::NEW_CROSS_L2_INBOX_IMPL)
+        );
+        assert_eq!(
+            description.transactions[2].deployed_address(),
+            Some(Interop::NEW_L2_TO_L2_XDM_IMPL)
+        );
+    }
 }
@@ -4,13 +4,13 @@
 //!
 //! [specs]: https://specs.optimism.io/protocol/isthmus/derivation.html#network-upgrade-automation-transactions
 
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
 use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::{Address, B256, Bytes, TxKind, U256, address, hex};
 use kona_protocol::Predeploys;
 use op_alloy_consensus::{TxDeposit, UpgradeDepositSource};
 
-use crate::Hardfork;
+use crate::{Hardfork, UpgradeActivation, UpgradeDescription, UpgradeTransaction};
 
 /// The Isthmus network upgrade transactions.
 #[derive(Debug, Default, Clone, Copy)]
@@ -145,6 +145,69 @@ impl Isthmus {
             .into()
     }
 
+    /// Returns the [`UpgradeDescription`] of the Isthmus network upgrade.
+    pub fn description() -> UpgradeDescription {
+        UpgradeDescription {
+            name: String::from("Isthmus"),
+            activation: UpgradeActivation::Isthmus,
+            transactions: vec![
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Isthmus: L1 Block Deployment"),
+                    deployer: Self::L1_BLOCK_DEPLOYER,
+                    nonce: 0,
+                    gas_limit: 425_000,
+                    bytecode: Self::l1_block_deployment_bytecode(),
+                },
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Isthmus: Gas Price Oracle Deployment"),
+                    deployer: Self::GAS_PRICE_ORACLE_DEPLOYER,
+                    nonce: 0,
+                    gas_limit: 1_625_000,
+                    bytecode: Self::gas_price_oracle_deployment_bytecode(),
+                },
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Isthmus: Operator Fee Vault Deployment"),
+                    deployer: Self::OPERATOR_FEE_VAULT_DEPLOYER,
+                    nonce: 0,
+                    gas_limit: 500_000,
+                    bytecode: Self::operator_fee_vault_deployment_bytecode(),
+                },
+                UpgradeTransaction::UpgradeProxy {
+                    intent: String::from("Isthmus: L1 Block Proxy Update"),
+                    proxy: Predeploys::L1_BLOCK_INFO,
+                    implementation: Self::NEW_L1_BLOCK,
+                    gas_limit: 50_000,
+                },
+                UpgradeTransaction::UpgradeProxy {
+                    intent: String::from("Isthmus: Gas Price Oracle Proxy Update"),
+                    proxy: Predeploys::GAS_PRICE_ORACLE,
+                    implementation: Self::GAS_PRICE_ORACLE,
+                    gas_limit: 50_000,
+                },
+                UpgradeTransaction::UpgradeProxy {
+                    intent: String::from("Isthmus: Operator Fee Vault Proxy Update"),
+                    proxy: Predeploys::OPERATOR_FEE_VAULT,
+                    implementation: Self::OPERATOR_FEE_VAULT,
+                    gas_limit: 50_000,
+                },
+                UpgradeTransaction::Call {
+                    intent: String::from("Isthmus: Gas Price Oracle Set Isthmus"),
+                    from: Self::DEPOSITOR_ACCOUNT,
+                    to: Predeploys::GAS_PRICE_ORACLE,
+                    gas_limit: 90_000,
+                    input: Self::ENABLE_ISTHMUS_INPUT.into(),
+                },
+                UpgradeTransaction::Deploy {
+                    intent: String::from("Isthmus: EIP-2935 Contract Deployment"),
+                    deployer: Self::EIP2935_FROM,
+                    nonce: 0,
+                    gas_limit: 250_000,
+                    bytecode: Self::eip2935_creation_data(),
+                },
+            ],
+        }
+    }
+
     /// Returns the list of [`TxDeposit`]s for the network upgrade.
     pub fn deposits() -> impl Iterator<Item = TxDeposit> {
         ([
@@ -358,4 +421,16 @@ mod tests {
             Isthmus::OPERATOR_FEE_VAULT_CODE_HASH,
         );
     }
+
+    #[test]
+    fn test_isthmus_description() {
+        let description = Isthmus::description();
+        description.verify(&Isthmus.txs().collect::<Vec<_>>()).unwrap();
+        assert_eq!(description.transactions[0].deployed_address(), Some(Isthmus::NEW_L1_BLOCK));
+        assert_eq!(description.transactions[1].deployed_address(), Some(Isthmus::GAS_PRICE_ORACLE));
+        assert_eq!(
+            description.transactions[2].deployed_address(),
+            Some(Isthmus::OPERATOR_FEE_VAULT)
+        );
+    }
 }
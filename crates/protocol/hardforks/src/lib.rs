@@ -27,6 +27,12 @@ pub use isthmus::Isthmus;
 mod interop;
 pub use interop::Interop;
 
+mod upgrade;
+pub use upgrade::{
+    UpgradeActivation, UpgradeDescription, UpgradeRegistry, UpgradeTransaction,
+    UpgradeVerificationError,
+};
+
 mod utils;
 pub(crate) use utils::upgrade_to_calldata;
 
@@ -0,0 +1,474 @@
+//! Data-driven network upgrade transactions.
+//!
+//! An [`UpgradeDescription`] describes the deposit transactions of a network upgrade as data, so
+//! devnets with custom predeploys can define their own fork activation transactions. The
+//! descriptions of the built-in hardforks are verified to reproduce their transactions exactly.
+
+use alloc::{string::String, vec::Vec};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, B256, Bytes, TxKind, U256};
+use kona_genesis::RollupConfig;
+use op_alloy_consensus::{TxDeposit, UpgradeDepositSource};
+
+use crate::{Ecotone, Fjord, Hardfork, Hardforks, Interop, Isthmus};
+
+/// The block at which an [`UpgradeDescription`] is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum UpgradeActivation {
+    /// The first block of the Ecotone hardfork.
+    Ecotone,
+    /// The first block of the Fjord hardfork.
+    Fjord,
+    /// The first block of the Isthmus hardfork.
+    Isthmus,
+    /// The first block of the Interop hardfork.
+    Interop,
+    /// The first block at or after the given timestamp.
+    Timestamp(u64),
+}
+
+impl UpgradeActivation {
+    /// The activations of the built-in hardforks, in activation order.
+    pub const BUILTIN: [Self; 4] = [Self::Ecotone, Self::Fjord, Self::Isthmus, Self::Interop];
+
+    /// Returns whether the activation is reached at the given timestamp.
+    pub fn is_active(&self, cfg: &RollupConfig, timestamp: u64) -> bool {
+        match self {
+            Self::Ecotone => cfg.is_ecotone_active(timestamp),
+            Self::Fjord => cfg.is_fjord_active(timestamp),
+            Self::Isthmus => cfg.is_isthmus_active(timestamp),
+            Self::Interop => cfg.is_interop_active(timestamp),
+            Self::Timestamp(activation) => timestamp >= *activation,
+        }
+    }
+
+    /// Returns whether the block with the given timestamp is the first block of the activation.
+    pub fn is_first_block(
+        &self,
+        cfg: &RollupConfig,
+        parent_timestamp: u64,
+        timestamp: u64,
+    ) -> bool {
+        self.is_active(cfg, timestamp) && !self.is_active(cfg, parent_timestamp)
+    }
+
+    /// Returns the transactions of the built-in hardfork of the activation, if any.
+    pub fn builtin_txs(&self) -> Option<Vec<Bytes>> {
+        match self {
+            Self::Ecotone => Some(Hardforks::ECOTONE.txs().collect()),
+            Self::Fjord => Some(Hardforks::FJORD.txs().collect()),
+            Self::Isthmus => Some(Hardforks::ISTHMUS.txs().collect()),
+            Self::Interop => Some(Hardforks::INTEROP.txs().collect()),
+            Self::Timestamp(_) => None,
+        }
+    }
+}
+
+/// A deposit transaction of an [`UpgradeDescription`].
+///
+/// The source hash of the deposit is derived from its intent, as an [`UpgradeDepositSource`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum UpgradeTransaction {
+    /// Deploys a contract from the deployer account.
+    Deploy {
+        /// The intent of the transaction.
+        intent: String,
+        /// The account that deploys the contract.
+        deployer: Address,
+        /// The nonce of the deployer account, which determines the contract address.
+        #[cfg_attr(feature = "serde", serde(default))]
+        nonce: u64,
+        /// The gas limit of the transaction.
+        gas_limit: u64,
+        /// The creation bytecode of the contract.
+        bytecode: Bytes,
+    },
+    /// Points a proxy at a new implementation through `upgradeTo`, from the zero address.
+    UpgradeProxy {
+        /// The intent of the transaction.
+        intent: String,
+        /// The address of the proxy.
+        proxy: Address,
+        /// The address of the new implementation.
+        implementation: Address,
+        /// The gas limit of the transaction.
+        gas_limit: u64,
+    },
+    /// Calls a contract.
+    Call {
+        /// The intent of the transaction.
+        intent: String,
+        /// The account that sends the call.
+        from: Address,
+        /// The called contract.
+        to: Address,
+        /// The gas limit of the transaction.
+        gas_limit: u64,
+        /// The calldata of the call.
+        input: Bytes,
+    },
+}
+
+impl UpgradeTransaction {
+    /// Returns the intent of the transaction.
+    pub fn intent(&self) -> &str {
+        match self {
+            Self::Deploy { intent, .. } |
+            Self::UpgradeProxy { intent, .. } |
+            Self::Call { intent, .. } => intent,
+        }
+    }
+
+    /// Returns the source hash of the transaction.
+    pub fn source_hash(&self) -> B256 {
+        UpgradeDepositSource { intent: String::from(self.intent()) }.source_hash()
+    }
+
+    /// Returns the address of the contract deployed by the transaction, if any.
+    pub fn deployed_address(&self) -> Option<Address> {
+        match self {
+            Self::Deploy { deployer, nonce, .. } => Some(deployer.create(*nonce)),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`TxDeposit`] of the transaction.
+    pub fn deposit(&self) -> TxDeposit {
+        let (from, to, gas_limit, input) = match self {
+            Self::Deploy { deployer, gas_limit, bytecode, .. } => {
+                (*deployer, TxKind::Create, *gas_limit, bytecode.clone())
+            }
+            Self::UpgradeProxy { proxy, implementation, gas_limit, .. } => (
+                Address::ZERO,
+                TxKind::Call(*proxy),
+                *gas_limit,
+                crate::upgrade_to_calldata(*implementation),
+            ),
+            Self::Call { from, to, gas_limit, input, .. } => {
+                (*from, TxKind::Call(*to), *gas_limit, input.clone())
+            }
+        };
+        TxDeposit {
+            source_hash: self.source_hash(),
+            from,
+            to,
+            mint: 0,
+            value: U256::ZERO,
+            gas_limit,
+            is_system_transaction: false,
+            input,
+        }
+    }
+}
+
+/// A network upgrade, described as data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpgradeDescription {
+    /// The name of the upgrade.
+    pub name: String,
+    /// The block at which the upgrade is applied.
+    pub activation: UpgradeActivation,
+    /// The upgrade transactions, in order.
+    pub transactions: Vec<UpgradeTransaction>,
+}
+
+impl UpgradeDescription {
+    /// Returns the list of [`TxDeposit`]s for the upgrade.
+    pub fn deposits(&self) -> impl Iterator<Item = TxDeposit> + '_ {
+        self.transactions.iter().map(UpgradeTransaction::deposit)
+    }
+
+    /// Verifies that the upgrade reproduces the given transactions exactly.
+    pub fn verify(&self, expected: &[Bytes]) -> Result<(), UpgradeVerificationError> {
+        if self.transactions.len() != expected.len() {
+            return Err(UpgradeVerificationError::TransactionCount {
+                name: self.name.clone(),
+                expected: expected.len(),
+                actual: self.transactions.len(),
+            });
+        }
+        for (index, (tx, expected)) in self.txs().zip(expected).enumerate() {
+            if tx != *expected {
+                return Err(UpgradeVerificationError::TransactionMismatch {
+                    name: self.name.clone(),
+                    index,
+                    intent: String::from(self.transactions[index].intent()),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Hardfork for UpgradeDescription {
+    /// Constructs the network upgrade transactions.
+    fn txs(&self) -> impl Iterator<Item = Bytes> + '_ {
+        self.deposits().map(|tx| tx.encoded_2718().into())
+    }
+}
+
+/// An error returned when an [`UpgradeDescription`] does not reproduce the expected transactions.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UpgradeVerificationError {
+    /// The upgrade has a different number of transactions.
+    #[error("upgrade {name} has {actual} transactions, expected {expected}")]
+    TransactionCount {
+        /// The name of the upgrade.
+        name: String,
+        /// The expected number of transactions.
+        expected: usize,
+        /// The number of transactions of the upgrade.
+        actual: usize,
+    },
+    /// An upgrade transaction does not match the expected transaction.
+    #[error("transaction {index} ({intent}) of upgrade {name} does not match")]
+    TransactionMismatch {
+        /// The name of the upgrade.
+        name: String,
+        /// The index of the transaction.
+        index: usize,
+        /// The intent of the transaction.
+        intent: String,
+    },
+}
+
+/// An ordered registry of [`UpgradeDescription`]s.
+///
+/// The [`Default`] registry contains the built-in hardforks, in activation order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpgradeRegistry {
+    /// The upgrades of the registry, in the order their transactions are applied.
+    pub upgrades: Vec<UpgradeDescription>,
+}
+
+impl Default for UpgradeRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl UpgradeRegistry {
+    /// Returns the registry of the built-in hardforks.
+    pub fn builtin() -> Self {
+        Self {
+            upgrades: alloc::vec![
+                Ecotone::description(),
+                Fjord::description(),
+                Isthmus::description(),
+                Interop::description(),
+            ],
+        }
+    }
+
+    /// Adds the upgrade to the registry, replacing the upgrade with the same name if any.
+    pub fn with_upgrade(mut self, upgrade: UpgradeDescription) -> Self {
+        match self.upgrades.iter_mut().find(|u| u.name == upgrade.name) {
+            Some(existing) => *existing = upgrade,
+            None => self.upgrades.push(upgrade),
+        }
+        self
+    }
+
+    /// Returns the upgrade transactions of the block with the given timestamp.
+    pub fn txs(&self, cfg: &RollupConfig, parent_timestamp: u64, timestamp: u64) -> Vec<Bytes> {
+        self.upgrades
+            .iter()
+            .filter(|u| u.activation.is_first_block(cfg, parent_timestamp, timestamp))
+            .flat_map(|u| u.txs())
+            .collect()
+    }
+
+    /// Returns the upgrade transactions of the built-in registry for the block with the given
+    /// timestamp, without building the descriptions of the built-in hardforks.
+    pub fn builtin_txs(cfg: &RollupConfig, parent_timestamp: u64, timestamp: u64) -> Vec<Bytes> {
+        UpgradeActivation::BUILTIN
+            .iter()
+            .filter(|a| a.is_first_block(cfg, parent_timestamp, timestamp))
+            .flat_map(|a| a.builtin_txs().unwrap_or_default())
+            .collect()
+    }
+
+    /// Verifies that the upgrades activated by a built-in hardfork reproduce its transactions.
+    pub fn verify_builtin(&self) -> Result<(), UpgradeVerificationError> {
+        for upgrade in &self.upgrades {
+            if let Some(expected) = upgrade.activation.builtin_txs() {
+                upgrade.verify(&expected)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::{address, hex};
+    use kona_genesis::HardForkConfig;
+
+    fn custom_upgrade(timestamp: u64) -> UpgradeDescription {
+        let deployer = address!("4210000000000000000000000000000000000100");
+        let deploy = UpgradeTransaction::Deploy {
+            intent: String::from("Custom: Predeploy Deployment"),
+            deployer,
+            nonce: 0,
+            gas_limit: 100_000,
+            bytecode: Bytes::from_static(&hex!("6000600055")),
+        };
+        UpgradeDescription {
+            name: String::from("Custom"),
+            activation: UpgradeActivation::Timestamp(timestamp),
+            transactions: vec![
+                deploy,
+                UpgradeTransaction::UpgradeProxy {
+                    intent: String::from("Custom: Predeploy Proxy Update"),
+                    proxy: address!("4200000000000000000000000000000000000100"),
+                    implementation: deployer.create(0),
+                    gas_limit: 50_000,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_builtin_registry_verifies() {
+        UpgradeRegistry::builtin().verify_builtin().unwrap();
+    }
+
+    #[test]
+    fn test_verify_detects_mismatch() {
+        let mut ecotone = Ecotone::description();
+        if let UpgradeTransaction::Deploy { gas_limit, .. } = &mut ecotone.transactions[0] {
+            *gas_limit += 1;
+        }
+        let registry = UpgradeRegistry::builtin().with_upgrade(ecotone);
+        assert_eq!(
+            registry.verify_builtin(),
+            Err(UpgradeVerificationError::TransactionMismatch {
+                name: String::from("Ecotone"),
+                index: 0,
+                intent: String::from("Ecotone: L1 Block Deployment"),
+            })
+        );
+
+        let mut fjord = Fjord::description();
+        fjord.transactions.pop();
+        assert_eq!(
+            fjord.verify(&Hardforks::FJORD.txs().collect::<Vec<_>>()),
+            Err(UpgradeVerificationError::TransactionCount {
+                name: String::from("Fjord"),
+                expected: 3,
+                actual: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_registry_txs_at_activation() {
+        let cfg = RollupConfig {
+            hardforks: HardForkConfig {
+                ecotone_time: Some(10),
+                fjord_time: Some(10),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let registry = UpgradeRegistry::builtin().with_upgrade(custom_upgrade(20));
+
+        let mut expected: Vec<Bytes> = Hardforks::ECOTONE.txs().collect();
+        expected.extend(Hardforks::FJORD.txs());
+        assert_eq!(registry.txs(&cfg, 8, 10), expected);
+        assert!(registry.txs(&cfg, 10, 12).is_empty());
+        assert_eq!(registry.txs(&cfg, 18, 20), custom_upgrade(20).txs().collect::<Vec<_>>());
+        assert_eq!(UpgradeRegistry::builtin_txs(&cfg, 8, 10), expected);
+        assert_eq!(
+            UpgradeRegistry::builtin_txs(&cfg, 18, 20),
+            UpgradeRegistry::builtin().txs(&cfg, 18, 20)
+        );
+    }
+
+    #[test]
+    fn test_with_upgrade_replaces_name() {
+        let mut isthmus = Isthmus::description();
+        isthmus.transactions.truncate(1);
+        let registry = UpgradeRegistry::builtin().with_upgrade(isthmus.clone());
+        assert_eq!(registry.upgrades.len(), 4);
+        assert_eq!(registry.upgrades[2], isthmus);
+
+        let mut other = custom_upgrade(20);
+        other.name = String::from("Other");
+        let registry = registry.with_upgrade(custom_upgrade(20)).with_upgrade(other.clone());
+        assert_eq!(registry.upgrades.len(), 6);
+        assert_eq!(registry.upgrades[4], custom_upgrade(20));
+        assert_eq!(registry.upgrades[5], other);
+
+        let replaced = registry.with_upgrade(custom_upgrade(30));
+        assert_eq!(replaced.upgrades.len(), 6);
+        assert_eq!(replaced.upgrades[4], custom_upgrade(30));
+    }
+
+    #[test]
+    fn test_upgrade_proxy_deposit() {
+        let upgrade = custom_upgrade(0);
+        let deposit = upgrade.transactions[1].deposit();
+        assert_eq!(deposit.from, Address::ZERO);
+        assert_eq!(
+            deposit.input,
+            crate::upgrade_to_calldata(upgrade.transactions[0].deployed_address().unwrap())
+        );
+        assert_eq!(
+            deposit.source_hash,
+            UpgradeDepositSource { intent: String::from("Custom: Predeploy Proxy Update") }
+                .source_hash()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_upgrade_registry_json_roundtrip() {
+        let registry = UpgradeRegistry::builtin().with_upgrade(custom_upgrade(100));
+        let json = serde_json::to_string(&registry).unwrap();
+        let decoded: UpgradeRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, registry);
+        decoded.verify_builtin().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_upgrade_description_from_toml() {
+        let raw = r#"
+            [[upgrades]]
+            name = "Custom"
+            activation = { timestamp = 100 }
+
+            [[upgrades.transactions]]
+            type = "deploy"
+            intent = "Custom: Predeploy Deployment"
+            deployer = "0x4210000000000000000000000000000000000100"
+            gas_limit = 100000
+            bytecode = "0x6000600055"
+
+            [[upgrades.transactions]]
+            type = "upgrade_proxy"
+            intent = "Custom: Predeploy Proxy Update"
+            proxy = "0x4200000000000000000000000000000000000100"
+            implementation = "0x1c5c6e6b4a1ef1f4d0b1e5b5a5e9f62f8e0d7b1b"
+            gas_limit = 50000
+        "#;
+        let registry: UpgradeRegistry = toml::from_str(raw).unwrap();
+        let mut expected = custom_upgrade(100);
+        expected.transactions[1] = UpgradeTransaction::UpgradeProxy {
+            intent: String::from("Custom: Predeploy Proxy Update"),
+            proxy: address!("4200000000000000000000000000000000000100"),
+            implementation: address!("1c5c6e6b4a1ef1f4d0b1e5b5a5e9f62f8e0d7b1b"),
+            gas_limit: 50_000,
+        };
+        assert_eq!(registry.upgrades, vec![expected]);
+    }
+}
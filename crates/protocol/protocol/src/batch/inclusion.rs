@@ -1,6 +1,8 @@
 //! Module containing the [`BatchWithInclusionBlock`] struct.
 
-use crate::{Batch, BatchValidationProvider, BatchValidity, BlockInfo, L2BlockInfo};
+use crate::{
+    Batch, BatchValidationProvider, BatchValidity, BatchValidityReason, BlockInfo, L2BlockInfo,
+};
 use kona_genesis::RollupConfig;
 
 /// A batch with its inclusion block.
@@ -30,13 +32,27 @@ impl BatchWithInclusionBlock {
         l2_safe_head: L2BlockInfo,
         fetcher: &mut BF,
     ) -> BatchValidity {
+        self.explain_batch(cfg, l1_blocks, l2_safe_head, fetcher).await.validity()
+    }
+
+    /// Validates the batch can be applied on top of the specified L2 safe head, returning the
+    /// [`BatchValidityReason`] behind the result.
+    ///
+    /// See [`BatchWithInclusionBlock::check_batch`].
+    pub async fn explain_batch<BF: BatchValidationProvider>(
+        &self,
+        cfg: &RollupConfig,
+        l1_blocks: &[BlockInfo],
+        l2_safe_head: L2BlockInfo,
+        fetcher: &mut BF,
+    ) -> BatchValidityReason {
         match &self.batch {
             Batch::Single(single_batch) => {
-                single_batch.check_batch(cfg, l1_blocks, l2_safe_head, &self.inclusion_block)
+                single_batch.explain_batch(cfg, l1_blocks, l2_safe_head, &self.inclusion_block)
             }
             Batch::Span(span_batch) => {
                 span_batch
-                    .check_batch(cfg, l1_blocks, l2_safe_head, &self.inclusion_block, fetcher)
+                    .explain_batch(cfg, l1_blocks, l2_safe_head, &self.inclusion_block, fetcher)
                     .await
             }
         }
@@ -71,5 +87,7 @@ mod tests {
         let mut validator = TestBatchValidator::default();
         let result = batch.check_batch(&cfg, &l1_blocks, l2_safe_head, &mut validator).await;
         assert_eq!(result, BatchValidity::Undecided);
+        let reason = batch.explain_batch(&cfg, &l1_blocks, l2_safe_head, &mut validator).await;
+        assert_eq!(reason, BatchValidityReason::EmptySpanBatch);
     }
 }
@@ -71,6 +71,9 @@ pub use element::{MAX_SPAN_BATCH_ELEMENTS, SpanBatchElement};
 mod validity;
 pub use validity::BatchValidity;
 
+mod reason;
+pub use reason::BatchValidityReason;
+
 mod single;
 pub use single::SingleBatch;
 
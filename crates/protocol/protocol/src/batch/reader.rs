@@ -1,6 +1,8 @@
 //! Contains the [`BatchReader`] which is used to iteratively consume batches from raw data.
 
-use crate::{Batch, BrotliDecompressionError, decompress_brotli};
+use crate::{
+    Batch, BrotliDecompressionError, ZstdDecompressionError, decompress_brotli, decompress_zstd,
+};
 use alloc::vec::Vec;
 use alloy_primitives::Bytes;
 use alloy_rlp::Decodable;
@@ -19,6 +21,9 @@ pub enum DecompressionError {
     /// A brotli decompression error.
     #[error("brotli decompression error: {0}")]
     BrotliError(#[from] BrotliDecompressionError),
+    /// A zstd decompression error.
+    #[error("zstd decompression error: {0}")]
+    ZstdError(#[from] ZstdDecompressionError),
     /// A zlib decompression error.
     #[error("zlib decompression error")]
     ZlibError,
@@ -43,6 +48,10 @@ pub struct BatchReader {
     max_rlp_bytes_per_channel: usize,
     /// Whether brotli decompression was used.
     pub brotli_used: bool,
+    /// Whether zstd decompression was used.
+    pub zstd_used: bool,
+    /// Whether the channel was uncompressed.
+    pub raw_used: bool,
 }
 
 impl BatchReader {
@@ -55,6 +64,16 @@ impl BatchReader {
     /// Brotli Compression Channel Version.
     pub const CHANNEL_VERSION_BROTLI: u8 = 1;
 
+    /// Zstd Compression Channel Version.
+    ///
+    /// Experimental, only accepted once [RollupConfig::is_experimental_compression_active].
+    pub const CHANNEL_VERSION_ZSTD: u8 = 2;
+
+    /// Uncompressed (raw) Channel Version.
+    ///
+    /// Experimental, only accepted once [RollupConfig::is_experimental_compression_active].
+    pub const CHANNEL_VERSION_RAW: u8 = 3;
+
     /// Creates a new [`BatchReader`] from the given data and max decompressed RLP bytes per
     /// channel.
     pub fn new<T>(data: T, max_rlp_bytes_per_channel: usize) -> Self
@@ -67,6 +86,8 @@ impl BatchReader {
             cursor: 0,
             max_rlp_bytes_per_channel,
             brotli_used: false,
+            zstd_used: false,
+            raw_used: false,
         }
     }
 
@@ -95,6 +116,18 @@ impl BatchReader {
             } else if compression_type == Self::CHANNEL_VERSION_BROTLI {
                 self.brotli_used = true;
                 self.decompressed = decompress_brotli(&data[1..], self.max_rlp_bytes_per_channel)?;
+            } else if compression_type == Self::CHANNEL_VERSION_ZSTD {
+                self.zstd_used = true;
+                self.decompressed = decompress_zstd(&data[1..], self.max_rlp_bytes_per_channel)?;
+            } else if compression_type == Self::CHANNEL_VERSION_RAW {
+                self.raw_used = true;
+                if data.len() - 1 > self.max_rlp_bytes_per_channel {
+                    return Err(DecompressionError::RlpTooLarge(
+                        data.len() - 1,
+                        self.max_rlp_bytes_per_channel,
+                    ));
+                }
+                self.decompressed = data[1..].to_vec();
             } else {
                 return Err(DecompressionError::UnsupportedType(compression_type));
             }
@@ -119,6 +152,13 @@ impl BatchReader {
             return None;
         }
 
+        // Confirm that the experimental channel formats are enabled.
+        if (self.zstd_used || self.raw_used) &&
+            !cfg.is_experimental_compression_active(batch.timestamp())
+        {
+            return None;
+        }
+
         // Advance the cursor on the reader.
         self.cursor = self.decompressed.len() - decompressed_reader.len();
         Some(batch)
@@ -162,4 +202,44 @@ mod test {
             .unwrap();
         assert_eq!(reader.cursor, decompressed_len);
     }
+
+    #[test]
+    fn test_batch_reader_raw() {
+        let raw = new_compressed_batch_data();
+        let decompressed = decompress_to_vec_zlib(&raw).unwrap();
+        let mut data = alloc::vec![BatchReader::CHANNEL_VERSION_RAW];
+        data.extend_from_slice(&decompressed);
+
+        // Raw channels are rejected until the experimental channel formats are enabled.
+        let mut reader = BatchReader::new(data.clone(), MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize);
+        assert!(reader.next_batch(&RollupConfig::default()).is_none());
+        assert!(reader.raw_used);
+
+        let mut reader = BatchReader::new(data, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize);
+        reader
+            .next_batch(&RollupConfig {
+                experimental_compression_time: Some(0),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(reader.cursor, decompressed.len());
+    }
+
+    #[test]
+    fn test_batch_reader_raw_too_large() {
+        let data = [BatchReader::CHANNEL_VERSION_RAW, 0xc0, 0xc0];
+        let mut reader = BatchReader::new(data, 1);
+        assert!(matches!(reader.decompress(), Err(DecompressionError::RlpTooLarge(2, 1))));
+    }
+
+    #[test]
+    fn test_batch_reader_zstd_invalid() {
+        let data = [BatchReader::CHANNEL_VERSION_ZSTD, 0xde, 0xad, 0xbe, 0xef];
+        let mut reader = BatchReader::new(data, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize);
+        assert!(matches!(
+            reader.decompress(),
+            Err(DecompressionError::ZstdError(ZstdDecompressionError::InvalidFrame))
+        ));
+        assert!(reader.zstd_used);
+    }
 }
@@ -0,0 +1,250 @@
+//! Contains the [`BatchValidityReason`], which explains a [`BatchValidity`].
+
+use crate::BatchValidity;
+use derive_more::Display;
+
+/// The reason behind the [`BatchValidity`] of a batch.
+///
+/// Returned by [`SingleBatch::explain_batch`], [`SpanBatch::explain_batch`] and
+/// [`BatchWithInclusionBlock::explain_batch`], and mapped to the [`BatchValidity`] returned by the
+/// respective `check_batch` method through [`BatchValidityReason::validity`].
+///
+/// [`SingleBatch::explain_batch`]: crate::SingleBatch::explain_batch
+/// [`SpanBatch::explain_batch`]: crate::SpanBatch::explain_batch
+/// [`BatchWithInclusionBlock::explain_batch`]: crate::BatchWithInclusionBlock::explain_batch
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "reason", rename_all = "snake_case"))]
+pub enum BatchValidityReason {
+    /// The batch is valid.
+    #[display("batch is valid")]
+    Valid,
+    /// No L1 blocks were supplied to validate the batch against.
+    #[display("missing L1 block input")]
+    MissingL1Blocks,
+    /// The span batch contains no batches.
+    #[display("empty span batch")]
+    EmptySpanBatch,
+    /// The next L1 origin is required to validate the batch, but was not supplied.
+    #[display("missing the next L1 origin")]
+    MissingNextL1Origin,
+    /// The L1 block of the span batch's final epoch was not supplied.
+    #[display("missing the L1 origin of epoch {epoch}")]
+    MissingL1Origin {
+        /// The final epoch of the span batch.
+        epoch: u64,
+    },
+    /// An L2 block required to validate the batch could not be fetched.
+    #[display("failed to fetch L2 block {number}")]
+    L2BlockUnavailable {
+        /// The number of the L2 block.
+        number: u64,
+    },
+    /// The batch timestamp is ahead of the next L2 timestamp.
+    #[display("batch timestamp {timestamp} is ahead of the next L2 timestamp {next_timestamp}")]
+    FutureTimestamp {
+        /// The timestamp of the batch.
+        timestamp: u64,
+        /// The timestamp of the next L2 block.
+        next_timestamp: u64,
+        /// Whether Holocene was active at the batch's inclusion block, in which case the batch is
+        /// dropped rather than kept for future processing.
+        holocene: bool,
+    },
+    /// The batch has no blocks after the L2 safe head.
+    #[display("batch timestamp {timestamp} is older than the next L2 timestamp {next_timestamp}")]
+    PastTimestamp {
+        /// The final timestamp of the batch.
+        timestamp: u64,
+        /// The timestamp of the next L2 block.
+        next_timestamp: u64,
+        /// Whether Holocene was active at the batch's inclusion block, in which case the batch is
+        /// outdated rather than dropped.
+        holocene: bool,
+    },
+    /// The span batch overlaps the L2 safe chain, but is not aligned with its blocks.
+    #[display("batch timestamp {timestamp} is misaligned with the L2 safe chain")]
+    MisalignedTimestamp {
+        /// The starting timestamp of the span batch.
+        timestamp: u64,
+    },
+    /// The batch does not build on the expected parent block.
+    #[display("parent hash does not match L2 block {parent}")]
+    ParentHashMismatch {
+        /// The number of the expected parent block.
+        parent: u64,
+    },
+    /// The batch was included after the sequencing window of its epoch expired.
+    #[display("batch for epoch {epoch} was included too late, at L1 block {inclusion_block}")]
+    SequenceWindowExpired {
+        /// The epoch of the batch.
+        epoch: u64,
+        /// The number of the batch's inclusion block.
+        inclusion_block: u64,
+    },
+    /// The batch epoch is older than the L1 origin of its parent block.
+    #[display("batch epoch {epoch} is older than the minimum epoch {minimum}")]
+    EpochTooOld {
+        /// The epoch of the batch.
+        epoch: u64,
+        /// The minimum allowed epoch.
+        minimum: u64,
+    },
+    /// The batch epoch is ahead of the next epoch.
+    #[display("batch epoch {epoch} is ahead of the maximum epoch {maximum}")]
+    EpochTooFarAhead {
+        /// The epoch of the batch.
+        epoch: u64,
+        /// The maximum allowed epoch.
+        maximum: u64,
+    },
+    /// The batch epoch hash does not match the hash of the L1 origin.
+    #[display("batch epoch hash does not match L1 block {epoch}")]
+    EpochHashMismatch {
+        /// The epoch of the batch.
+        epoch: u64,
+    },
+    /// The span batch's L1 origin is before the Delta hardfork.
+    #[display("span batch L1 origin is before the Delta hardfork")]
+    SpanBatchBeforeDelta,
+    /// The L2 block timestamp is before the timestamp of its L1 origin.
+    #[display("block timestamp {timestamp} is before the L1 origin timestamp {l1_timestamp}")]
+    TimestampBeforeL1Origin {
+        /// The timestamp of the L2 block.
+        timestamp: u64,
+        /// The timestamp of the L1 origin.
+        l1_timestamp: u64,
+    },
+    /// The maximum timestamp allowed by the sequencer drift overflows.
+    #[display("sequencer drift overflows")]
+    SequencerDriftOverflow,
+    /// The L2 block contains transactions, but exceeds the sequencer drift.
+    #[display("block timestamp {timestamp} exceeds the sequencer drift, max {max}")]
+    SequencerDriftExceeded {
+        /// The timestamp of the L2 block.
+        timestamp: u64,
+        /// The maximum timestamp allowed by the sequencer drift.
+        max: u64,
+    },
+    /// The empty L2 block exceeds the sequencer drift, but could have adopted the next L1 origin.
+    #[display("empty block at timestamp {timestamp} could have adopted the next L1 origin")]
+    NextL1OriginNotAdopted {
+        /// The timestamp of the L2 block.
+        timestamp: u64,
+    },
+    /// The first block of the Interop hardfork contains user transactions.
+    #[display("interop transition block contains user transactions")]
+    NonEmptyInteropTransition,
+    /// The L2 block contains an empty transaction.
+    #[display("transaction {index} is empty")]
+    EmptyTransaction {
+        /// The index of the transaction in the L2 block.
+        index: usize,
+    },
+    /// The L2 block contains a deposit transaction.
+    #[display("transaction {index} is a deposit")]
+    DepositTransaction {
+        /// The index of the transaction in the L2 block.
+        index: usize,
+    },
+    /// The L2 block contains an EIP-7702 transaction before the Isthmus hardfork.
+    #[display("transaction {index} is an EIP-7702 transaction before Isthmus")]
+    Eip7702BeforeIsthmus {
+        /// The index of the transaction in the L2 block.
+        index: usize,
+    },
+    /// The number of transactions in an overlapping L2 block does not match the safe chain.
+    #[display("overlapping L2 block {block} has {batch} transactions, expected {safe}")]
+    OverlapTransactionCountMismatch {
+        /// The number of the overlapping L2 block.
+        block: u64,
+        /// The number of non-deposit transactions in the safe L2 block.
+        safe: usize,
+        /// The number of transactions in the batch.
+        batch: usize,
+    },
+    /// A transaction in an overlapping L2 block does not match the safe chain.
+    #[display("transaction {index} of overlapping L2 block {block} does not match")]
+    OverlapTransactionMismatch {
+        /// The number of the overlapping L2 block.
+        block: u64,
+        /// The index of the transaction in the batch.
+        index: usize,
+    },
+    /// The safe L2 block that the batch overlaps could not be converted into an L2 block info.
+    #[display("overlapping L2 block {block} is invalid")]
+    OverlapInvalidBlock {
+        /// The number of the overlapping L2 block.
+        block: u64,
+    },
+    /// The L1 origin of an overlapping L2 block does not match the safe chain.
+    #[display("overlapping L2 block {block} has epoch {batch}, expected {safe}")]
+    OverlapL1OriginMismatch {
+        /// The number of the overlapping L2 block.
+        block: u64,
+        /// The L1 origin number of the safe L2 block.
+        safe: u64,
+        /// The epoch of the batch.
+        batch: u64,
+    },
+}
+
+impl BatchValidityReason {
+    /// Returns the [`BatchValidity`] that the reason results in.
+    pub const fn validity(&self) -> BatchValidity {
+        match self {
+            Self::Valid => BatchValidity::Accept,
+            Self::MissingL1Blocks |
+            Self::EmptySpanBatch |
+            Self::MissingNextL1Origin |
+            Self::MissingL1Origin { .. } |
+            Self::L2BlockUnavailable { .. } => BatchValidity::Undecided,
+            Self::FutureTimestamp { holocene: false, .. } => BatchValidity::Future,
+            Self::PastTimestamp { holocene: true, .. } => BatchValidity::Past,
+            _ => BatchValidity::Drop,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reason_validity() {
+        assert_eq!(BatchValidityReason::Valid.validity(), BatchValidity::Accept);
+        assert_eq!(BatchValidityReason::MissingNextL1Origin.validity(), BatchValidity::Undecided);
+        assert_eq!(
+            BatchValidityReason::DepositTransaction { index: 0 }.validity(),
+            BatchValidity::Drop
+        );
+    }
+
+    #[test]
+    fn test_reason_validity_holocene() {
+        let future = |holocene| BatchValidityReason::FutureTimestamp {
+            timestamp: 4,
+            next_timestamp: 2,
+            holocene,
+        };
+        assert_eq!(future(false).validity(), BatchValidity::Future);
+        assert_eq!(future(true).validity(), BatchValidity::Drop);
+
+        let past = |holocene| BatchValidityReason::PastTimestamp {
+            timestamp: 0,
+            next_timestamp: 2,
+            holocene,
+        };
+        assert_eq!(past(false).validity(), BatchValidity::Drop);
+        assert_eq!(past(true).validity(), BatchValidity::Past);
+    }
+
+    #[test]
+    fn test_reason_display() {
+        let reason = BatchValidityReason::SequencerDriftExceeded { timestamp: 10, max: 8 };
+        assert_eq!(
+            alloc::format!("{reason}"),
+            "block timestamp 10 exceeds the sequencer drift, max 8"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! This module contains the [`SingleBatch`] type.
 
-use crate::{BatchValidity, BlockInfo, L2BlockInfo};
+use crate::{BatchValidity, BatchValidityReason, BlockInfo, L2BlockInfo};
 use alloc::vec::Vec;
 use alloy_eips::BlockNumHash;
 use alloy_primitives::{BlockHash, Bytes};
@@ -43,20 +43,33 @@ impl SingleBatch {
         l2_safe_head: L2BlockInfo,
         inclusion_block: &BlockInfo,
     ) -> BatchValidity {
+        self.explain_batch_timestamp(cfg, l2_safe_head, inclusion_block).validity()
+    }
+
+    /// Validate the batch timestamp, returning the [`BatchValidityReason`] behind the result.
+    pub fn explain_batch_timestamp(
+        &self,
+        cfg: &RollupConfig,
+        l2_safe_head: L2BlockInfo,
+        inclusion_block: &BlockInfo,
+    ) -> BatchValidityReason {
         let next_timestamp = l2_safe_head.block_info.timestamp + cfg.block_time;
+        let holocene = cfg.is_holocene_active(inclusion_block.timestamp);
         if self.timestamp > next_timestamp {
-            if cfg.is_holocene_active(inclusion_block.timestamp) {
-                return BatchValidity::Drop;
-            }
-            return BatchValidity::Future;
+            return BatchValidityReason::FutureTimestamp {
+                timestamp: self.timestamp,
+                next_timestamp,
+                holocene,
+            };
         }
         if self.timestamp < next_timestamp {
-            if cfg.is_holocene_active(inclusion_block.timestamp) {
-                return BatchValidity::Past;
-            }
-            return BatchValidity::Drop;
+            return BatchValidityReason::PastTimestamp {
+                timestamp: self.timestamp,
+                next_timestamp,
+                holocene,
+            };
         }
-        BatchValidity::Accept
+        BatchValidityReason::Valid
     }
 
     /// Checks if the batch is valid.
@@ -71,34 +84,55 @@ impl SingleBatch {
         l2_safe_head: L2BlockInfo,
         inclusion_block: &BlockInfo,
     ) -> BatchValidity {
+        self.explain_batch(cfg, l1_blocks, l2_safe_head, inclusion_block).validity()
+    }
+
+    /// Checks if the batch is valid, returning the [`BatchValidityReason`] behind the result.
+    ///
+    /// See [`SingleBatch::check_batch`].
+    pub fn explain_batch(
+        &self,
+        cfg: &RollupConfig,
+        l1_blocks: &[BlockInfo],
+        l2_safe_head: L2BlockInfo,
+        inclusion_block: &BlockInfo,
+    ) -> BatchValidityReason {
         // Cannot have empty l1_blocks for batch validation.
         if l1_blocks.is_empty() {
-            return BatchValidity::Undecided;
+            return BatchValidityReason::MissingL1Blocks;
         }
 
         let epoch = l1_blocks[0];
 
         // If the batch is not accepted by the timestamp check, return the result.
-        let timestamp_check = self.check_batch_timestamp(cfg, l2_safe_head, inclusion_block);
-        if !timestamp_check.is_accept() {
+        let timestamp_check = self.explain_batch_timestamp(cfg, l2_safe_head, inclusion_block);
+        if timestamp_check != BatchValidityReason::Valid {
             return timestamp_check;
         }
 
         // Dependent on the above timestamp check.
         // If the timestamp is correct, then it must build on top of the safe head.
         if self.parent_hash != l2_safe_head.block_info.hash {
-            return BatchValidity::Drop;
+            return BatchValidityReason::ParentHashMismatch {
+                parent: l2_safe_head.block_info.number,
+            };
         }
 
         // Filter out batches that were included too late.
         if self.epoch_num + cfg.seq_window_size < inclusion_block.number {
-            return BatchValidity::Drop;
+            return BatchValidityReason::SequenceWindowExpired {
+                epoch: self.epoch_num,
+                inclusion_block: inclusion_block.number,
+            };
         }
 
         // Check the L1 origin of the batch
         let mut batch_origin = epoch;
         if self.epoch_num < epoch.number {
-            return BatchValidity::Drop;
+            return BatchValidityReason::EpochTooOld {
+                epoch: self.epoch_num,
+                minimum: epoch.number,
+            };
         } else if self.epoch_num == epoch.number {
             // Batch is sticking to the current epoch, continue.
         } else if self.epoch_num == epoch.number + 1 {
@@ -108,20 +142,26 @@ impl SingleBatch {
             // more information otherwise the eager algorithm may diverge from a non-eager
             // algorithm.
             if l1_blocks.len() < 2 {
-                return BatchValidity::Undecided;
+                return BatchValidityReason::MissingNextL1Origin;
             }
             batch_origin = l1_blocks[1];
         } else {
-            return BatchValidity::Drop;
+            return BatchValidityReason::EpochTooFarAhead {
+                epoch: self.epoch_num,
+                maximum: epoch.number + 1,
+            };
         }
 
         // Validate the batch epoch hash
         if self.epoch_hash != batch_origin.hash {
-            return BatchValidity::Drop;
+            return BatchValidityReason::EpochHashMismatch { epoch: self.epoch_num };
         }
 
         if self.timestamp < batch_origin.timestamp {
-            return BatchValidity::Drop;
+            return BatchValidityReason::TimestampBeforeL1Origin {
+                timestamp: self.timestamp,
+                l1_timestamp: batch_origin.timestamp,
+            };
         }
 
         // Check if we ran out of sequencer time drift
@@ -129,7 +169,7 @@ impl SingleBatch {
         let max = if let Some(max) = batch_origin.timestamp.checked_add(max_drift) {
             max
         } else {
-            return BatchValidity::Drop;
+            return BatchValidityReason::SequencerDriftOverflow;
         };
 
         let no_txs = self.transactions.is_empty();
@@ -137,7 +177,7 @@ impl SingleBatch {
             // If the sequencer is ignoring the time drift rule, then drop the batch and force an
             // empty batch instead, as the sequencer is not allowed to include anything
             // past this point without moving to the next epoch.
-            return BatchValidity::Drop;
+            return BatchValidityReason::SequencerDriftExceeded { timestamp: self.timestamp, max };
         }
         if self.timestamp > max && no_txs {
             // If the sequencer is co-operating by producing an empty batch,
@@ -146,12 +186,14 @@ impl SingleBatch {
             // epoch advancement regardless of time drift is allowed.
             if epoch.number == batch_origin.number {
                 if l1_blocks.len() < 2 {
-                    return BatchValidity::Undecided;
+                    return BatchValidityReason::MissingNextL1Origin;
                 }
                 let next_origin = l1_blocks[1];
                 // Check if the next L1 Origin could have been adopted
                 if self.timestamp >= next_origin.timestamp {
-                    return BatchValidity::Drop;
+                    return BatchValidityReason::NextL1OriginNotAdopted {
+                        timestamp: self.timestamp,
+                    };
                 }
             }
         }
@@ -163,26 +205,26 @@ impl SingleBatch {
                 target: "single_batch",
                 "Sequencer included user transactions in interop transition block. Dropping batch."
             );
-            return BatchValidity::Drop;
+            return BatchValidityReason::NonEmptyInteropTransition;
         }
 
         // We can do this check earlier, but it's intensive so we do it last for the sad-path.
-        for tx in self.transactions.iter() {
+        for (index, tx) in self.transactions.iter().enumerate() {
             if tx.is_empty() {
-                return BatchValidity::Drop;
+                return BatchValidityReason::EmptyTransaction { index };
             }
             if tx.as_ref().first() == Some(&(OpTxType::Deposit as u8)) {
-                return BatchValidity::Drop;
+                return BatchValidityReason::DepositTransaction { index };
             }
             // If isthmus is not active yet and the transaction is a 7702, drop the batch.
             if !cfg.is_isthmus_active(self.timestamp) &&
                 tx.as_ref().first() == Some(&(OpTxType::Eip7702 as u8))
             {
-                return BatchValidity::Drop;
+                return BatchValidityReason::Eip7702BeforeIsthmus { index };
             }
         }
 
-        BatchValidity::Accept
+        BatchValidityReason::Valid
     }
 }
 
@@ -227,6 +269,14 @@ mod tests {
             batch.check_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block),
             BatchValidity::Future
         );
+        assert_eq!(
+            batch.explain_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block),
+            BatchValidityReason::FutureTimestamp {
+                timestamp: 2,
+                next_timestamp: 1 + cfg.block_time,
+                holocene: false
+            }
+        );
     }
 
     #[test]
@@ -243,6 +293,10 @@ mod tests {
             batch.check_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block),
             BatchValidity::Drop
         );
+        assert_eq!(
+            batch.explain_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block),
+            BatchValidityReason::ParentHashMismatch { parent: 0 }
+        );
     }
 
     #[test]
@@ -594,6 +648,10 @@ mod tests {
             single_batch.check_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block),
             BatchValidity::Drop
         );
+        assert_eq!(
+            single_batch.explain_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block),
+            BatchValidityReason::DepositTransaction { index: single_batch.transactions.len() - 1 }
+        );
     }
 
     #[test]
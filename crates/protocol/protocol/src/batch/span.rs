@@ -13,9 +13,9 @@ use op_alloy_consensus::OpTxType;
 use tracing::{info, warn};
 
 use crate::{
-    BatchValidationProvider, BatchValidity, BlockInfo, L2BlockInfo, RawSpanBatch, SingleBatch,
-    SpanBatchBits, SpanBatchElement, SpanBatchError, SpanBatchPayload, SpanBatchPrefix,
-    SpanBatchTransactions,
+    BatchValidationProvider, BatchValidity, BatchValidityReason, BlockInfo, L2BlockInfo,
+    RawSpanBatch, SingleBatch, SpanBatchBits, SpanBatchElement, SpanBatchError, SpanBatchPayload,
+    SpanBatchPrefix, SpanBatchTransactions,
 };
 
 /// Container for the inputs required to build a span of L2 blocks in derived form.
@@ -378,10 +378,24 @@ impl SpanBatch {
         inclusion_block: &BlockInfo,
         fetcher: &mut BV,
     ) -> BatchValidity {
-        let (prefix_validity, parent_block) =
-            self.check_batch_prefix(cfg, l1_blocks, l2_safe_head, inclusion_block, fetcher).await;
-        if !matches!(prefix_validity, BatchValidity::Accept) {
-            return prefix_validity;
+        self.explain_batch(cfg, l1_blocks, l2_safe_head, inclusion_block, fetcher).await.validity()
+    }
+
+    /// Checks if the span batch is valid, returning the [`BatchValidityReason`] behind the result.
+    ///
+    /// See [`SpanBatch::check_batch`].
+    pub async fn explain_batch<BV: BatchValidationProvider>(
+        &self,
+        cfg: &RollupConfig,
+        l1_blocks: &[BlockInfo],
+        l2_safe_head: L2BlockInfo,
+        inclusion_block: &BlockInfo,
+        fetcher: &mut BV,
+    ) -> BatchValidityReason {
+        let (prefix_reason, parent_block) =
+            self.explain_batch_prefix(cfg, l1_blocks, l2_safe_head, inclusion_block, fetcher).await;
+        if prefix_reason != BatchValidityReason::Valid {
+            return prefix_reason;
         }
 
         let starting_epoch_num = self.starting_epoch_num();
@@ -416,7 +430,10 @@ impl SpanBatch {
                     l1_origin.timestamp,
                     l1_origin.id()
                 );
-                return BatchValidity::Drop;
+                return BatchValidityReason::TimestampBeforeL1Origin {
+                    timestamp: block_timestamp,
+                    l1_timestamp: l1_origin.timestamp,
+                };
             }
 
             // Check if we ran out of sequencer time drift
@@ -434,7 +451,7 @@ impl SpanBatch {
                                 target: "batch_span",
                                 "without the next L1 origin we cannot determine yet if this empty batch that exceeds the time drift is still valid"
                             );
-                            return BatchValidity::Undecided;
+                            return BatchValidityReason::MissingNextL1Origin;
                         }
                         if block_timestamp >= l1_blocks[origin_index + 1].timestamp {
                             // check if the next L1 origin could have been adopted
@@ -442,7 +459,9 @@ impl SpanBatch {
                                 target: "batch_span",
                                 "batch exceeded sequencer time drift without adopting next origin, and next L1 origin would have been valid"
                             );
-                            return BatchValidity::Drop;
+                            return BatchValidityReason::NextL1OriginNotAdopted {
+                                timestamp: block_timestamp,
+                            };
                         } else {
                             info!(
                                 target: "batch_span",
@@ -459,7 +478,10 @@ impl SpanBatch {
                         "batch exceeded sequencer time drift, sequencer must adopt new L1 origin to include transactions again, max_time: {}",
                         l1_origin.timestamp + max_drift
                     );
-                    return BatchValidity::Drop;
+                    return BatchValidityReason::SequencerDriftExceeded {
+                        timestamp: block_timestamp,
+                        max: l1_origin.timestamp + max_drift,
+                    };
                 }
             }
 
@@ -471,7 +493,7 @@ impl SpanBatch {
                         "transaction data must not be empty, but found empty tx, tx_index: {}",
                         i
                     );
-                    return BatchValidity::Drop;
+                    return BatchValidityReason::EmptyTransaction { index: i };
                 }
                 if tx.as_ref().first() == Some(&(OpTxType::Deposit as u8)) {
                     warn!(
@@ -479,7 +501,7 @@ impl SpanBatch {
                         "sequencers may not embed any deposits into batch data, but found tx that has one, tx_index: {}",
                         i
                     );
-                    return BatchValidity::Drop;
+                    return BatchValidityReason::DepositTransaction { index: i };
                 }
 
                 // If isthmus is not active yet and the transaction is a 7702, drop the batch.
@@ -487,7 +509,7 @@ impl SpanBatch {
                     tx.as_ref().first() == Some(&(OpTxType::Eip7702 as u8))
                 {
                     warn!(target: "batch_span", "EIP-7702 transactions are not supported pre-isthmus. tx_index: {}", i);
-                    return BatchValidity::Drop;
+                    return BatchValidityReason::Eip7702BeforeIsthmus { index: i };
                 }
             }
         }
@@ -502,7 +524,7 @@ impl SpanBatch {
                     Ok(p) => p,
                     Err(e) => {
                         warn!(target: "batch_span", "failed to fetch block number {safe_block_num}: {e}");
-                        return BatchValidity::Undecided;
+                        return BatchValidityReason::L2BlockUnavailable { number: safe_block_num };
                     }
                 };
                 let safe_block = &safe_block_payload.body;
@@ -520,7 +542,11 @@ impl SpanBatch {
                         safe_block.transactions.len(),
                         batch_txs.len()
                     );
-                    return BatchValidity::Drop;
+                    return BatchValidityReason::OverlapTransactionCountMismatch {
+                        block: safe_block_num,
+                        safe: safe_block.transactions.len() - deposit_count,
+                        batch: batch_txs.len(),
+                    };
                 }
                 let batch_txs_len = batch_txs.len();
                 #[allow(clippy::needless_range_loop)]
@@ -529,7 +555,10 @@ impl SpanBatch {
                     safe_block.transactions[j + deposit_count].encode_2718(&mut buf);
                     if buf != batch_txs[j].0 {
                         warn!(target: "batch_span", "overlapped block's transaction does not match");
-                        return BatchValidity::Drop;
+                        return BatchValidityReason::OverlapTransactionMismatch {
+                            block: safe_block_num,
+                            index: j,
+                        };
                     }
                 }
                 let safe_block_ref = match L2BlockInfo::from_block_and_genesis(
@@ -543,7 +572,7 @@ impl SpanBatch {
                             "failed to extract L2BlockInfo from execution payload, hash: {}, err: {e}",
                             safe_block_payload.header.hash_slow()
                         );
-                        return BatchValidity::Drop;
+                        return BatchValidityReason::OverlapInvalidBlock { block: safe_block_num };
                     }
                 };
                 if safe_block_ref.l1_origin.number != self.batches[i as usize].epoch_num {
@@ -551,12 +580,16 @@ impl SpanBatch {
                         "overlapped block's L1 origin number does not match {}, {}",
                         safe_block_ref.l1_origin.number, self.batches[i as usize].epoch_num
                     );
-                    return BatchValidity::Drop;
+                    return BatchValidityReason::OverlapL1OriginMismatch {
+                        block: safe_block_num,
+                        safe: safe_block_ref.l1_origin.number,
+                        batch: self.batches[i as usize].epoch_num,
+                    };
                 }
             }
         }
 
-        BatchValidity::Accept
+        BatchValidityReason::Valid
     }
 
     /// Checks the validity of the batch's prefix.
@@ -571,13 +604,31 @@ impl SpanBatch {
         inclusion_block: &BlockInfo,
         fetcher: &mut BF,
     ) -> (BatchValidity, Option<L2BlockInfo>) {
+        let (reason, parent_block) = self
+            .explain_batch_prefix(cfg, l1_origins, l2_safe_head, inclusion_block, fetcher)
+            .await;
+        (reason.validity(), parent_block)
+    }
+
+    /// Checks the validity of the batch's prefix, returning the [`BatchValidityReason`] behind the
+    /// result.
+    ///
+    /// See [`SpanBatch::check_batch_prefix`].
+    pub async fn explain_batch_prefix<BF: BatchValidationProvider>(
+        &self,
+        cfg: &RollupConfig,
+        l1_origins: &[BlockInfo],
+        l2_safe_head: L2BlockInfo,
+        inclusion_block: &BlockInfo,
+        fetcher: &mut BF,
+    ) -> (BatchValidityReason, Option<L2BlockInfo>) {
         if l1_origins.is_empty() {
             warn!(target: "batch_span", "missing L1 block input, cannot proceed with batch checking");
-            return (BatchValidity::Undecided, None);
+            return (BatchValidityReason::MissingL1Blocks, None);
         }
         if self.batches.is_empty() {
             warn!(target: "batch_span", "empty span batch, cannot proceed with batch checking");
-            return (BatchValidity::Undecided, None);
+            return (BatchValidityReason::EmptySpanBatch, None);
         }
 
         let epoch = l1_origins[0];
@@ -592,7 +643,7 @@ impl SpanBatch {
                     "eager batch wants to advance current epoch {:?}, but could not without more L1 blocks",
                     epoch.id()
                 );
-                return (BatchValidity::Undecided, None);
+                return (BatchValidityReason::MissingNextL1Origin, None);
             }
             batch_origin = l1_origins[1];
         }
@@ -603,7 +654,7 @@ impl SpanBatch {
                 batch_origin.id(),
                 batch_origin.timestamp
             );
-            return (BatchValidity::Drop, None);
+            return (BatchValidityReason::SpanBatchBeforeDelta, None);
         }
 
         if self.starting_timestamp() > next_timestamp {
@@ -615,20 +666,23 @@ impl SpanBatch {
             );
 
             // After holocene is activated, gaps are disallowed.
-            if cfg.is_holocene_active(inclusion_block.timestamp) {
-                return (BatchValidity::Drop, None);
-            }
-            return (BatchValidity::Future, None);
+            let reason = BatchValidityReason::FutureTimestamp {
+                timestamp: self.starting_timestamp(),
+                next_timestamp,
+                holocene: cfg.is_holocene_active(inclusion_block.timestamp),
+            };
+            return (reason, None);
         }
 
         // Drop the batch if it has no new blocks after the safe head.
         if self.final_timestamp() < next_timestamp {
             warn!(target: "batch_span", "span batch has no new blocks after safe head");
-            return if cfg.is_holocene_active(inclusion_block.timestamp) {
-                (BatchValidity::Past, None)
-            } else {
-                (BatchValidity::Drop, None)
+            let reason = BatchValidityReason::PastTimestamp {
+                timestamp: self.final_timestamp(),
+                next_timestamp,
+                holocene: cfg.is_holocene_active(inclusion_block.timestamp),
             };
+            return (reason, None);
         }
 
         // Find the parent block of the span batch.
@@ -640,12 +694,18 @@ impl SpanBatch {
             if self.starting_timestamp() > l2_safe_head.block_info.timestamp {
                 // Batch timestamp cannot be between safe head and next timestamp.
                 warn!(target: "batch_span", "batch has misaligned timestamp, block time is too short");
-                return (BatchValidity::Drop, None);
+                let reason = BatchValidityReason::MisalignedTimestamp {
+                    timestamp: self.starting_timestamp(),
+                };
+                return (reason, None);
             }
             if (l2_safe_head.block_info.timestamp - self.starting_timestamp()) % cfg.block_time != 0
             {
                 warn!(target: "batch_span", "batch has misaligned timestamp, not overlapped exactly");
-                return (BatchValidity::Drop, None);
+                let reason = BatchValidityReason::MisalignedTimestamp {
+                    timestamp: self.starting_timestamp(),
+                };
+                return (reason, None);
             }
             parent_num = l2_safe_head.block_info.number -
                 (l2_safe_head.block_info.timestamp - self.starting_timestamp()) / cfg.block_time -
@@ -655,7 +715,7 @@ impl SpanBatch {
                 Err(e) => {
                     warn!(target: "batch_span", "failed to fetch L2 block number {parent_num}: {e}");
                     // Unable to validate the batch for now. Retry later.
-                    return (BatchValidity::Undecided, None);
+                    return (BatchValidityReason::L2BlockUnavailable { number: parent_num }, None);
                 }
             };
         }
@@ -665,13 +725,17 @@ impl SpanBatch {
                 "parent block mismatch, expected: {parent_num}, received: {}. parent hash: {}, parent hash check: {}",
                 parent_block.block_info.number, parent_block.block_info.hash, self.parent_check,
             );
-            return (BatchValidity::Drop, None);
+            return (BatchValidityReason::ParentHashMismatch { parent: parent_num }, None);
         }
 
         // Filter out batches that were included too late.
         if starting_epoch_num + cfg.seq_window_size < inclusion_block.number {
             warn!(target: "batch_span", "batch was included too late, sequence window expired");
-            return (BatchValidity::Drop, None);
+            let reason = BatchValidityReason::SequenceWindowExpired {
+                epoch: starting_epoch_num,
+                inclusion_block: inclusion_block.number,
+            };
+            return (reason, None);
         }
 
         // Check the L1 origin of the batch
@@ -682,7 +746,11 @@ impl SpanBatch {
                 starting_epoch_num,
                 parent_block.l1_origin.number + 1
             );
-            return (BatchValidity::Drop, None);
+            let reason = BatchValidityReason::EpochTooFarAhead {
+                epoch: starting_epoch_num,
+                maximum: parent_block.l1_origin.number + 1,
+            };
+            return (reason, None);
         }
 
         // Verify the l1 origin hash for each l1 block.
@@ -698,7 +766,7 @@ impl SpanBatch {
                         "batch is for different L1 chain, epoch hash does not match, expected: {}",
                         l1_block.hash
                     );
-                    return (BatchValidity::Drop, None);
+                    return (BatchValidityReason::EpochHashMismatch { epoch: end_epoch_num }, None);
                 }
                 origin_checked = true;
                 break;
@@ -706,15 +774,19 @@ impl SpanBatch {
         }
         if !origin_checked {
             info!(target: "batch_span", "need more l1 blocks to check entire origins of span batch");
-            return (BatchValidity::Undecided, None);
+            return (BatchValidityReason::MissingL1Origin { epoch: end_epoch_num }, None);
         }
 
         if starting_epoch_num < parent_block.l1_origin.number {
             warn!(target: "batch_span", "dropped batch, epoch is too old, minimum: {:?}", parent_block.block_info.id());
-            return (BatchValidity::Drop, None);
+            let reason = BatchValidityReason::EpochTooOld {
+                epoch: starting_epoch_num,
+                minimum: parent_block.l1_origin.number,
+            };
+            return (reason, None);
         }
 
-        (BatchValidity::Accept, Some(parent_block))
+        (BatchValidityReason::Valid, Some(parent_block))
     }
 }
 
@@ -974,6 +1046,16 @@ mod tests {
         assert!(logs[0].contains(
             "received out-of-order batch for future processing after next batch (21 > 20)"
         ));
+        assert_eq!(
+            batch
+                .explain_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block, &mut fetcher)
+                .await,
+            BatchValidityReason::FutureTimestamp {
+                timestamp: 21,
+                next_timestamp: 20,
+                holocene: false
+            }
+        );
     }
 
     #[tokio::test]
@@ -1004,6 +1086,16 @@ mod tests {
         let logs = trace_store.get_by_level(Level::WARN);
         assert_eq!(logs.len(), 1);
         assert!(logs[0].contains("span batch has no new blocks after safe head"));
+        assert_eq!(
+            batch
+                .explain_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block, &mut fetcher)
+                .await,
+            BatchValidityReason::PastTimestamp {
+                timestamp: 10,
+                next_timestamp: 20,
+                holocene: false
+            }
+        );
     }
 
     #[tokio::test]
@@ -1073,6 +1165,12 @@ mod tests {
         assert!(logs[0].contains(
             "overlapped block's tx count does not match, safe_block_txs: 0, batch_txs: 1"
         ));
+        assert_eq!(
+            batch
+                .explain_batch(&cfg, &l1_blocks, l2_safe_head, &inclusion_block, &mut fetcher)
+                .await,
+            BatchValidityReason::OverlapTransactionCountMismatch { block: 9, safe: 0, batch: 1 }
+        );
     }
 
     #[tokio::test]
@@ -146,6 +146,23 @@ impl Channel {
         self.open_block.number
     }
 
+    /// Returns the L1 block that contained the first [`Frame`] in this channel.
+    pub const fn open_block(&self) -> BlockInfo {
+        self.open_block
+    }
+
+    /// Returns the highest L1 block that a [`Frame`] in this channel was included in.
+    pub const fn highest_l1_inclusion_block(&self) -> BlockInfo {
+        self.highest_l1_inclusion_block
+    }
+
+    /// Returns the [`Frame`]s ingested by the channel, ordered by frame number.
+    pub fn frames(&self) -> Vec<&Frame> {
+        let mut frames = self.inputs.values().collect::<Vec<_>>();
+        frames.sort_by_key(|frame| frame.number);
+        frames
+    }
+
     /// Returns the estimated size of the channel including [`Frame`] overhead.
     pub const fn size(&self) -> usize {
         self.estimated_size
@@ -253,6 +270,25 @@ mod test {
         assert!(!channel.is_ready());
     }
 
+    #[test]
+    fn test_channel_frames() {
+        let id = [0xFF; 16];
+        let open_block = BlockInfo { number: 42, ..Default::default() };
+        let inclusion_block = BlockInfo { number: 43, ..Default::default() };
+        let mut channel = Channel::new(id, open_block);
+
+        let frames = [
+            Frame { id, number: 1, is_last: true, data: b"four".to_vec() },
+            Frame { id, number: 0, is_last: false, data: b"seven".to_vec() },
+        ];
+        channel.add_frame(frames[0].clone(), open_block).unwrap();
+        channel.add_frame(frames[1].clone(), inclusion_block).unwrap();
+
+        assert_eq!(channel.open_block(), open_block);
+        assert_eq!(channel.highest_l1_inclusion_block(), inclusion_block);
+        assert_eq!(channel.frames(), vec![&frames[1], &frames[0]]);
+    }
+
     #[test]
     fn test_frame_validity() {
         let id = [0xFF; 16];
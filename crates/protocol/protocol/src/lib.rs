@@ -12,17 +12,20 @@ extern crate alloc;
 mod batch;
 pub use batch::{
     Batch, BatchDecodingError, BatchEncodingError, BatchReader, BatchTransaction, BatchType,
-    BatchValidationProvider, BatchValidity, BatchWithInclusionBlock, DecompressionError,
-    MAX_SPAN_BATCH_ELEMENTS, RawSpanBatch, SINGLE_BATCH_TYPE, SPAN_BATCH_TYPE, SingleBatch,
-    SpanBatch, SpanBatchBits, SpanBatchEip1559TransactionData, SpanBatchEip2930TransactionData,
-    SpanBatchEip7702TransactionData, SpanBatchElement, SpanBatchError,
-    SpanBatchLegacyTransactionData, SpanBatchPayload, SpanBatchPrefix, SpanBatchTransactionData,
-    SpanBatchTransactions, SpanDecodingError,
+    BatchValidationProvider, BatchValidity, BatchValidityReason, BatchWithInclusionBlock,
+    DecompressionError, MAX_SPAN_BATCH_ELEMENTS, RawSpanBatch, SINGLE_BATCH_TYPE, SPAN_BATCH_TYPE,
+    SingleBatch, SpanBatch, SpanBatchBits, SpanBatchEip1559TransactionData,
+    SpanBatchEip2930TransactionData, SpanBatchEip7702TransactionData, SpanBatchElement,
+    SpanBatchError, SpanBatchLegacyTransactionData, SpanBatchPayload, SpanBatchPrefix,
+    SpanBatchTransactionData, SpanBatchTransactions, SpanDecodingError,
 };
 
 mod brotli;
 pub use brotli::{BrotliDecompressionError, decompress_brotli};
 
+mod zstd;
+pub use zstd::{ZstdDecompressionError, decompress_zstd};
+
 mod sync;
 pub use sync::SyncStatus;
 
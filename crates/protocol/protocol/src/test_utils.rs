@@ -13,6 +13,12 @@ use crate::{
     L2BlockInfo,
 };
 
+mod generator;
+pub use generator::{
+    ChannelGenerator, ExpectedBatch, GeneratedCase, GeneratedCaseKind, GeneratedChannel,
+    SYNTHETIC_SAFE_CHAIN_LEN,
+};
+
 /// Raw encoded bedrock L1 block info transaction.
 pub const RAW_BEDROCK_INFO_TX: [u8; L1BlockInfoBedrock::L1_INFO_TX_LEN] = hex!(
     "015d8eb9000000000000000000000000000000000000000000000000000000000117c4eb0000000000000000000000000000000000000000000000000000000065280377000000000000000000000000000000000000000000000000000000026d05d953392012032675be9f94aae5ab442de73c5f4fb1bf30fa7dd0d2442239899a40fc00000000000000000000000000000000000000000000000000000000000000040000000000000000000000006887246668a3b87f54deb3b94ba47a6f63f3298500000000000000000000000000000000000000000000000000000000000000bc00000000000000000000000000000000000000000000000000000000000a6fe0"
@@ -0,0 +1,747 @@
+//! A seeded generator for synthetic channels and batches.
+//!
+//! The [`ChannelGenerator`] builds [`GeneratedCase`]s on top of a synthetic L2 safe chain. Each
+//! case carries the frames to submit to the pipeline along with the derivation outcome that is
+//! expected for every channel and batch under the generator's [`RollupConfig`].
+
+use alloc::{vec, vec::Vec};
+use alloy_consensus::{BlockBody, Header, SignableTransaction, TxEip1559, TxEip7702};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::{Address, B256, Bytes, Sealed, Signature, TxKind, U256};
+use alloy_rlp::Encodable;
+use core::ops::Range;
+use kona_genesis::RollupConfig;
+use op_alloy_consensus::{OpBlock, OpTxEnvelope, TxDeposit};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    Batch, BatchValidity, BlockInfo, ChannelId, DERIVATION_VERSION_0, Frame, L1BlockInfoBedrock,
+    L2BlockInfo, SingleBatch, SpanBatch, test_utils::TestBatchValidator,
+};
+
+/// The number of L2 blocks in the synthetic safe chain that every case builds on.
+pub const SYNTHETIC_SAFE_CHAIN_LEN: usize = 8;
+
+/// The L1 block time of the synthetic L1 chain.
+const L1_BLOCK_TIME: u64 = 12;
+
+/// The maximum number of frames a generated channel is split into.
+const MAX_FRAMES_PER_CHANNEL: usize = 4;
+
+/// The kind of [`GeneratedCase`] to generate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratedCaseKind {
+    /// A single channel of batches that extend the safe head.
+    Valid,
+    /// A batch that skips the block after the safe head.
+    Gap,
+    /// A batch that starts within the safe chain and extends past the safe head.
+    Overlap,
+    /// A batch that only contains blocks of the safe chain.
+    Outdated,
+    /// A channel of batches that extend the safe head, with two of its frames swapped.
+    OutOfOrderFrames,
+    /// Two channels that extend the safe head, with their frames interleaved.
+    Interleaved,
+    /// A batch that extends the safe head, but contains an invalid transaction.
+    InvalidTransaction,
+}
+
+impl GeneratedCaseKind {
+    /// All [`GeneratedCaseKind`]s.
+    pub const ALL: [Self; 7] = [
+        Self::Valid,
+        Self::Gap,
+        Self::Overlap,
+        Self::Outdated,
+        Self::OutOfOrderFrames,
+        Self::Interleaved,
+        Self::InvalidTransaction,
+    ];
+}
+
+/// The expected outcome of validating a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpectedBatch {
+    /// The L2 safe head that the batch is validated against.
+    pub l2_safe_head: L2BlockInfo,
+    /// The expected [`BatchValidity`] of the batch.
+    pub validity: BatchValidity,
+}
+
+/// A channel of a [`GeneratedCase`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedChannel {
+    /// The ID of the channel.
+    pub id: ChannelId,
+    /// The frames of the channel, in the order they are submitted in.
+    pub frames: Vec<Frame>,
+    /// The batches encoded in the channel.
+    pub batches: Vec<Batch>,
+    /// Whether the channel is expected to be read by the pipeline.
+    pub read: bool,
+    /// The expected outcome of each batch, in order. Empty if the channel is not read.
+    pub expected: Vec<ExpectedBatch>,
+}
+
+/// A synthetic derivation case, generated by the [`ChannelGenerator`].
+///
+/// All frames of the case are submitted in a single batcher transaction within the
+/// `inclusion_block`. Channels that contain an invalid batch always contain exactly one batch, so
+/// the expectations hold both with and without the Holocene channel flushing rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedCase {
+    /// The kind of the case.
+    pub kind: GeneratedCaseKind,
+    /// The L1 origin of every L2 block in the case.
+    pub l1_origin: BlockInfo,
+    /// The L1 block that includes the frames of the case.
+    pub inclusion_block: BlockInfo,
+    /// The L2 safe head before the case is derived.
+    pub l2_safe_head: L2BlockInfo,
+    /// The expected L2 safe head after the case is derived.
+    pub expected_safe_head: L2BlockInfo,
+    /// The frames of the case, in submission order.
+    pub frames: Vec<Frame>,
+    /// The channels of the case, in the order they are opened.
+    pub channels: Vec<GeneratedChannel>,
+    /// The L2 blocks that the batches of the case refer to, including the safe chain.
+    pub l2_blocks: Vec<L2BlockInfo>,
+    /// The [`OpBlock`]s of the `l2_blocks`.
+    pub op_blocks: Vec<OpBlock>,
+}
+
+impl GeneratedCase {
+    /// Returns the batcher transaction data that carries the frames of the case.
+    pub fn batcher_data(&self) -> Bytes {
+        let mut data = vec![DERIVATION_VERSION_0];
+        for frame in &self.frames {
+            data.extend_from_slice(&frame.encode());
+        }
+        data.into()
+    }
+
+    /// Returns a [`TestBatchValidator`] that serves the L2 blocks of the case.
+    pub fn validation_provider(&self) -> TestBatchValidator {
+        TestBatchValidator::new(self.l2_blocks.clone(), self.op_blocks.clone())
+    }
+}
+
+/// The synthetic L2 chain that a [`GeneratedCase`] is built on.
+#[derive(Debug, Default)]
+struct SyntheticChain {
+    l1_origin: BlockInfo,
+    inclusion_block: BlockInfo,
+    blocks: Vec<L2BlockInfo>,
+    op_blocks: Vec<OpBlock>,
+    transactions: Vec<Vec<Bytes>>,
+}
+
+/// Generates synthetic [`GeneratedCase`]s from a seeded RNG.
+///
+/// Each case builds on a fresh synthetic safe chain of [`SYNTHETIC_SAFE_CHAIN_LEN`] blocks that
+/// starts at the generator's timestamp. The hardforks of the [`RollupConfig`] are evaluated the
+/// same way the batch checks evaluate them: Delta at the L1 origin, Holocene at the inclusion
+/// block and Isthmus at each L2 block. Span batches are generated once Delta is active, and
+/// EIP-7702 transactions once Isthmus is active.
+///
+/// The config's sequencer drift must cover the L2 blocks of a case, which all share a single L1
+/// origin.
+#[derive(Debug, Clone)]
+pub struct ChannelGenerator {
+    cfg: RollupConfig,
+    rng: StdRng,
+    timestamp: u64,
+    max_blocks: usize,
+    max_transactions: usize,
+}
+
+impl ChannelGenerator {
+    /// Creates a new [`ChannelGenerator`] with the given config and seed.
+    pub fn new(cfg: RollupConfig, seed: u64) -> Self {
+        let timestamp = cfg.genesis.l2_time;
+        Self {
+            cfg,
+            rng: StdRng::seed_from_u64(seed),
+            timestamp,
+            max_blocks: 6,
+            max_transactions: 4,
+        }
+    }
+
+    /// Sets the timestamp that the synthetic chain starts at.
+    ///
+    /// The timestamp must not be before the L2 genesis timestamp.
+    pub const fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the maximum number of new L2 blocks per channel.
+    pub const fn with_max_blocks(mut self, max_blocks: usize) -> Self {
+        self.max_blocks = max_blocks;
+        self
+    }
+
+    /// Sets the maximum number of transactions per L2 block.
+    pub const fn with_max_transactions(mut self, max_transactions: usize) -> Self {
+        self.max_transactions = max_transactions;
+        self
+    }
+
+    /// Returns the [`RollupConfig`] of the generator.
+    pub const fn cfg(&self) -> &RollupConfig {
+        &self.cfg
+    }
+
+    /// Generates a case of a random [`GeneratedCaseKind`].
+    pub fn generate_random(&mut self) -> GeneratedCase {
+        let kind = GeneratedCaseKind::ALL[self.rng.random_range(0..GeneratedCaseKind::ALL.len())];
+        self.generate(kind)
+    }
+
+    /// Generates a case of the given [`GeneratedCaseKind`].
+    pub fn generate(&mut self, kind: GeneratedCaseKind) -> GeneratedCase {
+        let mut chain = self.safe_chain();
+        let l2_safe_head = chain.blocks[SYNTHETIC_SAFE_CHAIN_LEN - 1];
+        let holocene = self.cfg.is_holocene_active(chain.inclusion_block.timestamp);
+
+        let channels = match kind {
+            GeneratedCaseKind::Valid => {
+                let range = self.extend(&mut chain, 1);
+                let batches = self.batches(&chain, range);
+                vec![self.channel(&chain, batches, l2_safe_head, 1)]
+            }
+            GeneratedCaseKind::Gap => {
+                let range = self.extend(&mut chain, 2);
+                let batch = self.batch(&chain, range.start + 1..range.end);
+                let validity = if holocene { BatchValidity::Drop } else { BatchValidity::Future };
+                vec![self.invalid_channel(batch, l2_safe_head, validity, 1)]
+            }
+            GeneratedCaseKind::Overlap => {
+                let start = self.rng.random_range(1..SYNTHETIC_SAFE_CHAIN_LEN);
+                let range = self.extend(&mut chain, 1);
+                let batches = self.batches(&chain, start..range.end);
+                vec![self.channel(&chain, batches, l2_safe_head, 1)]
+            }
+            GeneratedCaseKind::Outdated => {
+                let start = self.rng.random_range(0..SYNTHETIC_SAFE_CHAIN_LEN - 1);
+                let end = self.rng.random_range(start + 1..=SYNTHETIC_SAFE_CHAIN_LEN);
+                let batch = self.batch(&chain, start..end);
+                let validity = if holocene { BatchValidity::Past } else { BatchValidity::Drop };
+                vec![self.invalid_channel(batch, l2_safe_head, validity, 1)]
+            }
+            GeneratedCaseKind::OutOfOrderFrames => {
+                let range = self.extend(&mut chain, 1);
+                let batches = self.batches(&chain, range);
+                let mut channel = self.channel(&chain, batches, l2_safe_head, 2);
+
+                // Holocene drops the frames that follow a non-sequential frame, so the channel is
+                // never completed.
+                let swap = self.rng.random_range(0..channel.frames.len() - 1);
+                channel.frames.swap(swap, swap + 1);
+                if holocene {
+                    channel.read = false;
+                    channel.expected.clear();
+                }
+                vec![channel]
+            }
+            GeneratedCaseKind::Interleaved => {
+                let first_range = self.extend(&mut chain, 1);
+                let first_batches = self.batches(&chain, first_range);
+                let mut first = self.channel(&chain, first_batches, l2_safe_head, 2);
+
+                let range = self.extend(&mut chain, 1);
+                let parent = chain.blocks[range.start - 1];
+                let batch = self.batch(&chain, range);
+
+                // Holocene drops the first channel once the second channel starts before it is
+                // complete, so the second channel no longer builds on the safe head.
+                let second = if holocene {
+                    first.read = false;
+                    first.expected.clear();
+                    self.invalid_channel(batch, l2_safe_head, BatchValidity::Drop, 2)
+                } else {
+                    self.invalid_channel(batch, parent, BatchValidity::Accept, 2)
+                };
+                vec![first, second]
+            }
+            GeneratedCaseKind::InvalidTransaction => {
+                let range = self.extend(&mut chain, 1);
+                let index = range.start;
+                let timestamp = chain.blocks[index].block_info.timestamp;
+
+                // EIP-7702 transactions are invalid before Isthmus, and can be carried by span
+                // batches. Deposits are always invalid, but can only be carried by single batches.
+                let (tx, span) = if self.cfg.is_isthmus_active(timestamp) {
+                    (self.deposit_tx(), false)
+                } else {
+                    let tx = OpTxEnvelope::Eip7702(
+                        self.eip7702_tx().into_signed(Signature::test_signature()),
+                    );
+                    (tx.encoded_2718().into(), self.cfg.is_delta_active(chain.l1_origin.timestamp))
+                };
+                let mut single = self.single_batch(&chain, index);
+                let position = self.rng.random_range(0..=single.transactions.len());
+                single.transactions.insert(position, tx);
+                let batch = if span {
+                    let mut span_batch = self.empty_span_batch();
+                    span_batch
+                        .append_singular_batch(single, chain.blocks[index].seq_num)
+                        .expect("EIP-7702 transactions can be added to span batches");
+                    Batch::Span(span_batch)
+                } else {
+                    Batch::Single(single)
+                };
+                vec![self.invalid_channel(batch, l2_safe_head, BatchValidity::Drop, 1)]
+            }
+        };
+
+        let frames = if kind == GeneratedCaseKind::Interleaved {
+            interleave(&channels[0].frames, &channels[1].frames)
+        } else {
+            channels.iter().flat_map(|c| c.frames.iter().cloned()).collect()
+        };
+        let expected_safe_head = self.expected_safe_head(&chain, &channels, l2_safe_head);
+
+        GeneratedCase {
+            kind,
+            l1_origin: chain.l1_origin,
+            inclusion_block: chain.inclusion_block,
+            l2_safe_head,
+            expected_safe_head,
+            frames,
+            channels,
+            l2_blocks: chain.blocks,
+            op_blocks: chain.op_blocks,
+        }
+    }
+
+    /// Builds a synthetic safe chain of [`SYNTHETIC_SAFE_CHAIN_LEN`] blocks.
+    fn safe_chain(&mut self) -> SyntheticChain {
+        let l1_origin = BlockInfo::new(
+            B256::from(self.rng.random::<[u8; 32]>()),
+            self.cfg.genesis.l1.number + 1,
+            B256::from(self.rng.random::<[u8; 32]>()),
+            self.timestamp,
+        );
+        let inclusion_block = BlockInfo::new(
+            B256::from(self.rng.random::<[u8; 32]>()),
+            l1_origin.number + 1,
+            l1_origin.hash,
+            l1_origin.timestamp + L1_BLOCK_TIME,
+        );
+        let mut chain = SyntheticChain { l1_origin, inclusion_block, ..Default::default() };
+        self.push_blocks(&mut chain, SYNTHETIC_SAFE_CHAIN_LEN);
+        chain
+    }
+
+    /// Extends the chain by a random number of blocks, at least `min`, and returns the indices of
+    /// the new blocks.
+    fn extend(&mut self, chain: &mut SyntheticChain, min: usize) -> Range<usize> {
+        let count = self.rng.random_range(min..=self.max_blocks.max(min));
+        let start = chain.blocks.len();
+        self.push_blocks(chain, count);
+        start..chain.blocks.len()
+    }
+
+    /// Pushes `count` blocks with random transactions onto the chain.
+    fn push_blocks(&mut self, chain: &mut SyntheticChain, count: usize) {
+        for _ in 0..count {
+            let (parent_hash, number, timestamp, seq_num) = match chain.blocks.last() {
+                Some(parent) => (
+                    parent.block_info.hash,
+                    parent.block_info.number + 1,
+                    parent.block_info.timestamp + self.cfg.block_time,
+                    parent.seq_num + 1,
+                ),
+                None => (
+                    B256::from(self.rng.random::<[u8; 32]>()),
+                    self.cfg.genesis.l2.number + 1,
+                    self.timestamp,
+                    0,
+                ),
+            };
+
+            let l1_info = L1BlockInfoBedrock {
+                number: chain.l1_origin.number,
+                time: chain.l1_origin.timestamp,
+                block_hash: chain.l1_origin.hash,
+                sequence_number: seq_num,
+                ..Default::default()
+            };
+            let deposit = TxDeposit { input: l1_info.encode_calldata(), ..Default::default() };
+            let mut transactions = vec![OpTxEnvelope::Deposit(Sealed::new(deposit))];
+            let count = self.rng.random_range(0..=self.max_transactions);
+            for _ in 0..count {
+                let tx = if self.cfg.is_isthmus_active(timestamp) && self.rng.random_bool(0.25) {
+                    OpTxEnvelope::Eip7702(
+                        self.eip7702_tx().into_signed(Signature::test_signature()),
+                    )
+                } else {
+                    OpTxEnvelope::Eip1559(
+                        self.eip1559_tx().into_signed(Signature::test_signature()),
+                    )
+                };
+                transactions.push(tx);
+            }
+
+            let block = OpBlock {
+                header: Header { parent_hash, number, timestamp, ..Default::default() },
+                body: BlockBody { transactions, ..Default::default() },
+            };
+            let block_info =
+                BlockInfo::new(block.header.hash_slow(), number, parent_hash, timestamp);
+            chain.blocks.push(L2BlockInfo::new(block_info, chain.l1_origin.id(), seq_num));
+            chain.transactions.push(
+                block.body.transactions[1..].iter().map(|tx| tx.encoded_2718().into()).collect(),
+            );
+            chain.op_blocks.push(block);
+        }
+    }
+
+    /// Returns a random EIP-1559 transaction.
+    fn eip1559_tx(&mut self) -> TxEip1559 {
+        let max_priority_fee_per_gas = self.rng.random_range(0..1_000_000_000u128);
+        TxEip1559 {
+            chain_id: self.cfg.l2_chain_id.id(),
+            nonce: self.rng.random_range(0..1_000_000),
+            gas_limit: self.rng.random_range(21_000..1_000_000),
+            max_fee_per_gas: max_priority_fee_per_gas + self.rng.random_range(0..100_000_000_000),
+            max_priority_fee_per_gas,
+            to: TxKind::Call(Address::from(self.rng.random::<[u8; 20]>())),
+            value: U256::from(self.rng.random::<u64>()),
+            input: self.random_input(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a random EIP-7702 transaction.
+    fn eip7702_tx(&mut self) -> TxEip7702 {
+        let max_priority_fee_per_gas = self.rng.random_range(0..1_000_000_000u128);
+        TxEip7702 {
+            chain_id: self.cfg.l2_chain_id.id(),
+            nonce: self.rng.random_range(0..1_000_000),
+            gas_limit: self.rng.random_range(21_000..1_000_000),
+            max_fee_per_gas: max_priority_fee_per_gas + self.rng.random_range(0..100_000_000_000),
+            max_priority_fee_per_gas,
+            to: Address::from(self.rng.random::<[u8; 20]>()),
+            value: U256::from(self.rng.random::<u64>()),
+            input: self.random_input(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns an encoded deposit transaction.
+    fn deposit_tx(&mut self) -> Bytes {
+        let deposit = TxDeposit {
+            source_hash: B256::from(self.rng.random::<[u8; 32]>()),
+            from: Address::from(self.rng.random::<[u8; 20]>()),
+            input: self.random_input(),
+            ..Default::default()
+        };
+        OpTxEnvelope::Deposit(Sealed::new(deposit)).encoded_2718().into()
+    }
+
+    /// Returns random transaction input data.
+    fn random_input(&mut self) -> Bytes {
+        let len = self.rng.random_range(0..64);
+        (0..len).map(|_| self.rng.random::<u8>()).collect()
+    }
+
+    /// Returns the [`SingleBatch`] of the block at the given index.
+    fn single_batch(&self, chain: &SyntheticChain, index: usize) -> SingleBatch {
+        let block = chain.blocks[index];
+        SingleBatch {
+            parent_hash: block.block_info.parent_hash,
+            epoch_num: block.l1_origin.number,
+            epoch_hash: block.l1_origin.hash,
+            timestamp: block.block_info.timestamp,
+            transactions: chain.transactions[index].clone(),
+        }
+    }
+
+    /// Returns an empty [`SpanBatch`] for the generator's chain.
+    fn empty_span_batch(&self) -> SpanBatch {
+        SpanBatch {
+            genesis_timestamp: self.cfg.genesis.l2_time,
+            chain_id: self.cfg.l2_chain_id.id(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the batches for the blocks at the given indices: a single span batch once Delta is
+    /// active, and a single batch per block before.
+    fn batches(&self, chain: &SyntheticChain, range: Range<usize>) -> Vec<Batch> {
+        if self.cfg.is_delta_active(chain.l1_origin.timestamp) {
+            vec![self.batch(chain, range)]
+        } else {
+            range.map(|i| Batch::Single(self.single_batch(chain, i))).collect()
+        }
+    }
+
+    /// Returns one batch for the blocks at the given indices: a span batch once Delta is active,
+    /// and a single batch of the first block before.
+    fn batch(&self, chain: &SyntheticChain, range: Range<usize>) -> Batch {
+        if !self.cfg.is_delta_active(chain.l1_origin.timestamp) {
+            return Batch::Single(self.single_batch(chain, range.start));
+        }
+
+        let mut span_batch = self.empty_span_batch();
+        for i in range {
+            span_batch
+                .append_singular_batch(self.single_batch(chain, i), chain.blocks[i].seq_num)
+                .expect("generated transactions can be added to span batches");
+        }
+        Batch::Span(span_batch)
+    }
+
+    /// Builds a channel of batches that are expected to be accepted once they extend the safe
+    /// head, split into at least `min_frames` frames.
+    fn channel(
+        &mut self,
+        chain: &SyntheticChain,
+        batches: Vec<Batch>,
+        l2_safe_head: L2BlockInfo,
+        min_frames: usize,
+    ) -> GeneratedChannel {
+        let holocene = self.cfg.is_holocene_active(chain.inclusion_block.timestamp);
+        let mut safe_head = l2_safe_head;
+        let expected = batches
+            .iter()
+            .map(|batch| {
+                let expected = match batch {
+                    Batch::Single(single) if single.timestamp <= safe_head.block_info.timestamp => {
+                        let validity =
+                            if holocene { BatchValidity::Past } else { BatchValidity::Drop };
+                        ExpectedBatch { l2_safe_head: safe_head, validity }
+                    }
+                    _ => ExpectedBatch { l2_safe_head: safe_head, validity: BatchValidity::Accept },
+                };
+                if expected.validity == BatchValidity::Accept {
+                    safe_head = chain.blocks[last_block_index(chain, batch)];
+                }
+                expected
+            })
+            .collect();
+
+        let mut channel = GeneratedChannel {
+            id: self.rng.random(),
+            frames: Vec::new(),
+            batches,
+            read: true,
+            expected,
+        };
+        self.split(&mut channel, min_frames);
+        channel
+    }
+
+    /// Builds a channel of a single batch with the given expected validity, split into at least
+    /// `min_frames` frames.
+    fn invalid_channel(
+        &mut self,
+        batch: Batch,
+        l2_safe_head: L2BlockInfo,
+        validity: BatchValidity,
+        min_frames: usize,
+    ) -> GeneratedChannel {
+        let mut channel = GeneratedChannel {
+            id: self.rng.random(),
+            frames: Vec::new(),
+            batches: vec![batch],
+            read: true,
+            expected: vec![ExpectedBatch { l2_safe_head, validity }],
+        };
+        self.split(&mut channel, min_frames);
+        channel
+    }
+
+    /// Compresses the batches of the channel and splits them into at least `min_frames` frames.
+    fn split(&mut self, channel: &mut GeneratedChannel, min_frames: usize) {
+        let mut rlp = Vec::new();
+        for batch in &channel.batches {
+            let mut encoded = Vec::new();
+            batch.encode(&mut encoded).expect("generated batches must encode");
+            Bytes::from(encoded).encode(&mut rlp);
+        }
+        let data = miniz_oxide::deflate::compress_to_vec_zlib(&rlp, 6);
+
+        let count = self.rng.random_range(min_frames..=MAX_FRAMES_PER_CHANNEL.max(min_frames));
+        let mut cuts = Vec::with_capacity(count + 1);
+        cuts.push(0);
+        while cuts.len() < count {
+            let cut = self.rng.random_range(1..data.len());
+            if !cuts.contains(&cut) {
+                cuts.push(cut);
+            }
+        }
+        cuts.sort_unstable();
+        cuts.push(data.len());
+
+        channel.frames = cuts
+            .windows(2)
+            .enumerate()
+            .map(|(i, w)| {
+                Frame::new(channel.id, i as u16, data[w[0]..w[1]].to_vec(), i == count - 1)
+            })
+            .collect();
+    }
+
+    /// Returns the expected safe head after all channels of the case are derived.
+    fn expected_safe_head(
+        &self,
+        chain: &SyntheticChain,
+        channels: &[GeneratedChannel],
+        l2_safe_head: L2BlockInfo,
+    ) -> L2BlockInfo {
+        channels
+            .iter()
+            .flat_map(|c| c.batches.iter().zip(c.expected.iter()))
+            .filter(|(_, expected)| expected.validity == BatchValidity::Accept)
+            .map(|(batch, _)| chain.blocks[last_block_index(chain, batch)])
+            .fold(l2_safe_head, |safe_head, block| {
+                if block.block_info.number > safe_head.block_info.number {
+                    block
+                } else {
+                    safe_head
+                }
+            })
+    }
+}
+
+/// Returns the index of the last block in the chain that the batch builds.
+fn last_block_index(chain: &SyntheticChain, batch: &Batch) -> usize {
+    let timestamp = match batch {
+        Batch::Single(single) => single.timestamp,
+        Batch::Span(span) => span.final_timestamp(),
+    };
+    chain
+        .blocks
+        .iter()
+        .position(|b| b.block_info.timestamp == timestamp)
+        .expect("generated batches build blocks of the chain")
+}
+
+/// Interleaves the frames of two channels, starting with the first.
+fn interleave(first: &[Frame], second: &[Frame]) -> Vec<Frame> {
+    let mut frames = Vec::with_capacity(first.len() + second.len());
+    for i in 0..first.len().max(second.len()) {
+        frames.extend(first.get(i).cloned());
+        frames.extend(second.get(i).cloned());
+    }
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BatchReader, BatchWithInclusionBlock, Channel};
+    use kona_genesis::{HardForkConfig, MAX_RLP_BYTES_PER_CHANNEL_FJORD};
+
+    fn rollup_config(hardforks: HardForkConfig) -> RollupConfig {
+        RollupConfig {
+            block_time: 2,
+            max_sequencer_drift: 600,
+            seq_window_size: 100,
+            hardforks,
+            ..Default::default()
+        }
+    }
+
+    async fn check_case(cfg: &RollupConfig, case: &GeneratedCase) {
+        assert_eq!(Frame::parse_frames(&case.batcher_data()).unwrap(), case.frames);
+
+        let mut fetcher = case.validation_provider();
+        for channel in case.channels.iter().filter(|c| c.read) {
+            let mut assembled = Channel::new(channel.id, case.inclusion_block);
+            for frame in &channel.frames {
+                assembled.add_frame(frame.clone(), case.inclusion_block).unwrap();
+            }
+            assert!(assembled.is_ready());
+
+            let data = assembled.frame_data().unwrap();
+            let mut reader = BatchReader::new(data, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize);
+            assert_eq!(channel.batches.len(), channel.expected.len());
+            for (batch, expected) in channel.batches.iter().zip(&channel.expected) {
+                let decoded = reader.next_batch(cfg).unwrap();
+                assert_eq!(decoded.timestamp(), batch.timestamp());
+
+                let decoded = BatchWithInclusionBlock::new(case.inclusion_block, decoded);
+                let validity = decoded
+                    .check_batch(cfg, &[case.l1_origin], expected.l2_safe_head, &mut fetcher)
+                    .await;
+                assert_eq!(validity, expected.validity, "{:?}", case.kind);
+            }
+            assert!(reader.next_batch(cfg).is_none());
+        }
+    }
+
+    async fn check_all_kinds(cfg: RollupConfig) {
+        for seed in 0..8 {
+            let mut generator = ChannelGenerator::new(cfg.clone(), seed);
+            for kind in GeneratedCaseKind::ALL {
+                let case = generator.generate(kind);
+                assert_eq!(case.kind, kind);
+                check_case(&cfg, &case).await;
+            }
+        }
+    }
+
+    #[test]
+    fn test_generator_deterministic() {
+        let cfg = rollup_config(HardForkConfig { delta_time: Some(0), ..Default::default() });
+        let mut first = ChannelGenerator::new(cfg.clone(), 42);
+        let mut second = ChannelGenerator::new(cfg, 42);
+        for _ in 0..8 {
+            assert_eq!(first.generate_random(), second.generate_random());
+        }
+    }
+
+    #[test]
+    fn test_generate_valid_extends_safe_head() {
+        let cfg = rollup_config(HardForkConfig { delta_time: Some(0), ..Default::default() });
+        let case = ChannelGenerator::new(cfg, 1).generate(GeneratedCaseKind::Valid);
+        assert_eq!(case.l2_safe_head, case.l2_blocks[SYNTHETIC_SAFE_CHAIN_LEN - 1]);
+        assert_eq!(case.expected_safe_head, *case.l2_blocks.last().unwrap());
+        assert!(matches!(case.channels[0].batches[..], [Batch::Span(_)]));
+    }
+
+    #[test]
+    fn test_generate_holocene_interleaved() {
+        let hardforks =
+            HardForkConfig { delta_time: Some(0), holocene_time: Some(0), ..Default::default() };
+        let case = ChannelGenerator::new(rollup_config(hardforks), 7)
+            .generate(GeneratedCaseKind::Interleaved);
+        assert!(!case.channels[0].read);
+        assert!(case.channels[1].read);
+        assert_eq!(case.frames[0].id, case.channels[0].id);
+        assert_eq!(case.frames[1].id, case.channels[1].id);
+        assert_eq!(case.expected_safe_head, case.l2_safe_head);
+    }
+
+    #[tokio::test]
+    async fn test_generated_cases_pre_delta() {
+        check_all_kinds(rollup_config(HardForkConfig::default())).await;
+    }
+
+    #[tokio::test]
+    async fn test_generated_cases_delta() {
+        check_all_kinds(rollup_config(HardForkConfig {
+            delta_time: Some(0),
+            ..Default::default()
+        }))
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_generated_cases_holocene_isthmus() {
+        let hardforks = HardForkConfig {
+            delta_time: Some(0),
+            fjord_time: Some(0),
+            holocene_time: Some(0),
+            isthmus_time: Some(0),
+            ..Default::default()
+        };
+        check_all_kinds(rollup_config(hardforks)).await;
+    }
+}
@@ -0,0 +1,77 @@
+//! Contains zstd decompression utilities.
+
+use alloc::{vec, vec::Vec};
+use ruzstd::{decoding::StreamingDecoder, io::Read};
+
+/// The size of the chunks that the decompressed data is read in.
+const ZSTD_READ_CHUNK_SIZE: usize = 32 * 1024;
+
+/// A zstd decompression error.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ZstdDecompressionError {
+    /// The zstd frame header is invalid.
+    #[error("invalid zstd frame header")]
+    InvalidFrame,
+    /// The zstd frame is corrupted.
+    #[error("corrupted zstd frame")]
+    Corrupted,
+    /// The decompressed data exceeds the maximum size.
+    #[error("the decompressed data exceeds the maximum size of {0} bytes")]
+    TooLarge(usize),
+}
+
+/// Decompresses a single zstd frame using the pure-rust decoder implemented in the
+/// [`ruzstd`](https://crates.io/crates/ruzstd) crate.
+///
+/// Decompression is aborted as soon as the output grows past `max_rlp_bytes_per_channel`.
+pub fn decompress_zstd(
+    mut data: &[u8],
+    max_rlp_bytes_per_channel: usize,
+) -> Result<Vec<u8>, ZstdDecompressionError> {
+    let mut decoder =
+        StreamingDecoder::new(&mut data).map_err(|_| ZstdDecompressionError::InvalidFrame)?;
+
+    let mut output = Vec::new();
+    let mut chunk = vec![0; ZSTD_READ_CHUNK_SIZE];
+    loop {
+        let read = decoder.read(&mut chunk).map_err(|_| ZstdDecompressionError::Corrupted)?;
+        if read == 0 {
+            break;
+        }
+        if output.len() + read > max_rlp_bytes_per_channel {
+            return Err(ZstdDecompressionError::TooLarge(max_rlp_bytes_per_channel));
+        }
+        output.extend_from_slice(&chunk[..read]);
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::hex;
+    use kona_genesis::MAX_RLP_BYTES_PER_CHANNEL_FJORD;
+
+    #[test]
+    fn test_decompress_zstd() {
+        let expected = hex!("75ed184249e9bc19675e");
+        let compressed = hex!("28b52ffd005851000075ed184249e9bc19675e");
+
+        let decompressed =
+            decompress_zstd(&compressed, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize).unwrap();
+        assert_eq!(decompressed, expected);
+    }
+
+    #[test]
+    fn test_decompress_zstd_too_large() {
+        let compressed = hex!("28b52ffd005851000075ed184249e9bc19675e");
+        assert_eq!(decompress_zstd(&compressed, 9), Err(ZstdDecompressionError::TooLarge(9)));
+    }
+
+    #[test]
+    fn test_decompress_zstd_invalid_frame() {
+        let err = decompress_zstd(&[0xde, 0xad, 0xbe, 0xef], usize::MAX).unwrap_err();
+        assert_eq!(err, ZstdDecompressionError::InvalidFrame);
+    }
+}
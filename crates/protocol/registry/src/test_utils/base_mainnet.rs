@@ -65,6 +65,7 @@ pub const BASE_MAINNET_CONFIG: RollupConfig = RollupConfig {
     superchain_config_address: Some(address!("95703e0982140D16f8ebA6d158FccEde42f04a4C")),
     da_challenge_address: None,
     blobs_enabled_l1_timestamp: None,
+    experimental_compression_time: None,
     interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
     alt_da_config: None,
     chain_op_config: BASE_MAINNET_BASE_FEE_CONFIG,
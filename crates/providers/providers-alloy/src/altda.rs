@@ -0,0 +1,102 @@
+//! Contains an online implementation of the `AltDAClient` trait.
+
+use alloy_primitives::{Bytes, hex};
+use async_trait::async_trait;
+use kona_derive::{
+    AltDAClient, AltDACommitment, AltDACommitmentType, AltDAError, MAX_ALTDA_INPUT_SIZE,
+};
+use kona_genesis::MAX_RLP_BYTES_PER_CHANNEL_FJORD;
+use reqwest::{Client, StatusCode};
+use std::time::Duration;
+
+/// The input retrieval method of the DA server.
+const GET_METHOD: &str = "get";
+
+/// The timeout of a request to the DA server.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An online implementation of the [AltDAClient] trait, which fetches inputs from a DA server.
+#[derive(Debug, Clone)]
+pub struct OnlineAltDAClient {
+    /// The base URL of the DA server.
+    pub base: String,
+    /// The inner reqwest client.
+    pub inner: Client,
+}
+
+impl OnlineAltDAClient {
+    /// Creates a new [OnlineAltDAClient] from the provided base URL of the DA server.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized.
+    pub fn new_http(mut base: String) -> Self {
+        // If base ends with a slash, remove it
+        if base.ends_with("/") {
+            base.remove(base.len() - 1);
+        }
+        let inner = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to initialize the DA server client");
+        Self { base, inner }
+    }
+
+    /// Returns the maximum size of the input of the given commitment.
+    ///
+    /// Inputs of keccak256 commitments are limited to [MAX_ALTDA_INPUT_SIZE]. Generic inputs carry
+    /// channel frames, and are limited to the maximum size of a channel.
+    const fn max_input_size(commitment: &AltDACommitment) -> usize {
+        match commitment.commitment_type() {
+            AltDACommitmentType::Keccak => MAX_ALTDA_INPUT_SIZE,
+            AltDACommitmentType::Generic => MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize,
+        }
+    }
+}
+
+#[async_trait]
+impl AltDAClient for OnlineAltDAClient {
+    type Error = AltDAError;
+
+    async fn get_input(
+        &mut self,
+        commitment: &AltDACommitment,
+    ) -> Result<Option<Bytes>, Self::Error> {
+        let url =
+            format!("{}/{}/{}", self.base, GET_METHOD, hex::encode_prefixed(commitment.encode()));
+        let mut response =
+            self.inner.get(url).send().await.map_err(|e| AltDAError::Backend(e.to_string()))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => return Ok(None),
+            status if status.is_success() => {}
+            status => {
+                return Err(AltDAError::Backend(format!("DA server responded with {status}")));
+            }
+        }
+
+        // Read the body in chunks up to one byte past the maximum input size, so that an oversized
+        // input is never buffered in full.
+        let max_size = Self::max_input_size(commitment);
+        let mut input = Vec::new();
+        while let Some(chunk) =
+            response.chunk().await.map_err(|e| AltDAError::Backend(e.to_string()))?
+        {
+            let remaining = max_size + 1 - input.len();
+            if chunk.len() >= remaining {
+                input.extend_from_slice(&chunk[..remaining]);
+                break;
+            }
+            input.extend_from_slice(&chunk);
+        }
+
+        // An oversized keccak256 input is returned truncated, which is enough for the `AltDASource`
+        // to skip it.
+        if input.len() > max_size && commitment.commitment_type() == AltDACommitmentType::Generic {
+            return Err(AltDAError::Backend(format!(
+                "DA server input exceeds the maximum size of {max_size} bytes"
+            )));
+        }
+        Ok(Some(input.into()))
+    }
+}
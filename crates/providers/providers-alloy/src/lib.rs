@@ -9,6 +9,9 @@
 mod metrics;
 pub use metrics::Metrics;
 
+mod altda;
+pub use altda::OnlineAltDAClient;
+
 mod beacon_client;
 pub use beacon_client::{
     APIConfigResponse, APIGenesisResponse, BeaconClient, OnlineBeaconClient, ReducedConfigData,
@@ -25,4 +28,4 @@ mod l2_chain_provider;
 pub use l2_chain_provider::{AlloyL2ChainProvider, AlloyL2ChainProviderError};
 
 mod pipeline;
-pub use pipeline::OnlinePipeline;
+pub use pipeline::{OnlineDataProvider, OnlineDataSource, OnlinePipeline, OnlinePipelineOptions};
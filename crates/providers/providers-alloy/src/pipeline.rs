@@ -1,22 +1,28 @@
 //! Contains an online derivation pipeline.
 
-use crate::{AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlineBlobProvider};
+use crate::{
+    AlloyChainProvider, AlloyL2ChainProvider, OnlineAltDAClient, OnlineBeaconClient,
+    OnlineBlobProvider,
+};
+use alloy_primitives::{Address, Bytes};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_derive::{
-    DerivationPipeline, EthereumDataSource, IndexedAttributesQueueStage, L2ChainProvider,
-    OriginProvider, Pipeline, PipelineBuilder, PipelineErrorKind, PipelineResult,
+    AltDASource, DataAvailabilityProvider, DerivationEventSink, DerivationPipeline,
+    EthereumDataSource, IndexedAttributesQueueStage, L2ChainProvider, OriginProvider, Pipeline,
+    PipelineBuilder, PipelineCheckpoint, PipelineError, PipelineErrorKind, PipelineResult,
     PolledAttributesQueueStage, ResetSignal, Signal, SignalReceiver, StatefulAttributesBuilder,
     StepResult,
 };
 use kona_genesis::{RollupConfig, SystemConfig};
+use kona_hardforks::UpgradeRegistry;
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use std::sync::Arc;
 
 /// An online polled derivation pipeline.
 pub type OnlinePolledDerivationPipeline = DerivationPipeline<
     PolledAttributesQueueStage<
-        OnlineDataProvider,
+        OnlineDataSource,
         AlloyChainProvider,
         AlloyL2ChainProvider,
         OnlineAttributesBuilder,
@@ -27,7 +33,7 @@ pub type OnlinePolledDerivationPipeline = DerivationPipeline<
 /// An online managed derivation pipeline.
 pub type OnlineManagedDerivationPipeline = DerivationPipeline<
     IndexedAttributesQueueStage<
-        OnlineDataProvider,
+        OnlineDataSource,
         AlloyChainProvider,
         AlloyL2ChainProvider,
         OnlineAttributesBuilder,
@@ -39,11 +45,101 @@ pub type OnlineManagedDerivationPipeline = DerivationPipeline<
 pub type OnlineDataProvider =
     EthereumDataSource<AlloyChainProvider, OnlineBlobProvider<OnlineBeaconClient>>;
 
+/// An RPC-backed data source, which resolves Alt-DA commitments through a DA server if the rollup
+/// uses Alt-DA.
+#[derive(Debug, Clone)]
+pub enum OnlineDataSource {
+    /// An Ethereum data source.
+    Ethereum(OnlineDataProvider),
+    /// An Alt-DA data source, wrapping an Ethereum data source.
+    AltDA(AltDASource<AlloyChainProvider, OnlineDataProvider, OnlineAltDAClient>),
+}
+
+impl OnlineDataSource {
+    /// Creates a new [OnlineDataSource] for the given rollup.
+    ///
+    /// If the rollup uses Alt-DA, an `altda_client` must be given.
+    pub fn new(
+        cfg: &RollupConfig,
+        chain_provider: AlloyChainProvider,
+        blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
+        altda_client: Option<OnlineAltDAClient>,
+    ) -> PipelineResult<Self> {
+        let ethereum =
+            EthereumDataSource::new_from_parts(chain_provider.clone(), blob_provider, cfg);
+        let Some(altda_cfg) = cfg.alt_da_config.as_ref() else {
+            return Ok(Self::Ethereum(ethereum));
+        };
+        let Some(client) = altda_client else {
+            return Err(PipelineError::Provider(
+                "Rollup uses Alt-DA, but no DA server is configured".to_string(),
+            )
+            .crit());
+        };
+        Ok(Self::AltDA(AltDASource::from_config(chain_provider, ethereum, client, altda_cfg)?))
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityProvider for OnlineDataSource {
+    type Item = Bytes;
+
+    async fn next(
+        &mut self,
+        block_ref: &BlockInfo,
+        batcher_addr: Address,
+    ) -> PipelineResult<Self::Item> {
+        match self {
+            Self::Ethereum(source) => source.next(block_ref, batcher_addr).await,
+            Self::AltDA(source) => source.next(block_ref, batcher_addr).await,
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Self::Ethereum(source) => source.clear(),
+            Self::AltDA(source) => source.clear(),
+        }
+    }
+}
+
 /// An RPC-backed payload attributes builder for the `AttributesQueue` stage of the derivation
 /// pipeline.
 pub type OnlineAttributesBuilder =
     StatefulAttributesBuilder<AlloyChainProvider, AlloyL2ChainProvider>;
 
+/// The optional components of an [OnlinePipeline].
+#[derive(Debug, Clone, Default)]
+pub struct OnlinePipelineOptions {
+    /// The client that resolves Alt-DA commitments. Must be set if the rollup uses Alt-DA.
+    pub altda_client: Option<OnlineAltDAClient>,
+    /// The registry that the network upgrade transactions are taken from. If unset, the built-in
+    /// hardforks are used.
+    pub upgrades: Option<UpgradeRegistry>,
+    /// The sink that the pipeline's stages emit their events to.
+    pub event_sink: Option<Arc<dyn DerivationEventSink>>,
+}
+
+impl OnlinePipelineOptions {
+    /// Sets the client that resolves Alt-DA commitments.
+    pub fn with_altda_client(mut self, altda_client: Option<OnlineAltDAClient>) -> Self {
+        self.altda_client = altda_client;
+        self
+    }
+
+    /// Sets the registry that the network upgrade transactions are taken from.
+    pub fn with_upgrades(mut self, upgrades: Option<UpgradeRegistry>) -> Self {
+        self.upgrades = upgrades;
+        self
+    }
+
+    /// Sets the sink that the pipeline's stages emit their events to.
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn DerivationEventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+}
+
 /// An online derivation pipeline.
 #[derive(Debug)]
 pub enum OnlinePipeline {
@@ -62,9 +158,15 @@ impl OnlinePipeline {
         blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
         chain_provider: AlloyChainProvider,
         mut l2_chain_provider: AlloyL2ChainProvider,
+        options: OnlinePipelineOptions,
     ) -> PipelineResult<Self> {
-        let mut pipeline =
-            Self::new_polled(cfg.clone(), blob_provider, chain_provider, l2_chain_provider.clone());
+        let mut pipeline = Self::new_polled(
+            cfg.clone(),
+            blob_provider,
+            chain_provider,
+            l2_chain_provider.clone(),
+            options,
+        )?;
 
         // Reset the pipeline to populate the initial L1/L2 cursor and system configuration in L1
         // Traversal.
@@ -92,29 +194,44 @@ impl OnlinePipeline {
     /// Before using the returned pipeline, a [`ResetSignal`] must be sent to
     /// instantiate the pipeline state. [`Self::new`] is a convenience method that
     /// constructs a new online pipeline and sends the reset signal.
+    ///
+    /// The optional components of the pipeline are configured through the
+    /// [`OnlinePipelineOptions`].
     pub fn new_polled(
         cfg: Arc<RollupConfig>,
         blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
         chain_provider: AlloyChainProvider,
         l2_chain_provider: AlloyL2ChainProvider,
-    ) -> Self {
-        let attributes = StatefulAttributesBuilder::new(
+        options: OnlinePipelineOptions,
+    ) -> PipelineResult<Self> {
+        let mut attributes = StatefulAttributesBuilder::new(
             cfg.clone(),
             l2_chain_provider.clone(),
             chain_provider.clone(),
         );
-        let dap = EthereumDataSource::new_from_parts(chain_provider.clone(), blob_provider, &cfg);
+        if let Some(upgrades) = options.upgrades {
+            attributes = attributes.with_upgrades(upgrades);
+        }
+        let dap = OnlineDataSource::new(
+            &cfg,
+            chain_provider.clone(),
+            blob_provider,
+            options.altda_client,
+        )?;
 
-        let pipeline = PipelineBuilder::new()
+        let mut builder = PipelineBuilder::new()
             .rollup_config(cfg.clone())
             .dap_source(dap)
             .l2_chain_provider(l2_chain_provider.clone())
             .chain_provider(chain_provider)
             .builder(attributes)
-            .origin(BlockInfo::default())
-            .build_polled();
+            .origin(BlockInfo::default());
+        if let Some(sink) = options.event_sink {
+            builder = builder.event_sink(sink);
+        }
+        let pipeline = builder.build_polled();
 
-        Self::Polled(pipeline)
+        Ok(Self::Polled(pipeline))
     }
 
     /// Constructs a new indexed derivation pipeline that is uninitialized.
@@ -124,29 +241,44 @@ impl OnlinePipeline {
     /// Before using the returned pipeline, a [`ResetSignal`] must be sent to
     /// instantiate the pipeline state. [`Self::new`] is a convenience method that
     /// constructs a new online pipeline and sends the reset signal.
+    ///
+    /// The optional components of the pipeline are configured through the
+    /// [`OnlinePipelineOptions`].
     pub fn new_indexed(
         cfg: Arc<RollupConfig>,
         blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
         chain_provider: AlloyChainProvider,
         l2_chain_provider: AlloyL2ChainProvider,
-    ) -> Self {
-        let attributes = StatefulAttributesBuilder::new(
+        options: OnlinePipelineOptions,
+    ) -> PipelineResult<Self> {
+        let mut attributes = StatefulAttributesBuilder::new(
             cfg.clone(),
             l2_chain_provider.clone(),
             chain_provider.clone(),
         );
-        let dap = EthereumDataSource::new_from_parts(chain_provider.clone(), blob_provider, &cfg);
+        if let Some(upgrades) = options.upgrades {
+            attributes = attributes.with_upgrades(upgrades);
+        }
+        let dap = OnlineDataSource::new(
+            &cfg,
+            chain_provider.clone(),
+            blob_provider,
+            options.altda_client,
+        )?;
 
-        let pipeline = PipelineBuilder::new()
+        let mut builder = PipelineBuilder::new()
             .rollup_config(cfg.clone())
             .dap_source(dap)
             .l2_chain_provider(l2_chain_provider.clone())
             .chain_provider(chain_provider)
             .builder(attributes)
-            .origin(BlockInfo::default())
-            .build_indexed();
+            .origin(BlockInfo::default());
+        if let Some(sink) = options.event_sink {
+            builder = builder.event_sink(sink);
+        }
+        let pipeline = builder.build_indexed();
 
-        Self::Managed(pipeline)
+        Ok(Self::Managed(pipeline))
     }
 }
 
@@ -159,6 +291,22 @@ impl SignalReceiver for OnlinePipeline {
             Self::Managed(pipeline) => pipeline.signal(signal).await,
         }
     }
+
+    /// Takes a checkpoint of the pipeline's stages.
+    fn checkpoint(&self, checkpoint: &mut PipelineCheckpoint) -> PipelineResult<()> {
+        match self {
+            Self::Polled(pipeline) => pipeline.checkpoint(checkpoint),
+            Self::Managed(pipeline) => pipeline.checkpoint(checkpoint),
+        }
+    }
+
+    /// Restores the pipeline's stages from a checkpoint.
+    async fn restore(&mut self, checkpoint: &PipelineCheckpoint) -> PipelineResult<()> {
+        match self {
+            Self::Polled(pipeline) => pipeline.restore(checkpoint).await,
+            Self::Managed(pipeline) => pipeline.restore(checkpoint).await,
+        }
+    }
 }
 
 impl OriginProvider for OnlinePipeline {
@@ -9,3 +9,4 @@
 #![no_std]
 
 mod metrics;
+mod profiling;
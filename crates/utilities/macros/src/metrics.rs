@@ -36,14 +36,14 @@ macro_rules! inc {
         #[cfg(feature = "metrics")]
         metrics::$instrument!($metric, "type" => $value).increment(1);
     };
-    ($instrument:ident, $metric:path $(, $label_key:expr $(=> $label_value:expr)?)*$(,)?) => {
-        #[cfg(feature = "metrics")]
-        metrics::$instrument!($metric $(, $label_key $(=> $label_value)?)*).increment(1);
-    };
     ($instrument:ident, $metric:path, $value:expr $(, $label_key:expr $(=> $label_value:expr)?)*$(,)?) => {
         #[cfg(feature = "metrics")]
         metrics::$instrument!($metric $(, $label_key $(=> $label_value)?)*).increment($value);
     };
+    ($instrument:ident, $metric:path $(, $label_key:expr $(=> $label_value:expr)?)*$(,)?) => {
+        #[cfg(feature = "metrics")]
+        metrics::$instrument!($metric $(, $label_key $(=> $label_value)?)*).increment(1);
+    };
 }
 
 /// Records a value, optionally with a specified label.
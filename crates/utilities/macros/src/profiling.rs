@@ -0,0 +1,25 @@
+//! Macros for recording profiling spans.
+//!
+//! The spans are only recorded if the `profiling` feature of the calling crate is enabled, so that
+//! regular builds don't pay for them at their callsites.
+
+/// Instruments a future with a `TRACE` span of the given name.
+#[macro_export]
+macro_rules! profile {
+    ($name:literal, $fut:expr) => {{
+        #[cfg(feature = "profiling")]
+        let fut = tracing::Instrument::instrument($fut, tracing::trace_span!($name));
+        #[cfg(not(feature = "profiling"))]
+        let fut = $fut;
+        fut
+    }};
+}
+
+/// Enters a `TRACE` span of the given name until the end of the enclosing scope.
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:literal $(, $($field:tt)+)?) => {
+        #[cfg(feature = "profiling")]
+        let _span = tracing::trace_span!($name $(, $($field)+)?).entered();
+    };
+}